@@ -1,5 +1,7 @@
 use core::{net::SocketAddr, time::Duration};
 
+use std::{collections::HashMap, path::PathBuf};
+
 use xitca_http::http::version::Version;
 
 use crate::{
@@ -8,7 +10,7 @@ use crate::{
     date::DateTimeService,
     error::Error,
     middleware, pool,
-    resolver::{base_resolver, ResolverService},
+    resolver::{base_resolver, Resolve, ResolveService, Resolver, ResolverService},
     response::Response,
     service::{async_fn::AsyncFn, http::base_service, HttpService, Service, ServiceRequest},
     timeout::TimeoutConfig,
@@ -22,15 +24,44 @@ use crate::{
 pub struct ClientBuilder {
     connector: Connector,
     resolver: ResolverService,
+    resolve_overrides: HashMap<Box<str>, Vec<SocketAddr>>,
+    resolve_cache_ttl: Option<Duration>,
     pool_capacity: usize,
+    pool_max_pending: usize,
+    pool_acquire_timeout: Option<Duration>,
     keep_alive_idle: Duration,
     keep_alive_born: Duration,
     timeout_config: TimeoutConfig,
     local_addr: Option<SocketAddr>,
+    connection_attempt_delay: Duration,
+    unix_socket: Option<PathBuf>,
+    #[cfg(feature = "http2")]
+    h2_config: crate::h2::Config,
+    #[cfg(feature = "http3")]
+    h3_config: crate::h3::Config,
     max_http_version: Version,
+    #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+    tls: TlsBuilder,
     service: HttpService,
 }
 
+#[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+#[derive(Clone, Copy)]
+enum TlsBackend {
+    #[cfg(feature = "openssl")]
+    Openssl,
+    #[cfg(any(feature = "rustls", feature = "rustls-ring-crypto"))]
+    Rustls,
+}
+
+// built in tls connector is constructed lazily when client builder finishes.
+#[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+struct TlsBuilder {
+    backend: Option<TlsBackend>,
+    config: crate::TlsConfig,
+    host_configs: HashMap<Box<str>, crate::TlsConfig>,
+}
+
 impl Default for ClientBuilder {
     fn default() -> Self {
         Self::new()
@@ -42,12 +73,28 @@ impl ClientBuilder {
         ClientBuilder {
             connector: connector::nop(),
             resolver: base_resolver(),
+            resolve_overrides: HashMap::new(),
+            resolve_cache_ttl: None,
             pool_capacity: 2,
+            pool_max_pending: usize::MAX,
+            pool_acquire_timeout: None,
             keep_alive_idle: Duration::from_secs(60),
             keep_alive_born: Duration::from_secs(3600),
             timeout_config: TimeoutConfig::new(),
             local_addr: None,
+            connection_attempt_delay: Duration::from_millis(250),
+            unix_socket: None,
+            #[cfg(feature = "http2")]
+            h2_config: crate::h2::Config::new(),
+            #[cfg(feature = "http3")]
+            h3_config: crate::h3::Config::new(),
             max_http_version: max_http_version(),
+            #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+            tls: TlsBuilder {
+                backend: None,
+                config: crate::TlsConfig::new(),
+                host_configs: HashMap::new(),
+            },
             service: base_service(),
         }
     }
@@ -155,17 +202,51 @@ impl ClientBuilder {
     #[cfg(feature = "openssl")]
     /// enable openssl as tls connector.
     pub fn openssl(mut self) -> Self {
-        self.connector = connector::openssl::connect(self.alpn_from_version());
+        self.tls.backend = Some(TlsBackend::Openssl);
         self
     }
 
     #[cfg(any(feature = "rustls", feature = "rustls-ring-crypto"))]
     /// enable rustls as tls connector.
     pub fn rustls(mut self) -> Self {
-        self.connector = connector::rustls::connect(self.alpn_from_version());
+        self.tls.backend = Some(TlsBackend::Rustls);
         self
     }
 
+    #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+    /// Set [TlsConfig] for built in tls connector enabled by [ClientBuilder::openssl] or [ClientBuilder::rustls].
+    /// Custom root certificates, client certificate for mutual tls and etc. can be configured.
+    ///
+    /// Default to trust built in root certificates with no client certificate.
+    ///
+    /// [TlsConfig]: crate::TlsConfig
+    pub fn tls_config(mut self, config: crate::TlsConfig) -> Self {
+        self.tls.config = config;
+        self
+    }
+
+    #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+    /// Set [TlsConfig] for connections to given host. It takes precedence over [ClientBuilder::tls_config].
+    /// Host is matched against tls server name which is request uri's host.
+    ///
+    /// Per host config does not apply to http/3 connections.
+    ///
+    /// [TlsConfig]: crate::TlsConfig
+    pub fn tls_config_for_host(mut self, host: impl Into<Box<str>>, config: crate::TlsConfig) -> Self {
+        self.tls.host_configs.insert(host.into(), config);
+        self
+    }
+
+    #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+    fn tls_connector_from_config(&self, backend: TlsBackend, config: &crate::TlsConfig) -> Result<Connector, Error> {
+        match backend {
+            #[cfg(feature = "openssl")]
+            TlsBackend::Openssl => connector::openssl::connect(self.alpn_from_version(), config),
+            #[cfg(any(feature = "rustls", feature = "rustls-ring-crypto"))]
+            TlsBackend::Rustls => connector::rustls::connect(self.alpn_from_version(), config),
+        }
+    }
+
     #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
     const fn alpn_from_version(&self) -> &[&[u8]] {
         match self.max_http_version {
@@ -218,6 +299,59 @@ impl ClientBuilder {
         self
     }
 
+    /// Use custom DNS resolver type implementing [Resolve] trait for domain look up.
+    ///
+    /// With `hickory-dns` feature enabled [HickoryResolver] can be used as async resolver.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use xitca_client::{resolver::SystemResolver, ClientBuilder};
+    ///
+    /// # fn resolve() {
+    /// let client = ClientBuilder::new().dns_resolver(SystemResolver).finish();
+    /// # }
+    /// ```
+    ///
+    /// [HickoryResolver]: crate::resolver::HickoryResolver
+    pub fn dns_resolver<R>(self, resolver: R) -> Self
+    where
+        R: Resolve + 'static,
+    {
+        self.resolver(ResolveService(resolver))
+    }
+
+    /// Override DNS resolution of given domain with static socket address. Can be called multiple times for
+    /// the same domain and all addresses would be used in order of insertion.
+    ///
+    /// When port of address is 0 the port from request uri would be used.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use xitca_client::ClientBuilder;
+    ///
+    /// # fn resolve() {
+    /// let client = ClientBuilder::new()
+    ///     .resolve("api.internal", ([10, 0, 0, 1], 0).into())
+    ///     .finish();
+    /// # }
+    /// ```
+    pub fn resolve(mut self, domain: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve_overrides
+            .entry(domain.into().into_boxed_str())
+            .or_default()
+            .push(addr);
+        self
+    }
+
+    /// Enable caching of DNS resolution result with given time to live.
+    /// Cached addresses are shared between requests to the same domain regardless of port.
+    ///
+    /// Default to no caching.
+    pub fn set_resolve_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.resolve_cache_ttl = Some(ttl);
+        self
+    }
+
     /// Use custom tls connector for tls handshaking. custom connector must impl [Service] trait.
     ///
     /// # Examples
@@ -256,6 +390,10 @@ impl ClientBuilder {
             + 'static,
     {
         self.connector = Box::new(connector);
+        #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+        {
+            self.tls.backend = None;
+        }
         self
     }
 
@@ -299,6 +437,15 @@ impl ClientBuilder {
         self
     }
 
+    /// Set timeout for waiting a connection from pool when connections to remote host reach pool capacity.
+    /// See [ClientBuilder::set_pool_capacity] for detail.
+    ///
+    /// Default to wait without timeout.
+    pub fn set_pool_acquire_timeout(mut self, dur: Duration) -> Self {
+        self.pool_acquire_timeout = Some(dur);
+        self
+    }
+
     /// Set [TimeoutConfig] for client.
     pub fn set_timeout(mut self, timeout_config: TimeoutConfig) -> Self {
         self.timeout_config = timeout_config;
@@ -313,6 +460,81 @@ impl ClientBuilder {
         self
     }
 
+    /// Set delay between concurrent connection attempts when domain is resolved to multiple addresses.
+    ///
+    /// When a domain is resolved to both IPv6 and IPv4 addresses client would try to connect to them in
+    /// interleaved order with IPv6 first. A new attempt starts after the delay if previous ones are not
+    /// finished yet and the first established connection is used. (RFC 8305 Happy Eyeballs)
+    ///
+    /// Default to 250 milliseconds.
+    pub fn set_connection_attempt_delay(mut self, dur: Duration) -> Self {
+        self.connection_attempt_delay = dur;
+        self
+    }
+
+    /// Route all `http://` and `https://` requests through unix domain socket of given path.
+    /// Request uri's authority would still be used for `Host` header and tls server name.
+    ///
+    /// Without this setting unix domain socket can be targeted per request with `http+unix://` uri scheme
+    /// where the host part is hex encoded socket path.
+    /// (e.g. `http+unix://2f7661722f72756e2f646f636b65722e736f636b/info` for `/var/run/docker.sock`)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use xitca_client::ClientBuilder;
+    ///
+    /// # async fn docker() -> Result<(), xitca_client::error::Error> {
+    /// let client = ClientBuilder::new().unix_socket("/var/run/docker.sock").finish();
+    /// let res = client.get("http://localhost/info").send().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unix_socket(mut self, path: impl Into<PathBuf>) -> Self {
+        self.unix_socket = Some(path.into());
+        self
+    }
+
+    /// Set interval of sending http/2 PING frame to check health of idle and active connections.
+    /// Connection would be closed and removed from pool when PING is not acknowledged in time.
+    /// See [ClientBuilder::set_h2_keep_alive_timeout] for setting the timeout.
+    ///
+    /// Default to no PING frame.
+    #[cfg(feature = "http2")]
+    pub fn set_h2_keep_alive_interval(mut self, dur: Duration) -> Self {
+        self.h2_config.keep_alive_interval = Some(dur);
+        self
+    }
+
+    /// Set timeout for receiving acknowledgement of http/2 PING frame.
+    ///
+    /// Default to 20 seconds.
+    #[cfg(feature = "http2")]
+    pub fn set_h2_keep_alive_timeout(mut self, dur: Duration) -> Self {
+        self.h2_config.keep_alive_timeout = dur;
+        self
+    }
+
+    /// Set if http/3 request should fall back to http/2 or http/1 when QUIC connection can not be established.
+    /// When disabled connection error is returned to caller instead.
+    ///
+    /// Default to true.
+    #[cfg(feature = "http3")]
+    pub fn set_h3_fallback(mut self, fallback: bool) -> Self {
+        self.h3_config.fallback = fallback;
+        self
+    }
+
+    /// Set if http/3 endpoints should be learned from `Alt-Svc` response header.
+    /// When enabled http/3 request is sent with http/2 or http/1 until origin advertised `h3` alternative
+    /// service and the advertised port is used for QUIC connection afterwards.
+    ///
+    /// Default to false and http/3 connection is always attempted first.
+    #[cfg(feature = "http3")]
+    pub fn set_h3_alt_svc_learning(mut self, learning: bool) -> Self {
+        self.h3_config.alt_svc = learning;
+        self
+    }
+
     /// Set capacity of the connection pool for re-useable connection.
     ///
     /// # Note
@@ -331,6 +553,23 @@ impl ClientBuilder {
         self
     }
 
+    /// Set max count of requests waiting for a connection when connections to a remote Domain reach pool
+    /// capacity. Request beyond the limit fails immediately with [PendingLimitError] and request waiting
+    /// longer than [ClientBuilder::set_pool_acquire_timeout] fails with [TimeoutError::PoolAcquire].
+    ///
+    /// # Note
+    /// max pending only applies to http/1 protocol like pool capacity.
+    /// pass 0 to fail request immediately when pool capacity is reached.
+    ///
+    /// Default to no limit.
+    ///
+    /// [PendingLimitError]: crate::error::PendingLimitError
+    /// [TimeoutError::PoolAcquire]: crate::error::TimeoutError::PoolAcquire
+    pub fn set_pool_max_pending(mut self, max: usize) -> Self {
+        self.pool_max_pending = max;
+        self
+    }
+
     /// Set duration of keep alive idle connection.
     ///
     /// This duration force a connection to be closed if it's idle for this long.
@@ -382,93 +621,37 @@ impl ClientBuilder {
     }
 
     /// Finish the builder and construct [Client] instance.
+    ///
+    /// # Panics
+    /// - panic when tls config contains malformed certificate or key. See [ClientBuilder::try_finish] for
+    ///   non panicking variant.
     pub fn finish(self) -> Client {
+        self.try_finish()
+            .unwrap_or_else(|e| panic!("Can not apply tls config: {e}"))
+    }
+
+    /// Finish the builder and construct [Client] instance.
+    ///
+    /// Error is returned when tls config contains malformed certificate or key.
+    pub fn try_finish(self) -> Result<Client, Error> {
         #[cfg(feature = "http3")]
         let h3_client = {
             use std::sync::Arc;
 
             use h3_quinn::quinn::Endpoint;
-            use webpki_roots::TLS_SERVER_ROOTS;
-            use xitca_tls::rustls::{ClientConfig, RootCertStore};
-
-            let mut root_store = RootCertStore::empty();
 
-            root_store.extend(TLS_SERVER_ROOTS.iter().cloned());
-
-            let mut cfg = ClientConfig::builder()
-                .with_root_certificates(root_store)
-                .with_no_client_auth();
-
-            cfg.alpn_protocols = vec![b"h3".to_vec(), b"h32-29".to_vec()];
+            #[allow(unused_mut)]
+            let mut config = self.tls.config.clone();
 
+            // http/3 connection to server with self signed certificates.
             #[cfg(feature = "dangerous")]
             {
-                use xitca_tls::rustls::{
-                    self,
-                    client::danger::HandshakeSignatureValid,
-                    crypto::{verify_tls12_signature, verify_tls13_signature},
-                    pki_types::{CertificateDer, ServerName, UnixTime},
-                    DigitallySignedStruct,
-                };
-
-                #[derive(Debug)]
-                pub(crate) struct SkipServerVerification;
-
-                impl SkipServerVerification {
-                    fn new() -> Arc<Self> {
-                        Arc::new(Self)
-                    }
-                }
+                config.verify = false;
+            }
 
-                impl rustls::client::danger::ServerCertVerifier for SkipServerVerification {
-                    fn verify_server_cert(
-                        &self,
-                        _end_entity: &CertificateDer<'_>,
-                        _intermediates: &[CertificateDer<'_>],
-                        _server_name: &ServerName<'_>,
-                        _ocsp: &[u8],
-                        _now: UnixTime,
-                    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
-                        Ok(rustls::client::danger::ServerCertVerified::assertion())
-                    }
-
-                    fn verify_tls12_signature(
-                        &self,
-                        message: &[u8],
-                        cert: &CertificateDer<'_>,
-                        dss: &DigitallySignedStruct,
-                    ) -> Result<HandshakeSignatureValid, rustls::Error> {
-                        verify_tls12_signature(
-                            message,
-                            cert,
-                            dss,
-                            &rustls::crypto::ring::default_provider().signature_verification_algorithms,
-                        )
-                    }
-
-                    fn verify_tls13_signature(
-                        &self,
-                        message: &[u8],
-                        cert: &CertificateDer<'_>,
-                        dss: &DigitallySignedStruct,
-                    ) -> Result<HandshakeSignatureValid, rustls::Error> {
-                        verify_tls13_signature(
-                            message,
-                            cert,
-                            dss,
-                            &rustls::crypto::ring::default_provider().signature_verification_algorithms,
-                        )
-                    }
-
-                    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
-                        rustls::crypto::ring::default_provider()
-                            .signature_verification_algorithms
-                            .supported_schemes()
-                    }
-                }
+            let mut cfg = crate::tls::config::rustls::client_config(&config)?;
 
-                cfg.dangerous().set_certificate_verifier(SkipServerVerification::new());
-            }
+            cfg.alpn_protocols = vec![b"h3".to_vec(), b"h32-29".to_vec()];
 
             let mut endpoint = match self.local_addr {
                 Some(addr) => Endpoint::client(addr).unwrap(),
@@ -481,19 +664,59 @@ impl ClientBuilder {
             endpoint
         };
 
-        Client {
-            exclusive_pool: pool::exclusive::Pool::new(self.pool_capacity, self.keep_alive_idle, self.keep_alive_born),
+        #[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+        let connector = match self.tls.backend {
+            Some(backend) => {
+                let default = self.tls_connector_from_config(backend, &self.tls.config)?;
+                if self.tls.host_configs.is_empty() {
+                    default
+                } else {
+                    let hosts = self
+                        .tls
+                        .host_configs
+                        .iter()
+                        .map(|(host, config)| Ok((host.clone(), self.tls_connector_from_config(backend, config)?)))
+                        .collect::<Result<_, Error>>()?;
+                    Box::new(connector::PerHost { default, hosts })
+                }
+            }
+            None => self.connector,
+        };
+
+        #[cfg(not(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto")))]
+        let connector = self.connector;
+
+        Ok(Client {
+            exclusive_pool: pool::exclusive::Pool::new(
+                self.pool_capacity,
+                self.pool_max_pending,
+                self.pool_acquire_timeout,
+                self.keep_alive_idle,
+                self.keep_alive_born,
+            ),
             shared_pool: pool::shared::Pool::with_capacity(self.pool_capacity),
-            connector: self.connector,
-            resolver: self.resolver,
+            connector,
+            resolver: Box::new(Resolver::new(
+                self.resolver,
+                self.resolve_overrides,
+                self.resolve_cache_ttl,
+            )),
             timeout_config: self.timeout_config,
             max_http_version: self.max_http_version,
             local_addr: self.local_addr,
+            connection_attempt_delay: self.connection_attempt_delay,
+            unix_socket: self.unix_socket.map(PathBuf::into_boxed_path),
+            #[cfg(feature = "http2")]
+            h2_config: self.h2_config,
+            #[cfg(feature = "http3")]
+            h3_config: self.h3_config,
+            #[cfg(feature = "http3")]
+            alt_svc: crate::h3::alt_svc::AltSvcCache::new(),
             date_service: DateTimeService::new(),
             service: self.service,
             #[cfg(feature = "http3")]
             h3_client,
-        }
+        })
     }
 }
 
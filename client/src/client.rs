@@ -1,14 +1,22 @@
-use core::{net::SocketAddr, pin::Pin};
+use core::{
+    future::{poll_fn, Future},
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Poll},
+    time::Duration,
+};
+
+use std::path::Path;
 
 use futures_core::stream::Stream;
-use tokio::time::{Instant, Sleep};
+use tokio::time::{sleep, Instant, Sleep};
 use xitca_io::net::{TcpSocket, TcpStream};
 
 use crate::{
     body::{BodyError, BoxBody},
     builder::ClientBuilder,
     bytes::Bytes,
-    connect::Connect,
+    connect::{interleave_addrs, Connect},
     connection::{ConnectionExclusive, ConnectionKey, ConnectionShared},
     date::DateTimeService,
     error::{Error, ResolveError, TimeoutError},
@@ -36,6 +44,14 @@ pub struct Client {
     pub(crate) timeout_config: TimeoutConfig,
     pub(crate) max_http_version: Version,
     pub(crate) local_addr: Option<SocketAddr>,
+    pub(crate) connection_attempt_delay: Duration,
+    pub(crate) unix_socket: Option<Box<Path>>,
+    #[cfg(feature = "http2")]
+    pub(crate) h2_config: crate::h2::Config,
+    #[cfg(feature = "http3")]
+    pub(crate) h3_config: crate::h3::Config,
+    #[cfg(feature = "http3")]
+    pub(crate) alt_svc: crate::h3::alt_svc::AltSvcCache,
     pub(crate) date_service: DateTimeService,
     pub(crate) service: HttpService,
     #[cfg(feature = "http3")]
@@ -280,6 +296,13 @@ impl Client {
     /// // write part can operate with Sink trait implement.
     /// write.send(Message::Text("996".into())).await?;
     ///
+    /// // ws can also be split into owned parts that can be moved into separate tasks.
+    /// let (mut write, mut read) = ws.into_split();
+    /// tokio::spawn(async move {
+    ///     let _ = write.send(Message::Text("996".into())).await;
+    /// });
+    /// let _ = read.next().await;
+    ///
     /// Ok(())
     /// # }
     /// ```
@@ -291,6 +314,19 @@ impl Client {
         self.get(url).version(Version::HTTP_11).mutate_marker()
     }
 
+    /// Start a new server-sent events request.
+    ///
+    /// See [EventSource] for detail.
+    ///
+    /// [EventSource]: crate::sse::EventSource
+    pub fn sse<U>(&self, url: U) -> crate::sse::SseRequest<'_>
+    where
+        uri::Uri: TryFrom<U>,
+        Error: From<<uri::Uri as TryFrom<U>>::Error>,
+    {
+        self.get(url).mutate_marker()
+    }
+
     #[cfg(all(feature = "websocket", feature = "http2"))]
     /// Start a new websocket request with HTTP/2.
     pub fn ws2<U>(&self, url: U) -> crate::ws::WsRequest<'_>
@@ -316,7 +352,10 @@ impl Client {
     ) -> Result<(ConnectionExclusive, Version), Error> {
         match connect.uri {
             Uri::Tcp(_) | Uri::Tls(_) => {
-                let conn = self.make_tcp(connect, timer).await?;
+                let conn = match self.unix_socket {
+                    Some(ref path) => self.make_unix(path, timer).await?,
+                    None => self.make_tcp(connect, timer).await?,
+                };
 
                 if matches!(connect.uri, Uri::Tcp(_)) {
                     return Ok((conn, expected_version));
@@ -335,10 +374,10 @@ impl Client {
 
                 Ok((conn, version))
             }
-            Uri::Unix(_) => self
-                .make_unix(connect, timer)
-                .await
-                .map(|conn| (conn, expected_version)),
+            Uri::Unix(_) => {
+                let path = connect.uri.unix_socket_path()?;
+                self.make_unix(&path, timer).await.map(|conn| (conn, expected_version))
+            }
         }
     }
 
@@ -370,63 +409,71 @@ impl Client {
     }
 
     async fn make_tcp_inner(&self, connect: &Connect<'_>) -> Result<TcpStream, Error> {
-        let mut iter = connect.addrs();
+        let local_addr = self.local_addr;
 
-        let mut addr = iter.next().ok_or_else(|| ResolveError::new(connect.hostname()))?;
+        // bound local address can only connect to remote address of the same family.
+        let addrs = connect
+            .addrs()
+            .filter(|addr| local_addr.is_none_or(|local| local.is_ipv6() == addr.is_ipv6()));
 
-        // try to connect with all addresses resolved by dns resolver.
-        // return the last error when all are fail to be connected.
-        loop {
-            match self.maybe_connect_with_local_addr(addr).await {
-                Ok(stream) => return Ok(stream),
-                Err(e) => match iter.next() {
-                    Some(a) => addr = a,
-                    None => return Err(e),
-                },
-            }
+        let mut addrs = interleave_addrs(addrs).into_iter();
+
+        let addr = addrs.next().ok_or_else(|| ResolveError::new(connect.hostname()))?;
+
+        if addrs.len() == 0 {
+            return connect_with_local_addr(addr, local_addr).await;
         }
-    }
 
-    async fn maybe_connect_with_local_addr(&self, addr: SocketAddr) -> Result<TcpStream, Error> {
-        match self.local_addr {
-            Some(local_addr) => {
-                let socket = match local_addr {
-                    SocketAddr::V4(_) => {
-                        let socket = TcpSocket::new_v4()?;
-                        socket.bind(local_addr)?;
-                        socket
-                    }
-                    SocketAddr::V6(_) => {
-                        let socket = TcpSocket::new_v6()?;
-                        socket.bind(local_addr)?;
-                        socket
+        // happy eyeballs connecting (RFC 8305). addresses are tried in interleaved family order.
+        // a new attempt starts when the previous one failed or when connection attempt delay
+        // elapsed. the first successful connection is returned and all other attempts are dropped.
+        // return the last error when all are fail to be connected.
+        let attempt_delay = self.connection_attempt_delay;
+        let mut delay = Box::pin(sleep(attempt_delay));
+        let mut attempts = vec![Box::pin(connect_with_local_addr(addr, local_addr))];
+        let mut err = None;
+
+        poll_fn(|cx| loop {
+            let mut i = 0;
+            while i < attempts.len() {
+                match attempts[i].as_mut().poll(cx) {
+                    Poll::Ready(Ok(stream)) => return Poll::Ready(Ok(stream)),
+                    Poll::Ready(Err(e)) => {
+                        drop(attempts.swap_remove(i));
+                        err = Some(e);
+                        if let Some(addr) = addrs.next() {
+                            attempts.push(Box::pin(connect_with_local_addr(addr, local_addr)));
+                            delay.as_mut().reset(Instant::now() + attempt_delay);
+                        }
                     }
+                    Poll::Pending => i += 1,
+                }
+            }
+
+            if addrs.len() == 0 {
+                return match attempts.is_empty() {
+                    true => Poll::Ready(Err(err.take().unwrap())),
+                    false => Poll::Pending,
                 };
-                let stream = socket.connect(addr).await?;
-                Ok(TcpStream::from(stream))
             }
-            None => TcpStream::connect(addr).await.map_err(Into::into),
-        }
+
+            ready!(delay.as_mut().poll(cx));
+
+            let addr = addrs.next().unwrap();
+            attempts.push(Box::pin(connect_with_local_addr(addr, local_addr)));
+            delay.as_mut().reset(Instant::now() + attempt_delay);
+        })
+        .await
     }
 
-    async fn make_unix(
-        &self,
-        _connect: &Connect<'_>,
-        timer: &mut Pin<Box<Sleep>>,
-    ) -> Result<ConnectionExclusive, Error> {
+    async fn make_unix(&self, _path: &Path, timer: &mut Pin<Box<Sleep>>) -> Result<ConnectionExclusive, Error> {
         timer
             .as_mut()
             .reset(Instant::now() + self.timeout_config.connect_timeout);
 
         #[cfg(unix)]
         {
-            let path = format!(
-                "/{}{}",
-                _connect.uri.authority().unwrap().as_str(),
-                _connect.uri.path_and_query().unwrap().as_str()
-            );
-
-            let stream = xitca_io::net::UnixStream::connect(path)
+            let stream = xitca_io::net::UnixStream::connect(_path)
                 .timeout(timer.as_mut())
                 .await
                 .map_err(|_| TimeoutError::Connect)??;
@@ -441,6 +488,28 @@ impl Client {
     }
 }
 
+async fn connect_with_local_addr(addr: SocketAddr, local_addr: Option<SocketAddr>) -> Result<TcpStream, Error> {
+    match local_addr {
+        Some(local_addr) => {
+            let socket = match local_addr {
+                SocketAddr::V4(_) => {
+                    let socket = TcpSocket::new_v4()?;
+                    socket.bind(local_addr)?;
+                    socket
+                }
+                SocketAddr::V6(_) => {
+                    let socket = TcpSocket::new_v6()?;
+                    socket.bind(local_addr)?;
+                    socket
+                }
+            };
+            let stream = socket.connect(addr).await?;
+            Ok(TcpStream::from(stream))
+        }
+        None => TcpStream::connect(addr).await.map_err(Into::into),
+    }
+}
+
 #[cfg(feature = "compress")]
 #[cfg(feature = "openssl")]
 #[cfg(test)]
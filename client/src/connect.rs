@@ -174,6 +174,22 @@ impl iter::ExactSizeIterator for AddrsIter<'_> {}
 
 impl iter::FusedIterator for AddrsIter<'_> {}
 
+// sort addresses for happy eyeballs connecting. (RFC 8305 section 4)
+// addresses are interleaved by family with IPv6 preferred as the first one.
+pub(crate) fn interleave_addrs(addrs: impl Iterator<Item = SocketAddr>) -> Vec<SocketAddr> {
+    let (v6, v4): (Vec<_>, Vec<_>) = addrs.partition(SocketAddr::is_ipv6);
+
+    let mut res = Vec::with_capacity(v6.len() + v4.len());
+    let (mut v6, mut v4) = (v6.into_iter(), v4.into_iter());
+
+    loop {
+        match (v6.next(), v4.next()) {
+            (None, None) => return res,
+            (a, b) => res.extend(a.into_iter().chain(b)),
+        }
+    }
+}
+
 fn parse_host(host: &str) -> (&str, Option<u16>) {
     let mut parts_iter = host.splitn(2, ':');
 
@@ -203,6 +219,20 @@ mod tests {
         assert_eq!(parse_host("example.com:false:false"), ("example.com", None));
     }
 
+    #[test]
+    fn test_interleave_addrs() {
+        let v4_1 = SocketAddr::from(([127, 0, 0, 1], 80));
+        let v4_2 = SocketAddr::from(([127, 0, 0, 2], 80));
+        let v4_3 = SocketAddr::from(([127, 0, 0, 3], 80));
+        let v6 = SocketAddr::from((IpAddr::from(core::net::Ipv6Addr::LOCALHOST), 80));
+
+        let addrs = interleave_addrs([v4_1, v4_2, v6, v4_3].into_iter());
+        assert_eq!(addrs, [v6, v4_1, v4_2, v4_3]);
+
+        let addrs = interleave_addrs([v4_1, v4_2].into_iter());
+        assert_eq!(addrs, [v4_1, v4_2]);
+    }
+
     #[test]
     fn test_addr_iter_multi() {
         let localhost = SocketAddr::from((IpAddr::from(Ipv4Addr::LOCALHOST), 8080));
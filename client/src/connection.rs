@@ -62,6 +62,11 @@ impl From<&Uri<'_>> for ConnectionKey {
     fn from(uri: &Uri<'_>) -> Self {
         match *uri {
             Uri::Tcp(uri) | Uri::Tls(uri) => ConnectionKey::Regular(uri.authority().unwrap().clone()),
+            // socket path is encoded in authority and path is part of request.
+            Uri::Unix(uri) if uri.scheme_str() == Some("http+unix") => ConnectionKey::Unix(AuthorityWithPath {
+                authority: uri.authority().unwrap().clone(),
+                path_and_query: PathAndQuery::from_static("/"),
+            }),
             Uri::Unix(uri) => ConnectionKey::Unix(AuthorityWithPath {
                 authority: uri.authority().unwrap().clone(),
                 path_and_query: uri.path_and_query().unwrap().clone(),
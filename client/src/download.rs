@@ -0,0 +1,395 @@
+//! streaming download helpers.
+
+use core::{future::poll_fn, pin::pin};
+
+use std::path::Path;
+
+use futures_core::stream::Stream;
+use tokio::{
+    fs::{File, OpenOptions},
+    io::{AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    time::Instant,
+};
+
+use crate::{
+    error::{ChecksumError, Error, ErrorResponse, TimeoutError},
+    http::{
+        header::{HeaderValue, CONTENT_LENGTH, CONTENT_RANGE, RANGE},
+        StatusCode,
+    },
+    request::RequestBuilder,
+    response::Response,
+    timeout::Timeout,
+};
+
+/// trait for verifying downloaded content.
+///
+/// # Examples
+/// ```rust
+/// use xitca_client::download::Checksum;
+///
+/// // a naive checksum summing all bytes.
+/// struct Sum {
+///     sum: u64,
+///     expect: u64,
+/// }
+///
+/// impl Checksum for Sum {
+///     fn update(&mut self, data: &[u8]) {
+///         self.sum = data.iter().fold(self.sum, |sum, b| sum.wrapping_add(*b as u64));
+///     }
+///
+///     fn verify(&self) -> bool {
+///         self.sum == self.expect
+///     }
+/// }
+/// ```
+pub trait Checksum {
+    /// feed downloaded data in order.
+    fn update(&mut self, data: &[u8]);
+
+    /// verify checksum after all data is fed.
+    fn verify(&self) -> bool;
+}
+
+type ProgressFn = Box<dyn FnMut(u64, Option<u64>) + Send>;
+
+/// options for streaming response body to writer or file.
+#[derive(Default)]
+pub struct Download {
+    progress: Option<ProgressFn>,
+    checksum: Option<Box<dyn Checksum + Send>>,
+}
+
+impl Download {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// set progress callback receiving downloaded bytes count and optional total bytes count.
+    /// for resumed download both counts include the previously downloaded part.
+    pub fn progress<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(u64, Option<u64>) + Send + 'static,
+    {
+        self.progress = Some(Box::new(func));
+        self
+    }
+
+    /// set checksum verifier of downloaded content. [ChecksumError] is returned when verification failed.
+    ///
+    /// [ChecksumError]: crate::error::ChecksumError
+    pub fn checksum<C>(mut self, checksum: C) -> Self
+    where
+        C: Checksum + Send + 'static,
+    {
+        self.checksum = Some(Box::new(checksum));
+        self
+    }
+}
+
+impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
+    /// Stream response body to writer and return the number of bytes written. Response is consumed.
+    ///
+    /// Unlike body collecting methods payload limit does not apply and response timeout is
+    /// treated as idle timeout between body chunks.
+    pub async fn stream_to<W>(self, writer: &mut W) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.stream_to_with(writer, Download::new()).await
+    }
+
+    /// Stream response body to writer with [Download] options and return the number of bytes written.
+    pub async fn stream_to_with<W>(self, writer: &mut W, mut download: Download) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let total = content_length(&self);
+        self.stream(writer, &mut download, 0, total).await
+    }
+
+    async fn stream<W>(
+        self,
+        writer: &mut W,
+        download: &mut Download,
+        offset: u64,
+        total: Option<u64>,
+    ) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (_, body) = self.res.into_parts();
+        let mut timer = self.timer;
+        let timeout = self.timeout;
+
+        let mut body = pin!(body);
+        let mut written = offset;
+
+        loop {
+            timer.as_mut().reset(Instant::now() + timeout);
+
+            match poll_fn(|cx| body.as_mut().poll_next(cx)).timeout(timer.as_mut()).await {
+                Ok(Some(Ok(buf))) => {
+                    if let Err(e) = writer.write_all(&buf).await {
+                        body.destroy_on_drop();
+                        return Err(e.into());
+                    }
+                    written += buf.len() as u64;
+                    if let Some(ref mut checksum) = download.checksum {
+                        checksum.update(&buf);
+                    }
+                    if let Some(ref mut progress) = download.progress {
+                        progress(written, total);
+                    }
+                }
+                Ok(Some(Err(e))) => {
+                    body.destroy_on_drop();
+                    return Err(e.into());
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    body.destroy_on_drop();
+                    return Err(TimeoutError::Response.into());
+                }
+            }
+        }
+
+        writer.flush().await?;
+
+        if download.checksum.as_ref().is_some_and(|checksum| !checksum.verify()) {
+            return Err(ChecksumError.into());
+        }
+
+        Ok(written - offset)
+    }
+}
+
+impl RequestBuilder<'_> {
+    /// Send request and download response body to file of given path. Return the total size of file.
+    ///
+    /// When file already exists download is resumed with `Range` request from the end of file. Server
+    /// ignoring range request would cause the file to be truncated and downloaded from start. Server rejecting
+    /// range request with `416 Range Not Satisfiable` means the file is complete when it's size equals to
+    /// the total size in `Content-Range` header and the file is verified with checksum when it's given.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use xitca_client::{download::Download, error::Error, Client};
+    /// # async fn download(client: &Client) -> Result<(), Error> {
+    /// let size = client
+    ///     .get("https://example.com/file.tar.gz")
+    ///     .download_to_file("file.tar.gz", Download::new().progress(|n, total| println!("{n}/{total:?}")))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_to_file(mut self, path: impl AsRef<Path>, mut download: Download) -> Result<u64, Error> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.as_ref())
+            .await?;
+
+        let offset = file.metadata().await?.len();
+
+        if offset > 0 {
+            let range = format!("bytes={offset}-");
+            self.headers_mut().insert(RANGE, HeaderValue::try_from(range).unwrap());
+        }
+
+        let res = self.send().await?;
+
+        let (offset, total) = match res.status() {
+            StatusCode::PARTIAL_CONTENT if offset > 0 => {
+                let (start, total) = content_range(&res).ok_or_else(|| range_error(&res))?;
+                if start != offset {
+                    return Err(range_error(&res));
+                }
+                (offset, total)
+            }
+            // file is possibly complete. confirm it with the total size of resource and checksum.
+            StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+                if unsatisfied_range(&res) != Some(offset) {
+                    return Err(ErrorResponse {
+                        expect_status: StatusCode::PARTIAL_CONTENT,
+                        status: res.status(),
+                        description: "file size does not match the total size of resource",
+                    }
+                    .into());
+                }
+                drop(res);
+                if let Some(ref mut checksum) = download.checksum {
+                    feed_checksum(&mut file, checksum.as_mut()).await?;
+                    if !checksum.verify() {
+                        return Err(ChecksumError.into());
+                    }
+                }
+                return Ok(offset);
+            }
+            status if status.is_success() => {
+                file.set_len(0).await?;
+                (0, content_length(&res))
+            }
+            status => {
+                return Err(ErrorResponse {
+                    expect_status: StatusCode::OK,
+                    status,
+                    description: "download failed with unexpected response",
+                }
+                .into());
+            }
+        };
+
+        // feed previously downloaded content to checksum.
+        if offset > 0 {
+            if let Some(ref mut checksum) = download.checksum {
+                feed_checksum(&mut file, checksum.as_mut()).await?;
+            }
+        }
+
+        let mut file = tokio::io::BufWriter::new(file);
+        tokio::io::AsyncSeekExt::seek(&mut file, std::io::SeekFrom::Start(offset)).await?;
+
+        let n = res.stream(&mut file, &mut download, offset, total).await?;
+
+        Ok(offset + n)
+    }
+}
+
+fn content_length<const PAYLOAD_LIMIT: usize>(res: &Response<PAYLOAD_LIMIT>) -> Option<u64> {
+    res.headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+// parse content-range header in form of `bytes {start}-{end}/{total}`
+fn content_range(res: &Response) -> Option<(u64, Option<u64>)> {
+    let range = res
+        .headers()
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes ")?;
+    let (range, total) = range.split_once('/')?;
+    let (start, _) = range.split_once('-')?;
+    Some((start.trim().parse().ok()?, total.trim().parse().ok()))
+}
+
+// parse content-range header of unsatisfied range in form of `bytes */{total}`
+fn unsatisfied_range(res: &Response) -> Option<u64> {
+    res.headers()
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes */")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+// feed content of file from current position to checksum.
+async fn feed_checksum(file: &mut File, checksum: &mut (dyn Checksum + Send)) -> Result<(), Error> {
+    let mut buf = vec![0; 8192];
+    loop {
+        match file.read(&mut buf).await? {
+            0 => return Ok(()),
+            n => checksum.update(&buf[..n]),
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn range_error(res: &Response) -> Error {
+    ErrorResponse {
+        expect_status: StatusCode::PARTIAL_CONTENT,
+        status: res.status(),
+        description: "content range does not match resuming position of file",
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    use crate::{
+        body::{BodyError, ResponseBody},
+        bytes::Bytes,
+        http,
+    };
+
+    use super::*;
+
+    struct Sum {
+        sum: u64,
+        expect: u64,
+    }
+
+    impl Checksum for Sum {
+        fn update(&mut self, data: &[u8]) {
+            self.sum = data.iter().fold(self.sum, |sum, b| sum + *b as u64);
+        }
+
+        fn verify(&self) -> bool {
+            self.sum == self.expect
+        }
+    }
+
+    fn response() -> Response {
+        let chunks = [&b"foo"[..], b"bar"].map(|b| Ok::<_, BodyError>(Bytes::from_static(b)));
+        let mut res = http::Response::new(ResponseBody::Unknown(Box::pin(futures::stream::iter(chunks))));
+        res.headers_mut().insert(CONTENT_LENGTH, HeaderValue::from_static("6"));
+        let timer = Box::pin(tokio::time::sleep(core::time::Duration::from_secs(1)));
+        Response::new(res, timer, core::time::Duration::from_secs(1))
+    }
+
+    #[tokio::test]
+    async fn stream_to() {
+        let progress = Arc::new(AtomicU64::new(0));
+        let progress2 = progress.clone();
+
+        let download = Download::new()
+            .progress(move |n, total| {
+                assert_eq!(total, Some(6));
+                progress2.store(n, Ordering::SeqCst);
+            })
+            .checksum(Sum {
+                sum: 0,
+                expect: b"foobar".iter().map(|b| *b as u64).sum(),
+            });
+
+        let mut buf = Vec::new();
+        let n = response().stream_to_with(&mut buf, download).await.unwrap();
+        assert_eq!(n, 6);
+        assert_eq!(buf, b"foobar");
+        assert_eq!(progress.load(Ordering::SeqCst), 6);
+
+        let download = Download::new().checksum(Sum { sum: 0, expect: 0 });
+        let err = response()
+            .stream_to_with(&mut Vec::new(), download)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::Std(e) if e.downcast_ref::<ChecksumError>().is_some()));
+    }
+
+    #[tokio::test]
+    async fn unsatisfied_range_total() {
+        let mut res = response();
+        res.headers_mut()
+            .insert(CONTENT_RANGE, HeaderValue::from_static("bytes */6"));
+        assert_eq!(unsatisfied_range(&res), Some(6));
+
+        res.headers_mut()
+            .insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-5/6"));
+        assert_eq!(unsatisfied_range(&res), None);
+    }
+}
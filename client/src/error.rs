@@ -93,6 +93,7 @@ pub enum InvalidUri {
     MissingAuthority,
     MissingPathQuery,
     UnknownScheme,
+    InvalidUnixSocketPath,
     Other(uri::InvalidUri),
 }
 
@@ -115,12 +116,14 @@ impl From<InvalidUri> for Error {
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum TimeoutError {
     Resolve,
     Connect,
     TlsHandshake,
     Request,
     Response,
+    PoolAcquire,
 }
 
 impl fmt::Display for TimeoutError {
@@ -131,6 +134,7 @@ impl fmt::Display for TimeoutError {
             Self::TlsHandshake => f.write_str("tls handshake timeout"),
             Self::Request => f.write_str("request sending timeout"),
             Self::Response => f.write_str("response receiving timeout"),
+            Self::PoolAcquire => f.write_str("connection pool acquiring timeout"),
         }
     }
 }
@@ -143,6 +147,13 @@ impl From<TimeoutError> for Error {
     }
 }
 
+#[cfg(feature = "signature")]
+impl From<http_signature::Error> for Error {
+    fn from(e: http_signature::Error) -> Self {
+        Self::Std(Box::new(e))
+    }
+}
+
 #[derive(Debug)]
 pub struct ResolveError {
     domain: String,
@@ -173,6 +184,8 @@ pub enum ParseError {
     String(str::Utf8Error),
     #[cfg(feature = "json")]
     Json(serde_json::Error),
+    #[cfg(feature = "urlencoded")]
+    Form(serde_urlencoded::de::Error),
     #[cfg(feature = "websocket")]
     WebSocket(http_ws::ProtocolError),
 }
@@ -226,6 +239,12 @@ mod _rustls {
             Self::Rustls(e)
         }
     }
+
+    impl From<xitca_tls::rustls::Error> for Error {
+        fn from(e: xitca_tls::rustls::Error) -> Self {
+            Self::Std(Box::new(e))
+        }
+    }
 }
 
 #[cfg(feature = "json")]
@@ -235,6 +254,13 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "urlencoded")]
+impl From<serde_urlencoded::de::Error> for Error {
+    fn from(e: serde_urlencoded::de::Error) -> Self {
+        Self::Parse(ParseError::Form(e))
+    }
+}
+
 #[cfg(feature = "http1")]
 impl From<crate::h1::Error> for Error {
     fn from(e: crate::h1::Error) -> Self {
@@ -285,6 +311,96 @@ impl From<ErrorResponse> for Error {
     }
 }
 
+/// error type for response body exceeding payload limit when collecting.
+#[derive(Debug)]
+pub struct PayloadLimitError {
+    pub limit: usize,
+}
+
+impl fmt::Display for PayloadLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "response body exceeds payload limit of {} bytes", self.limit)
+    }
+}
+
+impl error::Error for PayloadLimitError {}
+
+impl From<PayloadLimitError> for Error {
+    fn from(e: PayloadLimitError) -> Self {
+        Self::Std(Box::new(e))
+    }
+}
+
+/// error type for response with content type mismatching the typed body collecting.
+#[derive(Debug)]
+pub struct ContentTypeError {
+    pub expect: &'static str,
+    pub content_type: String,
+}
+
+impl fmt::Display for ContentTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expecting response with content type {}, got {} instead",
+            self.expect, self.content_type
+        )
+    }
+}
+
+impl error::Error for ContentTypeError {}
+
+impl From<ContentTypeError> for Error {
+    fn from(e: ContentTypeError) -> Self {
+        Self::Std(Box::new(e))
+    }
+}
+
+/// error type for request rejected by connection pool when the count of requests waiting for a connection
+/// to the same remote host exceeds limit. see [ClientBuilder::set_pool_max_pending] for detail.
+///
+/// [ClientBuilder::set_pool_max_pending]: crate::ClientBuilder::set_pool_max_pending
+#[derive(Debug)]
+pub struct PendingLimitError {
+    pub limit: usize,
+}
+
+impl fmt::Display for PendingLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "requests waiting for connection exceeds pending limit of {}",
+            self.limit
+        )
+    }
+}
+
+impl error::Error for PendingLimitError {}
+
+impl From<PendingLimitError> for Error {
+    fn from(e: PendingLimitError) -> Self {
+        Self::Std(Box::new(e))
+    }
+}
+
+/// error type for downloaded content failed checksum verification.
+#[derive(Debug)]
+pub struct ChecksumError;
+
+impl fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("downloaded content failed checksum verification")
+    }
+}
+
+impl error::Error for ChecksumError {}
+
+impl From<ChecksumError> for Error {
+    fn from(e: ChecksumError) -> Self {
+        Self::Std(Box::new(e))
+    }
+}
+
 #[derive(Debug)]
 pub enum FeatureError {
     Http1NotEnabled,
@@ -5,7 +5,24 @@ pub(crate) mod proto;
 
 pub use self::error::Error;
 
+use core::time::Duration;
+
 use h2::client::SendRequest;
 use xitca_http::bytes::Bytes;
 
 pub type Connection = SendRequest<Bytes>;
+
+#[derive(Clone, Copy)]
+pub(crate) struct Config {
+    pub(crate) keep_alive_interval: Option<Duration>,
+    pub(crate) keep_alive_timeout: Duration,
+}
+
+impl Config {
+    pub(crate) const fn new() -> Self {
+        Self {
+            keep_alive_interval: None,
+            keep_alive_timeout: Duration::from_secs(20),
+        }
+    }
+}
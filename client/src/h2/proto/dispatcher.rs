@@ -1,6 +1,12 @@
-use core::{cmp, future::poll_fn, pin::pin};
+use core::{
+    cmp,
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+    time::Duration,
+};
 
-use ::h2::{client, Reason};
+use ::h2::{client, Ping, PingPong, Reason};
 use futures_core::stream::Stream;
 use xitca_http::{
     date::DateTime,
@@ -17,9 +23,16 @@ use crate::{
     body::{BodyError, BodySize, ResponseBody},
     bytes::Bytes,
     date::DateTimeHandle,
-    h2::{body::ResponseBody as H2ResponseBody, Connection, Error},
+    h2::{body::ResponseBody as H2ResponseBody, Config, Connection, Error},
 };
 
+// wait for connection to be ready for opening new stream. concurrent streams are capped by server's
+// SETTINGS_MAX_CONCURRENT_STREAMS and this would wait until capacity is available.
+// error is returned when connection is closed or GOAWAY frame is received from server.
+pub(crate) async fn ready(conn: &mut Connection) -> Result<(), Error> {
+    poll_fn(|cx| conn.poll_ready(cx)).await.map_err(Into::into)
+}
+
 pub(crate) async fn send<B, E>(
     stream: &mut Connection,
     date: DateTimeHandle<'_>,
@@ -128,18 +141,58 @@ where
     Ok(res)
 }
 
-pub(crate) async fn handshake<S>(stream: S) -> Result<Connection, Error>
+pub(crate) async fn handshake<S>(stream: S, config: Config) -> Result<Connection, Error>
 where
     S: AsyncIo + Send + 'static,
 {
-    let (conn, task) = client::Builder::new()
+    let (conn, mut task) = client::Builder::new()
         .enable_push(false)
         .handshake(PollIoAdapter(stream))
         .await?;
 
-    tokio::spawn(async {
-        task.await.expect("http2 connection failed");
+    let keep_alive = config.keep_alive_interval.and_then(|interval| {
+        task.ping_pong()
+            .map(|ping_pong| keep_alive(ping_pong, interval, config.keep_alive_timeout))
+    });
+
+    tokio::spawn(async move {
+        let mut task = pin!(task);
+
+        let res = match keep_alive {
+            Some(keep_alive) => {
+                let mut keep_alive = pin!(keep_alive);
+                // connection is dropped when keep alive ping failed. all clones of Connection would observe
+                // closed connection afterwards.
+                poll_fn(|cx| {
+                    if let Poll::Ready(res) = task.as_mut().poll(cx) {
+                        return Poll::Ready(res);
+                    }
+                    keep_alive.as_mut().poll(cx).map(Err)
+                })
+                .await
+            }
+            None => task.await,
+        };
+
+        // connection closed by GOAWAY frame with NO_ERROR is treated as success.
+        if let Err(e) = res {
+            tracing::debug!("http2 connection closed with error: {e}");
+        }
     });
 
     Ok(conn)
 }
+
+async fn keep_alive(mut ping_pong: PingPong, interval: Duration, timeout: Duration) -> ::h2::Error {
+    loop {
+        tokio::time::sleep(interval).await;
+        match tokio::time::timeout(timeout, ping_pong.ping(Ping::opaque())).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => return e,
+            Err(_) => {
+                tracing::debug!("http2 keep alive ping timed out");
+                return ::h2::Error::from(Reason::PROTOCOL_ERROR);
+            }
+        }
+    }
+}
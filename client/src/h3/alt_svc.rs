@@ -0,0 +1,152 @@
+//! learning http/3 endpoints from Alt-Svc header. (RFC 7838)
+
+use core::time::Duration;
+
+use std::{collections::HashMap, sync::Mutex, time::Instant};
+
+use crate::{
+    error::Error,
+    http::{header::ALT_SVC, uri::Authority, HeaderMap},
+    response::Response,
+    service::{Service, ServiceRequest},
+};
+
+// default freshness of alt-svc entry when "ma" parameter is absent.
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub(crate) struct AltSvcCache(Mutex<HashMap<Authority, (u16, Instant)>>);
+
+impl AltSvcCache {
+    pub(crate) fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+
+    // get udp port of advertised http/3 endpoint for given origin.
+    pub(crate) fn get(&self, authority: &Authority) -> Option<u16> {
+        let mut map = self.0.lock().unwrap();
+        match map.get(authority) {
+            Some((port, expire)) if *expire > Instant::now() => Some(*port),
+            Some(_) => {
+                map.remove(authority);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn update(&self, authority: Authority, headers: &HeaderMap) {
+        let Some(value) = headers.get(ALT_SVC).and_then(|v| v.to_str().ok()) else {
+            return;
+        };
+
+        match parse(value) {
+            Some(AltSvc::Clear) => {
+                self.0.lock().unwrap().remove(&authority);
+            }
+            Some(AltSvc::H3 { port, max_age }) => {
+                let expire = Instant::now() + max_age;
+                self.0.lock().unwrap().insert(authority, (port, expire));
+            }
+            None => {}
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum AltSvc {
+    Clear,
+    H3 { port: u16, max_age: Duration },
+}
+
+// parse alt-svc header value and pick the first h3 alternative on the same host.
+// alternative pointing to different host is ignored.
+fn parse(value: &str) -> Option<AltSvc> {
+    let value = value.trim();
+
+    if value == "clear" {
+        return Some(AltSvc::Clear);
+    }
+
+    value.split(',').find_map(|alt| {
+        let mut params = alt.split(';').map(str::trim);
+
+        let (protocol, authority) = params.next()?.split_once('=')?;
+        if protocol != "h3" {
+            return None;
+        }
+
+        let port = authority.trim_matches('"').strip_prefix(':')?.parse().ok()?;
+
+        let max_age = params
+            .filter_map(|param| param.split_once('='))
+            .find(|(name, _)| *name == "ma")
+            .and_then(|(_, secs)| secs.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_MAX_AGE);
+
+        Some(AltSvc::H3 { port, max_age })
+    })
+}
+
+// record http/3 alternative services advertised by responses.
+pub(crate) struct AltSvcLearn<S> {
+    service: S,
+}
+
+impl<S> AltSvcLearn<S> {
+    pub(crate) const fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<'r, 'c, S> Service<ServiceRequest<'r, 'c>> for AltSvcLearn<S>
+where
+    S: for<'r2, 'c2> Service<ServiceRequest<'r2, 'c2>, Response = Response, Error = Error> + Send + Sync,
+{
+    type Response = Response;
+    type Error = Error;
+
+    async fn call(&self, req: ServiceRequest<'r, 'c>) -> Result<Self::Response, Self::Error> {
+        let client = req.client;
+
+        if !client.h3_config.alt_svc {
+            return self.service.call(req).await;
+        }
+
+        let authority = req.req.uri().authority().cloned();
+
+        let res = self.service.call(req).await?;
+
+        if let Some(authority) = authority {
+            client.alt_svc.update(authority, res.headers());
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_alt_svc() {
+        assert_eq!(parse("clear"), Some(AltSvc::Clear));
+        assert_eq!(
+            parse(r#"h3=":443"; ma=3600"#),
+            Some(AltSvc::H3 {
+                port: 443,
+                max_age: Duration::from_secs(3600)
+            })
+        );
+        assert_eq!(
+            parse(r#"h2=":443", h3=":8443""#),
+            Some(AltSvc::H3 {
+                port: 8443,
+                max_age: DEFAULT_MAX_AGE
+            })
+        );
+        assert_eq!(parse(r#"h3="alt.example.com:443""#), None);
+        assert_eq!(parse(r#"h3-29=":443""#), None);
+    }
+}
@@ -1,5 +1,6 @@
 mod error;
 
+pub(crate) mod alt_svc;
 pub(crate) mod body;
 pub(crate) mod proto;
 
@@ -10,3 +11,18 @@ use h3_quinn::OpenStreams;
 use xitca_http::bytes::Bytes;
 
 pub type Connection = client::SendRequest<OpenStreams, Bytes>;
+
+#[derive(Clone, Copy)]
+pub(crate) struct Config {
+    pub(crate) fallback: bool,
+    pub(crate) alt_svc: bool,
+}
+
+impl Config {
+    pub(crate) const fn new() -> Self {
+        Self {
+            fallback: true,
+            alt_svc: false,
+        }
+    }
+}
@@ -28,7 +28,7 @@
 //! Please reference [ClientBuilder::middleware]
 //!
 //! ## Customize core feature
-//! Please reference [ClientBuilder::resolver] and [ClientBuilder::tls_connector]
+//! Please reference [ClientBuilder::resolver], [ClientBuilder::dns_resolver] and [ClientBuilder::tls_connector]
 
 #![forbid(unsafe_code)]
 
@@ -40,7 +40,6 @@ mod connection;
 mod date;
 mod pool;
 mod request;
-mod resolver;
 mod response;
 mod service;
 mod timeout;
@@ -61,10 +60,16 @@ mod h3;
 #[cfg(feature = "websocket")]
 pub mod ws;
 
+#[cfg(feature = "download")]
+pub mod download;
+
 pub mod error;
 pub mod http_tunnel;
 pub mod middleware;
+pub mod resolver;
+pub mod sse;
 
+pub use self::body::BoxBody;
 pub use self::builder::ClientBuilder;
 pub use self::client::Client;
 pub use self::connect::Connect;
@@ -74,6 +79,9 @@ pub use self::service::{HttpService, Service, ServiceRequest};
 pub use self::timeout::TimeoutConfig;
 pub use self::tls::{connector::Connector, TlsStream};
 
+#[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+pub use self::tls::config::{Identity, TlsConfig};
+
 // re-export http crate.
 pub use xitca_http::http;
 
@@ -0,0 +1,585 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures_core::stream::Stream;
+
+use crate::{
+    body::{BodyError, ResponseBody},
+    bytes::Bytes,
+    error::Error,
+    http::{
+        self,
+        header::{
+            HeaderName, HeaderValue, AGE, CACHE_CONTROL, CONTENT_LENGTH, DATE, ETAG, EXPIRES, IF_MODIFIED_SINCE,
+            IF_NONE_MATCH, LAST_MODIFIED, VARY,
+        },
+        HeaderMap, Method, StatusCode, Version,
+    },
+    response::Response,
+    service::{Service, ServiceRequest},
+};
+
+// max size of response body can be stored in cache.
+const MAX_BODY_SIZE: usize = 1024 * 1024 * 8;
+
+/// a cached response and the information needed to calculate it's freshness.
+///
+/// fields are public so that [CacheStorage] implementation can persist it in any format.
+#[derive(Clone, Debug)]
+pub struct CacheEntry {
+    pub status: StatusCode,
+    pub version: Version,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+    /// request header values selected by the response's `Vary` header.
+    pub vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    /// time when request is sent.
+    pub request_time: SystemTime,
+    /// time when response is received.
+    pub response_time: SystemTime,
+}
+
+impl CacheEntry {
+    fn matches(&self, headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| headers.get(name) == value.as_ref())
+    }
+
+    fn is_fresh(&self, now: SystemTime, max_age: Option<u64>) -> bool {
+        if CacheControl::from_headers(&self.headers).no_cache {
+            return false;
+        }
+
+        let age = self.current_age(now);
+
+        if max_age.is_some_and(|max_age| age > Duration::from_secs(max_age)) {
+            return false;
+        }
+
+        age < self.freshness_lifetime()
+    }
+
+    // RFC 9111 section 4.2.1
+    fn freshness_lifetime(&self) -> Duration {
+        if let Some(max_age) = CacheControl::from_headers(&self.headers).max_age {
+            return Duration::from_secs(max_age);
+        }
+
+        let date = header_date(&self.headers, &DATE).unwrap_or(self.response_time);
+
+        if let Some(expires) = self.headers.get(EXPIRES) {
+            // invalid expires value means already expired.
+            return expires
+                .to_str()
+                .ok()
+                .and_then(parse_http_date)
+                .and_then(|expires| expires.duration_since(date).ok())
+                .unwrap_or_default();
+        }
+
+        // heuristic freshness. (RFC 9111 section 4.2.2)
+        header_date(&self.headers, &LAST_MODIFIED)
+            .and_then(|last_modified| date.duration_since(last_modified).ok())
+            .map(|dur| dur / 10)
+            .unwrap_or_default()
+    }
+
+    // RFC 9111 section 4.2.3
+    fn current_age(&self, now: SystemTime) -> Duration {
+        let date = header_date(&self.headers, &DATE).unwrap_or(self.response_time);
+
+        let age_value = self
+            .headers
+            .get(AGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+
+        let apparent_age = self.response_time.duration_since(date).unwrap_or_default();
+        let response_delay = self.response_time.duration_since(self.request_time).unwrap_or_default();
+        let corrected_initial_age = core::cmp::max(apparent_age, age_value + response_delay);
+        let resident_time = now.duration_since(self.response_time).unwrap_or_default();
+
+        corrected_initial_age + resident_time
+    }
+
+    // update stored headers with the ones from 304 response. (RFC 9111 section 3.2)
+    fn update(&mut self, headers: &HeaderMap) {
+        for name in headers.keys() {
+            if name == CONTENT_LENGTH {
+                continue;
+            }
+            self.headers.remove(name);
+            for value in headers.get_all(name) {
+                self.headers.append(name.clone(), value.clone());
+            }
+        }
+    }
+
+    fn to_response(&self, now: SystemTime, timeout: Duration) -> Response {
+        let mut res = http::Response::new(ResponseBody::Unknown(Box::pin(BufferedBody(Some(self.body.clone())))));
+        *res.status_mut() = self.status;
+        *res.version_mut() = self.version;
+        *res.headers_mut() = self.headers.clone();
+
+        let age = self.current_age(now).as_secs();
+        res.headers_mut().insert(AGE, HeaderValue::from(age));
+
+        Response::new(res, Box::pin(tokio::time::sleep(timeout)), timeout)
+    }
+}
+
+/// trait for storage of [Cache] middleware.
+///
+/// responses are keyed by request uri and one key can map to multiple entries when response
+/// contains `Vary` header.
+pub trait CacheStorage: Send + Sync {
+    /// get all stored entries of given key.
+    fn get(&self, key: &str) -> impl Future<Output = Vec<CacheEntry>> + Send;
+
+    /// store entry with given key. existing entry with the same [CacheEntry::vary] must be replaced.
+    fn put(&self, key: &str, entry: CacheEntry) -> impl Future<Output = ()> + Send;
+
+    /// remove all entries of given key.
+    fn remove(&self, key: &str) -> impl Future<Output = ()> + Send;
+}
+
+/// in memory storage for [Cache] middleware.
+#[derive(Default)]
+pub struct MemoryStorage {
+    entries: Mutex<HashMap<String, Vec<CacheEntry>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CacheStorage for MemoryStorage {
+    async fn get(&self, key: &str) -> Vec<CacheEntry> {
+        self.entries.lock().unwrap().get(key).cloned().unwrap_or_default()
+    }
+
+    async fn put(&self, key: &str, entry: CacheEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        let entries = entries.entry(key.to_string()).or_default();
+        entries.retain(|e| e.vary != entry.vary);
+        entries.push(entry);
+    }
+
+    async fn remove(&self, key: &str) {
+        self.entries.lock().unwrap().remove(key);
+    }
+}
+
+/// middleware for caching response according to RFC 9111 as a private cache.
+///
+/// - only `GET` request is served from cache.
+/// - response freshness is calculated from `Cache-Control`, `Expires`, `Date`, `Age` and `Last-Modified` headers.
+/// - stale response with `ETag` or `Last-Modified` header is revalidated with conditional request.
+/// - successful unsafe request (like `POST`) invalidates cached response of the same uri.
+/// - response without `Content-Length` header or with body larger than 8 Mb is not cached.
+pub struct Cache<S, St = MemoryStorage> {
+    service: S,
+    storage: St,
+}
+
+impl<S> Cache<S> {
+    /// construct cache middleware with in memory storage.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_client::{ClientBuilder, middleware::Cache};
+    /// let builder = ClientBuilder::new()
+    ///     .middleware(Cache::new);
+    /// ```
+    pub fn new(service: S) -> Self {
+        Self {
+            service,
+            storage: MemoryStorage::new(),
+        }
+    }
+}
+
+impl<S, St> Cache<S, St> {
+    /// use custom storage type for cache.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_client::{ClientBuilder, middleware::{Cache, MemoryStorage}};
+    /// let builder = ClientBuilder::new()
+    ///     .middleware(|service| Cache::new(service).storage(MemoryStorage::new()));
+    /// ```
+    pub fn storage<St2>(self, storage: St2) -> Cache<S, St2>
+    where
+        St2: CacheStorage,
+    {
+        Cache {
+            service: self.service,
+            storage,
+        }
+    }
+}
+
+impl<'r, 'c, S, St> Service<ServiceRequest<'r, 'c>> for Cache<S, St>
+where
+    S: for<'r2, 'c2> Service<ServiceRequest<'r2, 'c2>, Response = Response, Error = Error> + Send + Sync,
+    St: CacheStorage,
+{
+    type Response = Response;
+    type Error = Error;
+
+    async fn call(&self, req: ServiceRequest<'r, 'c>) -> Result<Self::Response, Self::Error> {
+        let ServiceRequest { req, client, timeout } = req;
+
+        let method = req.method().clone();
+        let key = req.uri().to_string();
+
+        if method != Method::GET {
+            let res = self.service.call(ServiceRequest { req, client, timeout }).await?;
+            // invalidate cache on successful unsafe request. (RFC 9111 section 4.4)
+            if !matches!(method, Method::HEAD | Method::OPTIONS | Method::TRACE)
+                && (res.status().is_success() || res.status().is_redirection())
+            {
+                self.storage.remove(&key).await;
+            }
+            return Ok(res);
+        }
+
+        let req_cc = CacheControl::from_headers(req.headers());
+
+        // user issued conditional request is passed through as is.
+        if req_cc.no_store || req.headers().contains_key(IF_NONE_MATCH) || req.headers().contains_key(IF_MODIFIED_SINCE)
+        {
+            return self.service.call(ServiceRequest { req, client, timeout }).await;
+        }
+
+        let response_timeout = client.timeout_config.response_timeout;
+
+        let entry = self
+            .storage
+            .get(&key)
+            .await
+            .into_iter()
+            .find(|entry| entry.matches(req.headers()));
+
+        if let Some(ref entry) = entry {
+            let now = SystemTime::now();
+            if !req_cc.no_cache && entry.is_fresh(now, req_cc.max_age) {
+                return Ok(entry.to_response(now, response_timeout));
+            }
+
+            if let Some(etag) = entry.headers.get(ETAG) {
+                req.headers_mut().insert(IF_NONE_MATCH, etag.clone());
+            }
+
+            if let Some(last_modified) = entry.headers.get(LAST_MODIFIED) {
+                req.headers_mut().insert(IF_MODIFIED_SINCE, last_modified.clone());
+            }
+        }
+
+        let req_headers = req.headers().clone();
+        let request_time = SystemTime::now();
+
+        let res = self.service.call(ServiceRequest { req, client, timeout }).await?;
+
+        let response_time = SystemTime::now();
+
+        if res.status() == StatusCode::NOT_MODIFIED {
+            if let Some(mut entry) = entry {
+                entry.update(res.headers());
+                entry.request_time = request_time;
+                entry.response_time = response_time;
+                let res = entry.to_response(response_time, response_timeout);
+                self.storage.put(&key, entry).await;
+                return Ok(res);
+            }
+        }
+
+        let Some(vary) = cacheable(&res, &req_headers) else {
+            return Ok(res);
+        };
+
+        let status = res.status();
+        let version = res.version();
+        let headers = res.headers().clone();
+        let body = res.limit::<MAX_BODY_SIZE>().bytes().await?;
+
+        let entry = CacheEntry {
+            status,
+            version,
+            headers,
+            body,
+            vary,
+            request_time,
+            response_time,
+        };
+
+        let res = entry.to_response(response_time, response_timeout);
+        self.storage.put(&key, entry).await;
+        Ok(res)
+    }
+}
+
+// check if response can be stored and collect the request headers selected by vary header.
+fn cacheable(res: &Response, req_headers: &HeaderMap) -> Option<Vec<(HeaderName, Option<HeaderValue>)>> {
+    if !matches!(
+        res.status().as_u16(),
+        200 | 203 | 204 | 300 | 301 | 308 | 404 | 405 | 410 | 414 | 501
+    ) {
+        return None;
+    }
+
+    let cc = CacheControl::from_headers(res.headers());
+
+    if cc.no_store {
+        return None;
+    }
+
+    let headers = res.headers();
+
+    // response must carry explicit freshness or validator information.
+    if cc.max_age.is_none()
+        && !headers.contains_key(EXPIRES)
+        && !headers.contains_key(ETAG)
+        && !headers.contains_key(LAST_MODIFIED)
+    {
+        return None;
+    }
+
+    let len = headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())?;
+
+    if len > MAX_BODY_SIZE {
+        return None;
+    }
+
+    let mut vary = Vec::new();
+
+    for value in headers.get_all(VARY) {
+        for name in value.to_str().ok()?.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            if name == "*" {
+                return None;
+            }
+            let name = HeaderName::from_bytes(name.as_bytes()).ok()?;
+            let value = req_headers.get(&name).cloned();
+            vary.push((name, value));
+        }
+    }
+
+    Some(vary)
+}
+
+#[derive(Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+impl CacheControl {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let mut cc = Self::default();
+
+        let directives = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim);
+
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+
+            if name.eq_ignore_ascii_case("no-store") {
+                cc.no_store = true;
+            } else if name.eq_ignore_ascii_case("no-cache") {
+                cc.no_cache = true;
+            } else if name.eq_ignore_ascii_case("max-age") {
+                cc.max_age = value.and_then(|v| v.parse().ok());
+            }
+        }
+
+        cc
+    }
+}
+
+fn header_date(headers: &HeaderMap, name: &HeaderName) -> Option<SystemTime> {
+    headers.get(name)?.to_str().ok().and_then(parse_http_date)
+}
+
+// parse IMF-fixdate format. (e.g. Sun, 06 Nov 1994 08:49:37 GMT)
+fn parse_http_date(date: &str) -> Option<SystemTime> {
+    let mut parts = date.split_ascii_whitespace().skip(1);
+
+    let day = parts.next()?.parse::<u64>().ok()?;
+    let month = match parts.next()? {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year = parts.next()?.parse::<u64>().ok()?;
+
+    let mut time = parts.next()?.splitn(3, ':').map(|n| n.parse::<u64>().ok());
+    let (hour, min, sec) = (time.next()??, time.next()??, time.next()??);
+
+    if parts.next()? != "GMT" || year < 1970 || !(1..=31).contains(&day) || hour > 23 || min > 59 || sec > 60 {
+        return None;
+    }
+
+    // days from civil algorithm.
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    let secs = days * 86400 + hour * 3600 + min * 60 + sec;
+
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+struct BufferedBody(Option<Bytes>);
+
+impl Stream for BufferedBody {
+    type Item = Result<Bytes, BodyError>;
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().0.take().map(Ok))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.as_ref().map(Bytes::len).unwrap_or(0);
+        (len, Some(len))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use std::sync::Arc;
+
+    use crate::{body::BoxBody, service::mock_service};
+
+    use super::*;
+
+    #[test]
+    fn http_date() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(date, UNIX_EPOCH + Duration::from_secs(784111777));
+        assert!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT").is_none());
+    }
+
+    fn body(bytes: &'static [u8]) -> ResponseBody {
+        ResponseBody::Unknown(Box::pin(BufferedBody(Some(Bytes::from_static(bytes)))))
+    }
+
+    #[tokio::test]
+    async fn fresh_and_revalidate() {
+        let (handle, service) = mock_service();
+
+        let cache = Cache::new(service);
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let count2 = count.clone();
+
+        let handler = move |req: http::Request<BoxBody>| {
+            count2.fetch_add(1, Ordering::SeqCst);
+            match req.uri().path() {
+                "/fresh" => Ok(http::Response::builder()
+                    .header(CACHE_CONTROL, "max-age=60")
+                    .header(CONTENT_LENGTH, "5")
+                    .body(body(b"fresh"))
+                    .unwrap()),
+                "/etag"
+                    if req
+                        .headers()
+                        .get(IF_NONE_MATCH)
+                        .is_some_and(|v| v.as_bytes() == b"\"996\"") =>
+                {
+                    Ok(http::Response::builder()
+                        .status(StatusCode::NOT_MODIFIED)
+                        .header(ETAG, "\"996\"")
+                        .body(ResponseBody::Eof)
+                        .unwrap())
+                }
+                "/etag" => Ok(http::Response::builder()
+                    .header(CACHE_CONTROL, "no-cache")
+                    .header(ETAG, "\"996\"")
+                    .header(CONTENT_LENGTH, "4")
+                    .body(body(b"etag"))
+                    .unwrap()),
+                p => panic!("unexpected uri path: {p}"),
+            }
+        };
+
+        for _ in 0..2 {
+            let mut req = http::Request::builder()
+                .uri("http://foo.bar/fresh")
+                .body(Default::default())
+                .unwrap();
+            let req = handle.mock(&mut req, handler.clone());
+            let res = cache.call(req).await.unwrap();
+            assert_eq!(res.string().await.unwrap(), "fresh");
+        }
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        for _ in 0..2 {
+            let mut req = http::Request::builder()
+                .uri("http://foo.bar/etag")
+                .body(Default::default())
+                .unwrap();
+            let req = handle.mock(&mut req, handler.clone());
+            let res = cache.call(req).await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            assert_eq!(res.string().await.unwrap(), "etag");
+        }
+
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        let mut req = http::Request::builder()
+            .method(Method::POST)
+            .uri("http://foo.bar/fresh")
+            .body(Default::default())
+            .unwrap();
+        let req = handle.mock(&mut req, handler.clone());
+        cache.call(req).await.unwrap();
+
+        assert!(cache.storage.get("http://foo.bar/fresh").await.is_empty());
+    }
+}
@@ -1,13 +1,45 @@
 //! middleware offer extended functionality to http client.
+//!
+//! # Ordering
+//! middlewares are applied in the order of [ClientBuilder::middleware] calls where the last applied one
+//! is the outermost layer. It would observe request first and response last.
+//!
+//! ```rust
+//! use xitca_client::{middleware::{FollowRedirect, Retry}, ClientBuilder, Service};
+//!
+//! let builder = ClientBuilder::new()
+//!     // inner layer. retry every request produced by redirect following.
+//!     .middleware(Retry::new)
+//!     // outer layer.
+//!     .middleware(FollowRedirect::new)
+//!     // outermost layer. observe the original request and final response.
+//!     .middleware_fn(async |req, http_service| {
+//!         let method = req.req.method().clone();
+//!         let res = http_service.call(req).await;
+//!         println!("{method} request finished with ok: {}", res.is_ok());
+//!         res
+//!     });
+//! ```
+//!
+//! [ClientBuilder::middleware]: crate::ClientBuilder::middleware
 
+mod cache;
 mod redirect;
+mod retry;
 
 mod async_fn;
 #[cfg(feature = "compress")]
 mod decompress;
+#[cfg(feature = "signature")]
+mod signature;
 
 #[cfg(feature = "compress")]
 pub use decompress::Decompress;
+#[cfg(feature = "signature")]
+pub use signature::{Algorithm, SignRequest, Signer, SigningKey};
 
 pub(crate) use async_fn::AsyncFn;
+pub use cache::{Cache, CacheEntry, CacheStorage, MemoryStorage};
 pub use redirect::FollowRedirect;
+pub use retry::{Retry, RetryIdempotent};
+pub use xitca_service::middleware::RetryPolicy;
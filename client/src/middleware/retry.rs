@@ -0,0 +1,227 @@
+use futures_core::stream::Stream;
+use xitca_service::middleware::RetryPolicy;
+
+use crate::{
+    body::BoxBody,
+    error::{Error, TimeoutError},
+    http::{Method, Request},
+    response::Response,
+    service::{Service, ServiceRequest},
+};
+
+/// middleware for retrying failed request according to given [RetryPolicy].
+///
+/// Default to [RetryIdempotent] policy where only idempotent request without body is retried and only when the
+/// error is caused by connection establishing or io failure. (e.g. connection reset on a pooled connection)
+pub struct Retry<S, P = RetryIdempotent> {
+    service: S,
+    policy: P,
+}
+
+impl<S> Retry<S> {
+    /// construct retry middleware for client.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_client::{ClientBuilder, middleware::{FollowRedirect, Retry}};
+    /// let builder = ClientBuilder::new()
+    ///     // every redirected request is retried on it's own.
+    ///     .middleware(Retry::new)
+    ///     .middleware(FollowRedirect::new);
+    /// ```
+    pub const fn new(service: S) -> Self {
+        Self {
+            service,
+            policy: RetryIdempotent,
+        }
+    }
+}
+
+impl<S, const MAX: usize> Retry<S, RetryIdempotent<MAX>> {
+    /// set max retry count for request. when max value is reached the last error is returned as output.
+    ///
+    /// Default to 3 times.
+    pub fn max<const MAX2: usize>(self) -> Retry<S, RetryIdempotent<MAX2>> {
+        Retry {
+            service: self.service,
+            policy: RetryIdempotent,
+        }
+    }
+}
+
+impl<S, P> Retry<S, P> {
+    /// replace retry policy with given one.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_client::{error::Error, http::Request, middleware::{Retry, RetryPolicy}, BoxBody, ClientBuilder};
+    /// // retry any request without body once regardless of the error type.
+    /// struct RetryOnce;
+    ///
+    /// impl RetryPolicy<Request<BoxBody>, Error> for RetryOnce {
+    ///     fn clone_request(&self, req: &Request<BoxBody>) -> Option<Request<BoxBody>> {
+    ///         let mut next = Request::new(BoxBody::default());
+    ///         *next.method_mut() = req.method().clone();
+    ///         *next.uri_mut() = req.uri().clone();
+    ///         *next.headers_mut() = req.headers().clone();
+    ///         Some(next)
+    ///     }
+    ///
+    ///     async fn retry(&self, attempt: usize, _: &Error) -> bool {
+    ///         attempt <= 1
+    ///     }
+    /// }
+    ///
+    /// let builder = ClientBuilder::new().middleware(|service| Retry::new(service).policy(RetryOnce));
+    /// ```
+    pub fn policy<P2>(self, policy: P2) -> Retry<S, P2> {
+        Retry {
+            service: self.service,
+            policy,
+        }
+    }
+}
+
+impl<'r, 'c, S, P> Service<ServiceRequest<'r, 'c>> for Retry<S, P>
+where
+    S: for<'r2, 'c2> Service<ServiceRequest<'r2, 'c2>, Response = Response, Error = Error> + Send + Sync,
+    P: RetryPolicy<Request<BoxBody>, Error> + Send + Sync,
+{
+    type Response = Response;
+    type Error = Error;
+
+    async fn call(&self, req: ServiceRequest<'r, 'c>) -> Result<Self::Response, Self::Error> {
+        let ServiceRequest { req, client, timeout } = req;
+
+        let mut attempt = 0;
+
+        loop {
+            let Some(next) = self.policy.clone_request(req) else {
+                return self.service.call(ServiceRequest { req, client, timeout }).await;
+            };
+
+            match self.service.call(ServiceRequest { req, client, timeout }).await {
+                Ok(res) => return Ok(res),
+                Err(e) => {
+                    attempt += 1;
+                    if !self.policy.retry(attempt, &e).await {
+                        return Err(e);
+                    }
+                    tracing::debug!(
+                        "retrying request to {} after error: {e}. retry count: {attempt}",
+                        next.uri()
+                    );
+                }
+            }
+
+            *req = next;
+        }
+    }
+}
+
+/// default [RetryPolicy] of [Retry] middleware.
+///
+/// Only idempotent request without body is retried and only when the error is caused by connection
+/// establishing or io failure. Request is retried for up to `MAX` times.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RetryIdempotent<const MAX: usize = 3>;
+
+impl<const MAX: usize> RetryPolicy<Request<BoxBody>, Error> for RetryIdempotent<MAX> {
+    fn clone_request(&self, req: &Request<BoxBody>) -> Option<Request<BoxBody>> {
+        // upper bound of zero indicates request has no body.
+        if !is_idempotent(req.method()) || !matches!(req.body().size_hint(), (_, Some(0))) {
+            return None;
+        }
+
+        let mut next = Request::new(BoxBody::default());
+        *next.method_mut() = req.method().clone();
+        *next.uri_mut() = req.uri().clone();
+        *next.version_mut() = req.version();
+        *next.headers_mut() = req.headers().clone();
+        *next.extensions_mut() = req.extensions().clone();
+        Some(next)
+    }
+
+    async fn retry(&self, attempt: usize, err: &Error) -> bool {
+        attempt <= MAX && is_retryable(err)
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE | Method::PUT | Method::DELETE
+    )
+}
+
+fn is_retryable(e: &Error) -> bool {
+    match e {
+        Error::Io(_) => true,
+        Error::Std(e) => e.downcast_ref::<TimeoutError>().is_some_and(|e| {
+            matches!(
+                e,
+                TimeoutError::Resolve | TimeoutError::Connect | TimeoutError::TlsHandshake
+            )
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use std::{io, sync::Arc};
+
+    use crate::{
+        body::{BoxBody, ResponseBody},
+        http::{self, StatusCode},
+        service::mock_service,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn retry() {
+        let (handle, service) = mock_service();
+
+        let retry = Retry::new(service).max::<1>();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let count2 = count.clone();
+
+        let handler = move |req: http::Request<BoxBody>| {
+            if count2.fetch_add(1, Ordering::SeqCst) & 1 == 0 {
+                return Err(Error::Io(io::ErrorKind::ConnectionReset.into()));
+            }
+            match req.uri().path() {
+                "/foo" => Ok(http::Response::builder()
+                    .status(StatusCode::IM_A_TEAPOT)
+                    .body(ResponseBody::Eof)
+                    .unwrap()),
+                p => panic!("unexpected uri path: {p}"),
+            }
+        };
+
+        let mut req = http::Request::builder()
+            .uri("http://foo.bar/foo")
+            .body(Default::default())
+            .unwrap();
+
+        let req = handle.mock(&mut req, handler.clone());
+        let res = retry.call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::IM_A_TEAPOT);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        let mut req = http::Request::builder()
+            .method(Method::POST)
+            .uri("http://foo.bar/foo")
+            .body(Default::default())
+            .unwrap();
+
+        let req = handle.mock(&mut req, handler);
+        let err = retry.call(req).await.err().unwrap();
+        assert!(matches!(err, Error::Io(_)));
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,93 @@
+pub use http_signature::{Algorithm, Signer, SigningKey};
+
+use crate::{
+    error::Error,
+    response::Response,
+    service::{Service, ServiceRequest},
+};
+
+/// middleware signing outgoing request with http message signature (RFC 9421).
+///
+/// `Signature-Input` and `Signature` headers are added to every request passing through. When used
+/// together with [FollowRedirect] it should be applied before it so redirected request is signed
+/// with it's new target.
+///
+/// [FollowRedirect]: crate::middleware::FollowRedirect
+pub struct SignRequest<S> {
+    service: S,
+    signer: Signer,
+}
+
+impl<S> SignRequest<S> {
+    /// construct signing middleware with given signer.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_client::{ClientBuilder, middleware::{SignRequest, Signer, SigningKey}};
+    /// let signer = Signer::new("client-1", SigningKey::hmac_sha256(b"shared secret"))
+    ///     .components(["@method", "@authority", "@request-target", "content-type"]);
+    ///
+    /// let builder = ClientBuilder::new()
+    ///     .middleware(|service| SignRequest::new(service, signer));
+    /// ```
+    pub fn new(service: S, signer: Signer) -> Self {
+        Self { service, signer }
+    }
+}
+
+impl<'r, 'c, S> Service<ServiceRequest<'r, 'c>> for SignRequest<S>
+where
+    S: for<'r2, 'c2> Service<ServiceRequest<'r2, 'c2>, Response = Response, Error = Error> + Send + Sync,
+{
+    type Response = Response;
+    type Error = Error;
+
+    async fn call(&self, req: ServiceRequest<'r, 'c>) -> Result<Self::Response, Self::Error> {
+        self.signer.sign(req.req)?;
+        self.service.call(req).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use http_signature::{Verifier, VerifyingKey};
+
+    use crate::{
+        body::{BoxBody, ResponseBody},
+        http::{self, StatusCode},
+        service::mock_service,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sign() {
+        let (handle, service) = mock_service();
+
+        let signer = Signer::new("key", SigningKey::hmac_sha256(b"secret"));
+        let sign = SignRequest::new(service, signer);
+
+        let handler = |req: http::Request<BoxBody>| {
+            let verifier =
+                Verifier::new(|key_id: &str| (key_id == "key").then(|| VerifyingKey::hmac_sha256(b"secret")))
+                    .required_components(["@method", "@authority", "@request-target"]);
+            let status = match verifier.verify(&req) {
+                Ok(_) => StatusCode::OK,
+                Err(_) => StatusCode::UNAUTHORIZED,
+            };
+            Ok(http::Response::builder()
+                .status(status)
+                .body(ResponseBody::Eof)
+                .unwrap())
+        };
+
+        let mut req = http::Request::builder()
+            .uri("http://foo.bar/foo?bar=baz")
+            .body(Default::default())
+            .unwrap();
+
+        let req = handle.mock(&mut req, handler);
+        let res = sign.call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
@@ -1,6 +1,7 @@
 use core::{
     hash::Hash,
     ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
     time::Duration,
 };
 
@@ -10,9 +11,31 @@ use std::{
     time::Instant,
 };
 
-use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
 
-type Entries<K, C> = HashMap<K, (Arc<Semaphore>, VecDeque<PooledConn<C>>)>;
+use crate::{
+    error::{Error, PendingLimitError, TimeoutError},
+    timeout::Timeout,
+};
+
+type Entries<K, C> = HashMap<K, Entry<C>>;
+
+struct Entry<C> {
+    permits: Arc<Semaphore>,
+    // count of callers waiting for a permit of entry.
+    pending: Arc<AtomicUsize>,
+    queue: VecDeque<PooledConn<C>>,
+}
+
+impl<C> Entry<C> {
+    fn new(cap: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(cap)),
+            pending: Arc::new(AtomicUsize::new(0)),
+            queue: VecDeque::with_capacity(cap),
+        }
+    }
+}
 
 #[doc(hidden)]
 pub struct Pool<K, C> {
@@ -21,6 +44,10 @@ pub struct Pool<K, C> {
     // the pool can have unbounded entries with different keys but a single
     // entry can only have up to cap size of C inside it.
     cap: usize,
+    // max count of callers waiting for a permit when entry is at full capacity.
+    max_pending: usize,
+    // max duration of caller waiting for a permit. wait without timeout when None.
+    acquire_timeout: Option<Duration>,
     keep_alive_idle: Duration,
     keep_alive_born: Duration,
 }
@@ -30,6 +57,8 @@ impl<K, C> Clone for Pool<K, C> {
         Self {
             conns: self.conns.clone(),
             cap: self.cap,
+            max_pending: self.max_pending,
+            acquire_timeout: self.acquire_timeout,
             keep_alive_idle: self.keep_alive_idle,
             keep_alive_born: self.keep_alive_born,
         }
@@ -40,10 +69,18 @@ impl<K, C> Pool<K, C>
 where
     K: Eq + Hash + Clone,
 {
-    pub(crate) fn new(cap: usize, keep_alive_idle: Duration, keep_alive_born: Duration) -> Self {
+    pub(crate) fn new(
+        cap: usize,
+        max_pending: usize,
+        acquire_timeout: Option<Duration>,
+        keep_alive_idle: Duration,
+        keep_alive_born: Duration,
+    ) -> Self {
         Self {
             conns: Arc::new(Mutex::new(HashMap::new())),
             cap,
+            max_pending,
+            acquire_timeout,
             keep_alive_idle,
             keep_alive_born,
         }
@@ -51,33 +88,63 @@ where
 
     // acquire a connection from pool. if a new connection needs to be made a spawner type
     // would be returned.
-    pub(crate) async fn acquire(&self, key: impl Into<K>) -> AcquireOutput<'_, K, C> {
+    // when entry is at full capacity caller waits for a permit until optional timeout and error would be
+    // returned immediately if there are already max pending count of callers waiting.
+    pub(crate) async fn acquire(&self, key: impl Into<K>) -> Result<AcquireOutput<'_, K, C>, Error> {
         let key = key.into();
 
         loop {
-            let permits = {
+            let (permits, pending) = {
                 let mut conns = self.conns.lock().unwrap();
                 match conns.get(&key) {
-                    Some((permits, _)) => permits.clone(),
+                    Some(entry) => (entry.permits.clone(), entry.pending.clone()),
                     None => {
                         // no entry in pool. create new entry and return a spawner where a new connection
                         // can be spawned into the entry.
                         let permit = entry_new(&key, self.cap, &mut *conns);
-                        return AcquireOutput::Spawner(Spawner {
+                        return Ok(AcquireOutput::Spawner(Spawner {
                             pool: self,
                             key,
                             _permit: permit,
                             is_new_entry: true,
                             fulfilled: false,
-                        });
+                        }));
                     }
                 }
             };
 
-            if let Ok(permit) = permits.acquire_owned().await {
+            let permit = match permits.clone().try_acquire_owned() {
+                Ok(permit) => Ok(permit),
+                // entry is at full capacity. wait for a permit when pending count is within limit.
+                Err(TryAcquireError::NoPermits) => {
+                    if pending.fetch_add(1, Ordering::Relaxed) >= self.max_pending {
+                        pending.fetch_sub(1, Ordering::Relaxed);
+                        return Err(PendingLimitError {
+                            limit: self.max_pending,
+                        }
+                        .into());
+                    }
+                    let _guard = PendingGuard(&pending);
+                    match self.acquire_timeout {
+                        Some(timeout) => {
+                            let mut timer = Box::pin(tokio::time::sleep(timeout));
+                            permits
+                                .acquire_owned()
+                                .timeout(timer.as_mut())
+                                .await
+                                .map_err(|_| TimeoutError::PoolAcquire)?
+                        }
+                        None => permits.acquire_owned().await,
+                    }
+                    .map_err(|_| ())
+                }
+                Err(TryAcquireError::Closed) => Err(()),
+            };
+
+            if let Ok(permit) = permit {
                 let mut conns = self.conns.lock().unwrap();
                 let queue = match conns.get_mut(&key) {
-                    Some((_, queue)) => queue,
+                    Some(entry) => &mut entry.queue,
                     // the entry is gone right after a permit is reserved.
                     // in this case try again from the beginning.
                     None => continue,
@@ -85,24 +152,24 @@ where
 
                 while let Some(conn) = queue.pop_front() {
                     if !conn.state.is_expired() {
-                        return AcquireOutput::Conn(Conn {
+                        return Ok(AcquireOutput::Conn(Conn {
                             pool: self.clone(),
                             key,
                             conn: Some(conn),
                             permit,
                             destroy_on_drop: false,
-                        });
+                        }));
                     }
                 }
 
                 // all connection in entry are expired. in this case spawn new connection.
-                return AcquireOutput::Spawner(Spawner {
+                return Ok(AcquireOutput::Spawner(Spawner {
                     pool: self,
                     key,
                     _permit: permit,
                     is_new_entry: false,
                     fulfilled: false,
-                });
+                }));
             }
 
             // the entry is gone when a permit is being reserved. in this case try again from the beginning.
@@ -113,25 +180,24 @@ where
         let key = key.into();
         let mut conns = self.conns.lock().unwrap();
         match conns.get_mut(&key) {
-            Some((permits, queue)) => {
+            Some(entry) => {
                 // try to acquire a permit immediately.
                 // when failed the entry is already at full capacity. in that case just throw the connection.
-                let res = permits.try_acquire();
+                let res = entry.permits.try_acquire();
                 if res.is_ok() {
-                    queue.push_back(PooledConn {
+                    entry.queue.push_back(PooledConn {
                         conn,
                         state: ConnState::new(self.keep_alive_idle, self.keep_alive_born),
                     });
                 }
             }
             None => {
-                let permits = Arc::new(Semaphore::new(self.cap));
-                let mut queue = VecDeque::with_capacity(self.cap);
-                queue.push_back(PooledConn {
+                let mut entry = Entry::new(self.cap);
+                entry.queue.push_back(PooledConn {
                     conn,
                     state: ConnState::new(self.keep_alive_idle, self.keep_alive_born),
                 });
-                conns.insert(key, (permits, queue));
+                conns.insert(key, entry);
             }
         }
     }
@@ -142,15 +208,25 @@ fn entry_new<K, C>(key: &K, cap: usize, entries: &mut Entries<K, C>) -> OwnedSem
 where
     K: Eq + Hash + Clone,
 {
-    let permits = Arc::new(Semaphore::new(cap));
-    let permit = permits
+    let entry = Entry::new(cap);
+    let permit = entry
+        .permits
         .clone()
         .try_acquire_owned()
         .expect("in place permit reservation must not fail");
-    entries.insert(key.clone(), (permits, VecDeque::with_capacity(cap)));
+    entries.insert(key.clone(), entry);
     permit
 }
 
+// decrease pending count of entry when waiting caller is finished or cancelled.
+struct PendingGuard<'a>(&'a AtomicUsize);
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 pub enum AcquireOutput<'a, K, C>
 where
     K: Eq + Hash + Clone,
@@ -221,9 +297,9 @@ where
 
             let mut conns = self.pool.conns.lock().unwrap();
 
-            if let Some((_, queue)) = conns.get_mut(&self.key) {
+            if let Some(entry) = conns.get_mut(&self.key) {
                 conn.state.update_idle();
-                queue.push_back(conn);
+                entry.queue.push_back(conn);
             }
 
             let _ = self.permit;
@@ -306,8 +382,8 @@ where
     pub(crate) fn spawned(mut self, conn: C) {
         self.fulfilled = true;
 
-        if let Some((_, queue)) = self.pool.conns.lock().unwrap().get_mut(&self.key) {
-            queue.push_back(PooledConn {
+        if let Some(entry) = self.pool.conns.lock().unwrap().get_mut(&self.key) {
+            entry.queue.push_back(PooledConn {
                 conn,
                 state: ConnState::new(self.pool.keep_alive_idle, self.pool.keep_alive_born),
             });
@@ -325,3 +401,53 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use core::{
+        future::Future,
+        task::{Context, Waker},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn pending_limit() {
+        let pool = Pool::<&str, ()>::new(
+            1,
+            1,
+            Some(Duration::from_millis(100)),
+            Duration::from_secs(60),
+            Duration::from_secs(60),
+        );
+
+        let AcquireOutput::Spawner(spawner) = pool.acquire("foo").await.unwrap() else {
+            panic!("first acquire must spawn new connection")
+        };
+
+        // entry is at full capacity and second caller waits until timeout.
+        let err = pool.acquire("foo").await.err().unwrap();
+        assert!(matches!(
+            err,
+            Error::Std(ref e) if matches!(e.downcast_ref(), Some(TimeoutError::PoolAcquire))
+        ));
+
+        // pending queue is full when one caller is waiting.
+        let mut waiting = Box::pin(pool.acquire("foo"));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(waiting.as_mut().poll(&mut cx).is_pending());
+
+        let err = pool.acquire("foo").await.err().unwrap();
+        assert!(matches!(
+            err,
+            Error::Std(ref e) if e.downcast_ref::<PendingLimitError>().is_some_and(|e| e.limit == 1)
+        ));
+
+        // other host is not affected.
+        assert!(matches!(pool.acquire("bar").await.unwrap(), AcquireOutput::Spawner(_)));
+
+        // waiting caller receives connection when it's spawned.
+        spawner.spawned(());
+        assert!(matches!(waiting.await.unwrap(), AcquireOutput::Conn(_)));
+    }
+}
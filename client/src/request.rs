@@ -20,8 +20,8 @@ use crate::{
 pub struct RequestBuilder<'a, M = marker::Http> {
     pub(crate) req: http::Request<BoxBody>,
     pub(crate) err: Vec<Error>,
-    client: &'a Client,
-    timeout: Duration,
+    pub(crate) client: &'a Client,
+    pub(crate) timeout: Duration,
     _marker: PhantomData<M>,
 }
 
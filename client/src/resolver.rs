@@ -1,4 +1,12 @@
-use std::net::ToSocketAddrs;
+//! DNS resolving for tcp and tls connections.
+
+use core::{
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+
+use std::{collections::HashMap, io, net::ToSocketAddrs, sync::Mutex, time::Instant};
 
 use crate::{
     connect::Connect,
@@ -6,30 +14,280 @@ use crate::{
     service::{Service, ServiceDyn},
 };
 
-pub type ResolverService =
+#[cfg(feature = "hickory-dns")]
+pub use hickory::HickoryResolver;
+
+pub(crate) type ResolverService =
     Box<dyn for<'r, 'c> ServiceDyn<&'r mut Connect<'c>, Response = (), Error = Error> + Send + Sync>;
 
+/// trait for async DNS resolver. A simplified alternative to implementing [Service] trait for
+/// [Connect] type when resolver logic only cares about host name and port.
+///
+/// # Examples
+/// ```rust
+/// use core::net::SocketAddr;
+///
+/// use xitca_client::{error::Error, resolver::Resolve, ClientBuilder};
+///
+/// struct MyResolver;
+///
+/// impl Resolve for MyResolver {
+///     async fn resolve(&self, _hostname: &str, port: u16) -> Result<Vec<SocketAddr>, Error> {
+///         Ok(vec![SocketAddr::from(([127, 0, 0, 1], port))])
+///     }
+/// }
+///
+/// # fn resolve() {
+/// let client = ClientBuilder::new().dns_resolver(MyResolver).finish();
+/// # }
+/// ```
+///
+/// [Service]: crate::Service
+pub trait Resolve: Send + Sync {
+    /// resolve given host name and port to a list of socket addresses.
+    fn resolve(&self, hostname: &str, port: u16) -> impl Future<Output = Result<Vec<SocketAddr>, Error>> + Send;
+}
+
+/// default resolver using operating system's resolving facility.
+/// lookup is offloaded to tokio's blocking thread pool.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemResolver;
+
+impl Resolve for SystemResolver {
+    async fn resolve(&self, hostname: &str, port: u16) -> Result<Vec<SocketAddr>, Error> {
+        let host = hostname.to_string();
+        let addrs = tokio::task::spawn_blocking(move || (host, port).to_socket_addrs())
+            .await
+            .map_err(io::Error::other)??;
+        Ok(addrs.collect())
+    }
+}
+
+// adapter type bridging Resolve trait to Service trait.
+pub(crate) struct ResolveService<R>(pub(crate) R);
+
+impl<'r, 'c, R> Service<&'r mut Connect<'c>> for ResolveService<R>
+where
+    R: Resolve,
+{
+    type Response = ();
+    type Error = Error;
+
+    async fn call(&self, req: &'r mut Connect<'c>) -> Result<Self::Response, Self::Error> {
+        let addrs = self.0.resolve(req.hostname(), req.port()).await?;
+        req.set_addrs(addrs);
+        Ok(())
+    }
+}
+
 pub(crate) fn base_resolver() -> ResolverService {
-    struct DefaultResolver;
+    Box::new(ResolveService(SystemResolver))
+}
+
+// resolver wrapping around user provided (or default) resolver service with static host
+// overrides and optional caching of resolved addresses.
+pub(crate) struct Resolver {
+    overrides: HashMap<Box<str>, Vec<SocketAddr>>,
+    cache: Option<ResolveCache>,
+    service: ResolverService,
+}
+
+impl Resolver {
+    pub(crate) fn new(
+        service: ResolverService,
+        overrides: HashMap<Box<str>, Vec<SocketAddr>>,
+        cache_ttl: Option<Duration>,
+    ) -> Self {
+        Self {
+            overrides,
+            cache: cache_ttl.map(ResolveCache::new),
+            service,
+        }
+    }
+}
 
-    impl<'r, 'c> Service<&'r mut Connect<'c>> for DefaultResolver {
-        type Response = ();
-        type Error = Error;
+impl<'r, 'c> Service<&'r mut Connect<'c>> for Resolver {
+    type Response = ();
+    type Error = Error;
 
-        async fn call(&self, req: &'r mut Connect<'c>) -> Result<Self::Response, Self::Error> {
-            let host = req.hostname();
+    async fn call(&self, req: &'r mut Connect<'c>) -> Result<Self::Response, Self::Error> {
+        if let Some(addrs) = self.overrides.get(req.hostname()) {
             let port = req.port();
+            req.set_addrs(addrs.iter().map(|addr| {
+                let mut addr = *addr;
+                // port 0 means override only replace ip address and keep the port of request.
+                if addr.port() == 0 {
+                    addr.set_port(port);
+                }
+                addr
+            }));
+            return Ok(());
+        }
+
+        let Some(cache) = self.cache.as_ref() else {
+            return Service::call(&self.service, req).await;
+        };
+
+        let port = req.port();
+
+        if let Some(ips) = cache.get(req.hostname()) {
+            req.set_addrs(ips.into_iter().map(|ip| SocketAddr::new(ip, port)));
+            return Ok(());
+        }
+
+        Service::call(&self.service, &mut *req).await?;
+
+        cache.insert(req.hostname(), req.addrs().map(|addr| addr.ip()).collect());
+
+        Ok(())
+    }
+}
+
+// host name mapped to it's expire time and resolved ip addresses.
+type CacheEntries = HashMap<Box<str>, (Instant, Vec<IpAddr>)>;
+
+struct ResolveCache {
+    ttl: Duration,
+    entries: Mutex<CacheEntries>,
+}
 
-            let host = host.to_string();
-            let addrs = tokio::task::spawn_blocking(move || (host, port).to_socket_addrs())
-                .await
-                .unwrap()?;
+impl ResolveCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
 
-            req.set_addrs(addrs);
+    fn get(&self, hostname: &str) -> Option<Vec<IpAddr>> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(hostname) {
+            Some((expire, ips)) if *expire > Instant::now() => Some(ips.clone()),
+            Some(_) => {
+                entries.remove(hostname);
+                None
+            }
+            None => None,
+        }
+    }
 
-            Ok(())
+    fn insert(&self, hostname: &str, ips: Vec<IpAddr>) {
+        // empty result is not cached so next lookup have a chance to retry.
+        if ips.is_empty() {
+            return;
         }
+        let expire = Instant::now() + self.ttl;
+        self.entries.lock().unwrap().insert(Box::from(hostname), (expire, ips));
     }
+}
 
-    Box::new(DefaultResolver)
+#[cfg(feature = "hickory-dns")]
+mod hickory {
+    use core::net::SocketAddr;
+
+    use hickory_resolver::{
+        config::{ResolverConfig, ResolverOpts},
+        TokioAsyncResolver,
+    };
+
+    use crate::error::Error;
+
+    use super::Resolve;
+
+    /// async DNS resolver powered by `hickory-resolver` crate.
+    pub struct HickoryResolver(TokioAsyncResolver);
+
+    impl HickoryResolver {
+        /// construct resolver with given configuration and options.
+        pub fn new(config: ResolverConfig, opts: ResolverOpts) -> Self {
+            Self(TokioAsyncResolver::tokio(config, opts))
+        }
+
+        /// construct resolver from operating system's configuration. (/etc/resolv.conf on unix)
+        pub fn from_system_conf() -> Result<Self, Error> {
+            TokioAsyncResolver::tokio_from_system_conf()
+                .map(Self)
+                .map_err(|e| Error::Std(Box::new(e)))
+        }
+    }
+
+    impl From<TokioAsyncResolver> for HickoryResolver {
+        fn from(resolver: TokioAsyncResolver) -> Self {
+            Self(resolver)
+        }
+    }
+
+    impl Resolve for HickoryResolver {
+        async fn resolve(&self, hostname: &str, port: u16) -> Result<Vec<SocketAddr>, Error> {
+            let lookup = self.0.lookup_ip(hostname).await.map_err(|e| Error::Std(Box::new(e)))?;
+            Ok(lookup.iter().map(|ip| SocketAddr::new(ip, port)).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use std::sync::Arc;
+
+    use crate::{http, uri::Uri};
+
+    use super::*;
+
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Resolve for Counted {
+        async fn resolve(&self, _: &str, port: u16) -> Result<Vec<SocketAddr>, Error> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![SocketAddr::from(([10, 0, 0, 1], port))])
+        }
+    }
+
+    fn make_resolver(
+        overrides: HashMap<Box<str>, Vec<SocketAddr>>,
+        ttl: Option<Duration>,
+    ) -> (Resolver, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let resolver = Resolver::new(Box::new(ResolveService(Counted(count.clone()))), overrides, ttl);
+        (resolver, count)
+    }
+
+    #[tokio::test]
+    async fn static_override() {
+        let mut overrides = HashMap::new();
+        overrides.insert(Box::from("api.internal"), vec![SocketAddr::from(([127, 0, 0, 1], 0))]);
+        let (resolver, count) = make_resolver(overrides, None);
+
+        let uri = http::Uri::from_static("http://api.internal:8080");
+        let mut connect = Connect::new(Uri::try_parse(&uri).unwrap());
+        Service::call(&resolver, &mut connect).await.unwrap();
+
+        assert_eq!(connect.addrs().next(), Some(SocketAddr::from(([127, 0, 0, 1], 8080))));
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn cache_ttl() {
+        let (resolver, count) = make_resolver(HashMap::new(), Some(Duration::from_secs(60)));
+
+        for port in [80, 8080] {
+            let uri = format!("http://example.com:{port}").parse::<http::Uri>().unwrap();
+            let mut connect = Connect::new(Uri::try_parse(&uri).unwrap());
+            Service::call(&resolver, &mut connect).await.unwrap();
+            assert_eq!(connect.addrs().next(), Some(SocketAddr::from(([10, 0, 0, 1], port))));
+        }
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        let (resolver, count) = make_resolver(HashMap::new(), Some(Duration::ZERO));
+
+        for _ in 0..2 {
+            let uri = http::Uri::from_static("http://example.com");
+            let mut connect = Connect::new(Uri::try_parse(&uri).unwrap());
+            Service::call(&resolver, &mut connect).await.unwrap();
+        }
+
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
 }
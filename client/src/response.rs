@@ -8,11 +8,14 @@ use core::{
 use futures_core::stream::Stream;
 use tokio::time::{Instant, Sleep};
 use tracing::debug;
-use xitca_http::{bytes::BytesMut, http};
+use xitca_http::{
+    bytes::{Bytes, BytesMut},
+    http,
+};
 
 use crate::{
     body::ResponseBody,
-    error::{Error, TimeoutError},
+    error::{ContentTypeError, Error, PayloadLimitError, TimeoutError},
     timeout::Timeout,
 };
 
@@ -21,8 +24,8 @@ const DEFAULT_PAYLOAD_LIMIT: usize = 1024 * 1024 * 8;
 /// new type of [http::Response] with extended functionalities.
 pub struct Response<const PAYLOAD_LIMIT: usize = DEFAULT_PAYLOAD_LIMIT> {
     pub(crate) res: http::Response<ResponseBody>,
-    timer: Pin<Box<Sleep>>,
-    timeout: Duration,
+    pub(crate) timer: Pin<Box<Sleep>>,
+    pub(crate) timeout: Duration,
 }
 
 impl<const PAYLOAD_LIMIT: usize> Deref for Response<PAYLOAD_LIMIT> {
@@ -46,7 +49,6 @@ impl<const PAYLOAD_LIMIT: usize> fmt::Debug for Response<PAYLOAD_LIMIT> {
 }
 
 impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
-    #[cfg(any(feature = "http1", feature = "http2", feature = "http3"))]
     pub(crate) fn new(res: http::Response<ResponseBody>, timer: Pin<Box<Sleep>>, timeout: Duration) -> Self {
         Self { res, timer, timeout }
     }
@@ -66,7 +68,8 @@ impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
         &mut self.res
     }
 
-    /// Set payload size limit in bytes. Payload size beyond limit would be discarded.
+    /// Set payload size limit in bytes. Collecting payload size beyond limit would result in
+    /// [PayloadLimitError].
     ///
     /// Default to 8 Mb.
     #[inline]
@@ -94,7 +97,29 @@ impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
     /// Collect response body as String. Response is consumed.
     #[inline]
     pub async fn string(self) -> Result<String, Error> {
-        self.collect().await
+        let body = self.collect::<Vec<u8>>().await?;
+        String::from_utf8(body).map_err(|e| e.utf8_error().into())
+    }
+
+    /// Collect response body as String. Response is consumed.
+    ///
+    /// Response with `Content-Type` header must be `text/*` type and when charset parameter presents it
+    /// must be utf-8.
+    pub async fn text(self) -> Result<String, Error> {
+        if let Some(mime) = self.content_type() {
+            let mut params = mime.split(';').map(str::trim);
+            let is_text = params.next().is_some_and(|ty| starts_with_ignore_case(ty, "text/"));
+            let is_utf8 = params
+                .filter_map(|param| param.split_once('='))
+                .filter(|(name, _)| name.eq_ignore_ascii_case("charset"))
+                .all(|(_, charset)| charset.trim_matches('"').eq_ignore_ascii_case("utf-8"));
+
+            if !is_text || !is_utf8 {
+                return Err(self.content_type_error("text/*; charset=utf-8"));
+            }
+        }
+
+        self.string().await
     }
 
     /// Collect response body as `Vec<u8>`. Response is consumed.
@@ -103,10 +128,17 @@ impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
         self.collect().await
     }
 
+    /// Collect response body as [Bytes]. Response is consumed.
+    #[inline]
+    pub async fn bytes(self) -> Result<Bytes, Error> {
+        self.collect::<BytesMut>().await.map(BytesMut::freeze)
+    }
+
     #[cfg(feature = "json")]
     /// Collect response body as json object. Response is consumed.
     ///
     /// The output type must impl [serde::de::DeserializeOwned] trait.
+    /// `Content-Type` header is not checked. See [Response::json_strict] for checked variant.
     pub async fn json<T>(self) -> Result<T, Error>
     where
         T: serde::de::DeserializeOwned,
@@ -117,6 +149,67 @@ impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
         Ok(serde_json::from_slice(bytes.chunk())?)
     }
 
+    #[cfg(feature = "json")]
+    /// Collect response body as json object. Response is consumed.
+    ///
+    /// The output type must impl [serde::de::DeserializeOwned] trait.
+    /// Response with `Content-Type` header must be `application/json` or `+json` suffixed type.
+    pub async fn json_strict<T>(self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        if let Some(mime) = self.content_type() {
+            let ty = essence(mime);
+            if !ty.eq_ignore_ascii_case("application/json") && !ends_with_ignore_case(ty, "+json") {
+                return Err(self.content_type_error("application/json"));
+            }
+        }
+
+        self.json().await
+    }
+
+    #[cfg(feature = "urlencoded")]
+    /// Collect response body as url encoded form. Response is consumed.
+    ///
+    /// The output type must impl [serde::de::DeserializeOwned] trait.
+    /// Response with `Content-Type` header must be `application/x-www-form-urlencoded` type.
+    pub async fn form<T>(self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        use xitca_http::bytes::Buf;
+
+        const FORM: &str = "application/x-www-form-urlencoded";
+
+        if let Some(mime) = self.content_type() {
+            if !essence(mime).eq_ignore_ascii_case(FORM) {
+                return Err(self.content_type_error(FORM));
+            }
+        }
+
+        let bytes = self.collect::<BytesMut>().await?;
+        Ok(serde_urlencoded::from_bytes(bytes.chunk())?)
+    }
+
+    pub(crate) fn content_type(&self) -> Option<&str> {
+        self.res
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .map(|v| v.to_str().unwrap_or(""))
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn content_type_error(mut self, expect: &'static str) -> Error {
+        // body is not going to be consumed.
+        self.res.body_mut().destroy_on_drop();
+        ContentTypeError {
+            expect,
+            content_type: self.content_type().unwrap_or_default().to_string(),
+        }
+        .into()
+    }
+
     async fn collect<B>(self) -> Result<B, Error>
     where
         B: Collectable,
@@ -126,16 +219,17 @@ impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
 
         let mut body = pin!(body);
 
-        let limit = res
+        let len = res
             .headers
             .get(http::header::CONTENT_LENGTH)
-            .and_then(|v| v.to_str().ok().and_then(|str| str.parse::<usize>().ok()))
-            .unwrap_or(PAYLOAD_LIMIT);
+            .and_then(|v| v.to_str().ok().and_then(|str| str.parse::<usize>().ok()));
 
-        let limit = std::cmp::min(limit, PAYLOAD_LIMIT);
+        if len.is_some_and(|len| len > PAYLOAD_LIMIT) {
+            body.destroy_on_drop();
+            return Err(PayloadLimitError { limit: PAYLOAD_LIMIT }.into());
+        }
 
-        // TODO: use a meaningful capacity.
-        let mut b = B::with_capacity(1024);
+        let mut b = B::with_capacity(len.unwrap_or(1024));
 
         timer.as_mut().reset(Instant::now() + self.timeout);
 
@@ -151,13 +245,13 @@ impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
                         }
                     };
 
-                    b.try_extend_from_slice(&buf)?;
-
-                    if buf.len() > limit {
-                        debug!("PAYLOAD_LIMIT reached and only part of the response body is collected.");
+                    if b.len() + buf.len() > PAYLOAD_LIMIT {
+                        debug!("PAYLOAD_LIMIT reached and response body collecting is aborted.");
                         body.destroy_on_drop();
-                        break;
+                        return Err(PayloadLimitError { limit: PAYLOAD_LIMIT }.into());
                     }
+
+                    b.extend_from_slice(&buf);
                 }
                 Ok(None) => break,
                 Err(_) => {
@@ -182,10 +276,26 @@ impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
     }
 }
 
+// mime type without parameters.
+pub(crate) fn essence(mime: &str) -> &str {
+    mime.split(';').next().unwrap_or("").trim()
+}
+
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.len() >= prefix.len() && s.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+#[cfg(feature = "json")]
+fn ends_with_ignore_case(s: &str, suffix: &str) -> bool {
+    s.len() >= suffix.len() && s.as_bytes()[s.len() - suffix.len()..].eq_ignore_ascii_case(suffix.as_bytes())
+}
+
 trait Collectable {
     fn with_capacity(cap: usize) -> Self;
 
-    fn try_extend_from_slice(&mut self, slice: &[u8]) -> Result<(), Error>;
+    fn len(&self) -> usize;
+
+    fn extend_from_slice(&mut self, slice: &[u8]);
 }
 
 impl Collectable for BytesMut {
@@ -195,9 +305,13 @@ impl Collectable for BytesMut {
     }
 
     #[inline]
-    fn try_extend_from_slice(&mut self, slice: &[u8]) -> Result<(), Error> {
-        self.extend_from_slice(slice);
-        Ok(())
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    #[inline]
+    fn extend_from_slice(&mut self, slice: &[u8]) {
+        Self::extend_from_slice(self, slice)
     }
 }
 
@@ -208,21 +322,97 @@ impl Collectable for Vec<u8> {
     }
 
     #[inline]
-    fn try_extend_from_slice(&mut self, slice: &[u8]) -> Result<(), Error> {
-        self.extend_from_slice(slice);
-        Ok(())
+    fn len(&self) -> usize {
+        Self::len(self)
     }
-}
 
-impl Collectable for String {
     #[inline]
-    fn with_capacity(cap: usize) -> Self {
-        Self::with_capacity(cap)
+    fn extend_from_slice(&mut self, slice: &[u8]) {
+        Self::extend_from_slice(self, slice)
     }
+}
 
-    fn try_extend_from_slice(&mut self, slice: &[u8]) -> Result<(), Error> {
-        let str = std::str::from_utf8(slice)?;
-        self.push_str(str);
-        Ok(())
+#[cfg(test)]
+mod test {
+    use crate::body::BodyError;
+
+    use super::*;
+
+    fn response(content_type: Option<&'static str>, chunks: &[&'static [u8]]) -> Response<8> {
+        let chunks = chunks
+            .iter()
+            .map(|chunk| Ok::<_, BodyError>(Bytes::from_static(chunk)))
+            .collect::<Vec<_>>();
+
+        let mut res = http::Response::new(ResponseBody::Unknown(Box::pin(futures::stream::iter(chunks))));
+
+        if let Some(ty) = content_type {
+            res.headers_mut()
+                .insert(http::header::CONTENT_TYPE, http::HeaderValue::from_static(ty));
+        }
+
+        Response::<8>::new(
+            res,
+            Box::pin(tokio::time::sleep(Duration::from_secs(0))),
+            Duration::from_secs(1),
+        )
+    }
+
+    #[tokio::test]
+    async fn text() {
+        let text = response(Some("text/plain; charset=utf-8"), &[b"hello", b" rs"])
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(text, "hello rs");
+
+        let text = response(None, &[b"hello"]).text().await.unwrap();
+        assert_eq!(text, "hello");
+
+        let err = response(Some("application/octet-stream"), &[b"hello"])
+            .text()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Std(e) if e.is::<ContentTypeError>()));
+
+        let err = response(Some("text/plain; charset=iso-8859-1"), &[b"hello"])
+            .text()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Std(e) if e.is::<ContentTypeError>()));
+    }
+
+    #[tokio::test]
+    async fn limit() {
+        let bytes = response(None, &[b"1234", b"5678"]).bytes().await.unwrap();
+        assert_eq!(bytes, Bytes::from_static(b"12345678"));
+
+        let err = response(None, &[b"1234", b"5678", b"9"]).bytes().await.unwrap_err();
+        assert!(matches!(err, Error::Std(e) if e.is::<PayloadLimitError>()));
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn json() {
+        let value = response(Some("application/problem+json"), &[b"[1,", b"2]"])
+            .json::<Vec<u8>>()
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2]);
+
+        let value = response(Some("text/plain"), &[b"[]"]).json::<Vec<u8>>().await.unwrap();
+        assert!(value.is_empty());
+
+        let value = response(Some("application/problem+json"), &[b"[3]"])
+            .json_strict::<Vec<u8>>()
+            .await
+            .unwrap();
+        assert_eq!(value, [3]);
+
+        let err = response(Some("text/plain"), &[b"[]"])
+            .json_strict::<Vec<u8>>()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Std(e) if e.is::<ContentTypeError>()));
     }
 }
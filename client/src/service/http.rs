@@ -35,13 +35,63 @@ pub(crate) fn base_service() -> HttpService {
 
             let mut connect = Connect::new(uri);
 
+            // http/3 can not be carried by unix domain socket.
+            // with alt-svc learning enabled http/3 is only attempted when origin advertised it.
+            #[cfg(feature = "http3")]
+            if version == Version::HTTP_3
+                && (client.unix_socket.is_some()
+                    || matches!(connect.uri, Uri::Unix(_))
+                    || (client.h3_config.alt_svc
+                        && connect
+                            .uri
+                            .authority()
+                            .and_then(|authority| client.alt_svc.get(authority))
+                            .is_none()))
+            {
+                #[cfg(feature = "http2")]
+                {
+                    version = Version::HTTP_2;
+                }
+
+                #[cfg(not(feature = "http2"))]
+                {
+                    version = Version::HTTP_11;
+                }
+            }
+
             let _date = client.date_service.handle();
 
+            // http/2 connection from pool can be going away. request would be retried once with a new connection.
+            #[cfg(feature = "http2")]
+            let mut h2_retried = false;
+
             loop {
                 match version {
                     Version::HTTP_2 | Version::HTTP_3 => match client.shared_pool.acquire(&connect.uri).await {
                         shared::AcquireOutput::Conn(mut _conn) => {
                             let mut _timer = Box::pin(tokio::time::sleep(timeout));
+
+                            #[cfg(feature = "http2")]
+                            match _conn.conn {
+                                crate::connection::ConnectionShared::H2(ref mut conn) => {
+                                    match crate::h2::proto::ready(conn).timeout(_timer.as_mut()).await {
+                                        Ok(Ok(_)) => {}
+                                        Ok(Err(_)) if !h2_retried => {
+                                            _conn.destroy_on_drop();
+                                            h2_retried = true;
+                                            continue;
+                                        }
+                                        Ok(Err(e)) => {
+                                            _conn.destroy_on_drop();
+                                            return Err(e.into());
+                                        }
+                                        Err(_) => return Err(TimeoutError::Request.into()),
+                                    }
+                                }
+                                #[cfg(feature = "http3")]
+                                _ => {}
+                            }
+
                             *req.version_mut() = version;
                             #[allow(unreachable_code)]
                             return match _conn.conn {
@@ -91,25 +141,31 @@ pub(crate) fn base_service() -> HttpService {
                                         .as_mut()
                                         .reset(tokio::time::Instant::now() + client.timeout_config.connect_timeout);
 
-                                    if let Ok(Ok(conn)) = crate::h3::proto::connect(
-                                        &client.h3_client,
-                                        connect.addrs(),
-                                        connect.hostname(),
-                                    )
-                                    .timeout(timer.as_mut())
-                                    .await
+                                    // connect to advertised port when it's learned from alt-svc header.
+                                    let alt_port = connect.uri.authority().and_then(|a| client.alt_svc.get(a));
+                                    let addrs = connect.addrs().map(|addr| match alt_port {
+                                        Some(port) => core::net::SocketAddr::new(addr.ip(), port),
+                                        None => addr,
+                                    });
+
+                                    match crate::h3::proto::connect(&client.h3_client, addrs, connect.hostname())
+                                        .timeout(timer.as_mut())
+                                        .await
                                     {
-                                        _spawner.spawned(conn.into());
-                                    } else {
-                                        #[cfg(feature = "http2")]
-                                        {
-                                            version = Version::HTTP_2;
-                                        }
+                                        Ok(Ok(conn)) => _spawner.spawned(conn.into()),
+                                        _ if client.h3_config.fallback => {
+                                            #[cfg(feature = "http2")]
+                                            {
+                                                version = Version::HTTP_2;
+                                            }
 
-                                        #[cfg(not(feature = "http2"))]
-                                        {
-                                            version = Version::HTTP_11;
+                                            #[cfg(not(feature = "http2"))]
+                                            {
+                                                version = Version::HTTP_11;
+                                            }
                                         }
+                                        Ok(Err(e)) => return Err(e.into()),
+                                        Err(_) => return Err(TimeoutError::Connect.into()),
                                     }
                                 }
 
@@ -126,7 +182,7 @@ pub(crate) fn base_service() -> HttpService {
                                         client.make_exclusive(&mut connect, &mut timer, Version::HTTP_2).await?;
 
                                     if alpn_version == Version::HTTP_2 {
-                                        let conn = crate::h2::proto::handshake(conn).await?;
+                                        let conn = crate::h2::proto::handshake(conn, client.h2_config).await?;
                                         _spawner.spawned(conn.into());
                                     } else {
                                         #[cfg(not(feature = "http1"))]
@@ -151,7 +207,7 @@ pub(crate) fn base_service() -> HttpService {
                             _ => unreachable!("outer match didn't  handle version correctly."),
                         },
                     },
-                    version => match client.exclusive_pool.acquire(&connect.uri).await {
+                    version => match client.exclusive_pool.acquire(&connect.uri).await? {
                         exclusive::AcquireOutput::Conn(mut _conn) => {
                             *req.version_mut() = version;
 
@@ -199,5 +255,13 @@ pub(crate) fn base_service() -> HttpService {
         }
     }
 
-    Box::new(HttpService)
+    #[cfg(feature = "http3")]
+    {
+        Box::new(crate::h3::alt_svc::AltSvcLearn::new(HttpService))
+    }
+
+    #[cfg(not(feature = "http3"))]
+    {
+        Box::new(HttpService)
+    }
 }
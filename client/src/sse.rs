@@ -0,0 +1,477 @@
+//! server-sent events (text/event-stream) consuming.
+
+use core::{
+    mem,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use futures_core::stream::Stream;
+
+use crate::{
+    body::{BoxBody, ResponseBody},
+    bytes::{Buf, BytesMut},
+    client::Client,
+    error::{Error, ErrorResponse},
+    http::{
+        self,
+        header::{HeaderName, HeaderValue, ACCEPT, CACHE_CONTROL},
+        StatusCode,
+    },
+    request::RequestBuilder,
+    response::{essence, Response},
+};
+
+const EVENT_STREAM: &str = "text/event-stream";
+
+#[allow(clippy::declare_interior_mutable_const)]
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+/// default reconnection delay when server did not send `retry` field.
+const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+
+/// new type of [RequestBuilder] with extended functionality for server-sent events handling.
+///
+/// [RequestBuilder]: crate::RequestBuilder
+pub type SseRequest<'a> = RequestBuilder<'a, marker::EventStream>;
+
+mod marker {
+    pub struct EventStream;
+}
+
+/// A single event dispatched from event stream.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Event {
+    /// event type. default to "message" when event field is absent.
+    pub event: String,
+    /// event data. multiple data fields are joined with line feed.
+    pub data: String,
+    /// last event id when this event is dispatched.
+    pub id: Option<String>,
+}
+
+impl Event {
+    #[cfg(feature = "json")]
+    /// deserialize event data as json.
+    pub fn json<T>(&self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&self.data).map_err(Into::into)
+    }
+}
+
+impl<const PAYLOAD_LIMIT: usize> Response<PAYLOAD_LIMIT> {
+    /// Convert response into [EventStream] which parse response body as text/event-stream.
+    ///
+    /// Response timeout does not apply to event stream as it's expected to be long living.
+    pub fn event_stream(self) -> Result<EventStream, Error> {
+        if !self
+            .content_type()
+            .is_some_and(|ty| essence(ty).eq_ignore_ascii_case(EVENT_STREAM))
+        {
+            return Err(self.content_type_error(EVENT_STREAM));
+        }
+        Ok(EventStream::new(self.res.into_body()))
+    }
+}
+
+/// [Stream] of [Event] parsed from response body. Stream ends when connection is closed by server.
+pub struct EventStream {
+    body: ResponseBody,
+    parser: Parser,
+}
+
+impl EventStream {
+    fn new(body: ResponseBody) -> Self {
+        Self {
+            body,
+            parser: Parser::default(),
+        }
+    }
+
+    /// reconnection time requested by server with `retry` field.
+    pub fn retry(&self) -> Option<Duration> {
+        self.parser.retry
+    }
+
+    /// last event id received from server.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.parser.id.as_deref()
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<Event, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(event) = this.parser.next_event() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            match ready!(Pin::new(&mut this.body).poll_next(cx)) {
+                Some(Ok(bytes)) => this.parser.feed(&bytes),
+                Some(Err(e)) => {
+                    this.body.destroy_on_drop();
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<'a> SseRequest<'a> {
+    /// Send the request and wait for event stream response asynchronously. The returned [EventSource]
+    /// would reconnect to server with `Last-Event-ID` header when connection is lost.
+    pub async fn send(mut self) -> Result<EventSource<'a>, Error> {
+        self.headers_mut()
+            .insert(ACCEPT, HeaderValue::from_static(EVENT_STREAM));
+        self.headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+
+        let mut template = http::Request::new(());
+        *template.method_mut() = self.req.method().clone();
+        *template.uri_mut() = self.req.uri().clone();
+        *template.version_mut() = self.req.version();
+        *template.headers_mut() = self.req.headers().clone();
+
+        let client = self.client;
+        let timeout = self.timeout;
+
+        let stream = open(self._send().await?)?;
+
+        Ok(EventSource {
+            client,
+            template,
+            timeout,
+            last_event_id: None,
+            retry: DEFAULT_RETRY,
+            max_backoff: Duration::from_secs(60),
+            failures: 0,
+            closed: false,
+            stream: Some(stream),
+        })
+    }
+}
+
+/// `EventSource` style client for server-sent events.
+///
+/// Events are received with [EventSource::next]. When connection is lost it's re-established
+/// with `Last-Event-ID` header after reconnection time which can be updated by server with `retry`
+/// field. Consecutive failed reconnections increase the delay exponentially up to a max backoff.
+///
+/// # Examples
+/// ```rust
+/// # async fn sse() -> Result<(), xitca_client::error::Error> {
+/// let client = xitca_client::Client::new();
+/// let mut source = client.sse("http://localhost:8080/events").send().await?;
+///
+/// while let Some(event) = source.next().await {
+///     match event {
+///         Ok(event) => println!("{}: {}", event.event, event.data),
+///         // connection error is yielded and reconnection happens on next call.
+///         Err(e) => eprintln!("{e}"),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct EventSource<'a> {
+    client: &'a Client,
+    template: http::Request<()>,
+    timeout: Duration,
+    last_event_id: Option<String>,
+    retry: Duration,
+    max_backoff: Duration,
+    failures: u32,
+    closed: bool,
+    stream: Option<EventStream>,
+}
+
+impl EventSource<'_> {
+    /// Set max delay between reconnection attempts.
+    ///
+    /// Default to 60 seconds.
+    pub fn max_backoff(mut self, dur: Duration) -> Self {
+        self.max_backoff = dur;
+        self
+    }
+
+    /// last event id received from server.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// close event source. [EventSource::next] would always return None afterwards.
+    pub fn close(&mut self) {
+        self.closed = true;
+        self.stream = None;
+    }
+
+    /// Receive next event. Reconnect to server when connection is lost.
+    ///
+    /// Connection and io errors are returned and reconnection is attempted on next call.
+    /// Server responding with status other than 200 or content type other than text/event-stream
+    /// closes event source permanently.
+    pub async fn next(&mut self) -> Option<Result<Event, Error>> {
+        use core::future::poll_fn;
+
+        loop {
+            if self.closed {
+                return None;
+            }
+
+            let Some(stream) = self.stream.as_mut() else {
+                if let Err(e) = self.reconnect().await {
+                    return Some(Err(e));
+                }
+                continue;
+            };
+
+            let res = poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await;
+
+            if let Some(retry) = stream.retry() {
+                self.retry = retry;
+            }
+            if let Some(id) = stream.last_event_id() {
+                if self.last_event_id.as_deref() != Some(id) {
+                    self.last_event_id = Some(id.to_owned());
+                }
+            }
+
+            match res {
+                Some(Ok(event)) => {
+                    self.failures = 0;
+                    return Some(Ok(event));
+                }
+                Some(Err(e)) => {
+                    self.stream = None;
+                    return Some(Err(e));
+                }
+                None => self.stream = None,
+            }
+        }
+    }
+
+    async fn reconnect(&mut self) -> Result<(), Error> {
+        let delay = self
+            .retry
+            .checked_mul(1 << self.failures.min(16))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        tokio::time::sleep(delay).await;
+
+        let mut req = http::Request::new(BoxBody::default());
+        *req.method_mut() = self.template.method().clone();
+        *req.uri_mut() = self.template.uri().clone();
+        *req.version_mut() = self.template.version();
+        *req.headers_mut() = self.template.headers().clone();
+
+        if let Some(id) = self.last_event_id.as_deref().filter(|id| !id.is_empty()) {
+            match HeaderValue::try_from(id) {
+                Ok(value) => {
+                    req.headers_mut().insert(LAST_EVENT_ID, value);
+                }
+                Err(e) => {
+                    self.close();
+                    return Err(Error::Std(Box::new(e)));
+                }
+            }
+        }
+
+        let res = match self.client.request(req).timeout(self.timeout).send().await {
+            Ok(res) => res,
+            Err(e) => {
+                self.failures += 1;
+                return Err(e);
+            }
+        };
+
+        match open(res) {
+            Ok(mut stream) => {
+                // continue with known last event id when server does not send new one.
+                stream.parser.id = self.last_event_id.clone();
+                self.failures = 0;
+                self.stream = Some(stream);
+                Ok(())
+            }
+            Err(e) => {
+                self.close();
+                Err(e)
+            }
+        }
+    }
+}
+
+fn open(res: Response) -> Result<EventStream, Error> {
+    let status = res.status();
+
+    if status != StatusCode::OK {
+        return Err(ErrorResponse {
+            expect_status: StatusCode::OK,
+            status,
+            description: "event stream can't be established",
+        }
+        .into());
+    }
+
+    res.event_stream()
+}
+
+// incremental text/event-stream parser.
+#[derive(Default)]
+struct Parser {
+    buf: BytesMut,
+    data: String,
+    event: String,
+    id: Option<String>,
+    retry: Option<Duration>,
+    started: bool,
+    // previous line ends with carriage return and a following line feed must be skipped.
+    skip_lf: bool,
+}
+
+impl Parser {
+    fn feed(&mut self, bytes: &[u8]) {
+        let mut bytes = bytes;
+        if !self.started && !bytes.is_empty() {
+            self.started = true;
+            bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        }
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn next_event(&mut self) -> Option<Event> {
+        loop {
+            if self.skip_lf && !self.buf.is_empty() {
+                self.skip_lf = false;
+                if self.buf[0] == b'\n' {
+                    self.buf.advance(1);
+                }
+            }
+
+            let pos = self.buf.iter().position(|b| *b == b'\n' || *b == b'\r')?;
+            let line = self.buf.split_to(pos);
+            let sep = self.buf[0];
+            self.buf.advance(1);
+
+            if sep == b'\r' {
+                self.skip_lf = true;
+            }
+
+            if let Some(event) = self.line(&String::from_utf8_lossy(&line)) {
+                return Some(event);
+            }
+        }
+    }
+
+    fn line(&mut self, line: &str) -> Option<Event> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        let (field, value) = match line.split_once(':') {
+            // comment line.
+            Some(("", _)) => return None,
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => value.clone_into(&mut self.event),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.id = Some(value.to_owned()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(ms) = value.parse() {
+                    self.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<Event> {
+        if self.data.is_empty() {
+            self.event.clear();
+            return None;
+        }
+
+        self.data.pop();
+
+        let event = match mem::take(&mut self.event) {
+            event if event.is_empty() => String::from("message"),
+            event => event,
+        };
+
+        Some(Event {
+            event,
+            data: mem::take(&mut self.data),
+            id: self.id.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(chunks: &[&[u8]]) -> (Vec<Event>, Parser) {
+        let mut parser = Parser::default();
+        let mut events = Vec::new();
+        for chunk in chunks {
+            parser.feed(chunk);
+            while let Some(event) = parser.next_event() {
+                events.push(event);
+            }
+        }
+        (events, parser)
+    }
+
+    #[test]
+    fn parse_event() {
+        let (events, parser) = parse(&[
+            b"\xEF\xBB\xBF: comment\n",
+            b"retry: 1000\n",
+            b"event: update\r\ndata: foo\r",
+            b"\ndata:bar\n\n",
+            b"id: 1\ndata\n\n",
+            b"event: ignored\n\n",
+            b"data: incomplete",
+        ]);
+
+        assert_eq!(
+            events,
+            [
+                Event {
+                    event: "update".into(),
+                    data: "foo\nbar".into(),
+                    id: None,
+                },
+                Event {
+                    event: "message".into(),
+                    data: "".into(),
+                    id: Some("1".into()),
+                },
+            ]
+        );
+        assert_eq!(parser.retry, Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn parse_empty_data() {
+        // a data line with empty value still produces event.
+        let (events, _) = parse(&[b"data:\n\ndata\ndata\n\n"]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].data, "");
+        assert_eq!(events[1].data, "\n");
+    }
+}
@@ -0,0 +1,396 @@
+//! backend agnostic tls configuration for built in openssl and rustls connectors.
+
+/// certificate or private key data in either PEM or DER encoding.
+#[derive(Clone, Debug)]
+pub(crate) enum Encoded {
+    Pem(Vec<u8>),
+    Der(Vec<u8>),
+}
+
+/// client certificate chain and private key presented to server for mutual tls authentication.
+#[derive(Clone, Debug)]
+pub struct Identity {
+    pub(crate) cert_chain: Encoded,
+    pub(crate) key: Encoded,
+}
+
+impl Identity {
+    /// construct identity from PEM encoded certificate chain and private key.
+    /// the first certificate in chain must be the client's end entity certificate.
+    pub fn from_pem(cert_chain: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            cert_chain: Encoded::Pem(cert_chain.into()),
+            key: Encoded::Pem(key.into()),
+        }
+    }
+
+    /// construct identity from a single DER encoded certificate and PKCS#8 DER encoded private key.
+    pub fn from_der(cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            cert_chain: Encoded::Der(cert.into()),
+            key: Encoded::Der(key.into()),
+        }
+    }
+}
+
+/// Configuration for tls connector enabled by [ClientBuilder::openssl] or [ClientBuilder::rustls].
+///
+/// Configuration is parsed and applied when [ClientBuilder::try_finish] is called and malformed
+/// certificate or key would cause it to return error. ([ClientBuilder::finish] would panic instead)
+///
+/// # Examples
+/// ```rust
+/// # #[cfg(feature = "rustls")]
+/// # fn config() {
+/// use xitca_client::{ClientBuilder, Identity, TlsConfig};
+///
+/// let ca = std::fs::read("ca.pem").unwrap();
+/// let cert = std::fs::read("client.pem").unwrap();
+/// let key = std::fs::read("client.key").unwrap();
+///
+/// let client = ClientBuilder::new()
+///     .rustls()
+///     // trust private certificate authority for all hosts.
+///     .tls_config(TlsConfig::new().add_root_certificate_pem(ca))
+///     // present client certificate only to given host.
+///     .tls_config_for_host("internal.example.com", TlsConfig::new().identity(Identity::from_pem(cert, key)))
+///     .try_finish()
+///     .expect("malformed certificate or key");
+/// # }
+/// ```
+///
+/// [ClientBuilder::openssl]: crate::ClientBuilder::openssl
+/// [ClientBuilder::rustls]: crate::ClientBuilder::rustls
+/// [ClientBuilder::finish]: crate::ClientBuilder::finish
+/// [ClientBuilder::try_finish]: crate::ClientBuilder::try_finish
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    pub(crate) roots: Vec<Encoded>,
+    pub(crate) builtin_roots: bool,
+    pub(crate) identity: Option<Identity>,
+    pub(crate) verify: bool,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TlsConfig {
+    pub const fn new() -> Self {
+        Self {
+            roots: Vec::new(),
+            builtin_roots: true,
+            identity: None,
+            verify: true,
+        }
+    }
+
+    /// add PEM encoded root certificate(s) to trusted certificate authorities.
+    /// multiple certificates in one bundle are accepted.
+    pub fn add_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.roots.push(Encoded::Pem(pem.into()));
+        self
+    }
+
+    /// add DER encoded root certificate to trusted certificate authorities.
+    pub fn add_root_certificate_der(mut self, der: impl Into<Vec<u8>>) -> Self {
+        self.roots.push(Encoded::Der(der.into()));
+        self
+    }
+
+    /// toggle trusting built in root certificates. (webpki roots for rustls and system default for openssl)
+    /// when disabled only certificates added to [TlsConfig] are trusted.
+    ///
+    /// Default to true.
+    pub fn builtin_roots(mut self, enable: bool) -> Self {
+        self.builtin_roots = enable;
+        self
+    }
+
+    /// present client certificate to server for mutual tls authentication.
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    #[cfg(feature = "dangerous")]
+    /// disable server certificate verification entirely. Only for trusted test environment as
+    /// connection is open to man-in-the-middle attack.
+    pub fn danger_accept_invalid_certs(mut self) -> Self {
+        self.verify = false;
+        self
+    }
+}
+
+#[cfg(feature = "openssl")]
+pub(crate) mod openssl {
+    use xitca_tls::openssl::{
+        error::ErrorStack,
+        pkey::PKey,
+        ssl::{SslConnectorBuilder, SslVerifyMode},
+        x509::{
+            store::{X509StoreBuilder, X509StoreBuilderRef},
+            X509,
+        },
+    };
+
+    use super::{Encoded, TlsConfig};
+
+    fn certs(encoded: &Encoded) -> Result<Vec<X509>, ErrorStack> {
+        match encoded {
+            Encoded::Pem(pem) => X509::stack_from_pem(pem),
+            Encoded::Der(der) => X509::from_der(der).map(|cert| vec![cert]),
+        }
+    }
+
+    fn add_roots(store: &mut X509StoreBuilderRef, config: &TlsConfig) -> Result<(), ErrorStack> {
+        for root in config.roots.iter() {
+            for cert in certs(root)? {
+                store.add_cert(cert)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn apply(builder: &mut SslConnectorBuilder, config: &TlsConfig) -> Result<(), ErrorStack> {
+        if config.builtin_roots {
+            add_roots(builder.cert_store_mut(), config)?;
+        } else {
+            // replace default cert store so only roots from config are trusted.
+            let mut store = X509StoreBuilder::new()?;
+            add_roots(&mut store, config)?;
+            builder.set_cert_store(store.build());
+        }
+
+        if let Some(ref identity) = config.identity {
+            let mut chain = certs(&identity.cert_chain)?.into_iter();
+            if let Some(cert) = chain.next() {
+                builder.set_certificate(&cert)?;
+            }
+            for cert in chain {
+                builder.add_extra_chain_cert(cert)?;
+            }
+
+            let key = match identity.key {
+                Encoded::Pem(ref pem) => PKey::private_key_from_pem(pem)?,
+                Encoded::Der(ref der) => PKey::private_key_from_der(der)?,
+            };
+            builder.set_private_key(&key)?;
+            builder.check_private_key()?;
+        }
+
+        if !config.verify {
+            builder.set_verify(SslVerifyMode::NONE);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "rustls", feature = "rustls-ring-crypto"))]
+pub(crate) mod rustls {
+    use std::sync::Arc;
+
+    use webpki_roots::TLS_SERVER_ROOTS;
+    use xitca_tls::rustls::{
+        self,
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+        pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+        ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+    };
+
+    use super::{Encoded, TlsConfig};
+
+    fn certs(encoded: &Encoded) -> Result<Vec<CertificateDer<'static>>, rustls::Error> {
+        match encoded {
+            Encoded::Pem(pem) => CertificateDer::pem_slice_iter(pem)
+                .collect::<Result<_, _>>()
+                .map_err(|e| rustls::Error::General(e.to_string())),
+            Encoded::Der(der) => Ok(vec![CertificateDer::from(der.clone())]),
+        }
+    }
+
+    pub(crate) fn client_config(config: &TlsConfig) -> Result<ClientConfig, rustls::Error> {
+        let mut root_certs = RootCertStore::empty();
+
+        if config.builtin_roots {
+            root_certs.extend(TLS_SERVER_ROOTS.iter().cloned());
+        }
+
+        for root in config.roots.iter() {
+            for cert in certs(root)? {
+                root_certs.add(cert)?;
+            }
+        }
+
+        let builder = ClientConfig::builder().with_root_certificates(root_certs);
+
+        let mut cfg = match config.identity {
+            Some(ref identity) => {
+                let chain = certs(&identity.cert_chain)?;
+                let key = match identity.key {
+                    Encoded::Pem(ref pem) => {
+                        PrivateKeyDer::from_pem_slice(pem).map_err(|e| rustls::Error::General(e.to_string()))?
+                    }
+                    Encoded::Der(ref der) => {
+                        PrivateKeyDer::try_from(der.clone()).map_err(|e| rustls::Error::General(e.into()))?
+                    }
+                };
+                builder.with_client_auth_cert(chain, key)?
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        if !config.verify {
+            let provider = cfg.crypto_provider().clone();
+            cfg.dangerous()
+                .set_certificate_verifier(Arc::new(SkipServerVerification(provider)));
+        }
+
+        Ok(cfg)
+    }
+
+    #[derive(Debug)]
+    pub(crate) struct SkipServerVerification(pub(crate) Arc<CryptoProvider>);
+
+    impl ServerCertVerifier for SkipServerVerification {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.0.signature_verification_algorithms.supported_schemes()
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto")))]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "openssl")]
+    #[test]
+    fn openssl_custom_root() {
+        use std::{net::TcpListener, thread};
+
+        use xitca_tls::openssl::{
+            asn1::Asn1Time,
+            bn::BigNum,
+            ec::{EcGroup, EcKey},
+            hash::MessageDigest,
+            nid::Nid,
+            pkey::PKey,
+            ssl::{SslAcceptor, SslConnector, SslMethod},
+            x509::{
+                extension::{BasicConstraints, SubjectAlternativeName},
+                X509NameBuilder, X509,
+            },
+        };
+
+        // self signed certificate acting as both root and server certificate.
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, "localhost").unwrap();
+        let name = name.build();
+
+        let mut cert = X509::builder().unwrap();
+        cert.set_version(2).unwrap();
+        cert.set_serial_number(&BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap())
+            .unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(1).unwrap()).unwrap();
+        cert.append_extension(BasicConstraints::new().critical().ca().build().unwrap())
+            .unwrap();
+        let san = SubjectAlternativeName::new()
+            .dns("localhost")
+            .build(&cert.x509v3_context(None, None))
+            .unwrap();
+        cert.append_extension(san).unwrap();
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+        let cert = cert.build();
+
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&cert).unwrap();
+        let acceptor = acceptor.build();
+
+        let handshake = |config: TlsConfig| {
+            let lst = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = lst.local_addr().unwrap();
+            let acceptor = acceptor.clone();
+            let server = thread::spawn(move || {
+                let (stream, _) = lst.accept().unwrap();
+                let _ = acceptor.accept(stream);
+            });
+
+            let mut builder = SslConnector::builder(SslMethod::tls()).unwrap();
+            openssl::apply(&mut builder, &config).unwrap();
+            let stream = std::net::TcpStream::connect(addr).unwrap();
+            let res = builder.build().connect("localhost", stream).is_ok();
+            server.join().unwrap();
+            res
+        };
+
+        let pem = cert.to_pem().unwrap();
+        assert!(handshake(
+            TlsConfig::new()
+                .builtin_roots(false)
+                .add_root_certificate_pem(pem.clone())
+        ));
+        assert!(handshake(TlsConfig::new().add_root_certificate_pem(pem)));
+        assert!(!handshake(TlsConfig::new().builtin_roots(false)));
+    }
+
+    #[cfg(any(feature = "rustls", feature = "rustls-ring-crypto"))]
+    #[test]
+    fn rustls_config() {
+        assert!(rustls::client_config(&TlsConfig::new()).is_ok());
+
+        let config = TlsConfig::new()
+            .builtin_roots(false)
+            .add_root_certificate_der(b"not a certificate".to_vec());
+        assert!(rustls::client_config(&config).is_err());
+
+        let config = TlsConfig::new().identity(Identity::from_pem(Vec::new(), Vec::new()));
+        assert!(rustls::client_config(&config).is_err());
+
+        let res = crate::ClientBuilder::new()
+            .rustls()
+            .tls_config_for_host("foo.bar", config)
+            .try_finish();
+        assert!(res.is_err());
+    }
+}
@@ -36,6 +36,24 @@ pub(crate) fn nop() -> Connector {
     Box::new(Nop)
 }
 
+// dispatch tls handshake to connector configured for server name and fall back to default one.
+#[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+pub(crate) struct PerHost {
+    pub(crate) default: Connector,
+    pub(crate) hosts: std::collections::HashMap<Box<str>, Connector>,
+}
+
+#[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+impl<'n> Service<(&'n str, TlsStream)> for PerHost {
+    type Response = (TlsStream, Version);
+    type Error = Error;
+
+    async fn call(&self, req: (&'n str, TlsStream)) -> Result<Self::Response, Self::Error> {
+        let connector = self.hosts.get(req.0).unwrap_or(&self.default);
+        ServiceDyn::call(&**connector, req).await
+    }
+}
+
 #[cfg(feature = "openssl")]
 pub(crate) mod openssl {
     use xitca_http::bytes::BufMut;
@@ -44,6 +62,8 @@ pub(crate) mod openssl {
         ssl::{SslConnector, SslMethod},
     };
 
+    use crate::tls::config::{self, TlsConfig};
+
     use super::*;
 
     impl<'n> Service<(&'n str, TlsStream)> for SslConnector {
@@ -69,7 +89,7 @@ pub(crate) mod openssl {
         }
     }
 
-    pub(crate) fn connect(protocols: &[&[u8]]) -> Connector {
+    pub(crate) fn connect(protocols: &[&[u8]], config: &TlsConfig) -> Result<Connector, Error> {
         let mut alpn = Vec::with_capacity(20);
         for proto in protocols {
             alpn.put_u8(proto.len() as u8);
@@ -81,7 +101,9 @@ pub(crate) mod openssl {
         ssl.set_alpn_protos(&alpn)
             .unwrap_or_else(|e| panic!("Can not set ALPN protocol: {e:?}"));
 
-        Box::new(ssl.build())
+        config::openssl::apply(&mut ssl, config)?;
+
+        Ok(Box::new(ssl.build()))
     }
 }
 
@@ -89,8 +111,9 @@ pub(crate) mod openssl {
 pub(crate) mod rustls {
     use std::sync::Arc;
 
-    use webpki_roots::TLS_SERVER_ROOTS;
-    use xitca_tls::rustls::{self, pki_types::ServerName, ClientConfig, ClientConnection, RootCertStore};
+    use xitca_tls::rustls::{self, pki_types::ServerName, ClientConfig, ClientConnection};
+
+    use crate::tls::config::{self, TlsConfig};
 
     use super::*;
 
@@ -123,17 +146,11 @@ pub(crate) mod rustls {
         }
     }
 
-    pub(crate) fn connect(protocols: &[&[u8]]) -> Connector {
-        let mut root_certs = RootCertStore::empty();
-
-        root_certs.extend(TLS_SERVER_ROOTS.iter().cloned());
-
-        let mut config = ClientConfig::builder()
-            .with_root_certificates(root_certs)
-            .with_no_client_auth();
+    pub(crate) fn connect(protocols: &[&[u8]], config: &TlsConfig) -> Result<Connector, Error> {
+        let mut config = config::rustls::client_config(config)?;
 
         config.alpn_protocols = protocols.iter().map(|p| p.to_vec()).collect();
 
-        Box::new(TlsConnector(Arc::new(config)))
+        Ok(Box::new(TlsConnector(Arc::new(config))))
     }
 }
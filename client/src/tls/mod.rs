@@ -1,3 +1,5 @@
+#[cfg(any(feature = "openssl", feature = "rustls", feature = "rustls-ring-crypto"))]
+pub(crate) mod config;
 pub(crate) mod connector;
 
 pub type TlsStream = Box<dyn xitca_io::io::AsyncIoDyn + Send>;
@@ -3,7 +3,7 @@ use core::{
     task::{Context, Poll},
 };
 
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use futures_core::stream::Stream;
 use futures_sink::Sink;
@@ -50,6 +50,65 @@ where
     }
 }
 
+/// owned sender part of tunneled connection. can be moved into a separate task.
+/// [Sink] trait is used to asynchronously send message.
+pub struct OwnedTunnelSink<I>(Arc<Tunnel<I>>);
+
+impl<M, I> Sink<M> for OwnedTunnelSink<I>
+where
+    I: Sink<M> + Unpin,
+{
+    type Error = I::Error;
+
+    #[inline]
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut TunnelSink(&self.0)).poll_ready(cx)
+    }
+
+    #[inline]
+    fn start_send(self: Pin<&mut Self>, item: M) -> Result<(), Self::Error> {
+        Pin::new(&mut TunnelSink(&self.0)).start_send(item)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut TunnelSink(&self.0)).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut TunnelSink(&self.0)).poll_close(cx)
+    }
+}
+
+/// owned receiver part of tunneled connection. can be moved into a separate task.
+/// [Stream] trait is used to asynchronously receive message.
+pub struct OwnedTunnelStream<I>(Arc<Tunnel<I>>);
+
+impl<I> Stream for OwnedTunnelStream<I>
+where
+    I: Stream + Unpin,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut TunnelStream(&self.0)).poll_next(cx)
+    }
+}
+
+impl<I> OwnedTunnelSink<I> {
+    /// try to join with [OwnedTunnelStream] split from the same tunnel and restore the unified tunnel.
+    /// the stream part is returned as error when it's split from a different tunnel.
+    pub fn reunite(self, stream: OwnedTunnelStream<I>) -> Result<Tunnel<I>, (Self, OwnedTunnelStream<I>)> {
+        if !Arc::ptr_eq(&self.0, &stream.0) {
+            return Err((self, stream));
+        }
+        drop(stream);
+        Ok(Arc::into_inner(self.0).expect("owned tunnel halves must be the only references"))
+    }
+}
+
 /// A unified tunnel that can be used as both sender/receiver.
 ///
 /// * This type can not do concurrent message handling which means send always block receive and vice versa.
@@ -68,6 +127,13 @@ where
         (TunnelSink(self), TunnelStream(self))
     }
 
+    /// Split into an owned sink and reader pair. Unlike [Tunnel::split] the pair is not bound to
+    /// the lifetime of tunnel and can be moved into different tasks.
+    pub fn into_split(self) -> (OwnedTunnelSink<I>, OwnedTunnelStream<I>) {
+        let tunnel = Arc::new(self);
+        (OwnedTunnelSink(tunnel.clone()), OwnedTunnelStream(tunnel))
+    }
+
     /// acquire inner tunnel type.
     pub fn into_inner(self) -> I {
         self.inner.into_inner().unwrap()
@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::{ops::Deref, path::PathBuf};
 
 use crate::{error::InvalidUri, http::uri};
 
@@ -31,12 +31,49 @@ impl<'a> Uri<'a> {
             (None, _, _) => Err(InvalidUri::MissingScheme),
             (Some("http" | "ws"), _, _) => Ok(Uri::Tcp(uri)),
             (Some("https" | "wss"), _, _) => Ok(Uri::Tls(uri)),
-            (Some("unix"), _, _) => Ok(Uri::Unix(uri)),
+            (Some("unix" | "http+unix"), _, _) => Ok(Uri::Unix(uri)),
             (Some(_), _, _) => Err(InvalidUri::UnknownScheme),
         }
     }
 }
 
+impl Uri<'_> {
+    // socket path of unix domain socket uri.
+    // - `http+unix://{hex encoded path}/{path_and_query}` where host is hex encoded socket path.
+    // - `unix://{path}` where the whole authority and path is treated as socket path.
+    pub(crate) fn unix_socket_path(&self) -> Result<PathBuf, InvalidUri> {
+        match self.scheme_str() {
+            Some("http+unix") => {
+                let host = self.host().ok_or(InvalidUri::MissingHost)?;
+                decode_hex(host)
+                    .map(PathBuf::from)
+                    .ok_or(InvalidUri::InvalidUnixSocketPath)
+            }
+            _ => {
+                let authority = self.authority().ok_or(InvalidUri::MissingAuthority)?;
+                let path = self.path_and_query().ok_or(InvalidUri::MissingPathQuery)?;
+                Ok(PathBuf::from(format!("/{}{}", authority.as_str(), path.as_str())))
+            }
+        }
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<String> {
+    let chunks = hex.as_bytes().chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return None;
+    }
+
+    let bytes = chunks
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).ok()?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    String::from_utf8(bytes).ok()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -66,5 +103,20 @@ mod test {
         assert_eq!(uri.scheme_str().unwrap(), "unix");
         assert_eq!(uri.host().unwrap(), "tmp");
         assert_eq!(uri.path(), "/foo.socket");
+        assert_eq!(uri.unix_socket_path().unwrap(), PathBuf::from("/tmp/foo.socket"));
+    }
+
+    #[test]
+    fn http_unix_parse() {
+        // hex encoded /tmp/foo.socket
+        let uri = uri::Uri::from_static("http+unix://2f746d702f666f6f2e736f636b6574/foo/bar");
+        let uri = Uri::try_parse(&uri).unwrap();
+        assert!(matches!(uri, Uri::Unix(_)));
+        assert_eq!(uri.path(), "/foo/bar");
+        assert_eq!(uri.unix_socket_path().unwrap(), PathBuf::from("/tmp/foo.socket"));
+
+        let uri = uri::Uri::from_static("http+unix://2f746d7/foo/bar");
+        let err = Uri::try_parse(&uri).unwrap().unix_socket_path().err().unwrap();
+        assert!(matches!(err, InvalidUri::InvalidUnixSocketPath));
     }
 }
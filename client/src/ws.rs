@@ -16,9 +16,12 @@ use super::{
     bytes::{Buf, BytesMut},
     connection::ConnectionExclusive,
     error::{Error, ErrorResponse},
-    http::{StatusCode, Version},
+    http::{
+        header::{HeaderMap, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY},
+        StatusCode, Version,
+    },
     request::RequestBuilder,
-    tunnel::{Tunnel, TunnelSink, TunnelStream},
+    tunnel::{OwnedTunnelSink, OwnedTunnelStream, Tunnel, TunnelSink, TunnelStream},
 };
 
 /// new type of [RequestBuilder] with extended functionality for websocket handling.
@@ -39,15 +42,25 @@ pub type WebSocket = Tunnel<WebSocketTunnel>;
 /// [Sink] trait is used to asynchronously send message.
 pub type WebSocketSink<'a> = TunnelSink<'a, WebSocketTunnel>;
 
-/// sender part of websocket connection.
+/// receiver part of websocket connection.
 /// [Stream] trait is used to asynchronously receive message.
 pub type WebSocketReader<'a> = TunnelStream<'a, WebSocketTunnel>;
 
+/// owned sender part of websocket connection produced by [Tunnel::into_split].
+/// [Sink] trait is used to asynchronously send message.
+pub type WebSocketOwnedSink = OwnedTunnelSink<WebSocketTunnel>;
+
+/// owned receiver part of websocket connection produced by [Tunnel::into_split].
+/// [Stream] trait is used to asynchronously receive message.
+pub type WebSocketOwnedReader = OwnedTunnelStream<WebSocketTunnel>;
+
 impl WsRequest<'_> {
     /// Send the request and wait for response asynchronously.
     pub async fn send(mut self) -> Result<WebSocket, Error> {
         http_ws::client_request_extend(&mut self.req);
 
+        let key = self.req.headers().get(SEC_WEBSOCKET_KEY).cloned();
+
         let res = self._send().await?;
 
         let status = res.status();
@@ -65,6 +78,17 @@ impl WsRequest<'_> {
             }));
         }
 
+        // http/2 websocket (RFC 8441) does not carry key in handshake.
+        if let Some(key) = key {
+            if !verify_accept(key.as_bytes(), res.headers()) {
+                return Err(Error::from(ErrorResponse {
+                    expect_status: StatusCode::SWITCHING_PROTOCOLS,
+                    status,
+                    description: "websocket handshake failed with invalid Sec-WebSocket-Accept header",
+                }));
+            }
+        }
+
         let body = res.res.into_body();
         Ok(WebSocket::new(WebSocketTunnel {
             codec: Codec::new().client_mode(),
@@ -87,6 +111,13 @@ impl WebSocket {
     }
 }
 
+// check Sec-WebSocket-Accept header of handshake response matches the key sent with request.
+fn verify_accept(key: &[u8], headers: &HeaderMap) -> bool {
+    headers
+        .get(SEC_WEBSOCKET_ACCEPT)
+        .is_some_and(|accept| accept.as_bytes() == http_ws::hash_key(key).as_slice())
+}
+
 pub struct WebSocketTunnel {
     codec: Codec,
     send_buf: BytesMut,
@@ -181,3 +212,26 @@ impl Stream for WebSocketTunnel {
             })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::http::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn accept() {
+        // sample handshake from RFC 6455 section 1.3
+        let key = b"dGhlIHNhbXBsZSBub25jZQ==";
+
+        let mut headers = HeaderMap::new();
+        assert!(!verify_accept(key, &headers));
+
+        headers.insert(
+            SEC_WEBSOCKET_ACCEPT,
+            HeaderValue::from_static("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="),
+        );
+        assert!(verify_accept(key, &headers));
+        assert!(!verify_accept(b"x3JJHMbDL1EzLkh9GBhXDw==", &headers));
+    }
+}
@@ -0,0 +1,89 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Error, Expr, ItemFn, spanned::Spanned};
+
+use crate::route::Args;
+
+pub(crate) enum Entry {
+    Main,
+    Test,
+}
+
+pub(crate) fn entry(entry: Entry, attr: Args, input: ItemFn) -> Result<TokenStream, Error> {
+    if input.sig.asyncness.is_none() {
+        return Err(Error::new(input.sig.fn_token.span(), "entry function must be async fn"));
+    }
+
+    if !input.sig.inputs.is_empty() {
+        return Err(Error::new(
+            input.sig.inputs.span(),
+            "entry function must not have arguments",
+        ));
+    }
+
+    let mut log = quote! {};
+
+    for attr in attr.vars {
+        let Expr::Assign(pair) = attr else {
+            return Err(Error::new(attr.span(), "expect '<name> = <value>' expression"));
+        };
+
+        let Expr::Path(ref name) = *pair.left else {
+            return Err(Error::new(pair.left.span(), "expect <name> to be path expression"));
+        };
+
+        match name.path.get_ident().map(ToString::to_string).as_deref() {
+            Some("log") => {
+                let level = match *pair.right {
+                    Expr::Path(ref value) => value.path.get_ident().map(|level| (level.to_string(), level.span())),
+                    _ => None,
+                };
+
+                let level = match level {
+                    Some((level, span)) if matches!(level.as_str(), "trace" | "debug" | "info" | "warn" | "error") => {
+                        syn::Ident::new(&level.to_uppercase(), span)
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            pair.right.span(),
+                            "expect 'log = <level>'. level can be one of trace, debug, info, warn and error",
+                        ));
+                    }
+                };
+
+                log = quote! {
+                    ::xitca_web::codegen::__private::init_log(::xitca_web::codegen::__private::Level::#level);
+                };
+            }
+            _ => return Err(Error::new(name.span(), "unknown attribute. expect log")),
+        }
+    }
+
+    let test = match entry {
+        Entry::Main => quote! {},
+        Entry::Test => quote! { #[::core::prelude::v1::test] },
+    };
+
+    let ItemFn {
+        attrs,
+        vis,
+        mut sig,
+        block,
+    } = input;
+
+    sig.asyncness = None;
+
+    Ok(quote! {
+        #test
+        #(#attrs)*
+        #vis #sig {
+            #log
+            ::xitca_web::codegen::__private::tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("failed to build async runtime")
+                .block_on(async move #block)
+        }
+    }
+    .into())
+}
@@ -1,6 +1,9 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Error, ItemImpl, Type, spanned::Spanned};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, ExprLit, Fields, ItemImpl, Lit, Meta, Token, Type,
+    punctuated::Punctuated, spanned::Spanned,
+};
 
 pub(crate) fn error(_: TokenStream, item: ItemImpl) -> Result<TokenStream, Error> {
     let Type::Path(ref err_ty) = *item.self_ty else {
@@ -30,3 +33,181 @@ pub(crate) fn error(_: TokenStream, item: ItemImpl) -> Result<TokenStream, Error
     }
     .into())
 }
+
+pub(crate) fn error_response(input: DeriveInput) -> Result<TokenStream, Error> {
+    let ty_ident = &input.ident;
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let params = input.generics.params.iter().collect::<Vec<_>>();
+
+    // container attribute serves as default of all variants.
+    let default = Response::from_attrs(&input.attrs, Response::default())?;
+
+    let (status, body) = match input.data {
+        Data::Struct(_) => {
+            let (status, body) = default.to_tokens();
+            (quote! { #status }, quote! { #body })
+        }
+        Data::Enum(ref data) => {
+            let mut status_arms = Vec::new();
+            let mut body_arms = Vec::new();
+
+            for variant in data.variants.iter() {
+                let ident = &variant.ident;
+                let pat = match variant.fields {
+                    Fields::Named(_) => quote! { Self::#ident { .. } },
+                    Fields::Unnamed(_) => quote! { Self::#ident(..) },
+                    Fields::Unit => quote! { Self::#ident },
+                };
+
+                let (status, body) = Response::from_attrs(&variant.attrs, default.clone())?.to_tokens();
+                status_arms.push(quote! { #pat => #status, });
+                body_arms.push(quote! { #pat => #body, });
+            }
+
+            (
+                quote! { match self { #(#status_arms)* } },
+                quote! { match self { #(#body_arms)* } },
+            )
+        }
+        Data::Union(_) => return Err(Error::new(ty_ident.span(), "expect Struct or Enum")),
+    };
+
+    Ok(quote! {
+        impl<'__r, __C, __B, #(#params),*> ::xitca_web::service::Service<::xitca_web::WebContext<'__r, __C, __B>> for #ty_ident #ty_generics
+        #where_clause
+        {
+            type Response = ::xitca_web::http::WebResponse;
+            type Error = ::core::convert::Infallible;
+
+            async fn call(&self, ctx: ::xitca_web::WebContext<'__r, __C, __B>) -> Result<Self::Response, Self::Error> {
+                let status = #status;
+                let body = #body;
+                Ok(::xitca_web::codegen::__private::error_response(ctx, status, body, self))
+            }
+        }
+
+        impl<#(#params),*> From<#ty_ident #ty_generics> for ::xitca_web::error::Error
+        #where_clause
+        {
+            fn from(e: #ty_ident #ty_generics) -> Self {
+                Self::from_service(e)
+            }
+        }
+    }
+    .into())
+}
+
+#[derive(Clone)]
+struct Response {
+    status: Status,
+    body: Body,
+}
+
+#[derive(Clone)]
+enum Status {
+    Code(u16),
+    Name(syn::Ident),
+}
+
+#[derive(Clone, Copy)]
+enum Body {
+    Empty,
+    Text,
+    Json,
+}
+
+impl Default for Response {
+    fn default() -> Self {
+        Self {
+            status: Status::Code(500),
+            body: Body::Text,
+        }
+    }
+}
+
+impl Response {
+    // parse #[response(status = <status>, body = <body>)] attribute on top of given default value.
+    fn from_attrs(attrs: &[Attribute], mut res: Self) -> Result<Self, Error> {
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("response")) {
+            let Meta::List(ref list) = attr.meta else {
+                return Err(Error::new(
+                    attr.span(),
+                    "expect #[response(status = <status>, body = <body>)]",
+                ));
+            };
+
+            let pairs = list.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+
+            for pair in pairs {
+                let Expr::Assign(pair) = pair else {
+                    return Err(Error::new(pair.span(), "expect '<name> = <value>' expression"));
+                };
+
+                let Expr::Path(ref name) = *pair.left else {
+                    return Err(Error::new(pair.left.span(), "expect <name> to be path expression"));
+                };
+
+                match name.path.get_ident().map(ToString::to_string).as_deref() {
+                    Some("status") => {
+                        res.status = match *pair.right {
+                            Expr::Lit(ExprLit {
+                                lit: Lit::Int(ref int), ..
+                            }) => {
+                                let code = int.base10_parse::<u16>()?;
+                                if !(100..1000).contains(&code) {
+                                    return Err(Error::new(int.span(), "status code must be in range of 100..=999"));
+                                }
+                                Status::Code(code)
+                            }
+                            Expr::Path(ref path) => match path.path.get_ident() {
+                                Some(ident) => Status::Name(ident.clone()),
+                                None => return Err(Error::new(path.span(), "expect status code constant name")),
+                            },
+                            _ => {
+                                return Err(Error::new(
+                                    pair.right.span(),
+                                    "expect status code or it's constant name",
+                                ));
+                            }
+                        };
+                    }
+                    Some("body") => {
+                        let body = match *pair.right {
+                            Expr::Path(ref path) => path.path.get_ident().map(ToString::to_string),
+                            _ => None,
+                        };
+                        res.body = match body.as_deref() {
+                            Some("empty") => Body::Empty,
+                            Some("text") => Body::Text,
+                            Some("json") => Body::Json,
+                            _ => return Err(Error::new(pair.right.span(), "expect empty, text or json")),
+                        };
+                    }
+                    _ => return Err(Error::new(name.span(), "expect status or body")),
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn to_tokens(&self) -> (impl quote::ToTokens + use<>, impl quote::ToTokens + use<>) {
+        let status = match self.status {
+            Status::Code(code) => quote! {
+                match ::xitca_web::http::StatusCode::from_u16(#code) {
+                    Ok(status) => status,
+                    Err(_) => unreachable!(),
+                }
+            },
+            Status::Name(ref ident) => quote! { ::xitca_web::http::StatusCode::#ident },
+        };
+
+        let body = match self.body {
+            Body::Empty => quote! { ::xitca_web::codegen::__private::ErrorBody::Empty },
+            Body::Text => quote! { ::xitca_web::codegen::__private::ErrorBody::Text },
+            Body::Json => quote! { ::xitca_web::codegen::__private::ErrorBody::Json },
+        };
+
+        (status, body)
+    }
+}
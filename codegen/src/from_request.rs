@@ -0,0 +1,176 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Error, Fields, GenericArgument, Lifetime, PathArguments, ReturnType, Type, spanned::Spanned,
+};
+
+pub(crate) fn from_request(input: DeriveInput) -> Result<TokenStream, Error> {
+    let ty_ident = &input.ident;
+
+    let Data::Struct(ref data) = input.data else {
+        return Err(Error::new(ty_ident.span(), "expect Struct"));
+    };
+
+    if input.generics.type_params().next().is_some() || input.generics.const_params().next().is_some() {
+        return Err(Error::new(
+            input.generics.span(),
+            "generic type param is not supported. only one lifetime param is allowed",
+        ));
+    }
+
+    let mut lifetimes = input.generics.lifetimes();
+    let lifetime = lifetimes.next().map(|param| param.lifetime.clone());
+    if let Some(param) = lifetimes.next() {
+        return Err(Error::new(param.span(), "only one lifetime param is allowed"));
+    }
+
+    // lifetime of FromRequest trait. struct's own lifetime param is reused when presented.
+    let a = lifetime
+        .clone()
+        .unwrap_or_else(|| Lifetime::new("'__a", ty_ident.span()));
+    // lifetime for constructing FromRequest::Type<'__b>.
+    let b = Lifetime::new("'__b", ty_ident.span());
+
+    let ty_b = match lifetime {
+        Some(_) => quote! { #ty_ident<#b> },
+        None => quote! { #ty_ident },
+    };
+
+    let ctx = quote! { ::xitca_web::WebContext<'__r, __C, __B> };
+
+    let mut bounds = Vec::new();
+    let mut futs = Vec::new();
+    let mut polls = Vec::new();
+    let mut values = Vec::new();
+
+    for (idx, field) in data.fields.iter().enumerate() {
+        let ty = &field.ty;
+
+        let mut field_ty_b = ty.clone();
+        if let Some(ref lifetime) = lifetime {
+            replace_lifetime(&mut field_ty_b, lifetime, &b);
+        }
+
+        bounds.push(quote! {
+            for<#b> #field_ty_b: ::xitca_web::handler::FromRequest<#b, #ctx>,
+            for<#b> ::xitca_web::error::Error: From<<#field_ty_b as ::xitca_web::handler::FromRequest<#b, #ctx>>::Error>,
+        });
+
+        let fut = format_ident!("__fut{idx}");
+        let res = format_ident!("__res{idx}");
+
+        futs.push(quote! {
+            let mut #fut = ::core::pin::pin!(<#ty as ::xitca_web::handler::FromRequest<#a, #ctx>>::from_request(ctx));
+            let mut #res = None;
+        });
+
+        polls.push(quote! {
+            if #res.is_none() {
+                match #fut.as_mut().poll(cx) {
+                    ::core::task::Poll::Ready(Ok(value)) => #res = Some(value),
+                    ::core::task::Poll::Ready(Err(e)) => return ::core::task::Poll::Ready(Err(::xitca_web::error::Error::from(e))),
+                    ::core::task::Poll::Pending => pending = true,
+                }
+            }
+        });
+
+        let value = quote! { #res.unwrap() };
+        values.push(match field.ident {
+            Some(ref ident) => quote! { #ident: #value },
+            None => value,
+        });
+    }
+
+    let construct = match data.fields {
+        Fields::Named(_) => quote! { Self { #(#values),* } },
+        Fields::Unnamed(_) => quote! { Self(#(#values),*) },
+        Fields::Unit => quote! { Self },
+    };
+
+    let ty_a = match lifetime {
+        Some(_) => quote! { #ty_ident<#a> },
+        None => quote! { #ty_ident },
+    };
+
+    Ok(quote! {
+        impl<#a, '__r, __C, __B> ::xitca_web::handler::FromRequest<#a, #ctx> for #ty_a
+        where
+            #(#bounds)*
+        {
+            type Type<#b> = #ty_b;
+            type Error = ::xitca_web::error::Error;
+
+            async fn from_request(ctx: &#a #ctx) -> Result<Self, Self::Error> {
+                use ::core::future::Future;
+
+                // extract all fields concurrently and return early on first error.
+                #(#futs)*
+
+                ::core::future::poll_fn(|cx| {
+                    let mut pending = false;
+                    #(#polls)*
+                    if pending {
+                        ::core::task::Poll::Pending
+                    } else {
+                        ::core::task::Poll::Ready(Ok(()))
+                    }
+                })
+                .await?;
+
+                Ok(#construct)
+            }
+        }
+    }
+    .into())
+}
+
+// replace given lifetime in type with another one.
+fn replace_lifetime(ty: &mut Type, from: &Lifetime, to: &Lifetime) {
+    let replace = |lifetime: &mut Lifetime| {
+        if lifetime == from {
+            *lifetime = to.clone();
+        }
+    };
+
+    match ty {
+        Type::Reference(ty) => {
+            if let Some(ref mut lifetime) = ty.lifetime {
+                replace(lifetime);
+            }
+            replace_lifetime(&mut ty.elem, from, to);
+        }
+        Type::Path(ty) => {
+            if let Some(ref mut qself) = ty.qself {
+                replace_lifetime(&mut qself.ty, from, to);
+            }
+            for seg in ty.path.segments.iter_mut() {
+                match seg.arguments {
+                    PathArguments::AngleBracketed(ref mut args) => {
+                        for arg in args.args.iter_mut() {
+                            match arg {
+                                GenericArgument::Lifetime(lifetime) => replace(lifetime),
+                                GenericArgument::Type(ty) => replace_lifetime(ty, from, to),
+                                _ => {}
+                            }
+                        }
+                    }
+                    PathArguments::Parenthesized(ref mut args) => {
+                        for ty in args.inputs.iter_mut() {
+                            replace_lifetime(ty, from, to);
+                        }
+                        if let ReturnType::Type(_, ref mut ty) = args.output {
+                            replace_lifetime(ty, from, to);
+                        }
+                    }
+                    PathArguments::None => {}
+                }
+            }
+        }
+        Type::Tuple(ty) => ty.elems.iter_mut().for_each(|ty| replace_lifetime(ty, from, to)),
+        Type::Slice(ty) => replace_lifetime(&mut ty.elem, from, to),
+        Type::Array(ty) => replace_lifetime(&mut ty.elem, from, to),
+        Type::Paren(ty) => replace_lifetime(&mut ty.elem, from, to),
+        Type::Group(ty) => replace_lifetime(&mut ty.elem, from, to),
+        _ => {}
+    }
+}
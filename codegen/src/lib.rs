@@ -1,5 +1,8 @@
+mod entry;
 mod error;
+mod from_request;
 mod route;
+mod schema;
 mod service;
 mod state;
 
@@ -9,7 +12,39 @@ use syn::{Error, ImplItem, ImplItemFn, spanned::Spanned};
 #[proc_macro_derive(State, attributes(borrow))]
 pub fn state_impl(item: TokenStream) -> TokenStream {
     let item = syn::parse_macro_input!(item);
-    state::state(item).unwrap_or_else(|e| e.to_compile_error().into())
+    state::state(item, state::Borrow::Marked).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+/// derive macro for borrowing every field of struct from application state.
+///
+/// field can be opted out with `#[borrow(skip)]` attribute.
+#[proc_macro_derive(BorrowState, attributes(borrow))]
+pub fn borrow_state_impl(item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item);
+    state::state(item, state::Borrow::All).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+/// derive macro for struct composed by types implementing `xitca_web::handler::FromRequest` trait.
+///
+/// all fields are extracted concurrently and the first error is returned when any of them failed.
+#[proc_macro_derive(FromRequest)]
+pub fn from_request_impl(item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item);
+    from_request::from_request(item).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+/// derive macro for implementing `xitca_web::schema::ApiSchema` trait.
+///
+/// doc comments of type, field and enum variant are used as description of their schema.
+///
+/// # Attributes
+/// - `#[schema(example = "<json>")]`: example value of schema in JSON format.
+/// - `#[schema(description = "<description>")]`: description of schema. override doc comments.
+/// - `#[schema(rename = "<name>")]`: rename field or enum variant.
+#[proc_macro_derive(ApiSchema, attributes(schema))]
+pub fn api_schema_impl(item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item);
+    schema::api_schema(item).unwrap_or_else(|e| e.to_compile_error().into())
 }
 
 /// attribute macro for `xitca-web` application.
@@ -26,6 +61,8 @@ pub fn state_impl(item: TokenStream) -> TokenStream {
 ///   `method = get` for example.
 /// - `enclosed = <type>`: typed middleware applied to route.
 /// - `enclosed_fn = <async function>`: async function as middleware applied to route
+///
+/// `method = <method>` can be repeated for routing multiple http methods to the same handler.
 /// ```
 #[proc_macro_attribute]
 pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -34,6 +71,92 @@ pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
     route::route(attr, item).unwrap_or_else(|e| e.to_compile_error().into())
 }
 
+/// attribute macro for async main function of `xitca-web` application.
+///
+/// main function is run by a single threaded async runtime. it's preferred runtime configuration as
+/// `xitca_web::HttpServer` runs every worker in it's own thread with it's own single threaded runtime.
+///
+/// # Pattern
+/// ```plain
+/// #[main[(log = <level>)]]
+/// ```
+///
+/// # Attributes
+/// - `log = <level>`: initialize global tracing subscriber with given level before entering main function.
+///   level can be one of `trace`, `debug`, `info`, `warn` and `error`. requires `logger` feature of `xitca-web`.
+#[proc_macro_attribute]
+pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = syn::parse_macro_input!(attr);
+    let item = syn::parse_macro_input!(item);
+    entry::entry(entry::Entry::Main, attr, item).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+/// attribute macro for async test function. see [macro@main] for attributes.
+///
+/// test function is run by a single threaded async runtime.
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = syn::parse_macro_input!(attr);
+    let item = syn::parse_macro_input!(item);
+    entry::entry(entry::Entry::Test, attr, item).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+macro_rules! method_route {
+    ($($method: ident),*) => {
+        $(
+            #[doc = concat!("shortcut of `#[route(\"path\", method = ", stringify!($method), "[, attributes])]`.")]
+            /// see [macro@route] for detail.
+            #[proc_macro_attribute]
+            pub fn $method(attr: TokenStream, item: TokenStream) -> TokenStream {
+                let attr = syn::parse_macro_input!(attr);
+                let item = syn::parse_macro_input!(item);
+                route::method_route(stringify!($method), attr, item).unwrap_or_else(|e| e.to_compile_error().into())
+            }
+        )*
+    };
+}
+
+method_route!(get, post, put, delete, head, options, connect, patch, trace);
+
+/// collect routes generated by [macro@route] and it's shortcut macros into one type that can be registered to
+/// application at once.
+///
+/// # Pattern
+/// ```plain
+/// routes![<route>, <route>, ...]
+/// ```
+#[proc_macro]
+pub fn routes(item: TokenStream) -> TokenStream {
+    let parser = syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated;
+    let paths = syn::parse_macro_input!(item with parser);
+    route::routes(paths)
+}
+
+/// derive macro for error type producing http response with given status code and body format.
+///
+/// # Pattern
+/// ```plain
+/// #[derive(ErrorResponse)]
+/// #[response(status = <status>, body = <body>)]
+/// enum Error {
+///     #[response(status = <status>, body = <body>)]
+///     Variant,
+/// }
+/// ```
+///
+/// # Attributes
+/// - `status = <status>`: http status code in form of number or constant name of status code type.
+///   `status = 404` or `status = NOT_FOUND` for example. default to `500`.
+/// - `body = <body>`: body format of response. `empty`, `text` or `json`. default to `text`.
+///
+/// attributes on container are default of all variants and can be overridden by attributes on variant.
+/// error type must implement `std::error::Error` trait and it's display format is used as response body.
+#[proc_macro_derive(ErrorResponse, attributes(response))]
+pub fn error_response_impl(item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item);
+    error::error_response(item).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
 #[proc_macro_attribute]
 pub fn error_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
     let item = syn::parse_macro_input!(item);
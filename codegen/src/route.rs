@@ -1,7 +1,7 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{
-    Error, Expr, FnArg, GenericArgument, ItemFn, PathArguments, Type,
+    Error, Expr, FnArg, GenericArgument, Ident, ItemFn, Path, PathArguments, Token, Type,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
@@ -13,13 +13,7 @@ pub(crate) fn route(attr: Args, input: ItemFn) -> Result<TokenStream, Error> {
 
     let path = attrs.next().ok_or_else(|| Error::new(input.span(), MSG))?;
 
-    // TODO: add support for multiple method = x.
-    let method = attrs.next().ok_or_else(|| Error::new(input.sig.ident.span(), MSG))?;
-    let Expr::Assign(method) = method else {
-        return Err(Error::new(method.span(), "expect 'method = <method>'"));
-    };
-    let method = &*method.right;
-
+    let mut methods = Vec::new();
     let mut middlewares = quote! {};
 
     for attr in attrs {
@@ -34,8 +28,20 @@ pub(crate) fn route(attr: Args, input: ItemFn) -> Result<TokenStream, Error> {
         let name = name
             .path
             .get_ident()
-            .ok_or_else(|| Error::new(name.span(), "expect enclosed or enclosed_fn path"))?;
+            .ok_or_else(|| Error::new(name.span(), "expect method, enclosed or enclosed_fn path"))?;
         match name.to_string().as_str() {
+            "method" => {
+                let Expr::Path(ref value) = *pair.right else {
+                    return Err(Error::new(pair.right.span(), "expect 'method = <method>'"));
+                };
+
+                let value = value
+                    .path
+                    .get_ident()
+                    .ok_or_else(|| Error::new(value.span(), "expect http method name"))?;
+                let method = Ident::new(&value.to_string().to_uppercase(), value.span());
+                methods.push(quote! { ::xitca_web::http::Method::#method });
+            }
             "enclosed_fn" => {
                 let Expr::Path(ref value) = *pair.right else {
                     return Err(Error::new(pair.right.span(), "expect <value> to be path expression"));
@@ -71,6 +77,10 @@ pub(crate) fn route(attr: Args, input: ItemFn) -> Result<TokenStream, Error> {
         }
     }
 
+    if methods.is_empty() {
+        return Err(Error::new(input.sig.ident.span(), MSG));
+    }
+
     let is_async = input.sig.asyncness.is_some();
     let ident = &input.sig.ident;
     let vis = &input.vis;
@@ -206,18 +216,40 @@ pub(crate) fn route(attr: Args, input: ItemFn) -> Result<TokenStream, Error> {
 
                 use xitca_web::codegen::__private::IntoObject;
                 use xitca_web::WebContext;
-                use xitca_web::route::#method;
+                use xitca_web::route::Route;
                 use xitca_web::service::ServiceExt;
 
-                WebContext::<'_, #state_ident>::into_object(#method(#handler(#ident)#middlewares))
+                WebContext::<'_, #state_ident>::into_object(Route::new([#(#methods),*]).route(#handler(#ident)#middlewares))
             }
         }
     }
     .into())
 }
 
+// shortcut attribute of route macro with given method.
+pub(crate) fn method_route(method: &str, mut attr: Args, input: ItemFn) -> Result<TokenStream, Error> {
+    if attr.vars.is_empty() {
+        return Err(Error::new(
+            input.span(),
+            format!("not enough attributes. try add #[{method}(<path>)]"),
+        ));
+    }
+    let method = Ident::new(method, input.sig.ident.span());
+    attr.vars.insert(1, syn::parse_quote! { method = #method });
+    route(attr, input)
+}
+
+// collect typed routes into nested tuple where each route is paired with the rest of them.
+pub(crate) fn routes(paths: Punctuated<Path, Token![,]>) -> TokenStream {
+    let routes = paths
+        .into_iter()
+        .rev()
+        .fold(quote! { () }, |rest, path| quote! { (#path, #rest) });
+    routes.into()
+}
+
 pub struct Args {
-    vars: Vec<Expr>,
+    pub(crate) vars: Vec<Expr>,
 }
 
 impl Parse for Args {
@@ -0,0 +1,187 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, ExprLit, Fields, Lit, Meta, MetaNameValue, Token, parse_quote,
+    punctuated::Punctuated, spanned::Spanned,
+};
+
+pub(crate) fn api_schema(mut input: DeriveInput) -> Result<TokenStream, Error> {
+    let ty_ident = &input.ident;
+
+    let attr = Attrs::parse(&input.attrs)?;
+
+    let kind = match input.data {
+        Data::Struct(ref data) => {
+            let kind = fields_kind(&data.fields)?;
+            quote! { #kind }
+        }
+        Data::Enum(ref data) => {
+            let mut variants = Vec::new();
+            let mut all_unit = true;
+
+            for variant in data.variants.iter() {
+                let attr = Attrs::parse(&variant.attrs)?;
+                let name = attr.rename.clone().unwrap_or_else(|| variant.ident.to_string());
+                all_unit &= matches!(variant.fields, Fields::Unit);
+                variants.push((name, attr, &variant.fields));
+            }
+
+            if all_unit {
+                let names = variants.iter().map(|(name, ..)| name);
+                quote! { ::xitca_web::schema::Kind::Enum(vec![#(#names.into()),*]) }
+            } else {
+                // externally tagged enum representation. unit variant is a string and the others are object with
+                // variant name as the only key.
+                let schemas = variants
+                    .into_iter()
+                    .map(|(name, attr, fields)| {
+                        let schema = match fields {
+                            Fields::Unit => quote! {
+                                ::xitca_web::schema::Schema::new(::xitca_web::schema::Kind::Enum(vec![#name.into()]))
+                            },
+                            fields => {
+                                let kind = fields_kind(fields)?;
+                                let schema = attr.apply(quote! { ::xitca_web::schema::Schema::new(#kind) });
+                                quote! {
+                                    ::xitca_web::schema::Schema::new(::xitca_web::schema::Kind::Object(vec![
+                                        ::xitca_web::schema::Field::new(#name, #schema)
+                                    ]))
+                                }
+                            }
+                        };
+                        Ok(schema)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                quote! { ::xitca_web::schema::Kind::OneOf(vec![#(#schemas),*]) }
+            }
+        }
+        Data::Union(_) => return Err(Error::new(ty_ident.span(), "expect Struct or Enum")),
+    };
+
+    let schema = attr.apply(quote! { ::xitca_web::schema::Schema::new(#kind) });
+
+    let params = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = input.generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote! { #param: ::xitca_web::schema::ApiSchema });
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::xitca_web::schema::ApiSchema for #ty_ident #ty_generics #where_clause {
+            fn schema() -> ::xitca_web::schema::Schema {
+                #schema
+            }
+        }
+    }
+    .into())
+}
+
+fn fields_kind(fields: &Fields) -> Result<impl quote::ToTokens + use<>, Error> {
+    match fields {
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter()
+                .map(|field| {
+                    let attr = Attrs::parse(&field.attrs)?;
+                    let name = attr
+                        .rename
+                        .clone()
+                        .unwrap_or_else(|| field.ident.as_ref().unwrap().to_string());
+                    let ty = &field.ty;
+                    let schema = attr.apply(quote! { <#ty as ::xitca_web::schema::ApiSchema>::schema() });
+                    Ok(quote! { ::xitca_web::schema::Field::new(#name, #schema) })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            Ok(quote! { ::xitca_web::schema::Kind::Object(vec![#(#fields),*]) })
+        }
+        // newtype shares the same schema with it's inner type.
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let ty = &fields.unnamed[0].ty;
+            Ok(quote! { <#ty as ::xitca_web::schema::ApiSchema>::schema().kind })
+        }
+        Fields::Unnamed(fields) => Err(Error::new(fields.span(), "tuple with multiple fields is not supported")),
+        Fields::Unit => Ok(quote! { ::xitca_web::schema::Kind::Null }),
+    }
+}
+
+// metadata collected from doc comments and #[schema(..)] attribute.
+#[derive(Default)]
+struct Attrs {
+    description: Option<String>,
+    example: Option<String>,
+    rename: Option<String>,
+}
+
+impl Attrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut res = Self::default();
+        let mut docs = Vec::new();
+
+        for attr in attrs {
+            if attr.path().is_ident("doc") {
+                if let Meta::NameValue(MetaNameValue {
+                    value: Expr::Lit(ExprLit {
+                        lit: Lit::Str(ref doc), ..
+                    }),
+                    ..
+                }) = attr.meta
+                {
+                    docs.push(doc.value().trim().to_string());
+                }
+            } else if attr.path().is_ident("schema") {
+                let pairs = attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+                for pair in pairs {
+                    let Expr::Assign(pair) = pair else {
+                        return Err(Error::new(pair.span(), "expect '<name> = \"<value>\"' expression"));
+                    };
+
+                    let Expr::Lit(ExprLit {
+                        lit: Lit::Str(ref value),
+                        ..
+                    }) = *pair.right
+                    else {
+                        return Err(Error::new(pair.right.span(), "expect string literal"));
+                    };
+
+                    let Expr::Path(ref name) = *pair.left else {
+                        return Err(Error::new(pair.left.span(), "expect <name> to be path expression"));
+                    };
+
+                    match name.path.get_ident().map(ToString::to_string).as_deref() {
+                        Some("example") => res.example = Some(value.value()),
+                        Some("rename") => res.rename = Some(value.value()),
+                        Some("description") => res.description = Some(value.value()),
+                        _ => return Err(Error::new(name.span(), "expect example, rename or description")),
+                    }
+                }
+            }
+        }
+
+        if res.description.is_none() && !docs.is_empty() {
+            res.description = Some(docs.join("\n").trim().to_string());
+        }
+
+        Ok(res)
+    }
+
+    // apply metadata to schema expression.
+    fn apply<T: quote::ToTokens>(&self, schema: T) -> impl quote::ToTokens + use<T> {
+        let mut schema = quote! { #schema };
+        if let Some(ref desc) = self.description {
+            schema = quote! { #schema.description(#desc) };
+        }
+        if let Some(ref example) = self.example {
+            schema = quote! { #schema.example(#example) };
+        }
+        schema
+    }
+}
@@ -1,40 +1,79 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Data, DeriveInput, Error};
+use quote::{ToTokens, quote};
+use syn::{Attribute, Data, DeriveInput, Error, Index, Meta, spanned::Spanned};
 
-pub(crate) fn state(input: DeriveInput) -> Result<TokenStream, Error> {
+// field borrowing strategy of derive macro.
+pub(crate) enum Borrow {
+    // only field with #[borrow] attribute is borrowed.
+    Marked,
+    // all fields are borrowed except those with #[borrow(skip)] attribute.
+    All,
+}
+
+pub(crate) fn state(input: DeriveInput, mode: Borrow) -> Result<TokenStream, Error> {
     let ty_ident = &input.ident;
-    let _generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let Data::Struct(ref ty) = input.data else {
         return Err(Error::new(ty_ident.span(), "expect Struct"));
     };
 
-    let fields = ty
-        .fields
-        .iter()
-        .enumerate()
-        .filter(|(_, field)| {
-            field.attrs.iter().any(|attr| {
-                attr.path()
-                    .segments
-                    .first()
-                    .filter(|seg| seg.ident.to_string().as_str() == "borrow")
-                    .is_some()
-            })
-        })
-        .map(|(_, field)| {
-            let ident = field.ident.as_ref().unwrap();
-            let ty = &field.ty;
-
-            quote! {
-                impl ::xitca_web::handler::state::BorrowState<#ty> for #ty_ident {
-                    fn borrow(&self) -> &#ty {
-                        &self.#ident
-                    }
+    let mut types = Vec::new();
+    let mut impls = Vec::new();
+
+    for (idx, field) in ty.fields.iter().enumerate() {
+        let attr = field.attrs.iter().find(|attr| attr.path().is_ident("borrow"));
+
+        let borrow = match (&mode, attr) {
+            (Borrow::Marked, None) => false,
+            (Borrow::Marked, Some(attr)) => !is_skip(attr)?,
+            (Borrow::All, None) => true,
+            (Borrow::All, Some(attr)) => !is_skip(attr)?,
+        };
+
+        if !borrow {
+            continue;
+        }
+
+        let ty = &field.ty;
+
+        let ty_str = ty.to_token_stream().to_string();
+        if types.contains(&ty_str) {
+            return Err(Error::new(
+                ty.span(),
+                format!("type {ty_str} is already borrowed from other field. add #[borrow(skip)] to one of them"),
+            ));
+        }
+        types.push(ty_str);
+
+        let ident = match field.ident {
+            Some(ref ident) => ident.to_token_stream(),
+            None => Index::from(idx).to_token_stream(),
+        };
+
+        impls.push(quote! {
+            impl #impl_generics ::xitca_web::handler::state::BorrowState<#ty> for #ty_ident #ty_generics #where_clause {
+                fn borrow(&self) -> &#ty {
+                    &self.#ident
                 }
             }
         });
+    }
 
-    Ok(quote! { #(#fields)* }.into())
+    Ok(quote! { #(#impls)* }.into())
+}
+
+fn is_skip(attr: &Attribute) -> Result<bool, Error> {
+    match attr.meta {
+        Meta::Path(_) => Ok(false),
+        Meta::List(ref list) => {
+            let ident = list.parse_args::<syn::Ident>()?;
+            if ident == "skip" {
+                Ok(true)
+            } else {
+                Err(Error::new(ident.span(), "expect #[borrow(skip)]"))
+            }
+        }
+        Meta::NameValue(ref nv) => Err(Error::new(nv.span(), "expect #[borrow(skip)]")),
+    }
 }
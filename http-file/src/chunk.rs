@@ -20,6 +20,9 @@ pin_project! {
         F: ChunkRead,
     {
         Empty,
+        Bytes {
+            bytes: Option<Bytes>
+        },
         Reader {
             #[pin]
             reader:  _ChunkReader<F>
@@ -35,6 +38,10 @@ where
         Self::Empty
     }
 
+    pub(super) fn bytes(bytes: Bytes) -> Self {
+        Self::Bytes { bytes: Some(bytes) }
+    }
+
     pub(super) fn reader(file: F, size: u64, chunk_size: usize) -> Self {
         Self::Reader {
             reader: _ChunkReader {
@@ -55,6 +62,7 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         match self.project() {
             ChunkReaderProj::Empty => Poll::Ready(None),
+            ChunkReaderProj::Bytes { bytes } => Poll::Ready(bytes.take().map(Ok)),
             ChunkReaderProj::Reader { reader } => reader.poll_next(cx),
         }
     }
@@ -63,6 +71,10 @@ where
         match self {
             // see xitca_http::body::none_body_hint for reason. this is a library hack.
             Self::Empty => (usize::MAX, Some(0)),
+            Self::Bytes { ref bytes } => {
+                let len = bytes.as_ref().map(Bytes::len).unwrap_or(0);
+                (len, Some(len))
+            }
             Self::Reader { ref reader } => reader.size_hint(),
         }
     }
@@ -6,16 +6,20 @@ mod buf;
 mod chunk;
 mod date;
 mod error;
+mod listing;
 
 pub use self::{chunk::ChunkReader, error::ServeError};
 
 use std::{
-    io::SeekFrom,
+    io::{self, SeekFrom},
     path::{Component, Path, PathBuf},
 };
 
 use http::{
-    header::{HeaderValue, ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, LAST_MODIFIED, RANGE},
+    header::{
+        HeaderMap, HeaderValue, ACCEPT_ENCODING, ACCEPT_RANGES, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE,
+        CONTENT_TYPE, LAST_MODIFIED, RANGE, VARY,
+    },
     Method, Request, Response, StatusCode,
 };
 use mime_guess::mime;
@@ -30,6 +34,10 @@ use self::{
 pub struct ServeDir<FS: AsyncFs = runtime::TokioFs> {
     chunk_size: usize,
     base_path: PathBuf,
+    index_file: Option<String>,
+    listing: bool,
+    precompressed: Precompressed,
+    fallback: Option<PathBuf>,
     async_fs: FS,
 }
 
@@ -38,9 +46,19 @@ pub struct ServeDir<FS: AsyncFs = runtime::TokioFs> {
 pub struct ServeDir<FS: AsyncFs> {
     chunk_size: usize,
     base_path: PathBuf,
+    index_file: Option<String>,
+    listing: bool,
+    precompressed: Precompressed,
+    fallback: Option<PathBuf>,
     async_fs: FS,
 }
 
+#[derive(Clone, Copy, Default)]
+struct Precompressed {
+    br: bool,
+    gzip: bool,
+}
+
 #[cfg(feature = "default")]
 impl ServeDir<runtime::TokioFs> {
     /// Construct a new ServeDir with given path.
@@ -64,6 +82,10 @@ impl<FS: AsyncFs> ServeDir<FS> {
         Self {
             chunk_size: 4096,
             base_path: path.into(),
+            index_file: None,
+            listing: false,
+            precompressed: Precompressed::default(),
+            fallback: None,
             async_fs,
         }
     }
@@ -76,6 +98,53 @@ impl<FS: AsyncFs> ServeDir<FS> {
         self
     }
 
+    /// file name used for resolving request targeting a directory. e.g. `index.html`
+    ///
+    /// # Default
+    /// disabled. request targeting a directory is treated as invalid path.
+    pub fn index_file(&mut self, name: impl Into<String>) -> &mut Self {
+        self.index_file = Some(name.into());
+        self
+    }
+
+    /// enable html listing of entries when request targeting a directory without index file.
+    /// entries with name starting with dot are not listed.
+    ///
+    /// # Default
+    /// disabled.
+    pub fn directory_listing(&mut self, enable: bool) -> &mut Self {
+        self.listing = enable;
+        self
+    }
+
+    /// prefer `<file>.br` variant of requested file when it exists and client accepts br encoding.
+    ///
+    /// # Default
+    /// disabled.
+    pub fn precompressed_br(&mut self, enable: bool) -> &mut Self {
+        self.precompressed.br = enable;
+        self
+    }
+
+    /// prefer `<file>.gz` variant of requested file when it exists and client accepts gzip encoding.
+    ///
+    /// # Default
+    /// disabled.
+    pub fn precompressed_gzip(&mut self, enable: bool) -> &mut Self {
+        self.precompressed.gzip = enable;
+        self
+    }
+
+    /// file path relative to base path served when requested file can not be found. useful for
+    /// single page application where client side routing is used. e.g. `index.html`
+    ///
+    /// # Default
+    /// disabled. [ServeError::NotFound] is returned when requested file can not be found.
+    pub fn fallback(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.fallback = Some(path.into());
+        self
+    }
+
     /// try to find a matching file from given input request and generate http response with stream
     /// reader of matched file.
     ///
@@ -89,22 +158,37 @@ impl<FS: AsyncFs> ServeDir<FS> {
     /// }
     /// ```
     pub async fn serve<Ext>(&self, req: &Request<Ext>) -> Result<Response<ChunkReader<FS::File>>, ServeError> {
+        self.serve_path(req, req.uri().path()).await
+    }
+
+    /// same as [ServeDir::serve] but match file with given path instead of request's uri path.
+    /// useful when request path contains prefix that should not be treated as part of file path.
+    pub async fn serve_path<Ext>(
+        &self,
+        req: &Request<Ext>,
+        path: &str,
+    ) -> Result<Response<ChunkReader<FS::File>>, ServeError> {
         if !matches!(*req.method(), Method::HEAD | Method::GET) {
             return Err(ServeError::MethodNotAllowed);
         }
 
-        let path = self.path_check(req.uri().path())?;
+        let mut path = self.path_check(path)?;
 
-        // TODO: enable nest dir serving?
         if path.is_dir() {
-            return Err(ServeError::InvalidPath);
+            match self.index_file {
+                Some(ref index) if !self.listing || path.join(index).is_file() => path.push(index),
+                _ if self.listing => return self.list_dir(req, path).await,
+                _ => return Err(ServeError::InvalidPath),
+            }
         }
 
-        let ct = mime_guess::from_path(&path)
-            .first_raw()
-            .unwrap_or_else(|| mime::APPLICATION_OCTET_STREAM.as_ref());
-
-        let mut file = self.async_fs.open(path).await?;
+        let (ct, mut file, encoding) = match self.open(req, path).await {
+            Err(ServeError::NotFound) if self.fallback.is_some() => {
+                let path = self.base_path.join(self.fallback.as_ref().unwrap());
+                self.open(req, path).await?
+            }
+            res => res?,
+        };
 
         let modified = date::mod_date_check(req, &mut file)?;
 
@@ -139,6 +223,16 @@ impl<FS: AsyncFs> ServeDir<FS> {
         res.headers_mut()
             .insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
 
+        if self.precompressed.br || self.precompressed.gzip {
+            res.headers_mut()
+                .insert(VARY, HeaderValue::from_static("accept-encoding"));
+        }
+
+        if let Some(encoding) = encoding {
+            res.headers_mut()
+                .insert(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+        }
+
         if let Some(modified) = modified {
             let val = date::date_to_header(modified);
             res.headers_mut().insert(LAST_MODIFIED, val);
@@ -155,6 +249,64 @@ impl<FS: AsyncFs> ServeDir<FS> {
 }
 
 impl<FS: AsyncFs> ServeDir<FS> {
+    // open file from given path. precompressed variant of file is preferred when enabled and
+    // accepted by client. returns mime type of path, opened file and it's content encoding.
+    async fn open<Ext>(
+        &self,
+        req: &Request<Ext>,
+        path: PathBuf,
+    ) -> Result<(&'static str, FS::File, Option<&'static str>), ServeError> {
+        let ct = mime_guess::from_path(&path)
+            .first_raw()
+            .unwrap_or_else(|| mime::APPLICATION_OCTET_STREAM.as_ref());
+
+        let variants = [
+            (self.precompressed.br, "br", "br"),
+            (self.precompressed.gzip, "gzip", "gz"),
+        ];
+
+        for (_, encoding, ext) in variants
+            .into_iter()
+            .filter(|(enabled, encoding, _)| *enabled && accept_encoding(req.headers(), encoding))
+        {
+            let mut variant = path.clone().into_os_string();
+            variant.push(".");
+            variant.push(ext);
+
+            match self.async_fs.open(variant.into()).await {
+                Ok(file) => return Ok((ct, file, Some(encoding))),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let file = self.async_fs.open(path).await?;
+
+        Ok((ct, file, None))
+    }
+
+    async fn list_dir<Ext>(
+        &self,
+        req: &Request<Ext>,
+        path: PathBuf,
+    ) -> Result<Response<ChunkReader<FS::File>>, ServeError> {
+        let entries = self.async_fs.read_dir(path).await?;
+        let html = listing::render(req.uri().path(), entries);
+
+        let mut res = Response::new(());
+        res.headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("text/html; charset=utf-8"));
+        res.headers_mut().insert(CONTENT_LENGTH, HeaderValue::from(html.len()));
+
+        let stream = if matches!(*req.method(), Method::HEAD) {
+            ChunkReader::empty()
+        } else {
+            ChunkReader::bytes(html)
+        };
+
+        Ok(res.map(|_| stream))
+    }
+
     fn path_check(&self, path: &str) -> Result<PathBuf, ServeError> {
         let path = path.trim_start_matches('/').as_bytes();
 
@@ -187,6 +339,26 @@ impl<FS: AsyncFs> ServeDir<FS> {
     }
 }
 
+// check if given content coding is acceptable according to accept-encoding header.
+fn accept_encoding(headers: &HeaderMap, encoding: &str) -> bool {
+    headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|h| h.split(','))
+        .any(|coding| {
+            let mut params = coding.split(';');
+            let name = params.next().unwrap_or("").trim();
+            let rejected = params.any(|p| {
+                p.trim()
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            !rejected && (name.eq_ignore_ascii_case(encoding) || name == "*")
+        })
+}
+
 #[cfg(test)]
 mod test {
     use core::future::poll_fn;
@@ -287,6 +459,116 @@ mod test {
         assert_eq!(lower, "hello, world!".len());
     }
 
+    #[tokio::test]
+    async fn encoded_traversal() {
+        let dir = ServeDir::new("sample");
+        let req = Request::builder().uri("/%2e%2e/Cargo.toml").body(()).unwrap();
+        assert!(matches!(dir.serve(&req).await.err(), Some(ServeError::InvalidPath)));
+    }
+
+    #[tokio::test]
+    async fn serve_path() {
+        let dir = ServeDir::new("sample");
+        let req = Request::builder().uri("/static/test.txt").body(()).unwrap();
+        let res = dir.serve_path(&req, "test.txt").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn index_file() {
+        let mut dir = ServeDir::new("sample");
+        let req = Request::builder().uri("/nested/").body(()).unwrap();
+        assert!(matches!(dir.serve(&req).await.err(), Some(ServeError::InvalidPath)));
+
+        dir.index_file("index.html");
+        let res = dir.serve(&req).await.unwrap();
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            HeaderValue::from_static("text/html")
+        );
+    }
+
+    #[tokio::test]
+    async fn directory_listing() {
+        let mut dir = ServeDir::new("sample");
+        dir.index_file("index.html").directory_listing(true);
+
+        let req = Request::builder().uri("/").body(()).unwrap();
+        let mut stream = Box::pin(dir.serve(&req).await.unwrap().into_body());
+
+        let mut html = String::new();
+        while let Some(Ok(bytes)) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            html.push_str(std::str::from_utf8(bytes.as_ref()).unwrap());
+        }
+
+        assert!(html.contains("<a href=\"/nested/\">nested/</a>"));
+        assert!(html.contains("<a href=\"/test.txt\">test.txt</a>"));
+
+        // index file has higher priority than listing.
+        let req = Request::builder().uri("/nested").body(()).unwrap();
+        let res = dir.serve(&req).await.unwrap();
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            HeaderValue::from_static("text/html")
+        );
+    }
+
+    #[tokio::test]
+    async fn precompressed() {
+        let mut dir = ServeDir::new("sample");
+        dir.precompressed_br(true).precompressed_gzip(true);
+
+        let req = Request::builder()
+            .uri("/test.txt")
+            .header(ACCEPT_ENCODING, "br, gzip;q=0.8")
+            .body(())
+            .unwrap();
+        let res = dir.serve(&req).await.unwrap();
+        assert_eq!(
+            res.headers().get(CONTENT_ENCODING).unwrap(),
+            HeaderValue::from_static("gzip")
+        );
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            HeaderValue::from_static("text/plain")
+        );
+        assert_eq!(
+            res.headers().get(VARY).unwrap(),
+            HeaderValue::from_static("accept-encoding")
+        );
+        assert_ne!(
+            res.headers().get(CONTENT_LENGTH).unwrap(),
+            HeaderValue::from("hello, world!".len())
+        );
+
+        let req = Request::builder()
+            .uri("/test.txt")
+            .header(ACCEPT_ENCODING, "gzip;q=0")
+            .body(())
+            .unwrap();
+        let res = dir.serve(&req).await.unwrap();
+        assert!(res.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(
+            res.headers().get(CONTENT_LENGTH).unwrap(),
+            HeaderValue::from("hello, world!".len())
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback() {
+        let mut dir = ServeDir::new("sample");
+        let req = Request::builder().uri("/app/route").body(()).unwrap();
+        assert!(matches!(dir.serve(&req).await.err(), Some(ServeError::NotFound)));
+
+        dir.fallback("nested/index.html");
+        let res = dir.serve(&req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            HeaderValue::from_static("text/html")
+        );
+    }
+
     async fn _basic<FS: AsyncFs>(dir: ServeDir<FS>) {
         let req = Request::builder().uri("/test.txt").body(()).unwrap();
 
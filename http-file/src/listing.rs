@@ -0,0 +1,105 @@
+use core::fmt::Write;
+
+use bytes::Bytes;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+use super::runtime::DirEntry;
+
+// characters must be escaped when used as uri path segment.
+const SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+// render a html page of directory entries. hidden entries(name starts with dot) are skipped.
+pub(super) fn render(path: &str, mut entries: Vec<DirEntry>) -> Bytes {
+    entries.retain(|entry| !entry.name.starts_with('.'));
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+
+    let base = path.trim_end_matches('/');
+    let title = escape(if path.is_empty() { "/" } else { path });
+
+    let mut html = String::with_capacity(256 + entries.len() * 64);
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Index of {title}</title></head>\
+         <body><h1>Index of {title}</h1><ul>"
+    );
+
+    if !base.is_empty() {
+        let _ = write!(html, "<li><a href=\"{}/\">../</a></li>", parent(base));
+    }
+
+    for DirEntry { name, is_dir } in entries {
+        let slash = if is_dir { "/" } else { "" };
+        let _ = write!(
+            html,
+            "<li><a href=\"{base}/{href}{slash}\">{name}{slash}</a></li>",
+            href = utf8_percent_encode(&name, SEGMENT),
+            name = escape(&name)
+        );
+    }
+
+    html.push_str("</ul></body></html>");
+
+    Bytes::from(html)
+}
+
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map(|(parent, _)| parent).unwrap_or("")
+}
+
+fn escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_escape() {
+        let entries = vec![
+            DirEntry {
+                name: String::from("b <c>.txt"),
+                is_dir: false,
+            },
+            DirEntry {
+                name: String::from(".hidden"),
+                is_dir: false,
+            },
+            DirEntry {
+                name: String::from("a"),
+                is_dir: true,
+            },
+        ];
+
+        let html = render("/foo/", entries);
+        let html = std::str::from_utf8(&html).unwrap();
+
+        assert!(!html.contains(".hidden"));
+        assert!(html.contains("<a href=\"/\">../</a>"));
+        assert!(html.contains("<a href=\"/foo/b%20%3Cc%3E.txt\">b &lt;c&gt;.txt</a>"));
+        assert!(html.find("/foo/a/").unwrap() < html.find("/foo/b%20").unwrap());
+    }
+}
@@ -17,6 +17,39 @@ pub trait AsyncFs {
 
     /// open a file from given path.
     fn open(&self, path: PathBuf) -> Self::OpenFuture;
+
+    /// read entries of a directory from given path. used for generating directory listing.
+    ///
+    /// default impl returns [io::ErrorKind::Unsupported] error.
+    fn read_dir(&self, path: PathBuf) -> impl Future<Output = io::Result<Vec<DirEntry>>> {
+        let _ = path;
+        async { Err(io::ErrorKind::Unsupported.into()) }
+    }
+}
+
+/// entry of a directory.
+#[derive(Debug, Clone)]
+pub struct DirEntry {
+    /// file name of entry.
+    pub name: String,
+    /// true when entry is a directory.
+    pub is_dir: bool,
+}
+
+#[cfg(any(feature = "tokio", feature = "tokio-uring"))]
+fn read_dir_blocking(path: PathBuf) -> impl Future<Output = io::Result<Vec<DirEntry>>> {
+    let handle = tokio::task::spawn_blocking(move || {
+        std::fs::read_dir(path)?
+            .map(|entry| {
+                let entry = entry?;
+                Ok(DirEntry {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    is_dir: entry.file_type()?.is_dir(),
+                })
+            })
+            .collect()
+    });
+    async { handle.await.unwrap() }
 }
 
 /// trait for generic over file metadata.
@@ -106,6 +139,10 @@ mod tokio_impl {
                 }),
             }
         }
+
+        fn read_dir(&self, path: PathBuf) -> impl Future<Output = io::Result<Vec<DirEntry>>> {
+            read_dir_blocking(path)
+        }
     }
 
     pub struct TokioFile {
@@ -199,6 +236,10 @@ mod tokio_uring_impl {
                 })
             })
         }
+
+        fn read_dir(&self, path: PathBuf) -> impl Future<Output = io::Result<Vec<DirEntry>>> {
+            read_dir_blocking(path)
+        }
     }
 
     pub struct TokioUringFile {
@@ -0,0 +1,88 @@
+use http::{header::HOST, Request};
+
+use crate::{error::Error, params::SignatureParams};
+
+// construct signature base from request and signature params. RFC 9421 section 2.5
+pub(crate) fn signature_base<B>(req: &Request<B>, params: &SignatureParams) -> Result<String, Error> {
+    let mut base = String::new();
+
+    for (idx, name) in params.components().enumerate() {
+        if params.components().take(idx).any(|c| c == name) {
+            return Err(Error::InvalidHeader);
+        }
+        let value = component_value(req, name)?;
+        base.push('"');
+        base.push_str(name);
+        base.push_str("\": ");
+        base.push_str(&value);
+        base.push('\n');
+    }
+
+    base.push_str("\"@signature-params\": ");
+    params.serialize(&mut base);
+
+    Ok(base)
+}
+
+fn component_value<B>(req: &Request<B>, name: &str) -> Result<String, Error> {
+    let uri = req.uri();
+    let missing = || Error::MissingComponent(name.to_owned());
+
+    let value = match name {
+        "@method" => req.method().as_str().to_owned(),
+        "@authority" => authority(req).ok_or_else(missing)?,
+        "@scheme" => uri.scheme_str().ok_or_else(missing)?.to_ascii_lowercase(),
+        "@target-uri" => {
+            // target uri can only be derived from request in absolute form.
+            if uri.scheme().is_none() || uri.authority().is_none() {
+                return Err(missing());
+            }
+            uri.to_string()
+        }
+        "@request-target" => uri.path_and_query().map(|p| p.as_str()).unwrap_or("/").to_owned(),
+        "@path" => match uri.path() {
+            "" => "/".to_owned(),
+            path => path.to_owned(),
+        },
+        "@query" => format!("?{}", uri.query().unwrap_or("")),
+        _ if name.starts_with('@') => return Err(Error::UnsupportedComponent(name.to_owned())),
+        _ if name.bytes().any(|b| b.is_ascii_uppercase()) => return Err(Error::InvalidHeader),
+        _ => {
+            let mut values = req.headers().get_all(name).iter().peekable();
+            values.peek().ok_or_else(missing)?;
+            let mut value = String::new();
+            for (idx, v) in values.enumerate() {
+                let v = v.to_str().map_err(|_| Error::InvalidHeader)?;
+                if idx > 0 {
+                    value.push_str(", ");
+                }
+                value.push_str(v.trim());
+            }
+            value
+        }
+    };
+
+    Ok(value)
+}
+
+// authority is taken from request uri in absolute form and fallback to host header.
+// it's normalized to lowercase with default port removed.
+fn authority<B>(req: &Request<B>) -> Option<String> {
+    let authority = match req.uri().authority() {
+        Some(authority) => authority.as_str(),
+        None => req.headers().get(HOST)?.to_str().ok()?,
+    };
+
+    let mut authority = authority.trim().to_ascii_lowercase();
+
+    let default_port = match req.uri().scheme_str() {
+        Some("https") => ":443",
+        _ => ":80",
+    };
+
+    if authority.ends_with(default_port) {
+        authority.truncate(authority.len() - default_port.len());
+    }
+
+    Some(authority)
+}
@@ -0,0 +1,59 @@
+use core::fmt;
+
+use std::error;
+
+/// error type for signing and verifying http message signature.
+#[derive(Debug)]
+pub enum Error {
+    /// request does not carry signature or signature with expected label.
+    MissingSignature,
+    /// malformed `Signature-Input` or `Signature` header.
+    InvalidHeader,
+    /// covered component can not be derived from request.
+    MissingComponent(String),
+    /// covered component is not supported.
+    UnsupportedComponent(String),
+    /// required component is not covered by signature.
+    UncoveredComponent(String),
+    /// key id is absent from signature or can not be resolved to a key.
+    UnknownKey,
+    /// algorithm of signature does not match the resolved key.
+    AlgorithmMismatch,
+    /// signature does not carry created parameter.
+    MissingCreated,
+    /// signature is created in the future.
+    CreatedInFuture,
+    /// signature is older than max age.
+    TooOld,
+    /// signature is expired.
+    Expired,
+    /// signature does not match signature base.
+    InvalidSignature,
+    /// key material is rejected.
+    InvalidKey,
+    /// signing key failed to produce signature.
+    Sign,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSignature => f.write_str("http message signature is missing"),
+            Self::InvalidHeader => f.write_str("Signature-Input or Signature header is malformed"),
+            Self::MissingComponent(c) => write!(f, "covered component {c} can not be derived from request"),
+            Self::UnsupportedComponent(c) => write!(f, "covered component {c} is not supported"),
+            Self::UncoveredComponent(c) => write!(f, "required component {c} is not covered by signature"),
+            Self::UnknownKey => f.write_str("signature key id is unknown"),
+            Self::AlgorithmMismatch => f.write_str("signature algorithm does not match key"),
+            Self::MissingCreated => f.write_str("signature created parameter is missing"),
+            Self::CreatedInFuture => f.write_str("signature is created in the future"),
+            Self::TooOld => f.write_str("signature is too old"),
+            Self::Expired => f.write_str("signature is expired"),
+            Self::InvalidSignature => f.write_str("signature is invalid"),
+            Self::InvalidKey => f.write_str("key material is rejected"),
+            Self::Sign => f.write_str("failed to sign http message"),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,202 @@
+use ring::{
+    hmac,
+    rand::SystemRandom,
+    signature::{self, EcdsaKeyPair, Ed25519KeyPair, KeyPair, RsaEncoding, RsaKeyPair, UnparsedPublicKey},
+};
+
+use crate::error::Error;
+
+/// signature algorithms registered by RFC 9421.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    HmacSha256,
+    Ed25519,
+    EcdsaP256Sha256,
+    RsaPssSha512,
+    RsaV15Sha256,
+}
+
+impl Algorithm {
+    /// name of algorithm used in `alg` parameter of signature.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::HmacSha256 => "hmac-sha256",
+            Self::Ed25519 => "ed25519",
+            Self::EcdsaP256Sha256 => "ecdsa-p256-sha256",
+            Self::RsaPssSha512 => "rsa-pss-sha512",
+            Self::RsaV15Sha256 => "rsa-v1_5-sha256",
+        }
+    }
+
+    /// parse algorithm from it's name. return None when algorithm is unknown.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hmac-sha256" => Some(Self::HmacSha256),
+            "ed25519" => Some(Self::Ed25519),
+            "ecdsa-p256-sha256" => Some(Self::EcdsaP256Sha256),
+            "rsa-pss-sha512" => Some(Self::RsaPssSha512),
+            "rsa-v1_5-sha256" => Some(Self::RsaV15Sha256),
+            _ => None,
+        }
+    }
+}
+
+/// key for producing signature.
+pub struct SigningKey {
+    inner: SigningInner,
+}
+
+enum SigningInner {
+    Hmac(hmac::Key),
+    Ed25519(Ed25519KeyPair),
+    Ecdsa(EcdsaKeyPair, SystemRandom),
+    Rsa(RsaKeyPair, &'static dyn RsaEncoding, Algorithm, SystemRandom),
+}
+
+impl SigningKey {
+    /// construct `hmac-sha256` key from shared secret.
+    pub fn hmac_sha256(secret: &[u8]) -> Self {
+        Self {
+            inner: SigningInner::Hmac(hmac::Key::new(hmac::HMAC_SHA256, secret)),
+        }
+    }
+
+    /// construct `ed25519` key from PKCS#8 v2 document in DER format.
+    pub fn ed25519(pkcs8: &[u8]) -> Result<Self, Error> {
+        let key = Ed25519KeyPair::from_pkcs8_maybe_unchecked(pkcs8).map_err(|_| Error::InvalidKey)?;
+        Ok(Self {
+            inner: SigningInner::Ed25519(key),
+        })
+    }
+
+    /// construct `ecdsa-p256-sha256` key from PKCS#8 document in DER format.
+    pub fn ecdsa_p256_sha256(pkcs8: &[u8]) -> Result<Self, Error> {
+        let rng = SystemRandom::new();
+        let key = EcdsaKeyPair::from_pkcs8(&signature::ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8, &rng)
+            .map_err(|_| Error::InvalidKey)?;
+        Ok(Self {
+            inner: SigningInner::Ecdsa(key, rng),
+        })
+    }
+
+    /// construct `rsa-pss-sha512` key from PKCS#8 document in DER format.
+    pub fn rsa_pss_sha512(pkcs8: &[u8]) -> Result<Self, Error> {
+        Self::rsa(pkcs8, &signature::RSA_PSS_SHA512, Algorithm::RsaPssSha512)
+    }
+
+    /// construct `rsa-v1_5-sha256` key from PKCS#8 document in DER format.
+    pub fn rsa_v1_5_sha256(pkcs8: &[u8]) -> Result<Self, Error> {
+        Self::rsa(pkcs8, &signature::RSA_PKCS1_SHA256, Algorithm::RsaV15Sha256)
+    }
+
+    fn rsa(pkcs8: &[u8], encoding: &'static dyn RsaEncoding, alg: Algorithm) -> Result<Self, Error> {
+        let key = RsaKeyPair::from_pkcs8(pkcs8).map_err(|_| Error::InvalidKey)?;
+        Ok(Self {
+            inner: SigningInner::Rsa(key, encoding, alg, SystemRandom::new()),
+        })
+    }
+
+    /// algorithm of key.
+    pub fn algorithm(&self) -> Algorithm {
+        match self.inner {
+            SigningInner::Hmac(_) => Algorithm::HmacSha256,
+            SigningInner::Ed25519(_) => Algorithm::Ed25519,
+            SigningInner::Ecdsa(..) => Algorithm::EcdsaP256Sha256,
+            SigningInner::Rsa(_, _, alg, _) => alg,
+        }
+    }
+
+    /// public key of asymmetric key pair in format expected by [VerifyingKey] constructors.
+    /// return None for symmetric key.
+    pub fn public_key(&self) -> Option<&[u8]> {
+        match self.inner {
+            SigningInner::Hmac(_) => None,
+            SigningInner::Ed25519(ref key) => Some(key.public_key().as_ref()),
+            SigningInner::Ecdsa(ref key, _) => Some(key.public_key().as_ref()),
+            SigningInner::Rsa(ref key, ..) => Some(key.public_key().as_ref()),
+        }
+    }
+
+    pub(crate) fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+        match self.inner {
+            SigningInner::Hmac(ref key) => Ok(hmac::sign(key, msg).as_ref().to_vec()),
+            SigningInner::Ed25519(ref key) => Ok(key.sign(msg).as_ref().to_vec()),
+            SigningInner::Ecdsa(ref key, ref rng) => key
+                .sign(rng, msg)
+                .map(|sig| sig.as_ref().to_vec())
+                .map_err(|_| Error::Sign),
+            SigningInner::Rsa(ref key, encoding, _, ref rng) => {
+                let mut sig = vec![0; key.public().modulus_len()];
+                key.sign(encoding, rng, msg, &mut sig).map_err(|_| Error::Sign)?;
+                Ok(sig)
+            }
+        }
+    }
+}
+
+/// key for verifying signature.
+#[derive(Clone)]
+pub struct VerifyingKey {
+    inner: VerifyingInner,
+}
+
+#[derive(Clone)]
+enum VerifyingInner {
+    Hmac(hmac::Key),
+    Public(Algorithm, Vec<u8>),
+}
+
+impl VerifyingKey {
+    /// construct `hmac-sha256` key from shared secret.
+    pub fn hmac_sha256(secret: &[u8]) -> Self {
+        Self {
+            inner: VerifyingInner::Hmac(hmac::Key::new(hmac::HMAC_SHA256, secret)),
+        }
+    }
+
+    /// construct `ed25519` key from 32 bytes public key.
+    pub fn ed25519(public_key: impl Into<Vec<u8>>) -> Self {
+        Self::public(Algorithm::Ed25519, public_key)
+    }
+
+    /// construct `ecdsa-p256-sha256` key from uncompressed public key point.
+    pub fn ecdsa_p256_sha256(public_key: impl Into<Vec<u8>>) -> Self {
+        Self::public(Algorithm::EcdsaP256Sha256, public_key)
+    }
+
+    /// construct `rsa-pss-sha512` key from PKCS#1 RSAPublicKey in DER format.
+    pub fn rsa_pss_sha512(public_key: impl Into<Vec<u8>>) -> Self {
+        Self::public(Algorithm::RsaPssSha512, public_key)
+    }
+
+    /// construct `rsa-v1_5-sha256` key from PKCS#1 RSAPublicKey in DER format.
+    pub fn rsa_v1_5_sha256(public_key: impl Into<Vec<u8>>) -> Self {
+        Self::public(Algorithm::RsaV15Sha256, public_key)
+    }
+
+    fn public(alg: Algorithm, public_key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            inner: VerifyingInner::Public(alg, public_key.into()),
+        }
+    }
+
+    /// algorithm of key.
+    pub fn algorithm(&self) -> Algorithm {
+        match self.inner {
+            VerifyingInner::Hmac(_) => Algorithm::HmacSha256,
+            VerifyingInner::Public(alg, _) => alg,
+        }
+    }
+
+    pub(crate) fn verify(&self, msg: &[u8], sig: &[u8]) -> bool {
+        let (alg, key): (&'static dyn signature::VerificationAlgorithm, _) = match self.inner {
+            VerifyingInner::Hmac(ref key) => return hmac::verify(key, msg, sig).is_ok(),
+            VerifyingInner::Public(Algorithm::Ed25519, ref key) => (&signature::ED25519, key),
+            VerifyingInner::Public(Algorithm::EcdsaP256Sha256, ref key) => (&signature::ECDSA_P256_SHA256_FIXED, key),
+            VerifyingInner::Public(Algorithm::RsaPssSha512, ref key) => (&signature::RSA_PSS_2048_8192_SHA512, key),
+            VerifyingInner::Public(Algorithm::RsaV15Sha256, ref key) => (&signature::RSA_PKCS1_2048_8192_SHA256, key),
+            VerifyingInner::Public(Algorithm::HmacSha256, _) => return false,
+        };
+        UnparsedPublicKey::new(alg, key).verify(msg, sig).is_ok()
+    }
+}
@@ -0,0 +1,241 @@
+#![doc=include_str!( "../README.md")]
+
+mod base;
+mod error;
+mod key;
+mod params;
+mod sfv;
+mod sign;
+mod verify;
+
+pub use error::Error;
+pub use key::{Algorithm, SigningKey, VerifyingKey};
+pub use params::SignatureParams;
+pub use sign::Signer;
+pub use verify::{KeyResolver, Verified, Verifier};
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use http::{HeaderMap, HeaderName};
+
+/// `Signature-Input` header name.
+pub const SIGNATURE_INPUT: HeaderName = HeaderName::from_static("signature-input");
+
+/// `Signature` header name.
+pub const SIGNATURE: HeaderName = HeaderName::from_static("signature");
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|dur| dur.as_secs())
+        .unwrap_or(0)
+}
+
+// parse dictionary header. multiple header lines are combined.
+fn parse_header(headers: &HeaderMap, name: &HeaderName) -> Result<sfv::Dictionary, Error> {
+    let mut value = String::new();
+    for (idx, v) in headers.get_all(name).iter().enumerate() {
+        if idx > 0 {
+            value.push_str(", ");
+        }
+        value.push_str(v.to_str().map_err(|_| Error::InvalidHeader)?);
+    }
+    sfv::parse_dictionary(&value).ok_or(Error::InvalidHeader)
+}
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+
+    use http::Request;
+    use ring::{rand::SystemRandom, signature::Ed25519KeyPair};
+
+    use super::*;
+
+    // RFC 9421 appendix B.1.5
+    const SHARED_SECRET: &str =
+        "uzvJfB4u3N0Jy4T7NZ75MDVcr8zSTInedJtkgcu46YW4XByzNJjxBdtjUkdJPBtbmHhIDi6pcl8jsasjlTMtDQ==";
+
+    fn shared_secret() -> Vec<u8> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        STANDARD.decode(SHARED_SECRET).unwrap()
+    }
+
+    // RFC 9421 appendix B.2
+    fn test_request() -> Request<()> {
+        Request::post("/foo?param=Value&Pet=dog")
+            .header("host", "example.com")
+            .header("date", "Tue, 20 Apr 2021 02:07:55 GMT")
+            .header("content-type", "application/json")
+            .header(
+                "content-digest",
+                "sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:",
+            )
+            .header("content-length", "18")
+            .body(())
+            .unwrap()
+    }
+
+    fn hmac_verifier() -> Verifier {
+        Verifier::new(|key_id: &str| {
+            (key_id == "test-shared-secret").then(|| VerifyingKey::hmac_sha256(&shared_secret()))
+        })
+    }
+
+    const CREATED: u64 = 1618884473;
+
+    #[test]
+    fn rfc_hmac_sha256() {
+        let mut req = test_request();
+        req.headers_mut().insert(
+            SIGNATURE_INPUT,
+            r#"sig-b25=("date" "@authority" "content-type");created=1618884473;keyid="test-shared-secret""#
+                .parse()
+                .unwrap(),
+        );
+        req.headers_mut().insert(
+            SIGNATURE,
+            "sig-b25=:pxcQw6G3AjtMBQjwo8XzkZf/bws5LelbaMk5rGIGtE8=:"
+                .parse()
+                .unwrap(),
+        );
+
+        let verified = hmac_verifier().verify_at(&req, CREATED).unwrap();
+        assert_eq!(verified.label(), "sig-b25");
+        assert_eq!(verified.params().key_id(), Some("test-shared-secret"));
+        assert_eq!(verified.params().created(), Some(CREATED));
+
+        // signer always attach alg parameter.
+        let mut req2 = test_request();
+        Signer::new("test-shared-secret", SigningKey::hmac_sha256(&shared_secret()))
+            .label("sig-b25")
+            .components(["date", "@authority", "content-type"])
+            .sign_at(&mut req2, CREATED)
+            .unwrap();
+        assert_eq!(
+            req2.headers().get(SIGNATURE_INPUT).unwrap(),
+            r#"sig-b25=("date" "@authority" "content-type");created=1618884473;keyid="test-shared-secret";alg="hmac-sha256""#
+        );
+        hmac_verifier().verify_at(&req2, CREATED).unwrap();
+    }
+
+    #[test]
+    fn tampered() {
+        let mut req = test_request();
+        Signer::new("test-shared-secret", SigningKey::hmac_sha256(&shared_secret()))
+            .components(["@method", "@path", "@query", "content-type"])
+            .sign_at(&mut req, CREATED)
+            .unwrap();
+
+        let verifier = hmac_verifier();
+        verifier.verify_at(&req, CREATED).unwrap();
+
+        req.headers_mut().insert("content-type", "text/plain".parse().unwrap());
+        assert!(matches!(
+            verifier.verify_at(&req, CREATED),
+            Err(Error::InvalidSignature)
+        ));
+
+        req.headers_mut().remove("content-type");
+        assert!(matches!(
+            verifier.verify_at(&req, CREATED),
+            Err(Error::MissingComponent(c)) if c == "content-type"
+        ));
+    }
+
+    #[test]
+    fn created_expires() {
+        let mut req = test_request();
+        Signer::new("test-shared-secret", SigningKey::hmac_sha256(&shared_secret()))
+            .expires_in(Duration::from_secs(60))
+            .sign_at(&mut req, CREATED)
+            .unwrap();
+
+        let verifier = hmac_verifier().clock_skew(Duration::ZERO);
+        verifier.verify_at(&req, CREATED + 60).unwrap();
+
+        assert!(matches!(verifier.verify_at(&req, CREATED + 61), Err(Error::Expired)));
+        assert!(matches!(
+            verifier.verify_at(&req, CREATED - 1),
+            Err(Error::CreatedInFuture)
+        ));
+
+        let verifier = verifier.max_age(Some(Duration::from_secs(10)));
+        assert!(matches!(verifier.verify_at(&req, CREATED + 11), Err(Error::TooOld)));
+
+        let mut req = test_request();
+        req.headers_mut().insert(
+            SIGNATURE_INPUT,
+            r#"sig-b25=("date" "@authority" "content-type");keyid="test-shared-secret""#
+                .parse()
+                .unwrap(),
+        );
+        req.headers_mut().insert(SIGNATURE, "sig-b25=:AAAA:".parse().unwrap());
+        assert!(matches!(verifier.verify_at(&req, CREATED), Err(Error::MissingCreated)));
+    }
+
+    #[test]
+    fn required_components_and_key() {
+        let mut req = test_request();
+        Signer::new("test-shared-secret", SigningKey::hmac_sha256(&shared_secret()))
+            .sign_at(&mut req, CREATED)
+            .unwrap();
+
+        let verifier = hmac_verifier().required_components(["@method", "content-digest"]);
+        assert!(matches!(
+            verifier.verify_at(&req, CREATED),
+            Err(Error::UncoveredComponent(c)) if c == "content-digest"
+        ));
+
+        let mut req = test_request();
+        Signer::new("unknown", SigningKey::hmac_sha256(&shared_secret()))
+            .sign_at(&mut req, CREATED)
+            .unwrap();
+        assert!(matches!(
+            hmac_verifier().verify_at(&req, CREATED),
+            Err(Error::UnknownKey)
+        ));
+
+        assert!(matches!(
+            hmac_verifier().verify_at(&test_request(), CREATED),
+            Err(Error::MissingSignature)
+        ));
+    }
+
+    #[test]
+    fn ed25519_multiple_signatures() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = SigningKey::ed25519(pkcs8.as_ref()).unwrap();
+        let public_key = key.public_key().unwrap().to_vec();
+
+        let mut req = Request::get("https://example.com:443/foo").body(()).unwrap();
+
+        Signer::new("test-shared-secret", SigningKey::hmac_sha256(&shared_secret()))
+            .sign(&mut req)
+            .unwrap();
+        Signer::new("test-key-ed25519", key)
+            .label("sig2")
+            .components(["@method", "@target-uri", "@authority", "@scheme"])
+            .sign(&mut req)
+            .unwrap();
+
+        let verifier = Verifier::new(move |key_id: &str| {
+            (key_id == "test-key-ed25519").then(|| VerifyingKey::ed25519(public_key.clone()))
+        });
+
+        let verified = verifier.verify(&req).unwrap();
+        assert_eq!(verified.label(), "sig2");
+        assert_eq!(verified.params().alg(), Some("ed25519"));
+
+        let verified = hmac_verifier().label("sig1").verify(&req).unwrap();
+        assert_eq!(verified.params().alg(), Some("hmac-sha256"));
+
+        // signing again with the same label replaces previous signature.
+        Signer::new("test-shared-secret", SigningKey::hmac_sha256(&shared_secret()))
+            .sign(&mut req)
+            .unwrap();
+        let input = req.headers().get(SIGNATURE_INPUT).unwrap().to_str().unwrap();
+        assert!(input.starts_with("sig2="));
+        assert_eq!(input.matches("sig1=").count(), 1);
+    }
+}
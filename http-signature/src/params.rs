@@ -0,0 +1,124 @@
+use crate::{
+    error::Error,
+    sfv::{self, Item, Member, Params},
+};
+
+/// covered components and metadata parameters of a signature.
+/// it's the value of `@signature-params` component and a member of `Signature-Input` header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureParams {
+    components: Vec<String>,
+    params: Params,
+}
+
+impl SignatureParams {
+    pub(crate) fn new(components: Vec<String>) -> Self {
+        Self {
+            components,
+            params: Params::new(),
+        }
+    }
+
+    pub(crate) fn push_integer(&mut self, key: &str, value: u64) {
+        self.params.push((key.to_owned(), Item::Integer(value as i64)));
+    }
+
+    pub(crate) fn push_string(&mut self, key: &str, value: &str) {
+        self.params.push((key.to_owned(), Item::String(value.to_owned())));
+    }
+
+    pub(crate) fn from_member(member: &Member) -> Result<Self, Error> {
+        let Member::InnerList(items, params) = member else {
+            return Err(Error::InvalidHeader);
+        };
+
+        let components = items
+            .iter()
+            .map(|(item, params)| match item {
+                Item::String(name) if params.is_empty() => Ok(name.clone()),
+                // component parameters like sf, key, bs, req, name are not supported.
+                Item::String(name) => Err(Error::UnsupportedComponent(name.clone())),
+                _ => Err(Error::InvalidHeader),
+            })
+            .collect::<Result<_, _>>()?;
+
+        for (key, item) in params {
+            match (key.as_str(), item) {
+                ("created" | "expires", Item::Integer(i)) if *i >= 0 => {}
+                ("keyid" | "alg" | "nonce" | "tag", Item::String(_)) => {}
+                ("created" | "expires" | "keyid" | "alg" | "nonce" | "tag", _) => return Err(Error::InvalidHeader),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            components,
+            params: params.clone(),
+        })
+    }
+
+    pub(crate) fn to_member(&self) -> Member {
+        let items = self
+            .components
+            .iter()
+            .map(|c| (Item::String(c.clone()), Params::new()))
+            .collect();
+        Member::InnerList(items, self.params.clone())
+    }
+
+    /// serialized value used as `@signature-params` component.
+    pub(crate) fn serialize(&self, out: &mut String) {
+        if let Member::InnerList(items, params) = self.to_member() {
+            sfv::write_inner_list(out, &items, &params);
+        }
+    }
+
+    /// identifiers of covered components in signed order.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.components.iter().map(String::as_str)
+    }
+
+    /// creation time of signature as unix timestamp in seconds.
+    pub fn created(&self) -> Option<u64> {
+        self.integer("created")
+    }
+
+    /// expiration time of signature as unix timestamp in seconds.
+    pub fn expires(&self) -> Option<u64> {
+        self.integer("expires")
+    }
+
+    /// identifier of key used for signing.
+    pub fn key_id(&self) -> Option<&str> {
+        self.string("keyid")
+    }
+
+    /// name of algorithm used for signing.
+    pub fn alg(&self) -> Option<&str> {
+        self.string("alg")
+    }
+
+    /// random value generated by signer.
+    pub fn nonce(&self) -> Option<&str> {
+        self.string("nonce")
+    }
+
+    /// application specific tag of signature.
+    pub fn tag(&self) -> Option<&str> {
+        self.string("tag")
+    }
+
+    fn integer(&self, key: &str) -> Option<u64> {
+        self.params.iter().find_map(|(k, item)| match item {
+            Item::Integer(i) if k == key => Some(*i as u64),
+            _ => None,
+        })
+    }
+
+    fn string(&self, key: &str) -> Option<&str> {
+        self.params.iter().find_map(|(k, item)| match item {
+            Item::String(s) if k == key => Some(s.as_str()),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,361 @@
+//! minimal structured field values (RFC 8941) parser and serializer covering what signature headers need.
+//! decimal bare item is not supported.
+
+use core::fmt::Write;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Item {
+    Integer(i64),
+    String(String),
+    Token(String),
+    Bytes(Vec<u8>),
+    Boolean(bool),
+}
+
+pub(crate) type Params = Vec<(String, Item)>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Member {
+    Item(Item, Params),
+    InnerList(Vec<(Item, Params)>, Params),
+}
+
+pub(crate) type Dictionary = Vec<(String, Member)>;
+
+pub(crate) fn parse_dictionary(input: &str) -> Option<Dictionary> {
+    let mut parser = Parser {
+        buf: input.as_bytes(),
+        pos: 0,
+    };
+
+    let mut dict = Dictionary::new();
+
+    parser.skip_sp();
+
+    if parser.is_eof() {
+        return Some(dict);
+    }
+
+    loop {
+        let key = parser.parse_key()?;
+
+        let member = if parser.peek() == Some(b'=') {
+            parser.pos += 1;
+            parser.parse_member()?
+        } else {
+            Member::Item(Item::Boolean(true), parser.parse_params()?)
+        };
+
+        // duplicated key overrides the value of previous one.
+        match dict.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, m)) => *m = member,
+            None => dict.push((key, member)),
+        }
+
+        parser.skip_ows();
+
+        if parser.is_eof() {
+            return Some(dict);
+        }
+
+        if parser.next()? != b',' {
+            return None;
+        }
+
+        parser.skip_ows();
+
+        // trailing comma is not allowed.
+        if parser.is_eof() {
+            return None;
+        }
+    }
+}
+
+pub(crate) fn write_dictionary(out: &mut String, dict: &Dictionary) {
+    for (idx, (key, member)) in dict.iter().enumerate() {
+        if idx > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(key);
+        match member {
+            Member::Item(Item::Boolean(true), params) => write_params(out, params),
+            Member::Item(item, params) => {
+                out.push('=');
+                write_item(out, item);
+                write_params(out, params);
+            }
+            Member::InnerList(items, params) => {
+                out.push('=');
+                write_inner_list(out, items, params);
+            }
+        }
+    }
+}
+
+pub(crate) fn write_inner_list(out: &mut String, items: &[(Item, Params)], params: &Params) {
+    out.push('(');
+    for (idx, (item, params)) in items.iter().enumerate() {
+        if idx > 0 {
+            out.push(' ');
+        }
+        write_item(out, item);
+        write_params(out, params);
+    }
+    out.push(')');
+    write_params(out, params);
+}
+
+pub(crate) fn write_params(out: &mut String, params: &Params) {
+    for (key, item) in params {
+        out.push(';');
+        out.push_str(key);
+        if *item != Item::Boolean(true) {
+            out.push('=');
+            write_item(out, item);
+        }
+    }
+}
+
+pub(crate) fn write_item(out: &mut String, item: &Item) {
+    match item {
+        Item::Integer(i) => {
+            let _ = write!(out, "{i}");
+        }
+        Item::String(s) => {
+            out.push('"');
+            for c in s.chars() {
+                if matches!(c, '"' | '\\') {
+                    out.push('\\');
+                }
+                out.push(c);
+            }
+            out.push('"');
+        }
+        Item::Token(t) => out.push_str(t),
+        Item::Bytes(b) => {
+            out.push(':');
+            out.push_str(&STANDARD.encode(b));
+            out.push(':');
+        }
+        Item::Boolean(b) => out.push_str(if *b { "?1" } else { "?0" }),
+    }
+}
+
+// check if string can be serialized as sf-string.
+pub(crate) fn is_valid_string(s: &str) -> bool {
+    s.bytes().all(|b| (0x20..=0x7e).contains(&b))
+}
+
+struct Parser<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn is_eof(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.buf.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn skip_sp(&mut self) {
+        while self.peek() == Some(b' ') {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_ows(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.pos += 1;
+        }
+        // predicates only accept ascii bytes.
+        core::str::from_utf8(&self.buf[start..self.pos]).unwrap_or_default()
+    }
+
+    fn parse_key(&mut self) -> Option<String> {
+        match self.peek()? {
+            b'a'..=b'z' | b'*' => {}
+            _ => return None,
+        }
+        let key = self.take_while(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*'));
+        Some(key.to_owned())
+    }
+
+    fn parse_member(&mut self) -> Option<Member> {
+        if self.peek() == Some(b'(') {
+            self.parse_inner_list()
+        } else {
+            let item = self.parse_bare_item()?;
+            let params = self.parse_params()?;
+            Some(Member::Item(item, params))
+        }
+    }
+
+    fn parse_inner_list(&mut self) -> Option<Member> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_sp();
+            if self.peek()? == b')' {
+                self.pos += 1;
+                let params = self.parse_params()?;
+                return Some(Member::InnerList(items, params));
+            }
+            let item = self.parse_bare_item()?;
+            let params = self.parse_params()?;
+            items.push((item, params));
+            if !matches!(self.peek()?, b' ' | b')') {
+                return None;
+            }
+        }
+    }
+
+    fn parse_params(&mut self) -> Option<Params> {
+        let mut params = Params::new();
+        while self.peek() == Some(b';') {
+            self.pos += 1;
+            self.skip_sp();
+            let key = self.parse_key()?;
+            let item = if self.peek() == Some(b'=') {
+                self.pos += 1;
+                self.parse_bare_item()?
+            } else {
+                Item::Boolean(true)
+            };
+            match params.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, i)) => *i = item,
+                None => params.push((key, item)),
+            }
+        }
+        Some(params)
+    }
+
+    fn parse_bare_item(&mut self) -> Option<Item> {
+        match self.peek()? {
+            b'-' | b'0'..=b'9' => self.parse_integer(),
+            b'"' => self.parse_string(),
+            b':' => self.parse_bytes(),
+            b'?' => self.parse_boolean(),
+            b'a'..=b'z' | b'A'..=b'Z' | b'*' => self.parse_token(),
+            _ => None,
+        }
+    }
+
+    fn parse_integer(&mut self) -> Option<Item> {
+        let neg = self.peek() == Some(b'-');
+        if neg {
+            self.pos += 1;
+        }
+        let digits = self.take_while(|b| b.is_ascii_digit());
+        if digits.is_empty() || digits.len() > 15 {
+            return None;
+        }
+        let i = digits.parse::<i64>().ok()?;
+        // decimal is not supported.
+        if self.peek() == Some(b'.') {
+            return None;
+        }
+        Some(Item::Integer(if neg { -i } else { i }))
+    }
+
+    fn parse_string(&mut self) -> Option<Item> {
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            match self.next()? {
+                b'\\' => match self.next()? {
+                    b @ (b'"' | b'\\') => s.push(b as char),
+                    _ => return None,
+                },
+                b'"' => return Some(Item::String(s)),
+                b @ 0x20..=0x7e => s.push(b as char),
+                _ => return None,
+            }
+        }
+    }
+
+    fn parse_token(&mut self) -> Option<Item> {
+        let token = self.take_while(|b| {
+            b.is_ascii_alphanumeric()
+                || matches!(
+                    b,
+                    b'!' | b'#'
+                        | b'$'
+                        | b'%'
+                        | b'&'
+                        | b'\''
+                        | b'*'
+                        | b'+'
+                        | b'-'
+                        | b'.'
+                        | b'^'
+                        | b'_'
+                        | b'`'
+                        | b'|'
+                        | b'~'
+                        | b':'
+                        | b'/'
+                )
+        });
+        Some(Item::Token(token.to_owned()))
+    }
+
+    fn parse_bytes(&mut self) -> Option<Item> {
+        self.pos += 1;
+        let b64 = self.take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='));
+        let bytes = STANDARD.decode(b64).ok()?;
+        (self.next()? == b':').then_some(Item::Bytes(bytes))
+    }
+
+    fn parse_boolean(&mut self) -> Option<Item> {
+        self.pos += 1;
+        match self.next()? {
+            b'1' => Some(Item::Boolean(true)),
+            b'0' => Some(Item::Boolean(false)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dictionary_round_trip() {
+        let input = r#"sig1=("@method" "content-type");created=1618884473;keyid="test-key", sig2=:AQID:, flag"#;
+        let dict = parse_dictionary(input).unwrap();
+        assert_eq!(dict.len(), 3);
+        assert_eq!(dict[1].1, Member::Item(Item::Bytes(vec![1, 2, 3]), Params::new()));
+        assert_eq!(dict[2].1, Member::Item(Item::Boolean(true), Params::new()));
+
+        let mut out = String::new();
+        write_dictionary(&mut out, &dict);
+        assert_eq!(out, input);
+    }
+
+    #[test]
+    fn dictionary_invalid() {
+        assert!(parse_dictionary("sig1=(\"@method\"").is_none());
+        assert!(parse_dictionary("sig1=:AQID:,").is_none());
+        assert!(parse_dictionary("Sig1=:AQID:").is_none());
+        assert!(parse_dictionary("sig1=1.5").is_none());
+    }
+}
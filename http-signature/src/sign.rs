@@ -0,0 +1,131 @@
+use core::time::Duration;
+
+use http::{HeaderMap, HeaderName, HeaderValue, Request};
+
+use crate::{
+    base::signature_base,
+    error::Error,
+    key::SigningKey,
+    now,
+    params::SignatureParams,
+    sfv::{self, Item, Member, Params},
+    SIGNATURE, SIGNATURE_INPUT,
+};
+
+/// signer producing http message signature for request.
+pub struct Signer {
+    key: SigningKey,
+    key_id: String,
+    label: String,
+    components: Vec<String>,
+    expires_in: Option<Duration>,
+    tag: Option<String>,
+}
+
+impl Signer {
+    /// construct a signer with given key id and signing key.
+    pub fn new(key_id: impl Into<String>, key: SigningKey) -> Self {
+        Self {
+            key,
+            key_id: key_id.into(),
+            label: String::from("sig1"),
+            components: vec![
+                String::from("@method"),
+                String::from("@authority"),
+                String::from("@request-target"),
+            ],
+            expires_in: None,
+            tag: None,
+        }
+    }
+
+    /// set label of signature. signature with the same label on request would be replaced when signing.
+    ///
+    /// # Default
+    /// "sig1"
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// set covered components of signature. derived component is prefixed with `@` and http header
+    /// field is in lower case.
+    ///
+    /// # Default
+    /// `@method`, `@authority` and `@request-target`
+    pub fn components<I, S>(mut self, components: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.components = components.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// set duration of signature being valid after it's creation. `expires` parameter is added to
+    /// signature when set.
+    ///
+    /// # Default
+    /// no expiration.
+    pub fn expires_in(mut self, dur: Duration) -> Self {
+        self.expires_in = Some(dur);
+        self
+    }
+
+    /// set application specific `tag` parameter of signature.
+    ///
+    /// # Default
+    /// no tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// sign request and add `Signature-Input` and `Signature` headers to it.
+    pub fn sign<B>(&self, req: &mut Request<B>) -> Result<(), Error> {
+        self.sign_at(req, now())
+    }
+
+    pub(crate) fn sign_at<B>(&self, req: &mut Request<B>, now: u64) -> Result<(), Error> {
+        if !sfv::is_valid_string(&self.key_id) || self.tag.as_deref().is_some_and(|t| !sfv::is_valid_string(t)) {
+            return Err(Error::Sign);
+        }
+
+        let mut params = SignatureParams::new(self.components.clone());
+        params.push_integer("created", now);
+        if let Some(dur) = self.expires_in {
+            params.push_integer("expires", now + dur.as_secs());
+        }
+        params.push_string("keyid", &self.key_id);
+        params.push_string("alg", self.key.algorithm().as_str());
+        if let Some(ref tag) = self.tag {
+            params.push_string("tag", tag);
+        }
+
+        let base = signature_base(req, &params)?;
+        let sig = self.key.sign(base.as_bytes())?;
+
+        let headers = req.headers_mut();
+        replace_member(headers, SIGNATURE_INPUT, &self.label, params.to_member())?;
+        replace_member(
+            headers,
+            SIGNATURE,
+            &self.label,
+            Member::Item(Item::Bytes(sig), Params::new()),
+        )
+    }
+}
+
+// insert member to dictionary header and replace existing one with the same label.
+fn replace_member(headers: &mut HeaderMap, name: HeaderName, label: &str, member: Member) -> Result<(), Error> {
+    let mut dict = crate::parse_header(headers, &name)?;
+    dict.retain(|(key, _)| key != label);
+    dict.push((label.to_owned(), member));
+
+    let mut value = String::new();
+    sfv::write_dictionary(&mut value, &dict);
+
+    let value = HeaderValue::try_from(value).map_err(|_| Error::Sign)?;
+    headers.insert(name, value);
+    Ok(())
+}
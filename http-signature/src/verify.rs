@@ -0,0 +1,216 @@
+use core::time::Duration;
+
+use std::sync::Arc;
+
+use http::Request;
+
+use crate::{
+    base::signature_base,
+    error::Error,
+    key::{Algorithm, VerifyingKey},
+    now,
+    params::SignatureParams,
+    parse_header,
+    sfv::{Item, Member},
+    SIGNATURE, SIGNATURE_INPUT,
+};
+
+/// resolve verifying key from key id carried by signature.
+///
+/// closure in form of `Fn(&str) -> Option<VerifyingKey>` is a resolver.
+pub trait KeyResolver: Send + Sync {
+    /// return None when key id is unknown.
+    fn resolve(&self, key_id: &str) -> Option<VerifyingKey>;
+}
+
+impl<F> KeyResolver for F
+where
+    F: Fn(&str) -> Option<VerifyingKey> + Send + Sync,
+{
+    fn resolve(&self, key_id: &str) -> Option<VerifyingKey> {
+        self(key_id)
+    }
+}
+
+/// successfully verified signature.
+#[derive(Clone, Debug)]
+pub struct Verified {
+    label: String,
+    params: SignatureParams,
+}
+
+impl Verified {
+    /// label of verified signature.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// covered components and parameters of verified signature.
+    pub fn params(&self) -> &SignatureParams {
+        &self.params
+    }
+}
+
+/// verifier checking http message signature of request.
+#[derive(Clone)]
+pub struct Verifier {
+    resolver: Arc<dyn KeyResolver>,
+    label: Option<String>,
+    required_components: Vec<String>,
+    require_created: bool,
+    max_age: Option<Duration>,
+    clock_skew: Duration,
+}
+
+impl Verifier {
+    /// construct a verifier with given key resolver.
+    pub fn new<R>(resolver: R) -> Self
+    where
+        R: KeyResolver + 'static,
+    {
+        Self {
+            resolver: Arc::new(resolver),
+            label: None,
+            required_components: Vec::new(),
+            require_created: true,
+            max_age: Some(Duration::from_secs(300)),
+            clock_skew: Duration::from_secs(30),
+        }
+    }
+
+    /// only verify signature with given label.
+    ///
+    /// # Default
+    /// signatures are tried in order and the first valid one is accepted.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// set components must be covered by signature. derived component is prefixed with `@` and http
+    /// header field is in lower case.
+    ///
+    /// # Default
+    /// no required component.
+    pub fn required_components<I, S>(mut self, components: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required_components = components.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// set if signature must carry `created` parameter.
+    ///
+    /// # Default
+    /// true
+    pub fn require_created(mut self, require: bool) -> Self {
+        self.require_created = require;
+        self
+    }
+
+    /// set max age of signature based on it's `created` parameter. pass None to accept signature
+    /// regardless of it's age.
+    ///
+    /// # Default
+    /// 5 minutes
+    pub fn max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// set tolerance of clock difference between signer and verifier when checking `created` and
+    /// `expires` parameters.
+    ///
+    /// # Default
+    /// 30 seconds
+    pub fn clock_skew(mut self, skew: Duration) -> Self {
+        self.clock_skew = skew;
+        self
+    }
+
+    /// verify signature of request.
+    pub fn verify<B>(&self, req: &Request<B>) -> Result<Verified, Error> {
+        self.verify_at(req, now())
+    }
+
+    pub(crate) fn verify_at<B>(&self, req: &Request<B>, now: u64) -> Result<Verified, Error> {
+        let inputs = parse_header(req.headers(), &SIGNATURE_INPUT)?;
+        let signatures = parse_header(req.headers(), &SIGNATURE)?;
+
+        let mut err = Error::MissingSignature;
+
+        for (label, input) in inputs {
+            if self.label.as_ref().is_some_and(|l| *l != label) {
+                continue;
+            }
+
+            let sig = match signatures.iter().find(|(l, _)| *l == label) {
+                Some((_, Member::Item(Item::Bytes(sig), _))) => sig,
+                _ => {
+                    err = Error::InvalidHeader;
+                    continue;
+                }
+            };
+
+            match self.verify_one(req, &input, sig, now) {
+                Ok(params) => return Ok(Verified { label, params }),
+                Err(e) => err = e,
+            }
+        }
+
+        Err(err)
+    }
+
+    fn verify_one<B>(&self, req: &Request<B>, input: &Member, sig: &[u8], now: u64) -> Result<SignatureParams, Error> {
+        let params = SignatureParams::from_member(input)?;
+
+        if let Some(c) = self
+            .required_components
+            .iter()
+            .find(|c| !params.components().any(|c2| c2 == c.as_str()))
+        {
+            return Err(Error::UncoveredComponent(c.clone()));
+        }
+
+        let skew = self.clock_skew.as_secs();
+
+        match params.created() {
+            Some(created) if created > now.saturating_add(skew) => return Err(Error::CreatedInFuture),
+            Some(created) => {
+                if let Some(max_age) = self.max_age {
+                    if now > created.saturating_add(max_age.as_secs()).saturating_add(skew) {
+                        return Err(Error::TooOld);
+                    }
+                }
+            }
+            None if self.require_created => return Err(Error::MissingCreated),
+            None => {}
+        }
+
+        if params
+            .expires()
+            .is_some_and(|expires| now > expires.saturating_add(skew))
+        {
+            return Err(Error::Expired);
+        }
+
+        let key_id = params.key_id().ok_or(Error::UnknownKey)?;
+        let key = self.resolver.resolve(key_id).ok_or(Error::UnknownKey)?;
+
+        if let Some(alg) = params.alg() {
+            if Algorithm::from_name(alg) != Some(key.algorithm()) {
+                return Err(Error::AlgorithmMismatch);
+            }
+        }
+
+        let base = signature_base(req, &params)?;
+
+        if !key.verify(base.as_bytes(), sig) {
+            return Err(Error::InvalidSignature);
+        }
+
+        Ok(params)
+    }
+}
@@ -24,6 +24,12 @@ use super::{
     error::BodyError,
 };
 
+#[cfg(feature = "runtime")]
+mod file;
+
+#[cfg(feature = "runtime")]
+pub use file::{FileBody, SendFile};
+
 // this is a crate level hack to hint for none body type.
 // A body type with this size hint means the body MUST not be polled/collected by anyone.
 pub const fn none_body_hint() -> (usize, Option<usize>) {
@@ -383,6 +389,15 @@ impl<B> ResponseBody<B> {
         }
     }
 
+    /// reference of bytes when ResponseBody is Bytes variant. Other variants would return None.
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&Bytes> {
+        match self.inner {
+            ResponseBodyInner::Bytes { ref bytes } => Some(bytes),
+            _ => None,
+        }
+    }
+
     /// erase generic body type by boxing the variant.
     #[inline]
     pub fn into_boxed<T, E>(self) -> ResponseBody
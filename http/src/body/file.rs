@@ -0,0 +1,188 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use std::{fs::File, io, sync::Arc};
+
+use futures_core::stream::Stream;
+use tokio::task::JoinHandle;
+
+use crate::bytes::Bytes;
+
+/// file backed response body.
+///
+/// when used as response body on plain text http/1 connection together with [SendFile] hint the file
+/// content is transferred with os level zero copy api (sendfile etc). in other cases it falls back
+/// to streaming file content with buffered reads in blocking thread pool.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_http::{body::FileBody, http::Response};
+/// fn response(file: std::fs::File) -> std::io::Result<Response<FileBody>> {
+///     let body = FileBody::new(file)?;
+///     let mut res = Response::new(());
+///     // insert hint for zero copy transfer.
+///     res.extensions_mut().insert(body.send_file());
+///     Ok(res.map(|_| body))
+/// }
+/// ```
+pub struct FileBody {
+    file: Arc<File>,
+    offset: u64,
+    remaining: u64,
+    chunk_size: usize,
+    on_flight: Option<JoinHandle<io::Result<Vec<u8>>>>,
+}
+
+impl FileBody {
+    /// construct body from whole content of given file.
+    pub fn new(file: File) -> io::Result<Self> {
+        let len = file.metadata()?.len();
+        Ok(Self::with_range(file, 0, len))
+    }
+
+    /// construct body from len bytes of given file content starting from offset.
+    /// it's caller's responsibility to make sure the range is within file's length.
+    pub fn with_range(file: File, offset: u64, len: u64) -> Self {
+        Self {
+            file: Arc::new(file),
+            offset,
+            remaining: len,
+            chunk_size: 64 * 1024,
+            on_flight: None,
+        }
+    }
+
+    /// hint for max size of chunk when falling back to buffered reads.
+    ///
+    /// # Default
+    /// 64KiB
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size;
+        self
+    }
+
+    /// produce zero copy hint of body. it must be inserted into response's extensions to take effect.
+    pub fn send_file(&self) -> SendFile {
+        SendFile {
+            file: self.file.clone(),
+            offset: self.offset,
+            len: self.remaining,
+        }
+    }
+}
+
+impl Stream for FileBody {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.remaining == 0 {
+            return Poll::Ready(None);
+        }
+
+        let handle = this.on_flight.get_or_insert_with(|| {
+            let file = this.file.clone();
+            let offset = this.offset;
+            let len = this.remaining.min(this.chunk_size as u64) as usize;
+            tokio::task::spawn_blocking(move || read_at(&file, offset, len))
+        });
+
+        let res = ready!(Pin::new(handle).poll(cx));
+        this.on_flight = None;
+
+        let buf = match res.map_err(io::Error::other).and_then(|res| res) {
+            Ok(buf) if buf.is_empty() => {
+                // file is truncated while being read.
+                this.remaining = 0;
+                return Poll::Ready(Some(Err(io::ErrorKind::UnexpectedEof.into())));
+            }
+            Ok(buf) => buf,
+            Err(e) => {
+                this.remaining = 0;
+                return Poll::Ready(Some(Err(e)));
+            }
+        };
+
+        this.offset += buf.len() as u64;
+        this.remaining -= buf.len() as u64;
+
+        Poll::Ready(Some(Ok(Bytes::from(buf))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+#[cfg(any(unix, windows))]
+fn read_at(file: &File, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0; len];
+
+    #[cfg(unix)]
+    let n = std::os::unix::fs::FileExt::read_at(file, &mut buf, offset)?;
+    #[cfg(windows)]
+    let n = std::os::windows::fs::FileExt::seek_read(file, &mut buf, offset)?;
+
+    buf.truncate(n);
+    Ok(buf)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn read_at(_: &File, _: u64, _: usize) -> io::Result<Vec<u8>> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+/// zero copy transfer hint of [FileBody]. see [FileBody::send_file] for detail.
+///
+/// http/1 dispatcher looks up this type from response's extensions and when the connection is
+/// capable the associated file range is written to connection directly and response body is
+/// dropped without being polled.
+#[derive(Clone)]
+pub struct SendFile {
+    pub(crate) file: Arc<File>,
+    pub(crate) offset: u64,
+    pub(crate) len: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use core::future::poll_fn;
+
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn buffered_read() {
+        let path = std::env::temp_dir().join(format!("xitca-http-file-body-{}", std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"hello,world!")
+            .unwrap();
+
+        let file = File::open(&path).unwrap();
+        let mut body = FileBody::with_range(file, 2, 8).chunk_size(3);
+
+        assert_eq!(body.size_hint(), (8, Some(8)));
+
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        let res = rt.block_on(async {
+            let mut res = Vec::new();
+            while let Some(bytes) = poll_fn(|cx| Pin::new(&mut body).poll_next(cx)).await {
+                let bytes = bytes.unwrap();
+                assert!(bytes.len() <= 3);
+                res.extend_from_slice(&bytes);
+            }
+            res
+        });
+
+        assert_eq!(res, b"llo,worl");
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -16,7 +16,7 @@ use xitca_service::Service;
 use xitca_unsafe_collection::futures::{Select as _, SelectOutput};
 
 use crate::{
-    body::NoneBody,
+    body::{NoneBody, SendFile},
     bytes::{Bytes, EitherBuf},
     config::HttpServiceConfig,
     date::DateTime,
@@ -223,7 +223,7 @@ where
             let (mut body_reader, body) = BodyReader::from_coding(decoder);
             let req = req.map(|ext| ext.map_body(|_| ReqB::from(body)));
 
-            let (parts, body) = match self
+            let (mut parts, body) = match self
                 .service
                 .call(req)
                 .select(self.request_body_handler(&mut body_reader))
@@ -235,32 +235,42 @@ where
                 SelectOutput::B(Ok(i)) => match i {},
             };
 
+            let send_file = parts.extensions.remove::<SendFile>();
+
             let encoder = &mut self.encode_head(parts, &body)?;
             let mut body = pin!(body);
 
-            loop {
-                match self
-                    .try_poll_body(body.as_mut())
-                    .select(self.io_ready(&mut body_reader))
-                    .await
-                {
-                    SelectOutput::A(Some(Ok(bytes))) => encoder.encode(bytes, &mut self.io.write_buf),
-                    SelectOutput::B(Ok(ready)) => {
-                        if ready.is_readable() {
-                            if let Err(e) = self.io.try_read() {
-                                body_reader.feed_error(e);
+            // body is written with zero copy file transfer when possible.
+            let sent = match send_file {
+                Some(file) => self.send_file(encoder, file).await?,
+                None => false,
+            };
+
+            if !sent {
+                loop {
+                    match self
+                        .try_poll_body(body.as_mut())
+                        .select(self.io_ready(&mut body_reader))
+                        .await
+                    {
+                        SelectOutput::A(Some(Ok(bytes))) => encoder.encode(bytes, &mut self.io.write_buf),
+                        SelectOutput::B(Ok(ready)) => {
+                            if ready.is_readable() {
+                                if let Err(e) = self.io.try_read() {
+                                    body_reader.feed_error(e);
+                                }
+                            }
+                            if ready.is_writable() {
+                                self.io.try_write()?;
                             }
                         }
-                        if ready.is_writable() {
-                            self.io.try_write()?;
+                        SelectOutput::A(None) => {
+                            encoder.encode_eof(&mut self.io.write_buf);
+                            break;
                         }
+                        SelectOutput::B(Err(e)) => return Err(e.into()),
+                        SelectOutput::A(Some(Err(e))) => return Err(Error::Body(e)),
                     }
-                    SelectOutput::A(None) => {
-                        encoder.encode_eof(&mut self.io.write_buf);
-                        break;
-                    }
-                    SelectOutput::B(Err(e)) => return Err(e.into()),
-                    SelectOutput::A(Some(Err(e))) => return Err(Error::Body(e)),
                 }
             }
 
@@ -273,6 +283,37 @@ where
         Ok(())
     }
 
+    // write file range to io directly. return false when io is not capable of zero copy transfer or
+    // response head does not match the file range(HEAD request etc) where body stream must be used
+    // instead.
+    async fn send_file(&mut self, encoder: &mut TransferCoding, file: SendFile) -> Result<bool, Error<S::Error, BE>> {
+        if !matches!(*encoder, TransferCoding::Length(len) if len == file.len) {
+            return Ok(false);
+        }
+
+        // response head must be written before file content.
+        self.io.drain_write().await?;
+
+        let SendFile { file, mut offset, len } = file;
+        let mut remaining = len;
+
+        while remaining > 0 {
+            match self.io.io.try_send_file(&file, &mut offset, remaining) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => remaining -= n as u64,
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    self.io.io.ready(Interest::WRITABLE).await?;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Unsupported && remaining == len => return Ok(false),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        *encoder = TransferCoding::eof();
+
+        Ok(true)
+    }
+
     fn encode_head(&mut self, parts: Parts, body: &impl Stream) -> Result<TransferCoding, ProtoError> {
         self.ctx.encode_head(parts, body, &mut self.io.write_buf)
     }
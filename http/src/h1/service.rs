@@ -8,6 +8,7 @@ use crate::{
     bytes::Bytes,
     error::{HttpServiceError, TimeoutError},
     http::{Request, RequestExt, Response},
+    metrics,
     service::HttpService,
     util::timer::Timeout,
 };
@@ -31,19 +32,33 @@ where
     type Error = HttpServiceError<S::Error, BE>;
 
     async fn call(&self, (io, addr): (St, SocketAddr)) -> Result<Self::Response, Self::Error> {
+        metrics::accepted();
+
         // at this stage keep-alive timer is used to tracks tls accept timeout.
         let mut timer = pin!(self.keep_alive());
 
-        let mut io = self
+        let io = self
             .tls_acceptor
             .call(io)
             .timeout(timer.as_mut())
             .await
-            .map_err(|_| HttpServiceError::Timeout(TimeoutError::TlsAccept))??;
-
-        super::dispatcher::run(&mut io, addr, timer, self.config, &self.service, self.date.get())
-            .await
-            .map_err(Into::into)
+            .map_err(|_| {
+                metrics::handshake_failed();
+                HttpServiceError::Timeout(TimeoutError::TlsAccept)
+            })?
+            .inspect_err(|_| metrics::handshake_failed())?;
+
+        let _guard = metrics::ActiveGuard::new(metrics::Protocol::H1);
+        super::dispatcher::run(
+            &mut metrics::CountIo::new(io),
+            addr,
+            timer,
+            self.config,
+            &self.service,
+            self.date.get(),
+        )
+        .await
+        .map_err(Into::into)
     }
 }
 
@@ -26,7 +26,7 @@ use crate::{
     h2::{body::RequestBody, error::Error},
     http::{
         Extension, Request, RequestExt, Response, Version,
-        header::{CONNECTION, CONTENT_LENGTH, DATE, HeaderMap, HeaderName, HeaderValue, TRAILER},
+        header::{CONNECTION, CONTENT_LENGTH, DATE, Entry, HeaderMap, HeaderName, HeaderValue, TRAILER},
     },
     util::{futures::Queue, timer::KeepAlive},
 };
@@ -110,7 +110,10 @@ where
 
                     queue.push(async move {
                         let fut = service.call(req);
-                        h2_handler(fut, tx, date).await
+                        // any error from handler would end the stream with reset.
+                        h2_handler(fut, tx, date)
+                            .await
+                            .inspect_err(|_| crate::metrics::h2_stream_reset())
                     });
                 }
                 SelectOutput::B(SelectOutput::A(_)) => io.graceful_shutdown(),
@@ -250,10 +253,20 @@ where
 
     let mut trailers = HeaderMap::with_capacity(0);
 
-    while let Some(value) = res.headers_mut().remove(TRAILER) {
-        let name = HeaderName::from_bytes(value.as_bytes()).unwrap();
-        let value = res.headers_mut().remove(name.clone()).unwrap();
-        trailers.append(name, value);
+    // every value of trailer header is the name of a header that would be sent as trailer.
+    let names = match res.headers_mut().entry(TRAILER) {
+        Entry::Occupied(entry) => entry.remove_entry_mult().1.collect(),
+        Entry::Vacant(_) => Vec::new(),
+    };
+
+    for name in names {
+        let name = HeaderName::from_bytes(name.as_bytes()).unwrap();
+        if let Entry::Occupied(entry) = res.headers_mut().entry(name) {
+            let (name, values) = entry.remove_entry_mult();
+            values.for_each(|value| {
+                trailers.append(name.clone(), value);
+            });
+        }
     }
 
     if !res.headers().contains_key(DATE) {
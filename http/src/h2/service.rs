@@ -8,6 +8,7 @@ use crate::{
     bytes::Bytes,
     error::{HttpServiceError, TimeoutError},
     http::{Request, RequestExt, Response},
+    metrics,
     service::HttpService,
     util::timer::Timeout,
 };
@@ -33,6 +34,8 @@ where
     type Error = HttpServiceError<S::Error, BE>;
 
     async fn call(&self, (io, addr): (St, SocketAddr)) -> Result<Self::Response, Self::Error> {
+        metrics::accepted();
+
         // tls accept timer.
         let timer = self.keep_alive();
         let mut timer = pin!(timer);
@@ -42,17 +45,27 @@ where
             .call(io)
             .timeout(timer.as_mut())
             .await
-            .map_err(|_| HttpServiceError::Timeout(TimeoutError::TlsAccept))??;
+            .map_err(|_| {
+                metrics::handshake_failed();
+                HttpServiceError::Timeout(TimeoutError::TlsAccept)
+            })?
+            .inspect_err(|_| metrics::handshake_failed())?;
 
         // update timer to first request timeout.
         self.update_first_request_deadline(timer.as_mut());
 
         let mut conn = ::h2::server::Builder::new()
             .enable_connect_protocol()
-            .handshake(PollIoAdapter(tls_stream))
+            .handshake(PollIoAdapter(metrics::CountIo::new(tls_stream)))
             .timeout(timer.as_mut())
             .await
-            .map_err(|_| HttpServiceError::Timeout(TimeoutError::H2Handshake))??;
+            .map_err(|_| {
+                metrics::handshake_failed();
+                HttpServiceError::Timeout(TimeoutError::H2Handshake)
+            })?
+            .inspect_err(|_| metrics::handshake_failed())?;
+
+        let _guard = metrics::ActiveGuard::new(metrics::Protocol::H2);
 
         let dispatcher = Dispatcher::new(
             &mut conn,
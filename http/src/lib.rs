@@ -49,6 +49,7 @@ pub mod body;
 pub mod config;
 pub mod error;
 pub mod http;
+pub mod metrics;
 pub mod util;
 
 #[cfg(feature = "runtime")]
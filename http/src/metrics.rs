@@ -0,0 +1,249 @@
+//! process wide runtime metrics of http services.
+//!
+//! counters are updated by [HttpServiceBuilder](crate::HttpServiceBuilder) produced services and can be observed
+//! with [snapshot] function. byte counters are opt-in and enabled by [enable_byte_counters] function.
+//!
+//! # Examples
+//! ```rust
+//! xitca_http::metrics::enable_byte_counters();
+//!
+//! let snapshot = xitca_http::metrics::snapshot();
+//! println!("active http/1 connections: {}", snapshot.active_h1_connections);
+//! ```
+
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// point in time copy of runtime metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MetricsSnapshot {
+    /// total count of connections accepted by http services.
+    pub accepted_connections: u64,
+    /// count of connections currently served with http/1.
+    pub active_h1_connections: u64,
+    /// count of connections currently served with http/2.
+    pub active_h2_connections: u64,
+    /// count of connections currently served with http/3.
+    pub active_h3_connections: u64,
+    /// total count of failed tls and http/2 handshakes. (including timeout)
+    pub handshake_failures: u64,
+    /// total count of http/2 streams ended with reset.
+    pub h2_stream_resets: u64,
+    /// total application bytes read from http/1 and http/2 connections. bytes of tls connection are counted
+    /// after decryption. stays 0 unless [enable_byte_counters] is called.
+    pub bytes_in: u64,
+    /// total application bytes written to http/1 and http/2 connections. bytes of tls connection are counted
+    /// before encryption. stays 0 unless [enable_byte_counters] is called.
+    pub bytes_out: u64,
+}
+
+/// take a snapshot of current process wide runtime metrics.
+pub fn snapshot() -> MetricsSnapshot {
+    let m = &METRICS;
+    MetricsSnapshot {
+        accepted_connections: m.accepted.load(Ordering::Relaxed),
+        active_h1_connections: m.active[Protocol::H1 as usize].load(Ordering::Relaxed),
+        active_h2_connections: m.active[Protocol::H2 as usize].load(Ordering::Relaxed),
+        active_h3_connections: m.active[Protocol::H3 as usize].load(Ordering::Relaxed),
+        handshake_failures: m.handshake_failures.load(Ordering::Relaxed),
+        h2_stream_resets: m.h2_stream_resets.load(Ordering::Relaxed),
+        bytes_in: m.bytes_in.load(Ordering::Relaxed),
+        bytes_out: m.bytes_out.load(Ordering::Relaxed),
+    }
+}
+
+/// enable counting of [MetricsSnapshot::bytes_in] and [MetricsSnapshot::bytes_out] for connections accepted
+/// after the call. counting adds an atomic operation to every read and write of connection and is disabled
+/// by default.
+pub fn enable_byte_counters() {
+    COUNT_BYTES.store(true, Ordering::Relaxed);
+}
+
+static COUNT_BYTES: AtomicBool = AtomicBool::new(false);
+
+struct Metrics {
+    accepted: AtomicU64,
+    active: [AtomicU64; 3],
+    handshake_failures: AtomicU64,
+    h2_stream_resets: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+}
+
+static METRICS: Metrics = Metrics {
+    accepted: AtomicU64::new(0),
+    active: [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)],
+    handshake_failures: AtomicU64::new(0),
+    h2_stream_resets: AtomicU64::new(0),
+    bytes_in: AtomicU64::new(0),
+    bytes_out: AtomicU64::new(0),
+};
+
+#[derive(Clone, Copy)]
+pub(crate) enum Protocol {
+    H1 = 0,
+    H2 = 1,
+    H3 = 2,
+}
+
+#[cfg(feature = "runtime")]
+pub(crate) fn accepted() {
+    METRICS.accepted.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(feature = "runtime")]
+pub(crate) fn handshake_failed() {
+    METRICS.handshake_failures.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(feature = "http2")]
+pub(crate) fn h2_stream_reset() {
+    METRICS.h2_stream_resets.fetch_add(1, Ordering::Relaxed);
+}
+
+/// guard type for tracking active connection. count is decreased when guard is dropped.
+#[cfg(any(feature = "http1", feature = "http2", feature = "http3"))]
+pub(crate) struct ActiveGuard(Protocol);
+
+#[cfg(any(feature = "http1", feature = "http2", feature = "http3"))]
+impl ActiveGuard {
+    pub(crate) fn new(proto: Protocol) -> Self {
+        METRICS.active[proto as usize].fetch_add(1, Ordering::Relaxed);
+        Self(proto)
+    }
+}
+
+#[cfg(any(feature = "http1", feature = "http2", feature = "http3"))]
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        METRICS.active[self.0 as usize].fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(any(feature = "http1", feature = "http2"))]
+pub(crate) use io::CountIo;
+
+#[cfg(any(feature = "http1", feature = "http2"))]
+mod io {
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use std::{fs::File, io};
+
+    use xitca_io::io::{AsyncIo, Interest, Ready};
+
+    use super::*;
+
+    /// io type wrapper counting bytes read from and written to it when byte counters are enabled.
+    pub(crate) struct CountIo<Io> {
+        io: Io,
+        enabled: bool,
+    }
+
+    impl<Io> CountIo<Io> {
+        pub(crate) fn new(io: Io) -> Self {
+            Self {
+                io,
+                enabled: COUNT_BYTES.load(Ordering::Relaxed),
+            }
+        }
+
+        #[inline]
+        fn add(&self, res: io::Result<usize>, counter: &AtomicU64) -> io::Result<usize> {
+            if let (true, Ok(n)) = (self.enabled, &res) {
+                counter.fetch_add(*n as u64, Ordering::Relaxed);
+            }
+            res
+        }
+    }
+
+    impl<Io> AsyncIo for CountIo<Io>
+    where
+        Io: AsyncIo,
+    {
+        #[inline]
+        fn ready(&mut self, interest: Interest) -> impl Future<Output = io::Result<Ready>> + Send {
+            self.io.ready(interest)
+        }
+
+        #[inline]
+        fn poll_ready(&mut self, interest: Interest, cx: &mut Context<'_>) -> Poll<io::Result<Ready>> {
+            self.io.poll_ready(interest, cx)
+        }
+
+        fn is_vectored_write(&self) -> bool {
+            self.io.is_vectored_write()
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.get_mut().io).poll_shutdown(cx)
+        }
+
+        fn try_send_file(&mut self, file: &File, offset: &mut u64, count: u64) -> io::Result<usize> {
+            let res = self.io.try_send_file(file, offset, count);
+            self.add(res, &METRICS.bytes_out)
+        }
+    }
+
+    impl<Io> io::Read for CountIo<Io>
+    where
+        Io: io::Read,
+    {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let res = self.io.read(buf);
+            self.add(res, &METRICS.bytes_in)
+        }
+    }
+
+    impl<Io> io::Write for CountIo<Io>
+    where
+        Io: io::Write,
+    {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let res = self.io.write(buf);
+            self.add(res, &METRICS.bytes_out)
+        }
+
+        #[inline]
+        fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+            let res = self.io.write_vectored(bufs);
+            self.add(res, &METRICS.bytes_out)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> io::Result<()> {
+            self.io.flush()
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "http1", feature = "http2", feature = "http3")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn active_guard() {
+        let before = snapshot().active_h3_connections;
+        let guard = ActiveGuard::new(Protocol::H3);
+        assert_eq!(snapshot().active_h3_connections, before + 1);
+        drop(guard);
+        assert_eq!(snapshot().active_h3_connections, before);
+    }
+
+    #[cfg(any(feature = "http1", feature = "http2"))]
+    #[test]
+    fn byte_counters() {
+        use std::io::Read;
+
+        enable_byte_counters();
+
+        let before = snapshot().bytes_in;
+        let mut io = CountIo::new(&b"foo"[..]);
+        assert_eq!(io.read(&mut [0; 8]).unwrap(), 3);
+        assert!(snapshot().bytes_in >= before + 3);
+    }
+}
@@ -14,6 +14,7 @@ use super::{
     date::{DateTime, DateTimeService},
     error::{HttpServiceError, TimeoutError},
     http::{Request, RequestExt, Response},
+    metrics,
     util::timer::{KeepAlive, Timeout},
     version::AsVersion,
 };
@@ -84,24 +85,33 @@ where
     type Error = HttpServiceError<S::Error, BE>;
 
     async fn call(&self, io: ServerStream) -> Result<Self::Response, Self::Error> {
+        metrics::accepted();
+
         // tls accept timer.
         let timer = self.keep_alive();
         let mut timer = pin!(timer);
 
         match io {
             #[cfg(feature = "http3")]
-            ServerStream::Udp(io, addr) => super::h3::Dispatcher::new(io, addr, &self.service)
-                .run()
-                .await
-                .map_err(From::from),
+            ServerStream::Udp(io, addr) => {
+                let _guard = metrics::ActiveGuard::new(metrics::Protocol::H3);
+                super::h3::Dispatcher::new(io, addr, &self.service)
+                    .run()
+                    .await
+                    .map_err(From::from)
+            }
             ServerStream::Tcp(io, _addr) => {
                 let io = TcpStream::from_std(io).expect("TODO: handle io error");
-                let mut _tls_stream = self
+                let _tls_stream = self
                     .tls_acceptor
                     .call(io)
                     .timeout(timer.as_mut())
                     .await
-                    .map_err(|_| HttpServiceError::Timeout(TimeoutError::TlsAccept))??;
+                    .map_err(|_| {
+                        metrics::handshake_failed();
+                        HttpServiceError::Timeout(TimeoutError::TlsAccept)
+                    })?
+                    .inspect_err(|_| metrics::handshake_failed())?;
 
                 let version = if self.config.peek_protocol {
                     // peek version from connection to figure out the real protocol used
@@ -113,16 +123,19 @@ where
 
                 match version {
                     #[cfg(feature = "http1")]
-                    super::http::Version::HTTP_11 | super::http::Version::HTTP_10 => super::h1::dispatcher::run(
-                        &mut _tls_stream,
-                        _addr,
-                        timer.as_mut(),
-                        self.config,
-                        &self.service,
-                        self.date.get(),
-                    )
-                    .await
-                    .map_err(From::from),
+                    super::http::Version::HTTP_11 | super::http::Version::HTTP_10 => {
+                        let _guard = metrics::ActiveGuard::new(metrics::Protocol::H1);
+                        super::h1::dispatcher::run(
+                            &mut metrics::CountIo::new(_tls_stream),
+                            _addr,
+                            timer.as_mut(),
+                            self.config,
+                            &self.service,
+                            self.date.get(),
+                        )
+                        .await
+                        .map_err(From::from)
+                    }
                     #[cfg(feature = "http2")]
                     super::http::Version::HTTP_2 => {
                         // update timer to first request timeout.
@@ -130,10 +143,16 @@ where
 
                         let mut conn = ::h2::server::Builder::new()
                             .enable_connect_protocol()
-                            .handshake(xitca_io::io::PollIoAdapter(_tls_stream))
+                            .handshake(xitca_io::io::PollIoAdapter(metrics::CountIo::new(_tls_stream)))
                             .timeout(timer.as_mut())
                             .await
-                            .map_err(|_| HttpServiceError::Timeout(TimeoutError::H2Handshake))??;
+                            .map_err(|_| {
+                                metrics::handshake_failed();
+                                HttpServiceError::Timeout(TimeoutError::H2Handshake)
+                            })?
+                            .inspect_err(|_| metrics::handshake_failed())?;
+
+                        let _guard = metrics::ActiveGuard::new(metrics::Protocol::H2);
 
                         super::h2::Dispatcher::new(
                             &mut conn,
@@ -159,10 +178,11 @@ where
 
                 #[cfg(feature = "http1")]
                 {
-                    let mut io = xitca_io::net::UnixStream::from_std(_io).expect("TODO: handle io error");
+                    let io = xitca_io::net::UnixStream::from_std(_io).expect("TODO: handle io error");
 
+                    let _guard = metrics::ActiveGuard::new(metrics::Protocol::H1);
                     super::h1::dispatcher::run(
-                        &mut io,
+                        &mut metrics::CountIo::new(io),
                         crate::unspecified_socket_addr(),
                         timer.as_mut(),
                         self.config,
@@ -10,7 +10,7 @@ use core::{
     task::{ready, Context, Poll},
 };
 
-use std::io;
+use std::{fs::File, io};
 
 /// A wrapper trait for an [AsyncRead]/[AsyncWrite] tokio type with additional methods.
 pub trait AsyncIo: io::Read + io::Write + Unpin {
@@ -44,6 +44,22 @@ pub trait AsyncIo: io::Read + io::Write + Unpin {
     /// # Why:
     /// tokio's network Stream types do not expose other api for shutdown besides [AsyncWrite::poll_shutdown].
     fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// try to write up to count bytes of given file starting from offset into Self without copying
+    /// them through user space. offset is advanced by the number of bytes written on success.
+    ///
+    /// # Why:
+    /// plain socket types can make use of os level zero copy file transfer(sendfile etc) while IO
+    /// types like tls stream can not.
+    ///
+    /// # Errors:
+    /// Default impl returns [io::ErrorKind::Unsupported] error and caller should fall back to
+    /// regular read and write. [io::ErrorKind::WouldBlock] error is returned when Self is not ready
+    /// for write.
+    fn try_send_file(&mut self, file: &File, offset: &mut u64, count: u64) -> io::Result<usize> {
+        let _ = (file, offset, count);
+        Err(io::ErrorKind::Unsupported.into())
+    }
 }
 
 /// object safe version of [AsyncIo] trait.
@@ -55,6 +71,8 @@ pub trait AsyncIoDyn: io::Read + io::Write + Unpin {
     fn is_vectored_write(&self) -> bool;
 
     fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    fn try_send_file(&mut self, file: &File, offset: &mut u64, count: u64) -> io::Result<usize>;
 }
 
 impl<Io> AsyncIoDyn for Io
@@ -78,6 +96,10 @@ where
     fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         AsyncIo::poll_shutdown(self, cx)
     }
+
+    fn try_send_file(&mut self, file: &File, offset: &mut u64, count: u64) -> io::Result<usize> {
+        AsyncIo::try_send_file(self, file, offset, count)
+    }
 }
 
 impl<IoDyn> AsyncIo for Box<IoDyn>
@@ -101,6 +123,10 @@ where
     fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         AsyncIoDyn::poll_shutdown(Pin::new(&mut **self.get_mut()), cx)
     }
+
+    fn try_send_file(&mut self, file: &File, offset: &mut u64, count: u64) -> io::Result<usize> {
+        AsyncIoDyn::try_send_file(&mut **self, file, offset, count)
+    }
 }
 
 fn _assert_object_safe(mut io: Box<dyn AsyncIoDyn>) {
@@ -19,6 +19,19 @@ pub use unix::{UnixListener, UnixStream};
 
 use core::net::SocketAddr;
 
+// zero copy transfer of file content to socket with sendfile syscall.
+#[cfg(target_os = "linux")]
+fn sendfile(
+    fd: std::os::fd::BorrowedFd<'_>,
+    file: &std::fs::File,
+    offset: &mut u64,
+    count: u64,
+) -> std::io::Result<usize> {
+    // linux transfers at most 0x7ffff000 bytes in one call.
+    let count = count.min(0x7fff_f000) as usize;
+    rustix::fs::sendfile(fd, file, Some(offset), count).map_err(Into::into)
+}
+
 macro_rules! default_aio_impl {
     ($ty: ty) => {
         impl crate::io::AsyncIo for $ty {
@@ -43,6 +56,19 @@ macro_rules! default_aio_impl {
                 crate::io::AsyncWrite::is_write_vectored(&self.0)
             }
 
+            #[cfg(target_os = "linux")]
+            fn try_send_file(
+                &mut self,
+                file: &::std::fs::File,
+                offset: &mut u64,
+                count: u64,
+            ) -> ::std::io::Result<usize> {
+                let fd = ::std::os::fd::AsFd::as_fd(&self.0);
+                self.0.try_io(crate::io::Interest::WRITABLE, || {
+                    crate::net::sendfile(fd, file, offset, count)
+                })
+            }
+
             fn poll_shutdown(
                 self: ::core::pin::Pin<&mut Self>,
                 cx: &mut ::core::task::Context<'_>,
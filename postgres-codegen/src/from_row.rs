@@ -0,0 +1,107 @@
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, LitStr};
+
+pub(crate) fn derive(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let Data::Struct(data) = input.data else {
+        return Err(Error::new(input.span(), "FromRow can only be derived for struct"));
+    };
+
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let attr = RowAttr::parse(&field.attrs)?;
+            let ty = &field.ty;
+
+            let value = if attr.flatten {
+                quote_spanned! { ty.span() =>
+                    <#ty as ::xitca_postgres::row::FromRow>::from_row(row)?
+                }
+            } else {
+                let col = match (attr.rename, field.ident.as_ref()) {
+                    (Some(name), _) => quote! { #name },
+                    (None, Some(ident)) => {
+                        let name = ident.to_string();
+                        let name = name.strip_prefix("r#").unwrap_or(&name);
+                        quote! { #name }
+                    }
+                    (None, None) => quote! { #idx },
+                };
+
+                if attr.default {
+                    quote_spanned! { ty.span() =>
+                        match row.try_get::<#ty>(#col) {
+                            Ok(value) => value,
+                            Err(e) if e.downcast_ref::<::xitca_postgres::error::InvalidColumnIndex>().is_some() => {
+                                ::core::default::Default::default()
+                            }
+                            Err(e) => return Err(e),
+                        }
+                    }
+                } else {
+                    quote_spanned! { ty.span() => row.try_get::<#ty>(#col)? }
+                }
+            };
+
+            Ok(match field.ident {
+                Some(ref ident) => quote! { #ident: #value },
+                None => value,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let body = match data.fields {
+        Fields::Named(_) => quote! { Self { #(#fields),* } },
+        Fields::Unnamed(_) => quote! { Self ( #(#fields),* ) },
+        Fields::Unit => quote! { Self },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::xitca_postgres::row::FromRow for #ident #ty_generics #where_clause {
+            fn from_row(row: &::xitca_postgres::row::Row<'_>) -> ::core::result::Result<Self, ::xitca_postgres::Error> {
+                ::core::result::Result::Ok(#body)
+            }
+        }
+    })
+}
+
+#[derive(Default)]
+struct RowAttr {
+    rename: Option<LitStr>,
+    flatten: bool,
+    default: bool,
+}
+
+impl RowAttr {
+    fn parse(attrs: &[syn::Attribute]) -> Result<Self, Error> {
+        let mut this = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("row")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    this.rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("flatten") {
+                    this.flatten = true;
+                } else if meta.path.is_ident("default") {
+                    this.default = true;
+                } else {
+                    return Err(meta.error("expect one of rename, flatten or default attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if this.flatten && (this.rename.is_some() || this.default) {
+            return Err(Error::new(
+                attrs[0].span(),
+                "flatten attribute can not be used together with rename or default",
+            ));
+        }
+
+        Ok(this)
+    }
+}
@@ -1,5 +1,12 @@
-//! this is an example macro for xitca-postgres
-//! it doesn't have any usability beyond as tutorial material.
+//! proc macros for xitca-postgres
+//! - `FromRow` derive macro for converting row to struct.
+//! - `PgType` derive macro for mapping enum and struct to postgres enum and composite type.
+//! - `query` macro for sql statement checked against database schema at compile time.
+//! - `sql` is an example macro. it doesn't have any usability beyond as tutorial material.
+
+mod from_row;
+mod pg_type;
+mod query;
 
 use quote::quote;
 use sqlparser::{dialect::PostgreSqlDialect, parser::Parser};
@@ -10,6 +17,47 @@ use syn::{
     Expr, ExprReference, Lit, LitStr,
 };
 
+#[proc_macro_derive(FromRow, attributes(row))]
+pub fn from_row(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    from_row::derive(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+#[proc_macro_derive(PgType, attributes(pg_type))]
+pub fn pg_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    pg_type::derive(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+/// sql statement checked against database schema at compile time.
+///
+/// The macro works in offline mode where schema is read from a sql file containing `CREATE TABLE` statements.
+/// Output of `pg_dump --schema-only` can be used as is. The file is located at `schema.sql` in the crate root by
+/// default and can be changed with `XITCA_POSTGRES_SCHEMA` environment variable. Statement is checked for
+/// existence of tables and columns. Types of parameters and output columns are inferred from schema and expression.
+///
+/// The macro expands to `xitca_postgres::statement::StatementTyped` where output rows are decoded into a generated
+/// struct with fields named after columns. Nullable column is mapped to `Option<T>`.
+///
+/// Column type and nullability inference can be overridden with alias:
+/// - `AS "name!"` forces column to be non null.
+/// - `AS "name?"` forces column to be nullable.
+/// - `AS "name: Type"` decodes column to given Rust type. it's needed for types without default mapping like `uuid`
+///   and user defined types.
+///
+/// # Examples
+/// ```ignore
+/// let user = xitca_postgres::query!("SELECT id, name AS \"name?\" FROM users WHERE id = $1", &id)
+///     .query_one(&client)
+///     .await?;
+/// let _: (i32, Option<String>) = (user.id, user.name);
+/// ```
+#[proc_macro]
+pub fn query(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as query::QueryInput);
+    query::expand(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
 #[proc_macro]
 pub fn sql(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let Query { sql, exprs, types } = syn::parse_macro_input!(input as Query);
@@ -0,0 +1,256 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields, LitStr};
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "PgType can not be derived for generic type",
+        ));
+    }
+
+    let name = PgTypeAttr::parse(&input.attrs, "name")?
+        .unwrap_or_else(|| LitStr::new(&input.ident.to_string(), input.ident.span()));
+
+    let ident = &input.ident;
+
+    let (from_sql, to_sql, from_accepts, to_accepts) = match input.data {
+        Data::Enum(ref data) => {
+            if data.variants.is_empty() {
+                return Err(Error::new(
+                    input.span(),
+                    "PgType can not be derived for enum without variant",
+                ));
+            }
+
+            let mut variants = Vec::new();
+            let mut names = Vec::new();
+
+            for variant in data.variants.iter() {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new(
+                        variant.span(),
+                        "PgType can only be derived for enum with unit variants",
+                    ));
+                }
+                let name = PgTypeAttr::parse(&variant.attrs, "rename")?
+                    .unwrap_or_else(|| LitStr::new(&variant.ident.to_string(), variant.ident.span()));
+                variants.push(&variant.ident);
+                names.push(name);
+            }
+
+            let from_sql = quote! {
+                match ::core::str::from_utf8(raw)? {
+                    #(#names => ::core::result::Result::Ok(Self::#variants),)*
+                    s => ::core::result::Result::Err(::std::format!("invalid variant `{}`", s).into()),
+                }
+            };
+
+            let to_sql = quote! {
+                let s = match *self {
+                    #(Self::#variants => #names,)*
+                };
+                buf.extend_from_slice(s.as_bytes());
+                ::core::result::Result::Ok(::xitca_postgres::types::IsNull::No)
+            };
+
+            let len = names.len();
+            let accepts = quote! {
+                if ty.name() != #name {
+                    return false;
+                }
+                match *ty.kind() {
+                    ::xitca_postgres::types::Kind::Enum(ref variants) => {
+                        variants.len() == #len && variants.iter().all(|v| ::core::matches!(v.as_str(), #(#names)|*))
+                    }
+                    _ => false,
+                }
+            };
+
+            (from_sql, to_sql, accepts.clone(), accepts)
+        }
+        Data::Struct(ref data) => {
+            let Fields::Named(ref fields) = data.fields else {
+                return Err(Error::new(
+                    data.fields.span(),
+                    "PgType can only be derived for struct with named fields",
+                ));
+            };
+
+            let mut idents = Vec::new();
+            let mut locals = Vec::new();
+            let mut names = Vec::new();
+            let mut tys = Vec::new();
+
+            for field in fields.named.iter() {
+                let ident = field.ident.as_ref().unwrap();
+                let name = PgTypeAttr::parse(&field.attrs, "rename")?.unwrap_or_else(|| {
+                    let name = ident.to_string();
+                    let name = name.strip_prefix("r#").unwrap_or(&name);
+                    LitStr::new(name, ident.span())
+                });
+                locals.push(format_ident!("__field_{}", idents.len()));
+                idents.push(ident);
+                names.push(name);
+                tys.push(&field.ty);
+            }
+
+            let len = names.len();
+
+            let fields = quote! {
+                let fields = match *ty.kind() {
+                    ::xitca_postgres::types::Kind::Composite(ref fields) => fields,
+                    _ => ::core::unreachable!(),
+                };
+            };
+
+            let from_sql = quote! {
+                #fields
+                let mut buf = raw;
+                let num_fields = ::xitca_postgres::types::private::read_be_i32(&mut buf)?;
+                if num_fields as usize != fields.len() {
+                    return ::core::result::Result::Err(
+                        ::std::format!("invalid field count: {} vs {}", num_fields, fields.len()).into()
+                    );
+                }
+
+                #(let mut #locals = ::core::option::Option::None;)*
+
+                for field in fields {
+                    let oid = ::xitca_postgres::types::private::read_be_i32(&mut buf)? as u32;
+                    if oid != field.type_().oid() {
+                        return ::core::result::Result::Err("unexpected field type oid".into());
+                    }
+                    match field.name() {
+                        #(#names => {
+                            #locals = ::core::option::Option::Some(
+                                ::xitca_postgres::types::private::read_value(field.type_(), &mut buf)?
+                            );
+                        })*
+                        _ => ::core::unreachable!(),
+                    }
+                }
+
+                ::core::result::Result::Ok(Self {
+                    #(#idents: #locals.unwrap(),)*
+                })
+            };
+
+            let to_sql = quote! {
+                #fields
+                buf.extend_from_slice(&(fields.len() as i32).to_be_bytes());
+
+                for field in fields {
+                    buf.extend_from_slice(&field.type_().oid().to_be_bytes());
+
+                    let base = buf.len();
+                    buf.extend_from_slice(&[0; 4]);
+                    let is_null = match field.name() {
+                        #(#names => ::xitca_postgres::types::ToSql::to_sql(&self.#idents, field.type_(), buf)?,)*
+                        _ => ::core::unreachable!(),
+                    };
+
+                    let count = match is_null {
+                        ::xitca_postgres::types::IsNull::Yes => -1,
+                        ::xitca_postgres::types::IsNull::No => {
+                            let len = buf.len() - base - 4;
+                            if len > i32::MAX as usize {
+                                return ::core::result::Result::Err("value too large to transmit".into());
+                            }
+                            len as i32
+                        }
+                    };
+
+                    buf[base..base + 4].copy_from_slice(&count.to_be_bytes());
+                }
+
+                ::core::result::Result::Ok(::xitca_postgres::types::IsNull::No)
+            };
+
+            let accepts = |tr: TokenStream| {
+                quote! {
+                    if ty.name() != #name {
+                        return false;
+                    }
+                    match *ty.kind() {
+                        ::xitca_postgres::types::Kind::Composite(ref fields) => {
+                            fields.len() == #len
+                                && fields.iter().all(|f| match f.name() {
+                                    #(#names => <#tys as #tr>::accepts(f.type_()),)*
+                                    _ => false,
+                                })
+                        }
+                        _ => false,
+                    }
+                }
+            };
+
+            (
+                from_sql,
+                to_sql,
+                accepts(quote! { ::xitca_postgres::types::FromSql<'__a> }),
+                accepts(quote! { ::xitca_postgres::types::ToSql }),
+            )
+        }
+        Data::Union(_) => return Err(Error::new(input.span(), "PgType can not be derived for union")),
+    };
+
+    Ok(quote! {
+        impl<'__a> ::xitca_postgres::types::FromSql<'__a> for #ident {
+            fn from_sql(
+                ty: &::xitca_postgres::types::Type,
+                raw: &'__a [u8],
+            ) -> ::core::result::Result<Self, ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>> {
+                let _ = ty;
+                #from_sql
+            }
+
+            fn accepts(ty: &::xitca_postgres::types::Type) -> bool {
+                #from_accepts
+            }
+        }
+
+        impl ::xitca_postgres::types::ToSql for #ident {
+            fn to_sql(
+                &self,
+                ty: &::xitca_postgres::types::Type,
+                buf: &mut ::xitca_postgres::types::private::BytesMut,
+            ) -> ::core::result::Result<
+                ::xitca_postgres::types::IsNull,
+                ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>,
+            > {
+                let _ = ty;
+                #to_sql
+            }
+
+            fn accepts(ty: &::xitca_postgres::types::Type) -> bool {
+                #to_accepts
+            }
+
+            ::xitca_postgres::types::to_sql_checked!();
+        }
+    })
+}
+
+struct PgTypeAttr;
+
+impl PgTypeAttr {
+    // parse #[pg_type(<key> = "..")] attribute. other keys are rejected.
+    fn parse(attrs: &[Attribute], key: &str) -> Result<Option<LitStr>, Error> {
+        let mut value = None;
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("pg_type")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident(key) {
+                    value = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error(format!("expect {key} attribute")))
+                }
+            })?;
+        }
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,266 @@
+mod analyze;
+mod schema;
+
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use sqlparser::{dialect::PostgreSqlDialect, parser::Parser};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Error, Expr, Ident, LitStr, Token, Type,
+};
+
+use self::{
+    analyze::{Analyzed, Output},
+    schema::Schema,
+};
+
+// environment variable for path of schema file. path is relative to the manifest directory of crate using the macro.
+const SCHEMA_ENV: &str = "XITCA_POSTGRES_SCHEMA";
+const SCHEMA_DEFAULT: &str = "schema.sql";
+
+pub(crate) struct QueryInput {
+    sql: LitStr,
+    params: Vec<Expr>,
+}
+
+impl Parse for QueryInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let sql = input.parse::<LitStr>()?;
+        let params = match input.parse::<Option<Token![,]>>()? {
+            Some(_) => Punctuated::<Expr, Token![,]>::parse_terminated(input)?
+                .into_iter()
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(Self { sql, params })
+    }
+}
+
+pub(crate) fn expand(input: QueryInput) -> Result<TokenStream, Error> {
+    let QueryInput { sql, params } = input;
+    let span = sql.span();
+
+    let (path, schema) = load_schema().map_err(|e| Error::new(span, e))?;
+
+    let mut stmts =
+        Parser::parse_sql(&PostgreSqlDialect {}, &sql.value()).map_err(|e| Error::new(span, e.to_string()))?;
+    if stmts.len() != 1 {
+        return Err(Error::new(span, "expect exactly one sql statement"));
+    }
+    let stmt = stmts.pop().unwrap();
+
+    let Analyzed { params: types, columns } = analyze::analyze(&schema, &stmt).map_err(|e| Error::new(span, e))?;
+
+    if types.len() != params.len() {
+        return Err(Error::new(
+            span,
+            format!("expect {} parameters but got {}", types.len(), params.len()),
+        ));
+    }
+
+    // parameter type check. a nullable parameter is accepted in the form of Option<T>.
+    let helpers = if types.iter().any(|ty| ty.borrowed_type().is_some()) {
+        quote! {
+            struct __Param<'a, B: ?Sized, T>(&'a T, ::core::marker::PhantomData<B>);
+
+            trait __Nullable<'a> {
+                fn param(&self) -> &'a (dyn ::xitca_postgres::types::ToSql + Sync);
+            }
+
+            impl<'a, B, T> __Nullable<'a> for __Param<'a, B, ::core::option::Option<T>>
+            where
+                B: ?Sized,
+                T: ::core::borrow::Borrow<B> + ::xitca_postgres::types::ToSql + Sync,
+            {
+                fn param(&self) -> &'a (dyn ::xitca_postgres::types::ToSql + Sync) {
+                    self.0
+                }
+            }
+
+            trait __NotNull<'a> {
+                fn param(&self) -> &'a (dyn ::xitca_postgres::types::ToSql + Sync);
+            }
+
+            impl<'a, B, T> __NotNull<'a> for &__Param<'a, B, T>
+            where
+                B: ?Sized,
+                T: ::core::borrow::Borrow<B> + ::xitca_postgres::types::ToSql + Sync,
+            {
+                fn param(&self) -> &'a (dyn ::xitca_postgres::types::ToSql + Sync) {
+                    self.0
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let len = params.len();
+    let args = types
+        .iter()
+        .zip(params.iter())
+        .map(|(ty, param)| match ty.borrowed_type() {
+            Some(borrowed) => quote_spanned! { param.span() =>
+                (&__Param::<#borrowed, _>(#param, ::core::marker::PhantomData)).param()
+            },
+            None => quote_spanned! { param.span() =>
+                #param as &(dyn ::xitca_postgres::types::ToSql + Sync)
+            },
+        });
+    let types = types.iter().map(|ty| ty.type_expr());
+
+    let fields = columns
+        .into_iter()
+        .map(|col| Field::try_from_output(col, span))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (idx, field) in fields.iter().enumerate() {
+        if fields[..idx].iter().any(|f| f.ident == field.ident) {
+            return Err(Error::new(
+                span,
+                format!("duplicate column name `{}`. rename it with `AS` alias", field.ident),
+            ));
+        }
+    }
+
+    let idents = fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+    let tys = fields.iter().map(|f| &f.ty);
+    let idx = 0..idents.len();
+
+    let path = path.to_string_lossy();
+
+    Ok(quote! {
+        {
+            // rebuild when schema file changes.
+            const _: &[u8] = include_bytes!(#path);
+
+            const TYPES: &[::xitca_postgres::types::Type] = &[#(#types),*];
+
+            #[allow(dead_code)]
+            struct Record {
+                #(pub #idents: #tys,)*
+            }
+
+            impl ::xitca_postgres::row::FromRow for Record {
+                fn from_row(
+                    row: &::xitca_postgres::row::Row<'_>
+                ) -> ::core::result::Result<Self, ::xitca_postgres::Error> {
+                    let _ = row;
+                    ::core::result::Result::Ok(Self {
+                        #(#idents: row.try_get(#idx)?,)*
+                    })
+                }
+            }
+
+            #helpers
+
+            ::xitca_postgres::statement::StatementTyped::<_, Record>::new(
+                ::xitca_postgres::statement::Statement::unnamed(#sql, TYPES)
+                    .bind::<[&(dyn ::xitca_postgres::types::ToSql + Sync); #len]>([#(#args),*])
+            )
+        }
+    })
+}
+
+fn load_schema() -> Result<(PathBuf, Schema), String> {
+    let mut path = PathBuf::from(env::var(SCHEMA_ENV).unwrap_or_else(|_| SCHEMA_DEFAULT.into()));
+    if path.is_relative() {
+        let dir = env::var("CARGO_MANIFEST_DIR").map_err(|_| "CARGO_MANIFEST_DIR is not set".to_string())?;
+        path = PathBuf::from(dir).join(path);
+    }
+
+    let sql = fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "failed to read schema file {}: {e}. generate one with `pg_dump --schema-only` or set {SCHEMA_ENV} to it's path",
+            path.display()
+        )
+    })?;
+
+    let schema = Schema::parse(&sql).map_err(|e| format!("failed to parse schema file {}: {e}", path.display()))?;
+
+    Ok((path, schema))
+}
+
+struct Field {
+    ident: Ident,
+    ty: TokenStream,
+}
+
+impl Field {
+    // column name can carry override in the form of `"name!"`, `"name?"` and `"name: Type"`.
+    fn try_from_output(col: Output, span: Span) -> Result<Self, Error> {
+        let (name, ty) = match col.name.split_once(':') {
+            Some((name, ty)) => {
+                let ty = syn::parse_str::<Type>(ty.trim())
+                    .map_err(|e| Error::new(span, format!("invalid type override of column `{name}`: {e}")))?;
+                (name.trim(), Some(quote! { #ty }))
+            }
+            None => (col.name.as_str(), None),
+        };
+
+        let (name, nullable) = if let Some(name) = name.strip_suffix('!') {
+            (name, false)
+        } else if let Some(name) = name.strip_suffix('?') {
+            (name, true)
+        } else {
+            (name, col.typed.nullable)
+        };
+
+        let ident = field_ident(name, span)?;
+
+        let ty = match ty {
+            Some(ty) => ty,
+            None => match col.typed.ty {
+                Some(ref ty) => ty.rust_type().ok_or_else(|| {
+                    Error::new(
+                        span,
+                        format!(
+                            "column `{name}` of type `{}` has no default Rust type. specify one with alias like `AS \"{name}: Type\"`",
+                            ty.name()
+                        ),
+                    )
+                })?,
+                None => {
+                    return Err(Error::new(
+                        span,
+                        format!(
+                            "can not infer type of column `{name}`. add an explicit cast like `::int4` or specify Rust type with alias like `AS \"{name}: Type\"`"
+                        ),
+                    ))
+                }
+            },
+        };
+
+        let ty = if nullable {
+            quote! { ::core::option::Option<#ty> }
+        } else {
+            ty
+        };
+
+        Ok(Self { ident, ty })
+    }
+}
+
+fn field_ident(name: &str, span: Span) -> Result<Ident, Error> {
+    let valid = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+    if valid {
+        if let Ok(mut ident) = syn::parse_str::<Ident>(name) {
+            ident.set_span(span);
+            return Ok(ident);
+        }
+        if !matches!(name, "_" | "self" | "Self" | "super" | "crate") {
+            return Ok(Ident::new_raw(name, span));
+        }
+    }
+
+    Err(Error::new(
+        span,
+        format!("column name `{name}` can not be used as field name. rename it with `AS` alias"),
+    ))
+}
@@ -0,0 +1,1051 @@
+use core::slice;
+
+use sqlparser::ast::{
+    Assignment, AssignmentTarget, BinaryOperator, Delete, Expr, FromTable, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments, GroupByExpr, Ident, Insert, JoinConstraint, JoinOperator, ObjectName, OnConflictAction,
+    OnInsert, OrderBy, Query, Select, SelectItem, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
+    UnaryOperator, Value,
+};
+
+use super::schema::{ident, Column, PgType, Schema, Table};
+
+/// type of expression. type is unknown for literal and expression the analyzer can not reason about.
+#[derive(Clone)]
+pub(crate) struct Typed {
+    pub(crate) ty: Option<PgType>,
+    pub(crate) nullable: bool,
+}
+
+impl Typed {
+    const UNKNOWN: Self = Self {
+        ty: None,
+        nullable: true,
+    };
+
+    fn new(name: &str, nullable: bool) -> Self {
+        Self {
+            ty: Some(PgType::named(name)),
+            nullable,
+        }
+    }
+}
+
+/// column produced by statement.
+pub(crate) struct Output {
+    pub(crate) name: String,
+    pub(crate) typed: Typed,
+}
+
+pub(crate) struct Analyzed {
+    pub(crate) params: Vec<PgType>,
+    pub(crate) columns: Vec<Output>,
+}
+
+/// check statement against schema and infer types of it's parameters and output columns.
+pub(crate) fn analyze(schema: &Schema, stmt: &Statement) -> Result<Analyzed, String> {
+    let mut analyzer = Analyzer {
+        schema,
+        ctes: Vec::new(),
+        scopes: Vec::new(),
+        params: Vec::new(),
+    };
+
+    let columns = match stmt {
+        Statement::Query(query) => analyzer.query(query)?,
+        Statement::Insert(insert) => analyzer.insert(insert)?,
+        Statement::Update {
+            table,
+            assignments,
+            from,
+            selection,
+            returning,
+        } => analyzer.update(
+            table,
+            assignments,
+            from.as_ref(),
+            selection.as_ref(),
+            returning.as_deref(),
+        )?,
+        Statement::Delete(delete) => analyzer.delete(delete)?,
+        _ => return Err("only SELECT, INSERT, UPDATE and DELETE statement can be checked".into()),
+    };
+
+    let params = analyzer
+        .params
+        .into_iter()
+        .enumerate()
+        .map(|(idx, ty)| {
+            let n = idx + 1;
+            match ty {
+                Some(PgType::Custom(name)) => Err(format!(
+                    "type `{name}` of parameter ${n} is not known at compile time. cast it to a builtin type"
+                )),
+                Some(ty) => Ok(ty),
+                None => Err(format!(
+                    "can not infer type of parameter ${n}. add an explicit cast like `${n}::int4`"
+                )),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Analyzed { params, columns })
+}
+
+struct Relation {
+    name: String,
+    table: Table,
+    nullable: bool,
+}
+
+struct Analyzer<'a> {
+    schema: &'a Schema,
+    ctes: Vec<(String, Table)>,
+    // relations visible to expressions. inner most scope is the last one.
+    scopes: Vec<Vec<Relation>>,
+    params: Vec<Option<PgType>>,
+}
+
+impl Analyzer<'_> {
+    fn query(&mut self, query: &Query) -> Result<Vec<Output>, String> {
+        let ctes = self.ctes.len();
+
+        if let Some(ref with) = query.with {
+            for cte in with.cte_tables.iter() {
+                let columns = self.query(&cte.query)?;
+                let table = table_from_outputs(columns, Some(&cte.alias));
+                self.ctes.push((ident(&cte.alias.name), table));
+            }
+        }
+
+        let columns = match *query.body {
+            SetExpr::Select(ref select) => self.select(select, query.order_by.as_ref())?,
+            ref body => self.set_expr(body)?,
+        };
+
+        if let Some(ref limit) = query.limit {
+            self.expr(limit, Some(&PgType::named("int8")))?;
+        }
+
+        if let Some(ref offset) = query.offset {
+            self.expr(&offset.value, Some(&PgType::named("int8")))?;
+        }
+
+        self.ctes.truncate(ctes);
+
+        Ok(columns)
+    }
+
+    fn set_expr(&mut self, set: &SetExpr) -> Result<Vec<Output>, String> {
+        match set {
+            SetExpr::Select(select) => self.select(select, None),
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                let mut left = self.set_expr(left)?;
+                let right = self.set_expr(right)?;
+                if left.len() != right.len() {
+                    return Err("each side of set operation must have the same number of columns".into());
+                }
+                for (left, right) in left.iter_mut().zip(right) {
+                    left.typed.nullable |= right.typed.nullable;
+                    if left.typed.ty.is_none() {
+                        left.typed.ty = right.typed.ty;
+                    }
+                }
+                Ok(left)
+            }
+            SetExpr::Values(values) => {
+                let mut columns = Vec::<Output>::new();
+                for row in values.rows.iter() {
+                    for (idx, expr) in row.iter().enumerate() {
+                        let hint = columns.get(idx).and_then(|col| col.typed.ty.clone());
+                        let typed = self.expr(expr, hint.as_ref())?;
+                        match columns.get_mut(idx) {
+                            Some(col) => {
+                                col.typed.nullable |= typed.nullable;
+                                if col.typed.ty.is_none() {
+                                    col.typed.ty = typed.ty;
+                                }
+                            }
+                            None => columns.push(Output {
+                                name: format!("column{}", idx + 1),
+                                typed,
+                            }),
+                        }
+                    }
+                }
+                Ok(columns)
+            }
+            set => Err(format!("unsupported query `{set}`")),
+        }
+    }
+
+    fn select(&mut self, select: &Select, order_by: Option<&OrderBy>) -> Result<Vec<Output>, String> {
+        self.scopes.push(Vec::new());
+
+        for table in select.from.iter() {
+            self.table_with_joins(table)?;
+        }
+
+        if let Some(ref selection) = select.selection {
+            self.expr(selection, Some(&PgType::named("bool")))?;
+        }
+
+        if let GroupByExpr::Expressions(ref exprs, _) = select.group_by {
+            for expr in exprs {
+                self.expr(expr, None)?;
+            }
+        }
+
+        if let Some(ref having) = select.having {
+            self.expr(having, Some(&PgType::named("bool")))?;
+        }
+
+        let columns = self.projection(&select.projection)?;
+
+        for order in order_by.into_iter().flat_map(|order_by| order_by.exprs.iter()) {
+            match order.expr {
+                // order by output column name.
+                Expr::Identifier(ref name) if columns.iter().any(|col| col.name == ident(name)) => {}
+                ref expr => {
+                    self.expr(expr, None)?;
+                }
+            }
+        }
+
+        self.scopes.pop();
+
+        Ok(columns)
+    }
+
+    fn insert(&mut self, insert: &Insert) -> Result<Vec<Output>, String> {
+        let table = self.table(&insert.table_name)?;
+
+        let columns = if insert.columns.is_empty() {
+            table.columns.clone()
+        } else {
+            insert
+                .columns
+                .iter()
+                .map(|name| find_column(&table, name, &insert.table_name).cloned())
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        if let Some(ref source) = insert.source {
+            match *source.body {
+                SetExpr::Values(ref values) => {
+                    for row in values.rows.iter() {
+                        if row.len() > columns.len() {
+                            return Err("INSERT has more expressions than target columns".into());
+                        }
+                        for (expr, col) in row.iter().zip(columns.iter()) {
+                            match expr {
+                                Expr::Identifier(i)
+                                    if i.quote_style.is_none() && i.value.eq_ignore_ascii_case("default") => {}
+                                expr => {
+                                    self.expr(expr, Some(&col.ty))?;
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    if self.query(source)?.len() > columns.len() {
+                        return Err("INSERT has more expressions than target columns".into());
+                    }
+                }
+            }
+        }
+
+        let name = match insert.table_alias {
+            Some(ref alias) => ident(alias),
+            None => relation_name(&insert.table_name),
+        };
+
+        self.scopes.push(vec![Relation {
+            name,
+            table: table.clone(),
+            nullable: false,
+        }]);
+
+        if let Some(OnInsert::OnConflict(ref conflict)) = insert.on {
+            if let OnConflictAction::DoUpdate(ref update) = conflict.action {
+                self.push_relation(Relation {
+                    name: "excluded".into(),
+                    table: table.clone(),
+                    nullable: false,
+                });
+                self.assignments(&table, &insert.table_name, &update.assignments)?;
+                if let Some(ref selection) = update.selection {
+                    self.expr(selection, Some(&PgType::named("bool")))?;
+                }
+            }
+        }
+
+        let columns = self.returning(insert.returning.as_deref())?;
+
+        self.scopes.pop();
+
+        Ok(columns)
+    }
+
+    fn update(
+        &mut self,
+        table: &TableWithJoins,
+        assignments: &[Assignment],
+        from: Option<&TableWithJoins>,
+        selection: Option<&Expr>,
+        returning: Option<&[SelectItem]>,
+    ) -> Result<Vec<Output>, String> {
+        let TableFactor::Table { ref name, .. } = table.relation else {
+            return Err(format!("unsupported UPDATE target `{table}`"));
+        };
+
+        self.scopes.push(Vec::new());
+        self.table_with_joins(table)?;
+        if let Some(from) = from {
+            self.table_with_joins(from)?;
+        }
+
+        let target = self.table(name)?;
+        self.assignments(&target, name, assignments)?;
+
+        if let Some(selection) = selection {
+            self.expr(selection, Some(&PgType::named("bool")))?;
+        }
+
+        let columns = self.returning(returning)?;
+
+        self.scopes.pop();
+
+        Ok(columns)
+    }
+
+    fn delete(&mut self, delete: &Delete) -> Result<Vec<Output>, String> {
+        let (FromTable::WithFromKeyword(ref tables) | FromTable::WithoutKeyword(ref tables)) = delete.from;
+
+        self.scopes.push(Vec::new());
+        for table in tables.iter().chain(delete.using.iter().flatten()) {
+            self.table_with_joins(table)?;
+        }
+
+        if let Some(ref selection) = delete.selection {
+            self.expr(selection, Some(&PgType::named("bool")))?;
+        }
+
+        let columns = self.returning(delete.returning.as_deref())?;
+
+        self.scopes.pop();
+
+        Ok(columns)
+    }
+
+    fn assignments(&mut self, table: &Table, name: &ObjectName, assignments: &[Assignment]) -> Result<(), String> {
+        for assignment in assignments {
+            let hint = match assignment.target {
+                AssignmentTarget::ColumnName(ref col) => {
+                    let col = col.0.last().ok_or("empty column name")?;
+                    Some(find_column(table, col, name)?.ty.clone())
+                }
+                AssignmentTarget::Tuple(_) => None,
+            };
+            self.expr(&assignment.value, hint.as_ref())?;
+        }
+        Ok(())
+    }
+
+    fn returning(&mut self, returning: Option<&[SelectItem]>) -> Result<Vec<Output>, String> {
+        match returning {
+            Some(items) => self.projection(items),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn projection(&mut self, items: &[SelectItem]) -> Result<Vec<Output>, String> {
+        let mut columns = Vec::new();
+
+        for item in items {
+            match item {
+                SelectItem::UnnamedExpr(expr) => columns.push(Output {
+                    name: column_name(expr),
+                    typed: self.expr(expr, None)?,
+                }),
+                SelectItem::ExprWithAlias { expr, alias } => columns.push(Output {
+                    name: ident(alias),
+                    typed: self.expr(expr, None)?,
+                }),
+                SelectItem::Wildcard(_) => {
+                    for rel in self.scopes.last().into_iter().flatten() {
+                        columns.extend(relation_outputs(rel));
+                    }
+                }
+                SelectItem::QualifiedWildcard(name, _) => {
+                    let name = relation_name(name);
+                    let rel = self
+                        .scopes
+                        .iter()
+                        .rev()
+                        .flatten()
+                        .find(|rel| rel.name == name)
+                        .ok_or_else(|| format!("missing FROM-clause entry for table `{name}`"))?;
+                    columns.extend(relation_outputs(rel));
+                }
+            }
+        }
+
+        Ok(columns)
+    }
+
+    fn table_with_joins(&mut self, table: &TableWithJoins) -> Result<(), String> {
+        let start = self.scopes.last().map(Vec::len).unwrap_or(0);
+
+        self.table_factor(&table.relation, false)?;
+
+        for join in table.joins.iter() {
+            let (left_nullable, right_nullable, constraint) = match join.join_operator {
+                JoinOperator::Inner(ref c) => (false, false, Some(c)),
+                JoinOperator::LeftOuter(ref c) => (false, true, Some(c)),
+                JoinOperator::RightOuter(ref c) => (true, false, Some(c)),
+                JoinOperator::FullOuter(ref c) => (true, true, Some(c)),
+                JoinOperator::CrossJoin => (false, false, None),
+                _ => return Err(format!("unsupported join `{join}`")),
+            };
+
+            if left_nullable {
+                for rel in self.scopes.last_mut().into_iter().flatten().skip(start) {
+                    rel.nullable = true;
+                }
+            }
+
+            self.table_factor(&join.relation, right_nullable)?;
+
+            if let Some(JoinConstraint::On(expr)) = constraint {
+                self.expr(expr, Some(&PgType::named("bool")))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn table_factor(&mut self, factor: &TableFactor, nullable: bool) -> Result<(), String> {
+        match factor {
+            TableFactor::Table {
+                name,
+                alias,
+                args: None,
+                ..
+            } => {
+                let mut table = self.table(name)?;
+                let name = match alias {
+                    Some(alias) => {
+                        rename_columns(&mut table, alias);
+                        ident(&alias.name)
+                    }
+                    None => relation_name(name),
+                };
+                self.push_relation(Relation { name, table, nullable });
+            }
+            TableFactor::Derived { subquery, alias, .. } => {
+                let columns = self.query(subquery)?;
+                let name = alias.as_ref().map(|alias| ident(&alias.name)).unwrap_or_default();
+                let table = table_from_outputs(columns, alias.as_ref());
+                self.push_relation(Relation { name, table, nullable });
+            }
+            TableFactor::NestedJoin { table_with_joins, .. } => self.table_with_joins(table_with_joins)?,
+            factor => return Err(format!("unsupported table expression `{factor}`")),
+        }
+        Ok(())
+    }
+
+    fn push_relation(&mut self, rel: Relation) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(rel);
+        }
+    }
+
+    // look up table from common table expressions and schema.
+    fn table(&self, name: &ObjectName) -> Result<Table, String> {
+        if let [ref cte] = name.0[..] {
+            let cte = ident(cte);
+            if let Some((_, table)) = self.ctes.iter().rev().find(|(name, _)| *name == cte) {
+                return Ok(table.clone());
+            }
+        }
+        self.schema
+            .table(name)
+            .cloned()
+            .ok_or_else(|| format!("relation `{name}` does not exist"))
+    }
+
+    fn column(&self, idents: &[Ident]) -> Result<Typed, String> {
+        let (rel, name) = match idents {
+            [name] => (None, ident(name)),
+            [.., rel, name] => (Some(ident(rel)), ident(name)),
+            [] => return Err("empty column name".into()),
+        };
+
+        for scope in self.scopes.iter().rev() {
+            let mut found = scope
+                .iter()
+                .filter(|r| rel.as_ref().is_none_or(|rel| r.name == *rel))
+                .filter_map(|r| r.table.columns.iter().find(|c| c.name == name).map(|c| (r, c)));
+
+            match (found.next(), found.next()) {
+                (Some((rel, col)), None) => {
+                    return Ok(Typed {
+                        ty: Some(col.ty.clone()),
+                        nullable: col.nullable || rel.nullable,
+                    })
+                }
+                (Some(_), Some(_)) => return Err(format!("column reference `{name}` is ambiguous")),
+                _ => {}
+            }
+
+            if let Some(ref rel) = rel {
+                if scope.iter().any(|r| r.name == *rel) {
+                    return Err(format!("column `{rel}.{name}` does not exist"));
+                }
+            }
+        }
+
+        match rel {
+            Some(rel) => Err(format!("missing FROM-clause entry for table `{rel}`")),
+            None => Err(format!("column `{name}` does not exist")),
+        }
+    }
+
+    fn param(&mut self, placeholder: &str, hint: Option<&PgType>) -> Result<(), String> {
+        let idx = placeholder
+            .strip_prefix('$')
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .ok_or_else(|| format!("unsupported placeholder `{placeholder}`. use `$1`, `$2`, etc. instead"))?;
+
+        if self.params.len() < idx {
+            self.params.resize(idx, None);
+        }
+
+        match (&mut self.params[idx - 1], hint) {
+            (ty @ None, Some(hint)) => *ty = Some(hint.clone()),
+            (Some(ty), Some(hint)) if ty != hint => {
+                return Err(format!(
+                    "inconsistent types deduced for parameter {placeholder}: `{}` versus `{}`",
+                    ty.name(),
+                    hint.name()
+                ))
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    // infer type of expression. placeholder inside expression takes the hint as it's type.
+    fn expr(&mut self, expr: &Expr, hint: Option<&PgType>) -> Result<Typed, String> {
+        let bool = PgType::named("bool");
+
+        match expr {
+            Expr::Identifier(name) => self.column(slice::from_ref(name)),
+            Expr::CompoundIdentifier(idents) => self.column(idents),
+            Expr::Value(Value::Placeholder(p)) => {
+                self.param(p, hint)?;
+                Ok(Typed {
+                    ty: hint.cloned(),
+                    nullable: true,
+                })
+            }
+            Expr::Value(Value::Boolean(_)) => Ok(Typed::new("bool", false)),
+            Expr::Value(Value::Null) => Ok(Typed::UNKNOWN),
+            Expr::Value(_) => Ok(Typed {
+                ty: None,
+                nullable: false,
+            }),
+            Expr::Nested(expr) => self.expr(expr, hint),
+            Expr::Cast { expr, data_type, .. } => {
+                let ty = PgType::from_data_type(data_type);
+                let inner = self.expr(expr, Some(&ty))?;
+                Ok(Typed {
+                    ty: Some(ty),
+                    nullable: inner.nullable,
+                })
+            }
+            Expr::BinaryOp { left, op, right } => match op {
+                BinaryOperator::And | BinaryOperator::Or => {
+                    let left = self.expr(left, Some(&bool))?;
+                    let right = self.expr(right, Some(&bool))?;
+                    Ok(Typed::new("bool", left.nullable || right.nullable))
+                }
+                BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq => {
+                    let (left, right) = self.balance(left, right)?;
+                    Ok(Typed::new("bool", left.nullable || right.nullable))
+                }
+                BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo => {
+                    let (left, right) = self.balance(left, right)?;
+                    let ty = left.ty.or(right.ty).filter(is_numeric);
+                    Ok(Typed {
+                        ty,
+                        nullable: left.nullable || right.nullable,
+                    })
+                }
+                BinaryOperator::StringConcat => {
+                    let text = PgType::named("text");
+                    let left = self.expr(left, Some(&text))?;
+                    let right = self.expr(right, Some(&text))?;
+                    Ok(Typed::new("text", left.nullable || right.nullable))
+                }
+                _ => {
+                    self.expr(left, None)?;
+                    self.expr(right, None)?;
+                    Ok(Typed::UNKNOWN)
+                }
+            },
+            Expr::UnaryOp { op, expr } => match op {
+                UnaryOperator::Not => {
+                    let typed = self.expr(expr, Some(&bool))?;
+                    Ok(Typed::new("bool", typed.nullable))
+                }
+                UnaryOperator::Plus | UnaryOperator::Minus => self.expr(expr, hint),
+                _ => {
+                    self.expr(expr, None)?;
+                    Ok(Typed::UNKNOWN)
+                }
+            },
+            Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::IsTrue(expr)
+            | Expr::IsNotTrue(expr)
+            | Expr::IsFalse(expr)
+            | Expr::IsNotFalse(expr) => {
+                self.expr(expr, None)?;
+                Ok(Typed::new("bool", false))
+            }
+            Expr::IsDistinctFrom(left, right) | Expr::IsNotDistinctFrom(left, right) => {
+                self.balance(left, right)?;
+                Ok(Typed::new("bool", false))
+            }
+            Expr::InList { expr, list, .. } => {
+                let mut typed = self.expr(expr, None)?;
+                for item in list {
+                    let item = self.expr(item, typed.ty.as_ref())?;
+                    if typed.ty.is_none() && item.ty.is_some() {
+                        typed = self.expr(expr, item.ty.as_ref())?;
+                    }
+                }
+                Ok(Typed::new("bool", true))
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                let columns = self.query(subquery)?;
+                let hint = columns.first().and_then(|col| col.typed.ty.clone());
+                self.expr(expr, hint.as_ref())?;
+                Ok(Typed::new("bool", true))
+            }
+            Expr::Between { expr, low, high, .. } => {
+                let (typed, _) = self.balance(expr, low)?;
+                self.expr(high, typed.ty.as_ref())?;
+                Ok(Typed::new("bool", true))
+            }
+            Expr::Like { expr, pattern, .. }
+            | Expr::ILike { expr, pattern, .. }
+            | Expr::SimilarTo { expr, pattern, .. } => {
+                let text = PgType::named("text");
+                let left = self.expr(expr, Some(&text))?;
+                let right = self.expr(pattern, Some(&text))?;
+                Ok(Typed::new("bool", left.nullable || right.nullable))
+            }
+            Expr::AnyOp { left, right, .. } | Expr::AllOp { left, right, .. } => {
+                let typed = self.expr(left, None)?;
+                let hint = match typed.ty {
+                    Some(PgType::Builtin(b)) => Some(PgType::Array(b)),
+                    _ => None,
+                };
+                self.expr(right, hint.as_ref())?;
+                Ok(Typed::new("bool", true))
+            }
+            Expr::Exists { subquery, .. } => {
+                self.query(subquery)?;
+                Ok(Typed::new("bool", false))
+            }
+            Expr::Subquery(query) => {
+                let columns = self.query(query)?;
+                match columns.as_slice() {
+                    [col] => Ok(Typed {
+                        ty: col.typed.ty.clone(),
+                        nullable: true,
+                    }),
+                    _ => Err("subquery must return only one column".into()),
+                }
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                let operand = match operand {
+                    Some(operand) => self.expr(operand, None)?.ty,
+                    None => Some(bool),
+                };
+                for cond in conditions {
+                    self.expr(cond, operand.as_ref())?;
+                }
+
+                let mut typed = Typed {
+                    ty: None,
+                    nullable: else_result.is_none(),
+                };
+                for result in results.iter().chain(else_result.as_deref()) {
+                    let res = self.expr(result, typed.ty.as_ref().or(hint))?;
+                    typed.nullable |= res.nullable;
+                    if typed.ty.is_none() {
+                        typed.ty = res.ty;
+                    }
+                }
+                Ok(typed)
+            }
+            Expr::Function(func) => self.function(func),
+            _ => Ok(Typed::UNKNOWN),
+        }
+    }
+
+    // infer type of two sides of operator where one side can be used as hint for the other.
+    fn balance(&mut self, left: &Expr, right: &Expr) -> Result<(Typed, Typed), String> {
+        let mut left_typed = self.expr(left, None)?;
+        let right_typed = self.expr(right, left_typed.ty.as_ref())?;
+        if left_typed.ty.is_none() && right_typed.ty.is_some() {
+            left_typed = self.expr(left, right_typed.ty.as_ref())?;
+        }
+        Ok((left_typed, right_typed))
+    }
+
+    fn function(&mut self, func: &Function) -> Result<Typed, String> {
+        let name = func.name.0.last().map(ident).unwrap_or_default();
+
+        let args = match func.args {
+            FunctionArguments::List(ref list) => list
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                    | FunctionArg::Named {
+                        arg: FunctionArgExpr::Expr(expr),
+                        ..
+                    } => Some(expr),
+                    _ => None,
+                })
+                .collect(),
+            FunctionArguments::Subquery(ref query) => {
+                self.query(query)?;
+                Vec::new()
+            }
+            FunctionArguments::None => Vec::new(),
+        };
+
+        if let Some(ref filter) = func.filter {
+            self.expr(filter, Some(&PgType::named("bool")))?;
+        }
+
+        let typed = match name.as_str() {
+            "count" => {
+                for arg in args {
+                    self.expr(arg, None)?;
+                }
+                Typed::new("int8", false)
+            }
+            "now" | "current_timestamp" => Typed::new("timestamptz", false),
+            "coalesce" => {
+                let mut typed = Typed {
+                    ty: None,
+                    nullable: true,
+                };
+                for arg in args.iter() {
+                    let res = self.expr(arg, typed.ty.as_ref())?;
+                    typed.nullable &= res.nullable;
+                    if typed.ty.is_none() {
+                        typed.ty = res.ty;
+                    }
+                }
+                // type placeholders appear before the first typed argument.
+                for arg in args {
+                    self.expr(arg, typed.ty.as_ref())?;
+                }
+                typed
+            }
+            "lower" | "upper" | "btrim" | "ltrim" | "rtrim" => {
+                let mut nullable = false;
+                for arg in args {
+                    nullable |= self.expr(arg, Some(&PgType::named("text")))?.nullable;
+                }
+                Typed::new("text", nullable)
+            }
+            "length" | "char_length" => {
+                let mut nullable = false;
+                for arg in args {
+                    nullable |= self.expr(arg, Some(&PgType::named("text")))?.nullable;
+                }
+                Typed::new("int4", nullable)
+            }
+            "min" | "max" => {
+                let mut typed = Typed::UNKNOWN;
+                for arg in args {
+                    typed.ty = self.expr(arg, None)?.ty;
+                }
+                typed
+            }
+            "sum" | "avg" => {
+                let mut ty = None;
+                for arg in args {
+                    ty = self.expr(arg, None)?.ty;
+                }
+                let ty = ty
+                    .map(|ty| ty.name())
+                    .and_then(|ty| match (name.as_str(), ty.as_str()) {
+                        ("sum", "int2" | "int4") => Some("int8"),
+                        ("sum", "float4") => Some("float4"),
+                        (_, "float4" | "float8") => Some("float8"),
+                        (_, "int2" | "int4" | "int8" | "numeric") => Some("numeric"),
+                        _ => None,
+                    });
+                Typed {
+                    ty: ty.map(PgType::named),
+                    nullable: true,
+                }
+            }
+            "array_agg" => {
+                let mut ty = None;
+                for arg in args {
+                    ty = match self.expr(arg, None)?.ty {
+                        Some(PgType::Builtin(b)) => Some(PgType::Array(b)),
+                        _ => None,
+                    };
+                }
+                Typed { ty, nullable: true }
+            }
+            _ => {
+                for arg in args {
+                    self.expr(arg, None)?;
+                }
+                Typed::UNKNOWN
+            }
+        };
+
+        Ok(typed)
+    }
+}
+
+fn is_numeric(ty: &PgType) -> bool {
+    matches!(
+        ty.name().as_str(),
+        "int2" | "int4" | "int8" | "float4" | "float8" | "numeric"
+    )
+}
+
+fn find_column<'t>(table: &'t Table, col: &Ident, name: &ObjectName) -> Result<&'t Column, String> {
+    let col = ident(col);
+    table
+        .columns
+        .iter()
+        .find(|c| c.name == col)
+        .ok_or_else(|| format!("column `{col}` of relation `{name}` does not exist"))
+}
+
+fn relation_name(name: &ObjectName) -> String {
+    name.0.last().map(ident).unwrap_or_default()
+}
+
+fn relation_outputs(rel: &Relation) -> impl Iterator<Item = Output> + '_ {
+    rel.table.columns.iter().map(|col| Output {
+        name: col.name.clone(),
+        typed: Typed {
+            ty: Some(col.ty.clone()),
+            nullable: col.nullable || rel.nullable,
+        },
+    })
+}
+
+fn rename_columns(table: &mut Table, alias: &TableAlias) {
+    for (col, name) in table.columns.iter_mut().zip(alias.columns.iter()) {
+        col.name = ident(name);
+    }
+}
+
+fn table_from_outputs(columns: Vec<Output>, alias: Option<&TableAlias>) -> Table {
+    let mut table = Table {
+        columns: columns
+            .into_iter()
+            .map(|col| Column {
+                name: col.name,
+                ty: col.typed.ty.unwrap_or_else(|| PgType::Custom("unknown".into())),
+                nullable: col.typed.nullable,
+            })
+            .collect(),
+    };
+    if let Some(alias) = alias {
+        rename_columns(&mut table, alias);
+    }
+    table
+}
+
+// column name postgres gives to expression without alias.
+fn column_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(name) => ident(name),
+        Expr::CompoundIdentifier(idents) => idents.last().map(ident).unwrap_or_default(),
+        Expr::Function(func) => func.name.0.last().map(ident).unwrap_or_default(),
+        Expr::Nested(expr) => column_name(expr),
+        Expr::Cast { expr, data_type, .. } => match column_name(expr) {
+            name if name == "?column?" => PgType::from_data_type(data_type).name(),
+            name => name,
+        },
+        _ => "?column?".into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sqlparser::{dialect::PostgreSqlDialect, parser::Parser};
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        -- pg_dump output contains statements the macro does not care about.
+        SET statement_timeout = 0;
+        CREATE FUNCTION noop() RETURNS void LANGUAGE sql AS $$ SELECT 1; $$;
+        CREATE TABLE public.users (
+            id serial PRIMARY KEY,
+            name text NOT NULL,
+            email varchar(255),
+            age int4
+        );
+        /* posts of users */
+        CREATE TABLE posts (
+            id bigint NOT NULL,
+            user_id integer NOT NULL,
+            title text NOT NULL,
+            tags text[],
+            PRIMARY KEY (id)
+        );
+    "#;
+
+    fn analyze(sql: &str) -> Result<Analyzed, String> {
+        let schema = Schema::parse(SCHEMA).unwrap();
+        let stmt = Parser::parse_sql(&PostgreSqlDialect {}, sql).unwrap().pop().unwrap();
+        super::analyze(&schema, &stmt)
+    }
+
+    fn columns(analyzed: &Analyzed) -> Vec<(&str, String, bool)> {
+        analyzed
+            .columns
+            .iter()
+            .map(|col| {
+                let ty = col.typed.ty.as_ref().map(PgType::name).unwrap_or_default();
+                (col.name.as_str(), ty, col.typed.nullable)
+            })
+            .collect()
+    }
+
+    fn params(analyzed: &Analyzed) -> Vec<String> {
+        analyzed.params.iter().map(PgType::name).collect()
+    }
+
+    #[test]
+    fn select() {
+        let res = analyze("SELECT id, name, email FROM users WHERE id = $1 AND name LIKE $2").unwrap();
+        assert_eq!(params(&res), ["int4", "text"]);
+        assert_eq!(
+            columns(&res),
+            [
+                ("id", "int4".into(), false),
+                ("name", "text".into(), false),
+                ("email", "varchar".into(), true)
+            ]
+        );
+
+        let res = analyze("SELECT count(*), $1::int8 AS n FROM users LIMIT $2").unwrap();
+        assert_eq!(params(&res), ["int8", "int8"]);
+        assert_eq!(
+            columns(&res),
+            [("count", "int8".into(), false), ("n", "int8".into(), true)]
+        );
+    }
+
+    #[test]
+    fn join() {
+        let res = analyze(
+            "SELECT u.name, p.title, p.tags FROM users u LEFT JOIN posts p ON p.user_id = u.id WHERE u.id IN ($1, $2)",
+        )
+        .unwrap();
+        assert_eq!(params(&res), ["int4", "int4"]);
+        assert_eq!(
+            columns(&res),
+            [
+                ("name", "text".into(), false),
+                ("title", "text".into(), true),
+                ("tags", "text[]".into(), true)
+            ]
+        );
+
+        let res = analyze("SELECT * FROM posts WHERE id = ANY($1)").unwrap();
+        assert_eq!(params(&res), ["int8[]"]);
+        assert_eq!(res.columns.len(), 4);
+    }
+
+    #[test]
+    fn subquery() {
+        let res = analyze(
+            "WITH adult AS (SELECT id FROM users WHERE age >= $1) \
+             SELECT t.title FROM (SELECT * FROM posts) t WHERE t.user_id IN (SELECT id FROM adult)",
+        )
+        .unwrap();
+        assert_eq!(params(&res), ["int4"]);
+        assert_eq!(columns(&res), [("title", "text".into(), false)]);
+    }
+
+    #[test]
+    fn insert_update_delete() {
+        let res = analyze("INSERT INTO users (name, email) VALUES ($1, $2) RETURNING id").unwrap();
+        assert_eq!(params(&res), ["text", "varchar"]);
+        assert_eq!(columns(&res), [("id", "int4".into(), false)]);
+
+        let res =
+            analyze("INSERT INTO users (id, name) VALUES ($1, $2) ON CONFLICT (id) DO UPDATE SET name = excluded.name")
+                .unwrap();
+        assert_eq!(params(&res), ["int4", "text"]);
+        assert!(res.columns.is_empty());
+
+        let res = analyze("UPDATE users SET age = $1 WHERE id = $2 RETURNING *").unwrap();
+        assert_eq!(params(&res), ["int4", "int4"]);
+        assert_eq!(res.columns.len(), 4);
+
+        let res = analyze("DELETE FROM posts WHERE user_id = $1").unwrap();
+        assert_eq!(params(&res), ["int4"]);
+    }
+
+    #[test]
+    fn error() {
+        let err = |sql| analyze(sql).err().unwrap();
+        assert_eq!(err("SELECT * FROM foo"), "relation `foo` does not exist");
+        assert_eq!(err("SELECT foo FROM users"), "column `foo` does not exist");
+        assert_eq!(err("SELECT p.foo FROM posts p"), "column `p.foo` does not exist");
+        assert_eq!(err("SELECT id FROM users, posts"), "column reference `id` is ambiguous");
+        assert_eq!(
+            err("INSERT INTO users (foo) VALUES (1)"),
+            "column `foo` of relation `users` does not exist"
+        );
+        assert_eq!(
+            err("SELECT $1"),
+            "can not infer type of parameter $1. add an explicit cast like `$1::int4`"
+        );
+        assert_eq!(
+            err("SELECT id FROM users WHERE id = $1 AND name = $1"),
+            "inconsistent types deduced for parameter $1: `int4` versus `text`"
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use sqlparser::{
+    ast::{
+        ArrayElemTypeDef, ColumnOption, CreateTable, DataType, Ident, ObjectName, Statement, TableConstraint,
+        TimezoneInfo,
+    },
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+
+/// tables parsed from `CREATE TABLE` statements of schema file.
+#[derive(Default)]
+pub(crate) struct Schema {
+    tables: HashMap<String, Table>,
+}
+
+impl Schema {
+    /// parse schema from sql text. statements other than `CREATE TABLE` are ignored so the output of
+    /// `pg_dump --schema-only` can be used as is.
+    pub(crate) fn parse(sql: &str) -> Result<Self, String> {
+        let mut schema = Self::default();
+
+        for stmt in split_statements(sql) {
+            if !is_create_table(&stmt) {
+                continue;
+            }
+
+            let stmts = Parser::parse_sql(&PostgreSqlDialect {}, &stmt).map_err(|e| format!("{e} in `{stmt}`"))?;
+            for stmt in stmts {
+                if let Statement::CreateTable(table) = stmt {
+                    let (name, table) = Table::from_create(table);
+                    schema.tables.insert(name, table);
+                }
+            }
+        }
+
+        Ok(schema)
+    }
+
+    pub(crate) fn table(&self, name: &ObjectName) -> Option<&Table> {
+        self.tables.get(&table_name(name))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Table {
+    pub(crate) columns: Vec<Column>,
+}
+
+impl Table {
+    fn from_create(table: CreateTable) -> (String, Self) {
+        let primary_key = table
+            .constraints
+            .iter()
+            .filter_map(|c| match c {
+                TableConstraint::PrimaryKey { columns, .. } => Some(columns),
+                _ => None,
+            })
+            .flatten()
+            .map(ident)
+            .collect::<Vec<_>>();
+
+        let columns = table
+            .columns
+            .into_iter()
+            .map(|col| {
+                let name = ident(&col.name);
+                let not_null = primary_key.contains(&name)
+                    || col.options.iter().any(|opt| {
+                        matches!(
+                            opt.option,
+                            ColumnOption::NotNull | ColumnOption::Unique { is_primary: true, .. }
+                        )
+                    });
+                Column {
+                    name,
+                    ty: PgType::from_data_type(&col.data_type),
+                    nullable: !not_null,
+                }
+            })
+            .collect();
+
+        (table_name(&table.name), Self { columns })
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Column {
+    pub(crate) name: String,
+    pub(crate) ty: PgType,
+    pub(crate) nullable: bool,
+}
+
+/// postgres type known by the macro.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PgType {
+    Builtin(&'static Builtin),
+    Array(&'static Builtin),
+    // user defined or otherwise unsupported type.
+    Custom(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Builtin {
+    name: &'static str,
+    // name of associated constant of xitca_postgres::types::Type.
+    konst: &'static str,
+    // owned Rust type used for column and it's borrowed form used for parameter.
+    rust: Option<(&'static str, &'static str)>,
+}
+
+macro_rules! builtin {
+    ($name: literal, $konst: literal) => {
+        Builtin {
+            name: $name,
+            konst: $konst,
+            rust: None,
+        }
+    };
+    ($name: literal, $konst: literal, $owned: literal, $borrowed: literal) => {
+        Builtin {
+            name: $name,
+            konst: $konst,
+            rust: Some(($owned, $borrowed)),
+        }
+    };
+}
+
+const BUILTINS: &[Builtin] = &[
+    builtin!("bool", "BOOL", "bool", "bool"),
+    builtin!("int2", "INT2", "i16", "i16"),
+    builtin!("int4", "INT4", "i32", "i32"),
+    builtin!("int8", "INT8", "i64", "i64"),
+    builtin!("float4", "FLOAT4", "f32", "f32"),
+    builtin!("float8", "FLOAT8", "f64", "f64"),
+    builtin!("oid", "OID", "u32", "u32"),
+    builtin!("text", "TEXT", "::std::string::String", "str"),
+    builtin!("varchar", "VARCHAR", "::std::string::String", "str"),
+    builtin!("bpchar", "BPCHAR", "::std::string::String", "str"),
+    builtin!("name", "NAME", "::std::string::String", "str"),
+    builtin!("bytea", "BYTEA", "::std::vec::Vec<u8>", "[u8]"),
+    builtin!("uuid", "UUID"),
+    builtin!("json", "JSON"),
+    builtin!("jsonb", "JSONB"),
+    builtin!("date", "DATE"),
+    builtin!("time", "TIME"),
+    builtin!("timetz", "TIMETZ"),
+    builtin!("timestamp", "TIMESTAMP"),
+    builtin!("timestamptz", "TIMESTAMPTZ"),
+    builtin!("interval", "INTERVAL"),
+    builtin!("numeric", "NUMERIC"),
+];
+
+fn builtin(name: &str) -> Option<&'static Builtin> {
+    let name = match name {
+        "boolean" => "bool",
+        "smallint" | "smallserial" | "serial2" => "int2",
+        "int" | "integer" | "serial" | "serial4" => "int4",
+        "bigint" | "bigserial" | "serial8" => "int8",
+        "real" => "float4",
+        "double precision" => "float8",
+        "character varying" => "varchar",
+        "char" | "character" => "bpchar",
+        "decimal" => "numeric",
+        name => name,
+    };
+    BUILTINS.iter().find(|b| b.name == name)
+}
+
+impl PgType {
+    pub(crate) fn from_data_type(ty: &DataType) -> Self {
+        let name = match ty {
+            DataType::Bool | DataType::Boolean => "bool",
+            DataType::Int2(_) | DataType::SmallInt(_) => "int2",
+            DataType::Int(_) | DataType::Int4(_) | DataType::Integer(_) => "int4",
+            DataType::Int8(_) | DataType::BigInt(_) => "int8",
+            DataType::Float4 | DataType::Real => "float4",
+            DataType::Float8 | DataType::Double | DataType::DoublePrecision => "float8",
+            DataType::Float(Some(p)) if *p <= 24 => "float4",
+            DataType::Float(_) => "float8",
+            DataType::Text | DataType::String(_) => "text",
+            DataType::Varchar(_) | DataType::CharacterVarying(_) | DataType::CharVarying(_) => "varchar",
+            DataType::Char(_) | DataType::Character(_) => "bpchar",
+            DataType::Bytea => "bytea",
+            DataType::Uuid => "uuid",
+            DataType::JSON => "json",
+            DataType::JSONB => "jsonb",
+            DataType::Date => "date",
+            DataType::Time(_, TimezoneInfo::WithTimeZone | TimezoneInfo::Tz) => "timetz",
+            DataType::Time(..) => "time",
+            DataType::Timestamp(_, TimezoneInfo::WithTimeZone | TimezoneInfo::Tz) => "timestamptz",
+            DataType::Timestamp(..) => "timestamp",
+            DataType::Interval => "interval",
+            DataType::Numeric(_) | DataType::Decimal(_) | DataType::Dec(_) => "numeric",
+            DataType::Array(
+                ArrayElemTypeDef::SquareBracket(ty, _)
+                | ArrayElemTypeDef::AngleBracket(ty)
+                | ArrayElemTypeDef::Parenthesis(ty),
+            ) => {
+                return match Self::from_data_type(ty) {
+                    Self::Builtin(b) => Self::Array(b),
+                    ty => Self::Custom(format!("{}[]", ty.name())),
+                }
+            }
+            DataType::Custom(name, _) => {
+                let name = name.0.last().map(ident).unwrap_or_default();
+                return Self::named(&name);
+            }
+            ty => return Self::Custom(ty.to_string()),
+        };
+        Self::Builtin(builtin(name).expect("builtin type must be known"))
+    }
+
+    /// look up builtin type by name and fall back to user defined type.
+    pub(crate) fn named(name: &str) -> Self {
+        builtin(name)
+            .map(Self::Builtin)
+            .unwrap_or_else(|| Self::Custom(name.to_string()))
+    }
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Self::Builtin(b) => b.name.to_string(),
+            Self::Array(b) => format!("{}[]", b.name),
+            Self::Custom(name) => name.clone(),
+        }
+    }
+
+    /// expression of `xitca_postgres::types::Type`. user defined type is not known at compile time.
+    pub(crate) fn type_expr(&self) -> Option<TokenStream> {
+        let konst = match self {
+            Self::Builtin(b) => format_ident!("{}", b.konst),
+            Self::Array(b) => format_ident!("{}_ARRAY", b.konst),
+            Self::Custom(_) => return None,
+        };
+        Some(quote! { ::xitca_postgres::types::Type::#konst })
+    }
+
+    /// owned Rust type for decoding column.
+    pub(crate) fn rust_type(&self) -> Option<TokenStream> {
+        match self {
+            Self::Builtin(b) => b.rust.map(|(owned, _)| parse_type(owned)),
+            Self::Array(b) => b.rust.map(|(owned, _)| {
+                let owned = parse_type(owned);
+                quote! { ::std::vec::Vec<#owned> }
+            }),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// borrowed Rust type for checking parameter. type of parameter must implement `Borrow` trait for it.
+    pub(crate) fn borrowed_type(&self) -> Option<TokenStream> {
+        match self {
+            Self::Builtin(b) => b.rust.map(|(_, borrowed)| parse_type(borrowed)),
+            _ => None,
+        }
+    }
+}
+
+fn parse_type(ty: &str) -> TokenStream {
+    ty.parse().expect("builtin Rust type must be valid")
+}
+
+/// normalize identifier the same way as postgres. unquoted identifier is case insensitive.
+pub(crate) fn ident(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_lowercase(),
+    }
+}
+
+// table name without default `public` schema.
+fn table_name(name: &ObjectName) -> String {
+    match name.0.as_slice() {
+        [schema, table] if ident(schema) == "public" => ident(table),
+        idents => idents.iter().map(ident).collect::<Vec<_>>().join("."),
+    }
+}
+
+fn is_create_table(stmt: &str) -> bool {
+    let mut words = stmt.split_whitespace().map(str::to_ascii_uppercase);
+    if words.next().as_deref() != Some("CREATE") {
+        return false;
+    }
+    words
+        .take_while(|w| {
+            matches!(
+                w.as_str(),
+                "TABLE" | "UNLOGGED" | "TEMP" | "TEMPORARY" | "GLOBAL" | "LOCAL"
+            )
+        })
+        .any(|w| w == "TABLE")
+}
+
+// split sql text into statements with comments removed. string literals, quoted identifiers and dollar quoted
+// strings are kept as is.
+fn split_statements(sql: &str) -> Vec<String> {
+    let mut stmts = Vec::new();
+    let mut stmt = String::new();
+    let mut rest = sql;
+
+    let mut push = |stmt: &mut String| {
+        let s = stmt.trim();
+        if !s.is_empty() {
+            stmts.push(s.to_string());
+        }
+        stmt.clear();
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '-' if rest.starts_with("--") => {
+                rest = rest.find('\n').map(|n| &rest[n..]).unwrap_or_default();
+                continue;
+            }
+            '/' if rest.starts_with("/*") => {
+                rest = rest.find("*/").map(|n| &rest[n + 2..]).unwrap_or_default();
+                stmt.push(' ');
+                continue;
+            }
+            ';' => {
+                push(&mut stmt);
+                rest = &rest[1..];
+                continue;
+            }
+            '\'' | '"' => rest[1..].find(c).map(|n| n + 2).unwrap_or(rest.len()),
+            '$' => {
+                let tag = rest[1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .filter(|n| rest[1 + n..].starts_with('$'))
+                    .map(|n| &rest[..n + 2]);
+                match tag {
+                    Some(tag) => rest[tag.len()..]
+                        .find(tag)
+                        .map(|n| tag.len() + n + tag.len())
+                        .unwrap_or(rest.len()),
+                    None => 1,
+                }
+            }
+            c => c.len_utf8(),
+        };
+        stmt.push_str(&rest[..len]);
+        rest = &rest[len..];
+    }
+
+    push(&mut stmt);
+
+    stmts
+}
@@ -80,6 +80,10 @@ impl Prepare for PoolConnection {
     fn _get_type_blocking(&self, oid: Oid) -> Result<Type, Error> {
         self.conn._get_type_blocking(oid)
     }
+
+    fn _unnamed_statement(&self) -> bool {
+        self.conn._unnamed_statement()
+    }
 }
 
 // trait for how a query is sent.
@@ -0,0 +1,288 @@
+//! bulk insert of multiple rows.
+//!
+//! [`BulkInsert`] inserts rows with multi-row `INSERT` statements where rows are batched into chunks. When the
+//! number of rows exceeds a threshold it switches to `COPY ... FROM STDIN (FORMAT binary)` which is the fastest
+//! way of loading data into postgres.
+
+use core::fmt::Write;
+
+use crate::{
+    copy::{binary::BinaryCopyInWriter, r#Copy, CopyIn},
+    error::{Error, InvalidParamCount},
+    execute::Execute,
+    prepare::Prepare,
+    statement::Statement,
+    types::{ToSql, Type},
+    util::quote_ident,
+};
+
+// maximum number of parameters a statement can bind to.
+const MAX_PARAMS: usize = u16::MAX as usize;
+
+const DEFAULT_CHUNK_SIZE: usize = 1000;
+const DEFAULT_COPY_THRESHOLD: usize = 10_000;
+
+/// trait for type that can be inserted as a row with [`BulkInsert`].
+///
+/// it's implemented for tuples of up to 8 elements. For struct the trait can be implemented by pushing field
+/// references in the same order as the columns given to [`BulkInsert::new`].
+///
+/// # Examples
+/// ```
+/// use xitca_postgres::{bulk::ToRow, types::ToSql};
+///
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// impl ToRow for User {
+///     fn to_row<'a>(&'a self, row: &mut Vec<&'a (dyn ToSql + Sync)>) {
+///         row.push(&self.id);
+///         row.push(&self.name);
+///     }
+/// }
+/// ```
+pub trait ToRow {
+    /// push values of row to given buffer.
+    fn to_row<'a>(&'a self, row: &mut Vec<&'a (dyn ToSql + Sync)>);
+}
+
+macro_rules! tuple_impl {
+    ($($t: ident: $idx: tt),*) => {
+        impl<$($t),*> ToRow for ($($t,)*)
+        where
+            $($t: ToSql + Sync),*
+        {
+            fn to_row<'a>(&'a self, row: &mut Vec<&'a (dyn ToSql + Sync)>) {
+                $(row.push(&self.$idx);)*
+            }
+        }
+    };
+}
+
+tuple_impl!(A: 0);
+tuple_impl!(A: 0, B: 1);
+tuple_impl!(A: 0, B: 1, C: 2);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+/// builder for inserting multiple rows into a table.
+///
+/// # Examples
+/// ```
+/// # use xitca_postgres::{Client, Error};
+/// # async fn bulk(client: &mut Client) -> Result<(), Error> {
+/// use xitca_postgres::{bulk::BulkInsert, types::Type};
+///
+/// let rows = (0..100).map(|i| (i, format!("user_{i}"))).collect::<Vec<_>>();
+///
+/// let inserted = BulkInsert::new("users", &["id", "name"], &[Type::INT4, Type::TEXT])
+///     .chunk_size(50)
+///     .execute(client, &rows)
+///     .await?;
+/// assert_eq!(inserted, 100);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BulkInsert<'a> {
+    table: &'a str,
+    columns: &'a [&'a str],
+    types: &'a [Type],
+    chunk_size: usize,
+    copy_threshold: usize,
+}
+
+impl<'a> BulkInsert<'a> {
+    /// construct a new bulk insert for given table and columns.
+    ///
+    /// table name is used as is and can be schema qualified. column names are quoted and therefore case sensitive.
+    /// column types must be in the same order as columns.
+    pub fn new(table: &'a str, columns: &'a [&'a str], types: &'a [Type]) -> Self {
+        Self {
+            table,
+            columns,
+            types,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            copy_threshold: DEFAULT_COPY_THRESHOLD,
+        }
+    }
+
+    /// maximum number of rows inserted by a single `INSERT` statement. default to 1000.
+    ///
+    /// chunk size is further limited by postgres where a statement can not have more than 65535 parameters.
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size;
+        self
+    }
+
+    /// number of rows above which `COPY` is used instead of `INSERT`. default to 10000.
+    ///
+    /// `COPY` does not support `ON CONFLICT` and triggers fire the same way as `INSERT`. set to [`usize::MAX`] to
+    /// always use `INSERT`.
+    pub fn copy_threshold(mut self, threshold: usize) -> Self {
+        self.copy_threshold = threshold;
+        self
+    }
+
+    /// insert rows and return how many rows are inserted.
+    ///
+    /// rows are inserted with multiple statements and the operation is not atomic. use it inside a transaction
+    /// when all rows must be inserted or none.
+    pub async fn execute<C, R>(&self, cli: &mut C, rows: &[R]) -> Result<u64, Error>
+    where
+        C: r#Copy + Prepare + Send,
+        R: ToRow + Sync,
+    {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        if rows.len() > self.copy_threshold {
+            self.copy(cli, rows).await
+        } else {
+            self.insert(cli, rows).await
+        }
+    }
+
+    async fn insert<C, R>(&self, cli: &C, rows: &[R]) -> Result<u64, Error>
+    where
+        C: Prepare,
+        R: ToRow + Sync,
+    {
+        let cols = self.columns.len();
+        let chunk_size = self.chunk_size.min(MAX_PARAMS / cols.max(1)).max(1);
+
+        let mut inserted = 0;
+        let mut params = Vec::with_capacity(chunk_size.min(rows.len()) * cols);
+        let mut types = Vec::new();
+
+        for chunk in rows.chunks(chunk_size) {
+            params.clear();
+            for row in chunk {
+                self.push_row(row, &mut params)?;
+            }
+
+            let sql = self.insert_sql(chunk.len());
+            types.clear();
+            for _ in 0..chunk.len() {
+                types.extend_from_slice(self.types);
+            }
+
+            inserted += Statement::unnamed(&sql, &types)
+                .bind(params.iter().cloned())
+                .execute(cli)
+                .await?;
+        }
+
+        Ok(inserted)
+    }
+
+    async fn copy<C, R>(&self, cli: &mut C, rows: &[R]) -> Result<u64, Error>
+    where
+        C: r#Copy + Prepare + Send,
+        R: ToRow + Sync,
+    {
+        let sql = self.copy_sql();
+        let stmt = Statement::named(&sql, &[]).execute(&*cli).await?.leak();
+
+        let res = async {
+            let copy_in = CopyIn::new(&mut *cli, &stmt).await?;
+            let mut writer = BinaryCopyInWriter::new(copy_in, self.types);
+            let mut params = Vec::with_capacity(self.columns.len());
+            for row in rows {
+                params.clear();
+                self.push_row(row, &mut params)?;
+                writer.write(params.drain(..))?;
+            }
+            writer.finish().await
+        }
+        .await;
+
+        // close the statement.
+        drop(stmt.into_guarded(&*cli));
+
+        res
+    }
+
+    fn push_row<'r, R>(&self, row: &'r R, params: &mut Vec<&'r (dyn ToSql + Sync)>) -> Result<(), Error>
+    where
+        R: ToRow,
+    {
+        let len = params.len();
+        row.to_row(params);
+        let count = params.len() - len;
+        if count != self.columns.len() {
+            return Err(Error::from(InvalidParamCount {
+                expected: self.columns.len(),
+                params: count,
+            }));
+        }
+        Ok(())
+    }
+
+    fn column_list(&self) -> String {
+        self.columns
+            .iter()
+            .map(|col| quote_ident(col))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn insert_sql(&self, rows: usize) -> String {
+        let cols = self.columns.len();
+        let mut sql = format!("INSERT INTO {} ({}) VALUES ", self.table, self.column_list());
+        for row in 0..rows {
+            if row > 0 {
+                sql.push_str(", ");
+            }
+            sql.push('(');
+            for col in 0..cols {
+                if col > 0 {
+                    sql.push_str(", ");
+                }
+                let _ = write!(sql, "${}", row * cols + col + 1);
+            }
+            sql.push(')');
+        }
+        sql
+    }
+
+    fn copy_sql(&self) -> String {
+        format!(
+            "COPY {} ({}) FROM STDIN (FORMAT binary)",
+            self.table,
+            self.column_list()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sql() {
+        let insert = BulkInsert::new("public.foo", &["id", "Name"], &[Type::INT4, Type::TEXT]);
+        assert_eq!(
+            insert.insert_sql(2),
+            "INSERT INTO public.foo (\"id\", \"Name\") VALUES ($1, $2), ($3, $4)"
+        );
+        assert_eq!(
+            insert.copy_sql(),
+            "COPY public.foo (\"id\", \"Name\") FROM STDIN (FORMAT binary)"
+        );
+    }
+
+    #[test]
+    fn row_param_count() {
+        let insert = BulkInsert::new("foo", &["id", "name"], &[Type::INT4, Type::TEXT]);
+        let mut params = Vec::new();
+        insert.push_row(&(1, "foo"), &mut params).unwrap();
+        assert_eq!(params.len(), 2);
+        assert!(insert.push_row(&(1,), &mut params).is_err());
+    }
+}
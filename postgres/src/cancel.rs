@@ -1,5 +1,7 @@
 //! module for canceling ongoing queries
 
+use core::{fmt, future::Future, pin::pin, time::Duration};
+
 use postgres_protocol::message::frontend;
 use xitca_io::bytes::BytesMut;
 
@@ -16,16 +18,68 @@ impl Session {
     ///
     /// Cancellation is inherently racy. There is no guarantee that the cancellation request will reach the server
     /// before the query terminates normally, or that the connection associated with this token is still active.
-    pub async fn query_cancel(self) -> Result<(), Error> {
-        let Session { id, key, info } = self;
-        let (_tx, mut drv) = super::driver::connect_info(info).await?;
+    ///
+    /// Each call opens a new connection to database and the token can be used multiple times for canceling queries
+    /// issued at different time on the same connection.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use core::time::Duration;
+    /// # use xitca_postgres::{Client, Error, Execute};
+    /// async fn query_with_cancel(cli: &Client) -> Result<(), Error> {
+    ///     let token = cli.cancel_token();
+    ///     // cancel the query when it's not finished in time. the query resolves to error with
+    ///     // SqlState::QUERY_CANCELED code after cancellation.
+    ///     let query = "SELECT pg_sleep(10)".execute(cli);
+    ///     match tokio::time::timeout(Duration::from_secs(1), query).await {
+    ///         Ok(res) => res.map(|_| ()),
+    ///         Err(_) => token.query_cancel().await,
+    ///     }
+    /// }
+    /// ```
+    pub async fn query_cancel(&self) -> Result<(), Error> {
+        let (_tx, mut drv) = super::driver::connect_info(self.info.clone()).await?;
         let mut buf = BytesMut::new();
-        frontend::cancel_request(id, key, &mut buf);
+        frontend::cancel_request(self.id, self.key, &mut buf);
         drv.send(buf).await
     }
 
+    /// await on given query future with a timeout. When timeout is reached a cancel request is sent through
+    /// [`Session::query_cancel`] and the query future is awaited again for the outcome of cancellation. A canceled
+    /// query resolves to error with [`SqlState::QUERY_CANCELED`] code and the connection stays usable afterward.
+    ///
+    /// Query future must be from the connection associated with Self. Due to racy nature of cancellation the query
+    /// may still finish successfully after timeout.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use core::time::Duration;
+    /// # use xitca_postgres::{error::{DbError, SqlState}, Client, Error, Execute};
+    /// async fn query_with_timeout(cli: &Client) -> Result<(), Error> {
+    ///     let fut = "SELECT pg_sleep(10)".execute(cli);
+    ///     let err = cli.cancel_token().timeout(Duration::from_secs(1), fut).await.unwrap_err();
+    ///     assert_eq!(err.downcast_ref::<DbError>().unwrap().code(), &SqlState::QUERY_CANCELED);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`SqlState::QUERY_CANCELED`]: crate::error::SqlState::QUERY_CANCELED
+    pub async fn timeout<F, T>(&self, dur: Duration, fut: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let mut fut = pin!(fut);
+        match tokio::time::timeout(dur, fut.as_mut()).await {
+            Ok(res) => res,
+            Err(_) => {
+                self.query_cancel().await?;
+                fut.await
+            }
+        }
+    }
+
     /// blocking version of [`Session::query_cancel`]
-    pub fn query_cancel_blocking(self) -> Result<(), Error> {
+    pub fn query_cancel_blocking(&self) -> Result<(), Error> {
         match tokio::runtime::Handle::try_current() {
             Ok(_) => Err(RuntimeError::RequireNoTokio.into()),
             Err(_) => tokio::runtime::Builder::new_current_thread()
@@ -35,3 +89,12 @@ impl Session {
         }
     }
 }
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // secret key is omitted on purpose.
+        f.debug_struct("Session")
+            .field("process_id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
@@ -9,6 +9,7 @@ use xitca_io::bytes::BytesMut;
 use xitca_unsafe_collection::no_hash::NoHashBuilder;
 
 use super::{
+    config::Config,
     copy::{r#Copy, CopyIn, CopyOut},
     driver::{
         codec::{
@@ -18,6 +19,8 @@ use super::{
         DriverTx,
     },
     error::Error,
+    execute::Execute,
+    iter::AsyncLendingIterator,
     prepare::Prepare,
     query::Query,
     session::Session,
@@ -104,6 +107,9 @@ pub struct Client {
 pub(crate) struct ClientCache {
     session: Session,
     type_info: Mutex<CachedTypeInfo>,
+    pub(crate) pgbouncer: bool,
+    #[cfg(feature = "tracing")]
+    trace_statement: bool,
 }
 
 /// A cache of type info and prepared statements for fetching type info
@@ -160,6 +166,16 @@ impl Client {
         self.tx.is_closed()
     }
 
+    /// latest value of run-time parameter reported by server. (e.g: `server_version`, `TimeZone`)
+    ///
+    /// values are reported on connect and updated when [`Driver`] receives `ParameterStatus` message from server.
+    /// only parameters marked as `GUC_REPORT` by server are reported.
+    ///
+    /// [`Driver`]: crate::Driver
+    pub fn parameter(&self, name: &str) -> Option<Box<str>> {
+        self.tx.parameter(name)
+    }
+
     pub fn typeinfo(&self) -> Option<Statement> {
         self.cache
             .type_info
@@ -210,6 +226,20 @@ impl Client {
         self.cache.type_info.lock().unwrap().types.insert(oid, type_.clone());
     }
 
+    /// Looks up user defined type by name and caches it's definition the same way as types used in a query.
+    ///
+    /// Name is parsed by postgres and can be schema qualified. Returned [Type] can be used for preparing statement
+    /// and it's oid is only valid for the database it's resolved from.
+    pub async fn resolve_type(&self, name: &str) -> Result<Type, Error> {
+        let mut rows = Statement::unnamed("SELECT $1::regtype::oid", &[Type::TEXT])
+            .bind([name])
+            .query(self)
+            .await?;
+        let row = rows.try_next().await?.ok_or_else(Error::unexpected)?;
+        let oid = row.try_get::<Oid>(0)?;
+        self._get_type(oid).await
+    }
+
     /// Clears the client's type information cache.
     ///
     /// When user-defined types are used in a query, the client loads their definitions from the database and caches
@@ -219,10 +249,13 @@ impl Client {
         self.cache.type_info.lock().unwrap().types.clear();
     }
 
-    pub(crate) fn new(tx: DriverTx, session: Session) -> Self {
+    pub(crate) fn new(tx: DriverTx, session: Session, cfg: &Config) -> Self {
         Self {
             tx,
             cache: Box::new(ClientCache {
+                #[cfg(feature = "tracing")]
+                trace_statement: cfg.get_trace_statement(),
+                pgbouncer: cfg.get_pgbouncer(),
                 session,
                 type_info: Mutex::new(CachedTypeInfo {
                     typeinfo: None,
@@ -252,6 +285,11 @@ impl Prepare for Arc<Client> {
     fn _get_type_blocking(&self, oid: Oid) -> Result<Type, Error> {
         Client::_get_type_blocking(self, oid)
     }
+
+    #[inline]
+    fn _unnamed_statement(&self) -> bool {
+        Client::_unnamed_statement(self)
+    }
 }
 
 impl Query for Arc<Client> {
@@ -270,6 +308,28 @@ impl Query for Client {
     where
         S: Encode,
     {
+        #[cfg(feature = "tracing")]
+        {
+            let span = stmt
+                .trace_info()
+                .map(|info| crate::trace::QuerySpan::new(info, self.cache.trace_statement));
+            match encode::send_encode_query(&self.tx, stmt) {
+                Ok((output, mut res)) => {
+                    if let Some(span) = span {
+                        res.set_span(span);
+                    }
+                    Ok((output, res))
+                }
+                Err(e) => {
+                    if let Some(span) = span {
+                        span.on_error(&e);
+                    }
+                    Err(e)
+                }
+            }
+        }
+
+        #[cfg(not(feature = "tracing"))]
         encode::send_encode_query(&self.tx, stmt)
     }
 }
@@ -1,5 +1,12 @@
-use core::future::Future;
+pub mod binary;
 
+use core::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::stream::Stream;
 use postgres_protocol::message::{backend, frontend};
 use xitca_io::bytes::{Buf, Bytes, BytesMut};
 
@@ -47,9 +54,12 @@ where
 
         async {
             let mut res = res?;
-            match res.recv().await? {
-                backend::Message::BindComplete => {}
-                _ => return Err(Error::unexpected()),
+            loop {
+                match res.recv().await? {
+                    backend::Message::ParseComplete => {}
+                    backend::Message::BindComplete => break,
+                    _ => return Err(Error::unexpected()),
+                }
             }
 
             match res.recv().await? {
@@ -95,8 +105,31 @@ where
     }
 }
 
+/// async stream of raw data emitted by `COPY ... TO STDOUT` statement.
+///
+/// data can be consumed with either [`AsyncLendingIterator`] or [`Stream`] trait. Each item is a chunk of data
+/// in the format specified by the copy statement and chunk boundary is not guaranteed to align with rows.
+///
+/// # Examples
+/// ```
+/// # use xitca_postgres::{Client, Error, Execute, Statement};
+/// # async fn copy_out(client: &Client) -> Result<(), Error> {
+/// use futures::stream::TryStreamExt;
+///
+/// let stmt = Statement::named("COPY foo TO STDOUT", &[]).execute(client).await?;
+/// let stream = client.copy_out(&stmt).await?;
+///
+/// // pipe copy data to destination without collecting rows.
+/// let csv = stream.try_fold(Vec::new(), |mut buf, bytes| async move {
+///     buf.extend_from_slice(&bytes);
+///     Ok(buf)
+/// }).await?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct CopyOut {
     res: Response,
+    done: bool,
 }
 
 impl CopyOut {
@@ -106,9 +139,12 @@ impl CopyOut {
         async {
             let mut res = res?;
 
-            match res.recv().await? {
-                backend::Message::BindComplete => {}
-                _ => return Err(Error::unexpected()),
+            loop {
+                match res.recv().await? {
+                    backend::Message::ParseComplete => {}
+                    backend::Message::BindComplete => break,
+                    _ => return Err(Error::unexpected()),
+                }
             }
 
             match res.recv().await? {
@@ -116,11 +152,36 @@ impl CopyOut {
                 _ => return Err(Error::unexpected()),
             }
 
-            Ok(CopyOut { res })
+            Ok(CopyOut { res, done: false })
         }
     }
 }
 
+impl Stream for CopyOut {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.done {
+            match ready!(this.res.poll_recv(cx)) {
+                Ok(backend::Message::CopyData(body)) => return Poll::Ready(Some(Ok(body.into_bytes()))),
+                // drain remaining messages until server is ready for next query.
+                Ok(backend::Message::CopyDone | backend::Message::CommandComplete(_)) => {}
+                Ok(backend::Message::ReadyForQuery(_)) => this.done = true,
+                Ok(_) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(Error::unexpected())));
+                }
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
 impl AsyncLendingIterator for CopyOut {
     type Ok<'i>
         = Bytes
@@ -129,10 +190,6 @@ impl AsyncLendingIterator for CopyOut {
     type Err = Error;
 
     async fn try_next(&mut self) -> Result<Option<Self::Ok<'_>>, Self::Err> {
-        match self.res.recv().await? {
-            backend::Message::CopyData(body) => Ok(Some(body.into_bytes())),
-            backend::Message::CopyDone => Ok(None),
-            _ => Err(Error::unexpected()),
-        }
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await.transpose()
     }
 }
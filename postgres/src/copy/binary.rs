@@ -0,0 +1,412 @@
+//! typed encoder and decoder for binary format of `COPY` statement.
+
+use core::{
+    future::poll_fn,
+    ops::Range,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use std::{io, sync::Arc};
+
+use futures_core::stream::Stream;
+use xitca_io::bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{
+    driver::codec::AsParams,
+    error::{Error, InvalidColumnIndex, InvalidParamCount, WrongType},
+    iter::AsyncLendingIterator,
+    types::{BorrowToSql, FromSql, IsNull, ToSql, Type},
+};
+
+use super::{r#Copy, CopyIn, CopyOut};
+
+const MAGIC: &[u8] = b"PGCOPY\n\xff\r\n\0";
+const HEADER_LEN: usize = MAGIC.len() + 4 + 4;
+
+// buffered data size for sending to database.
+const FLUSH_THRESHOLD: usize = 4096;
+
+/// encoder for `COPY ... FROM STDIN (FORMAT binary)` statement.
+///
+/// values are encoded with column types given to [`BinaryCopyInWriter::new`]. Header is written on construction
+/// and trailer is written when calling [`BinaryCopyInWriter::finish`].
+///
+/// # Examples
+/// ```
+/// # use xitca_postgres::{Client, Error, Execute, Statement};
+/// # async fn copy_in(client: &mut Client) -> Result<(), Error> {
+/// use xitca_postgres::{copy::binary::BinaryCopyInWriter, types::Type};
+///
+/// let stmt = Statement::named("COPY foo (id, name) FROM STDIN (FORMAT binary)", &[])
+///     .execute(&*client)
+///     .await?
+///     .leak();
+/// let copy_in = client.copy_in(&stmt).await?;
+///
+/// let mut writer = BinaryCopyInWriter::new(copy_in, &[Type::INT4, Type::TEXT]);
+/// writer.write_dyn(&[&1i32, &"alice"])?;
+/// writer.write_dyn(&[&2i32, &"bob"])?;
+/// let rows = writer.finish().await?;
+/// assert_eq!(rows, 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BinaryCopyInWriter<'a, C>
+where
+    C: r#Copy + Send,
+{
+    copy_in: CopyIn<'a, C>,
+    types: Vec<Type>,
+    buf: BytesMut,
+}
+
+impl<'a, C> BinaryCopyInWriter<'a, C>
+where
+    C: r#Copy + Send,
+{
+    pub fn new(copy_in: CopyIn<'a, C>, types: &[Type]) -> Self {
+        let mut buf = BytesMut::with_capacity(FLUSH_THRESHOLD);
+        buf.put_slice(MAGIC);
+        // flags field.
+        buf.put_i32(0);
+        // header extension length.
+        buf.put_i32(0);
+        Self {
+            copy_in,
+            types: types.to_vec(),
+            buf,
+        }
+    }
+
+    /// encode one row of values. number of values must match the number of column types.
+    ///
+    /// encoded data is buffered and send to database when buffer is large enough.
+    pub fn write<P>(&mut self, params: P) -> Result<(), Error>
+    where
+        P: AsParams,
+    {
+        let params = params.into_iter();
+        if params.len() != self.types.len() {
+            return Err(Error::from(InvalidParamCount {
+                expected: self.types.len(),
+                params: params.len(),
+            }));
+        }
+
+        let row_start = self.buf.len();
+        if let Err(e) = self._write(params) {
+            // remove partial written row.
+            self.buf.truncate(row_start);
+            return Err(e);
+        }
+
+        if self.buf.len() >= FLUSH_THRESHOLD {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// [BinaryCopyInWriter::write] for dynamic typed values.
+    pub fn write_dyn(&mut self, params: &[&(dyn ToSql + Sync)]) -> Result<(), Error> {
+        self.write(params.iter().cloned())
+    }
+
+    /// write trailer and finish copy. return how many rows are affected
+    pub async fn finish(mut self) -> Result<u64, Error> {
+        self.buf.put_i16(-1);
+        self.flush()?;
+        self.copy_in.finish().await
+    }
+
+    fn _write<I>(&mut self, params: I) -> Result<(), Error>
+    where
+        I: Iterator<Item: BorrowToSql>,
+    {
+        self.buf.put_i16(self.types.len() as i16);
+
+        for (param, ty) in params.zip(self.types.iter()) {
+            let len_pos = self.buf.len();
+            self.buf.put_i32(0);
+
+            let len = match param.borrow_to_sql().to_sql_checked(ty, &mut self.buf)? {
+                IsNull::Yes => -1,
+                IsNull::No => {
+                    let len = self.buf.len() - len_pos - 4;
+                    i32::try_from(len).map_err(|_| invalid_data("value is too large to transmit"))?
+                }
+            };
+
+            self.buf[len_pos..len_pos + 4].copy_from_slice(&len.to_be_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        self.copy_in.copy(self.buf.split().freeze())
+    }
+}
+
+/// decoder for `COPY ... TO STDOUT (FORMAT binary)` statement.
+///
+/// # Examples
+/// ```
+/// # use xitca_postgres::{Client, Error, Execute, Statement};
+/// # async fn copy_out(client: &Client) -> Result<(), Error> {
+/// use xitca_postgres::{copy::binary::BinaryCopyOutReader, iter::AsyncLendingIterator, types::Type};
+///
+/// let stmt = Statement::named("COPY foo (id, name) TO STDOUT (FORMAT binary)", &[])
+///     .execute(client)
+///     .await?;
+/// let copy_out = client.copy_out(&stmt).await?;
+///
+/// let mut reader = BinaryCopyOutReader::new(copy_out, &[Type::INT4, Type::TEXT]);
+/// while let Some(row) = reader.try_next().await? {
+///     let id = row.get::<i32>(0);
+///     let name = row.get::<&str>(1);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct BinaryCopyOutReader {
+    copy_out: CopyOut,
+    types: Arc<[Type]>,
+    parser: Parser,
+}
+
+impl BinaryCopyOutReader {
+    pub fn new(copy_out: CopyOut, types: &[Type]) -> Self {
+        Self {
+            copy_out,
+            types: types.into(),
+            parser: Parser::default(),
+        }
+    }
+}
+
+impl Stream for BinaryCopyOutReader {
+    type Item = Result<BinaryCopyOutRow, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(row) = this.parser.try_parse(&this.types)? {
+                return Poll::Ready(Some(Ok(row)));
+            }
+
+            match ready!(Pin::new(&mut this.copy_out).poll_next(cx)) {
+                Some(Ok(bytes)) => {
+                    if this.parser.trailer && !bytes.is_empty() {
+                        return Poll::Ready(Some(Err(invalid_data("unexpected data after binary copy trailer"))));
+                    }
+                    this.parser.buf.extend_from_slice(&bytes);
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None if this.parser.trailer => return Poll::Ready(None),
+                None => return Poll::Ready(Some(Err(invalid_data("binary copy data ended without trailer")))),
+            }
+        }
+    }
+}
+
+// incremental parser of binary copy data where chunk boundary does not have to align with rows.
+#[derive(Default)]
+struct Parser {
+    buf: BytesMut,
+    header: bool,
+    trailer: bool,
+}
+
+impl Parser {
+    fn try_parse(&mut self, types: &Arc<[Type]>) -> Result<Option<BinaryCopyOutRow>, Error> {
+        if self.trailer || (!self.header && !self.try_parse_header()?) {
+            return Ok(None);
+        }
+        self.try_parse_row(types)
+    }
+
+    fn try_parse_header(&mut self) -> Result<bool, Error> {
+        if self.buf.len() < HEADER_LEN {
+            return Ok(false);
+        }
+
+        if &self.buf[..MAGIC.len()] != MAGIC {
+            return Err(invalid_data("invalid binary copy header"));
+        }
+
+        let ext_len = i32::from_be_bytes(self.buf[HEADER_LEN - 4..HEADER_LEN].try_into().unwrap());
+        let ext_len = usize::try_from(ext_len).map_err(|_| invalid_data("invalid binary copy header extension"))?;
+
+        if self.buf.len() < HEADER_LEN + ext_len {
+            return Ok(false);
+        }
+
+        self.buf.advance(HEADER_LEN + ext_len);
+        self.header = true;
+        Ok(true)
+    }
+
+    fn try_parse_row(&mut self, types: &Arc<[Type]>) -> Result<Option<BinaryCopyOutRow>, Error> {
+        if self.buf.len() < 2 {
+            return Ok(None);
+        }
+
+        let mut buf = &self.buf[..];
+        let count = buf.get_i16();
+
+        if count == -1 {
+            self.buf.advance(2);
+            self.trailer = true;
+            return Ok(None);
+        }
+
+        if count as usize != types.len() {
+            return Err(invalid_data("binary copy row field count does not match types"));
+        }
+
+        let mut ranges = Vec::with_capacity(types.len());
+        // position relative to start of row. field count is excluded from row buffer.
+        let mut pos = 2;
+
+        for _ in 0..count {
+            if buf.len() < 4 {
+                return Ok(None);
+            }
+            let len = buf.get_i32();
+            pos += 4;
+
+            if len < 0 {
+                ranges.push(None);
+                continue;
+            }
+
+            let len = len as usize;
+            if buf.len() < len {
+                return Ok(None);
+            }
+            buf.advance(len);
+            ranges.push(Some(pos - 2..pos - 2 + len));
+            pos += len;
+        }
+
+        let mut row = self.buf.split_to(pos).freeze();
+        row.advance(2);
+
+        Ok(Some(BinaryCopyOutRow {
+            buf: row,
+            ranges,
+            types: types.clone(),
+        }))
+    }
+}
+
+impl AsyncLendingIterator for BinaryCopyOutReader {
+    type Ok<'i>
+        = BinaryCopyOutRow
+    where
+        Self: 'i;
+    type Err = Error;
+
+    async fn try_next(&mut self) -> Result<Option<Self::Ok<'_>>, Self::Err> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await.transpose()
+    }
+}
+
+/// a row of binary copy data.
+pub struct BinaryCopyOutRow {
+    buf: Bytes,
+    ranges: Vec<Option<Range<usize>>>,
+    types: Arc<[Type]>,
+}
+
+impl BinaryCopyOutRow {
+    /// Returns the number of values in the row.
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns `true` if the row contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Deserializes a value from the row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds or if the value cannot be converted to the specified type.
+    pub fn get<'s, T>(&'s self, idx: usize) -> T
+    where
+        T: FromSql<'s>,
+    {
+        self.try_get(idx)
+            .unwrap_or_else(|e| panic!("error retrieving column {idx}: {e}"))
+    }
+
+    /// Like [`BinaryCopyOutRow::get`], but returns a `Result` rather than panicking.
+    pub fn try_get<'s, T>(&'s self, idx: usize) -> Result<T, Error>
+    where
+        T: FromSql<'s>,
+    {
+        let ty = self.types.get(idx).ok_or_else(|| InvalidColumnIndex(idx.to_string()))?;
+
+        if !T::accepts(ty) {
+            return Err(Error::from(WrongType::new::<T>(ty.clone())));
+        }
+
+        match self.ranges[idx] {
+            Some(ref range) => T::from_sql(ty, &self.buf[range.clone()]),
+            None => T::from_sql_null(ty),
+        }
+        .map_err(Into::into)
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn invalid_data(msg: &'static str) -> Error {
+    Error::from(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_row() {
+        let mut buf = BytesMut::new();
+        buf.put_slice(MAGIC);
+        buf.put_i32(0);
+        buf.put_i32(0);
+        buf.put_i16(2);
+        buf.put_i32(4);
+        buf.put_i32(9527);
+        buf.put_i32(-1);
+        buf.put_i16(-1);
+
+        let types: Arc<[Type]> = [Type::INT4, Type::TEXT].into();
+
+        // feed data byte by byte to simulate chunked copy data.
+        let mut parser = Parser::default();
+        let mut rows = Vec::new();
+        for b in buf.iter() {
+            parser.buf.put_u8(*b);
+            if let Some(row) = parser.try_parse(&types).unwrap() {
+                rows.push(row);
+            }
+        }
+
+        assert!(parser.trailer);
+        assert!(parser.buf.is_empty());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<i32>(0), 9527);
+        assert_eq!(rows[0].get::<Option<&str>>(1), None);
+        assert!(rows[0].try_get::<i32>(2).is_err());
+        assert!(rows[0].try_get::<&str>(0).is_err());
+    }
+}
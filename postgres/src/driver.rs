@@ -13,6 +13,9 @@ mod tls;
 #[cfg(feature = "quic")]
 pub(crate) mod quic;
 
+#[cfg(feature = "io-uring")]
+pub(crate) mod io_uring;
+
 use core::{
     future::{Future, IntoFuture},
     net::SocketAddr,
@@ -30,10 +33,10 @@ use xitca_io::{
 
 use super::{
     client::Client,
-    config::{Config, SslMode, SslNegotiation},
+    config::{Config, Host, LoadBalanceHosts, SslMode, SslNegotiation},
     error::{unexpected_eof_err, ConfigError, Error},
     iter::AsyncLendingIterator,
-    session::{ConnectInfo, Session},
+    session::{ConnectInfo, Session, TargetSessionAttrs},
 };
 
 use self::generic::GenericDriver;
@@ -53,11 +56,37 @@ pub(super) async fn connect(cfg: &mut Config) -> Result<(Client, Driver), Error>
         return Err(ConfigError::EmptyPort.into());
     }
 
+    let mut hosts = cfg.get_hosts().to_vec();
+    if matches!(cfg.get_load_balance_hosts(), LoadBalanceHosts::Random) {
+        shuffle(&mut hosts);
+    }
+
+    match cfg.get_target_session_attrs() {
+        // try to find a standby server first and fallback to any server.
+        TargetSessionAttrs::PreferStandby => match connect_hosts(&hosts, cfg).await {
+            Ok(res) => Ok(res),
+            Err(_) => {
+                cfg.target_session_attrs(TargetSessionAttrs::Any);
+                let res = connect_hosts(&hosts, cfg).await;
+                cfg.target_session_attrs(TargetSessionAttrs::PreferStandby);
+                res
+            }
+        },
+        _ => connect_hosts(&hosts, cfg).await,
+    }
+}
+
+async fn connect_hosts(hosts: &[Host], cfg: &mut Config) -> Result<(Client, Driver), Error> {
     let mut err = None;
-    let hosts = cfg.get_hosts().to_vec();
-    for host in hosts {
-        match self::connect::connect_host(host, cfg).await {
-            Ok((tx, session, drv)) => return Ok((Client::new(tx, session), drv)),
+    for host in hosts.iter().cloned() {
+        let res = match cfg.get_connect_timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, self::connect::connect_host(host, cfg))
+                .await
+                .unwrap_or_else(|_| Err(io::Error::from(io::ErrorKind::TimedOut).into())),
+            None => self::connect::connect_host(host, cfg).await,
+        };
+        match res {
+            Ok((tx, session, drv)) => return Ok((Client::new(tx, session, cfg), drv)),
             Err(e) => err = Some(e),
         }
     }
@@ -65,12 +94,23 @@ pub(super) async fn connect(cfg: &mut Config) -> Result<(Client, Driver), Error>
     Err(err.unwrap())
 }
 
+// Fisher-Yates shuffle with randomness from std hasher's random keys.
+fn shuffle<T>(slice: &mut [T]) {
+    use std::hash::{BuildHasher, RandomState};
+
+    let state = RandomState::new();
+    for i in (1..slice.len()).rev() {
+        let j = (state.hash_one(i) % (i as u64 + 1)) as usize;
+        slice.swap(i, j);
+    }
+}
+
 pub(super) async fn connect_io<Io>(io: Io, cfg: &mut Config) -> Result<(Client, Driver), Error>
 where
     Io: AsyncIo + Send + 'static,
 {
     let (tx, session, drv) = prepare_driver(ConnectInfo::default(), Box::new(io) as _, cfg).await?;
-    Ok((Client::new(tx, session), Driver::Dynamic(drv)))
+    Ok((Client::new(tx, session, cfg), Driver::Dynamic(drv)))
 }
 
 pub(super) async fn connect_info(info: ConnectInfo) -> Result<(DriverTx, Driver), Error> {
@@ -129,7 +169,7 @@ where
         SslMode::Disable => Ok(false),
         _ if matches!(ssl_negotiation, SslNegotiation::Direct) => Ok(true),
         mode => match (query_tls_availability(io).await?, mode) {
-            (false, SslMode::Require) => Err(Error::todo()),
+            (false, SslMode::Require | SslMode::VerifyCa | SslMode::VerifyFull) => Err(Error::todo()),
             (bool, _) => Ok(bool),
         },
     }
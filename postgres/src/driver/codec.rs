@@ -8,13 +8,17 @@ use core::{
 
 use postgres_protocol::message::backend;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
-use xitca_io::bytes::BytesMut;
+use xitca_io::bytes::{Buf, Bytes, BytesMut};
 
 use crate::{
     error::{Completed, DriverDownReceiving, Error},
+    pool::metrics::QueryTimer,
     types::BorrowToSql,
 };
 
+#[cfg(feature = "tracing")]
+use crate::trace::QuerySpan;
+
 use super::DriverTx;
 
 pub(super) fn request_pair(msg_count: usize) -> (ResponseSender, Response) {
@@ -25,6 +29,9 @@ pub(super) fn request_pair(msg_count: usize) -> (ResponseSender, Response) {
             rx,
             buf: BytesMut::new(),
             complete: false,
+            timer: None,
+            #[cfg(feature = "tracing")]
+            span: None,
         },
     )
 }
@@ -34,9 +41,23 @@ pub struct Response {
     rx: ResponseReceiver,
     buf: BytesMut,
     complete: bool,
+    // query timer of pool connection. it's dropped together with response.
+    timer: Option<QueryTimer>,
+    // tracing span of query. it's closed together with response.
+    #[cfg(feature = "tracing")]
+    span: Option<QuerySpan>,
 }
 
 impl Response {
+    pub(crate) fn set_timer(&mut self, timer: QueryTimer) {
+        self.timer = Some(timer);
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn set_span(&mut self, span: QuerySpan) {
+        self.span = Some(span);
+    }
+
     pub(crate) fn blocking_recv(&mut self) -> Result<backend::Message, Error> {
         if self.buf.is_empty() {
             let res = self.rx.blocking_recv();
@@ -57,6 +78,16 @@ impl Response {
         Poll::Ready(self.parse_message())
     }
 
+    // receive raw message frame in the form of (tag, body). used for message types not covered by
+    // postgres-protocol crate. (e.g: CopyBothResponse)
+    pub(crate) fn poll_recv_raw(&mut self, cx: &mut Context<'_>) -> Poll<Result<(u8, Bytes), Error>> {
+        if self.buf.is_empty() {
+            let res = ready!(self.rx.poll_recv(cx));
+            self.on_recv(res)?;
+        }
+        Poll::Ready(self.parse_raw())
+    }
+
     pub(crate) fn try_into_row_affected(mut self) -> impl Future<Output = Result<u64, Error>> + Send {
         let mut rows = 0;
         poll_fn(move |cx| {
@@ -117,18 +148,49 @@ impl Response {
                 return if self.complete {
                     Err(Completed.into())
                 } else {
-                    Err(DriverDownReceiving.into())
+                    Err(self.on_error(DriverDownReceiving.into()))
                 }
             }
         }
     }
 
+    fn parse_raw(&mut self) -> Result<(u8, Bytes), Error> {
+        let header = backend::Header::parse(&self.buf)?.expect("must not parse message from empty buffer.");
+        if header.tag() == backend::ERROR_RESPONSE_TAG {
+            return match self.parse_message() {
+                Err(e) => Err(e),
+                Ok(_) => Err(Error::unexpected()),
+            };
+        }
+        let mut frame = self.buf.split_to(header.len() as usize + 1).freeze();
+        frame.advance(5);
+        Ok((header.tag(), frame))
+    }
+
     fn parse_message(&mut self) -> Result<backend::Message, Error> {
         match backend::Message::parse(&mut self.buf)?.expect("must not parse message from empty buffer.") {
-            backend::Message::ErrorResponse(body) => Err(Error::db(body.fields())),
+            backend::Message::ErrorResponse(body) => Err(self.on_error(Error::db(body.fields()))),
+            #[cfg(feature = "tracing")]
+            backend::Message::CommandComplete(body) => {
+                if let Some(ref mut span) = self.span {
+                    span.on_rows(body_to_affected_rows(&body)?);
+                }
+                Ok(backend::Message::CommandComplete(body))
+            }
             msg => Ok(msg),
         }
     }
+
+    fn on_error(&self, e: Error) -> Error {
+        if let Some(ref timer) = self.timer {
+            timer.on_error(&e);
+        }
+        #[cfg(feature = "tracing")]
+        if let Some(ref span) = self.span {
+            span.on_error(&e);
+        }
+        e
+    }
 }
 
 // Extract the number of rows affected.
@@ -10,6 +10,9 @@ use crate::{
     types::{BorrowToSql, IsNull, Type},
 };
 
+#[cfg(feature = "tracing")]
+use crate::trace::TraceInfo;
+
 use super::{
     response::{
         IntoResponse, IntoRowStreamGuard, NoOpIntoRowStream, StatementCreateResponse, StatementCreateResponseBlocking,
@@ -42,6 +45,14 @@ pub trait Encode: sealed::Sealed + Sized {
     fn count_hint(&self) -> usize {
         1
     }
+
+    #[cfg(feature = "tracing")]
+    #[doc(hidden)]
+    /// describe the query for tracing span. None means the query is not traced.
+    #[inline(always)]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        None
+    }
 }
 
 impl sealed::Sealed for &str {}
@@ -54,6 +65,11 @@ impl Encode for &str {
         frontend::query(self, buf)?;
         Ok(Vec::new())
     }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(TraceInfo::new("simple_query", 0).statement(self))
+    }
 }
 
 impl sealed::Sealed for &Statement {}
@@ -63,13 +79,18 @@ impl<'s> Encode for &'s Statement {
 
     #[inline]
     fn encode<const SYNC_MODE: bool>(self, buf: &mut BytesMut) -> Result<Self::Output, Error> {
-        encode_bind(self.name(), self.params(), [] as [i32; 0], "", buf)?;
+        encode_statement_bind(self, [] as [i32; 0], "", buf)?;
         frontend::execute("", 0, buf)?;
         if SYNC_MODE {
             frontend::sync(buf);
         }
         Ok(self.columns())
     }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(TraceInfo::new("execute", 0).name(self.name()))
+    }
 }
 
 impl<C> sealed::Sealed for StatementCreate<'_, '_, C> {}
@@ -83,7 +104,17 @@ where
     #[inline]
     fn encode<const SYNC_MODE: bool>(self, buf: &mut BytesMut) -> Result<Self::Output, Error> {
         let Self { name, stmt, types, cli } = self;
-        encode_statement_create(&name, stmt, types, buf).map(|_| StatementCreateResponse { name, cli })
+        let sql = name.is_empty().then(|| Box::from(stmt));
+        encode_statement_create(&name, stmt, types, buf).map(|_| StatementCreateResponse { name, sql, cli })
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(
+            TraceInfo::new("prepare", self.types.len())
+                .statement(self.stmt)
+                .name(&self.name),
+        )
     }
 }
 
@@ -98,7 +129,17 @@ where
     #[inline]
     fn encode<const SYNC_MODE: bool>(self, buf: &mut BytesMut) -> Result<Self::Output, Error> {
         let Self { name, stmt, types, cli } = self;
-        encode_statement_create(&name, stmt, types, buf).map(|_| StatementCreateResponseBlocking { name, cli })
+        let sql = name.is_empty().then(|| Box::from(stmt));
+        encode_statement_create(&name, stmt, types, buf).map(|_| StatementCreateResponseBlocking { name, sql, cli })
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(
+            TraceInfo::new("prepare", self.types.len())
+                .statement(self.stmt)
+                .name(&self.name),
+        )
     }
 }
 
@@ -138,13 +179,18 @@ where
     #[inline]
     fn encode<const SYNC_MODE: bool>(self, buf: &mut BytesMut) -> Result<Self::Output, Error> {
         let StatementQuery { stmt, params } = self;
-        encode_bind(stmt.name(), stmt.params(), params, "", buf)?;
+        encode_statement_bind(stmt, params, "", buf)?;
         frontend::execute("", 0, buf)?;
         if SYNC_MODE {
             frontend::sync(buf);
         }
         Ok(stmt.columns())
     }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(TraceInfo::new("execute", self.stmt.params().len()).name(self.stmt.name()))
+    }
 }
 
 impl<C, P> sealed::Sealed for StatementUnnamedQuery<'_, '_, P, C> {}
@@ -173,12 +219,16 @@ where
         }
         Ok(IntoRowStreamGuard(cli))
     }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(TraceInfo::new("query", self.types.len()).statement(self.stmt))
+    }
 }
 
 pub(crate) struct PortalCreate<'a, P> {
     pub(crate) name: &'a str,
-    pub(crate) stmt: &'a str,
-    pub(crate) types: &'a [Type],
+    pub(crate) stmt: &'a Statement,
     pub(crate) params: P,
 }
 
@@ -192,13 +242,8 @@ where
 
     #[inline]
     fn encode<const SYNC_MODE: bool>(self, buf: &mut BytesMut) -> Result<Self::Output, Error> {
-        let PortalCreate {
-            name,
-            stmt,
-            types,
-            params,
-        } = self;
-        encode_bind(stmt, types, params, name, buf)?;
+        let PortalCreate { name, stmt, params } = self;
+        encode_statement_bind(stmt, params, name, buf)?;
         frontend::sync(buf);
         Ok(NoOpIntoRowStream)
     }
@@ -243,24 +288,39 @@ impl<'s> Encode for PortalQuery<'s> {
         frontend::sync(buf);
         Ok(columns)
     }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(TraceInfo::new("fetch", 0).name(self.name))
+    }
 }
 
 impl sealed::Sealed for PipelineQuery<'_, '_> {}
 
 impl<'s> Encode for PipelineQuery<'s, '_> {
-    type Output = Vec<&'s [Column]>;
+    type Output = (Vec<&'s [Column]>, bool);
 
     #[inline]
     fn encode<const SYNC_MODE: bool>(self, buf_drv: &mut BytesMut) -> Result<Self::Output, Error> {
-        let Self { columns, buf, .. } = self;
+        let Self {
+            columns,
+            buf,
+            sync_mode,
+            ..
+        } = self;
         buf_drv.extend_from_slice(buf);
-        Ok(columns)
+        Ok((columns, sync_mode))
     }
 
     #[inline(always)]
     fn count_hint(&self) -> usize {
         self.count
     }
+
+    #[cfg(feature = "tracing")]
+    fn trace_info(&self) -> Option<TraceInfo<'_>> {
+        Some(TraceInfo::new("pipeline", 0))
+    }
 }
 
 pub(crate) fn send_encode_query<S>(tx: &DriverTx, stmt: S) -> Result<(S::Output, Response), Error>
@@ -271,6 +331,18 @@ where
     tx.send(|buf| stmt.encode::<true>(buf), msg_count)
 }
 
+// statement prepared as unnamed statement is parsed again before binding as database session may change
+// between queries when connecting through pooler.
+fn encode_statement_bind<P>(stmt: &Statement, params: P, portal: &str, buf: &mut BytesMut) -> Result<(), Error>
+where
+    P: AsParams,
+{
+    if let Some(sql) = stmt.sql() {
+        frontend::parse("", sql, stmt.params().iter().map(Type::oid), buf)?;
+    }
+    encode_bind(stmt.name(), stmt.params(), params, portal, buf)
+}
+
 fn encode_bind<P>(stmt: &str, types: &[Type], params: P, portal: &str, buf: &mut BytesMut) -> Result<(), Error>
 where
     P: AsParams,
@@ -42,14 +42,15 @@ impl IntoResponse for Vec<Column> {
     }
 }
 
-impl sealed::Sealed for Vec<&[Column]> {}
+impl sealed::Sealed for (Vec<&[Column]>, bool) {}
 
-impl<'c> IntoResponse for Vec<&'c [Column]> {
+impl<'c> IntoResponse for (Vec<&'c [Column]>, bool) {
     type Response = PipelineStream<'c>;
 
     #[inline]
     fn into_response(self, res: Response) -> Self::Response {
-        PipelineStream::new(res, self)
+        let (columns, sync_mode) = self;
+        PipelineStream::new(res, columns, sync_mode)
     }
 }
 
@@ -85,6 +86,7 @@ impl IntoResponse for NoOpIntoRowStream {
 
 pub struct StatementCreateResponse<'a, C> {
     pub(super) name: String,
+    pub(super) sql: Option<Box<str>>,
     pub(super) cli: &'a C,
 }
 
@@ -98,7 +100,7 @@ where
 
     fn into_response(self, mut res: Response) -> Self::Response {
         Box::pin(async move {
-            let Self { name, cli } = self;
+            let Self { name, sql, cli } = self;
 
             match res.recv().await? {
                 backend::Message::ParseComplete => {}
@@ -133,13 +135,14 @@ where
                 }
             }
 
-            Ok(Statement::new(name, params, columns))
+            Ok(Statement::new(name, params, columns).with_sql(sql))
         })
     }
 }
 
 pub struct StatementCreateResponseBlocking<'a, C> {
     pub(super) name: String,
+    pub(super) sql: Option<Box<str>>,
     pub(super) cli: &'a C,
 }
 
@@ -152,7 +155,7 @@ where
     type Response = Result<Statement, Error>;
 
     fn into_response(self, mut res: Response) -> Self::Response {
-        let Self { name, cli } = self;
+        let Self { name, sql, cli } = self;
 
         match res.blocking_recv()? {
             backend::Message::ParseComplete => {}
@@ -187,6 +190,6 @@ where
             }
         }
 
-        Ok(Statement::new(name, params, columns))
+        Ok(Statement::new(name, params, columns).with_sql(sql))
     }
 }
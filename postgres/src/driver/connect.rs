@@ -45,7 +45,7 @@ pub(super) async fn connect_host(host: Host, cfg: &mut Config) -> Result<(Driver
                 #[cfg(feature = "tls")]
                 {
                     let io = super::tls::connect_tls(io, &host, cfg).await?;
-                    let info = ConnectInfo::new(Addr::Tcp(host, addr), ssl_mode, ssl_negotiation);
+                    let info = ConnectInfo::new(Addr::Tcp(host, addr), cfg);
                     prepare_driver(info, io, cfg)
                         .await
                         .map(|(tx, session, drv)| (tx, session, Driver::Tls(drv)))
@@ -55,7 +55,7 @@ pub(super) async fn connect_host(host: Host, cfg: &mut Config) -> Result<(Driver
                     Err(crate::error::FeatureError::Tls.into())
                 }
             } else {
-                let info = ConnectInfo::new(Addr::Tcp(host, addr), ssl_mode, ssl_negotiation);
+                let info = ConnectInfo::new(Addr::Tcp(host, addr), cfg);
                 prepare_driver(info, io, cfg)
                     .await
                     .map(|(tx, session, drv)| (tx, session, Driver::Tcp(drv)))
@@ -71,7 +71,7 @@ pub(super) async fn connect_host(host: Host, cfg: &mut Config) -> Result<(Driver
                 #[cfg(feature = "tls")]
                 {
                     let io = super::tls::connect_tls(io, host_str.as_ref(), cfg).await?;
-                    let info = ConnectInfo::new(Addr::Unix(host_str, host), ssl_mode, ssl_negotiation);
+                    let info = ConnectInfo::new(Addr::Unix(host_str, host), cfg);
                     prepare_driver(info, io, cfg)
                         .await
                         .map(|(tx, session, drv)| (tx, session, Driver::UnixTls(drv)))
@@ -81,7 +81,7 @@ pub(super) async fn connect_host(host: Host, cfg: &mut Config) -> Result<(Driver
                     Err(crate::error::FeatureError::Tls.into())
                 }
             } else {
-                let info = ConnectInfo::new(Addr::Unix(host_str, host), ssl_mode, ssl_negotiation);
+                let info = ConnectInfo::new(Addr::Unix(host_str, host), cfg);
                 prepare_driver(info, io, cfg)
                     .await
                     .map(|(tx, session, drv)| (tx, session, Driver::Unix(drv)))
@@ -92,7 +92,7 @@ pub(super) async fn connect_host(host: Host, cfg: &mut Config) -> Result<(Driver
         #[cfg(feature = "quic")]
         Host::Quic(host) => {
             let (io, addr) = super::quic::connect_quic(&host, cfg.get_ports()).await?;
-            let info = ConnectInfo::new(Addr::Quic(host, addr), ssl_mode, ssl_negotiation);
+            let info = ConnectInfo::new(Addr::Quic(host, addr), cfg);
             prepare_driver(info, io, cfg)
                 .await
                 .map(|(tx, session, drv)| (tx, session, Driver::Quic(drv)))
@@ -103,12 +103,11 @@ pub(super) async fn connect_host(host: Host, cfg: &mut Config) -> Result<(Driver
 #[cold]
 #[inline(never)]
 pub(super) async fn connect_info(info: ConnectInfo) -> Result<(DriverTx, Driver), Error> {
-    let ConnectInfo {
-        addr,
-        ssl_mode,
-        ssl_negotiation,
-    } = info;
-    match addr {
+    let ssl_mode = info.ssl_mode;
+    let ssl_negotiation = info.ssl_negotiation;
+    #[cfg(feature = "tls")]
+    let mut cfg = info.tls_config();
+    match info.addr {
         Addr::Tcp(_host, addr) => {
             let mut io = TcpStream::connect(addr).await?;
             let _ = io.set_nodelay(true);
@@ -116,7 +115,7 @@ pub(super) async fn connect_info(info: ConnectInfo) -> Result<(DriverTx, Driver)
             if should_connect_tls(&mut io, ssl_mode, ssl_negotiation).await? {
                 #[cfg(feature = "tls")]
                 {
-                    let io = super::tls::connect_tls(io, &_host, &mut Config::default()).await?;
+                    let io = super::tls::connect_tls(io, &_host, &mut cfg).await?;
                     let (io, tx) = GenericDriver::new(io);
                     Ok((tx, Driver::Tls(io)))
                 }
@@ -135,7 +134,7 @@ pub(super) async fn connect_info(info: ConnectInfo) -> Result<(DriverTx, Driver)
             if should_connect_tls(&mut io, ssl_mode, ssl_negotiation).await? {
                 #[cfg(feature = "tls")]
                 {
-                    let io = super::tls::connect_tls(io, &_host, &mut Config::default()).await?;
+                    let io = super::tls::connect_tls(io, &_host, &mut cfg).await?;
                     let (io, tx) = GenericDriver::new(io);
                     Ok((tx, Driver::UnixTls(io)))
                 }
@@ -1,11 +1,12 @@
 use core::{
     future::{poll_fn, Future},
+    ops::Deref,
     pin::Pin,
     task::Poll,
 };
 
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, VecDeque},
     io,
     sync::{Arc, Mutex},
 };
@@ -44,6 +45,11 @@ impl DriverTx {
         Arc::strong_count(&self.0) == 1
     }
 
+    // latest value of run-time parameter reported by server.
+    pub(crate) fn parameter(&self, name: &str) -> Option<Box<str>> {
+        self.0.guarded.lock().unwrap().params.get(name).cloned()
+    }
+
     pub(crate) fn send_one_way<F>(&self, func: F) -> Result<(), Error>
     where
         F: FnOnce(&mut BytesMut) -> Result<(), Error>,
@@ -92,7 +98,34 @@ pub(crate) struct SharedState {
 }
 
 impl SharedState {
-    fn wait(&self) -> impl Future<Output = WaitState> + '_ {
+    fn new() -> Self {
+        Self {
+            guarded: Mutex::new(State {
+                closed: false,
+                buf: BytesMut::new(),
+                res: VecDeque::new(),
+                params: BTreeMap::new(),
+            }),
+            waker: AtomicWaker::new(),
+        }
+    }
+}
+
+impl SharedState {
+    // take pending write buffer.
+    #[cfg(feature = "io-uring")]
+    pub(super) fn take_buf(&self) -> BytesMut {
+        self.guarded.lock().unwrap().buf.split()
+    }
+
+    #[cfg(feature = "io-uring")]
+    pub(super) fn close(&self) {
+        let mut inner = self.guarded.lock().unwrap();
+        inner.buf.clear();
+        inner.closed = true;
+    }
+
+    pub(super) fn wait(&self) -> impl Future<Output = WaitState> + '_ {
         poll_fn(|cx| {
             let inner = self.guarded.lock().unwrap();
             if !inner.buf.is_empty() {
@@ -108,7 +141,7 @@ impl SharedState {
     }
 }
 
-enum WaitState {
+pub(super) enum WaitState {
     WantWrite,
     WantClose,
 }
@@ -117,20 +150,33 @@ struct State {
     closed: bool,
     buf: BytesMut,
     res: VecDeque<ResponseSender>,
+    params: BTreeMap<Box<str>, Box<str>>,
 }
 
 pub struct GenericDriver<Io> {
     io: Io,
     read_buf: PagedBytesMut,
-    shared_state: Arc<SharedState>,
+    shared_state: SharedStateGuard,
+    // messages received before session is ready. emitted on first poll of driver.
+    pending: VecDeque<backend::Message>,
     read_state: ReadState,
     write_state: WriteState,
 }
 
 // in case driver is dropped without closing the shared state
-impl<Io> Drop for GenericDriver<Io> {
+struct SharedStateGuard(Arc<SharedState>);
+
+impl Deref for SharedStateGuard {
+    type Target = SharedState;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for SharedStateGuard {
     fn drop(&mut self) {
-        self.shared_state.guarded.lock().unwrap().closed = true;
+        self.0.guarded.lock().unwrap().closed = true;
     }
 }
 
@@ -151,20 +197,14 @@ where
     Io: AsyncIo + Send,
 {
     pub(crate) fn new(io: Io) -> (Self, DriverTx) {
-        let state = Arc::new(SharedState {
-            guarded: Mutex::new(State {
-                closed: false,
-                buf: BytesMut::new(),
-                res: VecDeque::new(),
-            }),
-            waker: AtomicWaker::new(),
-        });
+        let state = Arc::new(SharedState::new());
 
         (
             Self {
                 io,
                 read_buf: PagedBytesMut::new(),
-                shared_state: state.clone(),
+                shared_state: SharedStateGuard(state.clone()),
+                pending: VecDeque::new(),
                 read_state: ReadState::WantRead,
                 write_state: WriteState::Waiting,
             },
@@ -188,7 +228,35 @@ where
         self.recv_with(|buf| backend::Message::parse(buf).map_err(Error::from).transpose())
     }
 
+    // deconstruct driver into io, unprocessed read buffer, pending messages and shared state.
+    #[cfg(feature = "io-uring")]
+    pub(super) fn into_parts(self) -> (Io, BytesMut, VecDeque<backend::Message>, Arc<SharedState>) {
+        let Self {
+            io,
+            mut read_buf,
+            mut shared_state,
+            pending,
+            ..
+        } = self;
+        // leave a dummy state for guard to close on drop.
+        let state = core::mem::replace(&mut shared_state.0, Arc::new(SharedState::new()));
+        (io, read_buf.get_mut().split(), pending, state)
+    }
+
+    pub(crate) fn set_parameter(&mut self, name: &str, value: &str) {
+        let mut inner = self.shared_state.guarded.lock().unwrap();
+        inner.params.insert(name.into(), value.into());
+    }
+
+    pub(crate) fn push_pending(&mut self, msg: backend::Message) {
+        self.pending.push_back(msg);
+    }
+
     pub(crate) async fn try_next(&mut self) -> Result<Option<backend::Message>, Error> {
+        if let Some(msg) = self.pending.pop_front() {
+            return Ok(Some(msg));
+        }
+
         loop {
             if let Some(msg) = self.try_decode()? {
                 return Ok(Some(msg));
@@ -342,8 +410,15 @@ where
     }
 
     fn try_decode(&mut self) -> Result<Option<backend::Message>, Error> {
-        let mut inner = self.shared_state.guarded.lock().unwrap();
-        while let Some(res) = ResponseMessage::try_from_buf(self.read_buf.get_mut())? {
+        self.shared_state.decode(self.read_buf.get_mut())
+    }
+}
+
+impl SharedState {
+    // route decoded messages to their responses and return async message not belonging to any of them.
+    pub(super) fn decode(&self, buf: &mut BytesMut) -> Result<Option<backend::Message>, Error> {
+        let mut inner = self.guarded.lock().unwrap();
+        while let Some(res) = ResponseMessage::try_from_buf(buf)? {
             match res {
                 ResponseMessage::Normal(mut msg) => {
                     let front = inner.res.front_mut().ok_or_else(|| msg.parse_error())?;
@@ -354,7 +429,12 @@ where
                         SenderState::Continue => {}
                     }
                 }
-                ResponseMessage::Async(msg) => return Ok(Some(msg)),
+                ResponseMessage::Async(msg) => {
+                    if let backend::Message::ParameterStatus(ref body) = msg {
+                        inner.params.insert(body.name()?.into(), body.value()?.into());
+                    }
+                    return Ok(Some(msg));
+                }
             }
         }
         Ok(None)
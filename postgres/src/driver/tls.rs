@@ -1,17 +1,20 @@
-use std::sync::Arc;
+use std::{io, sync::Arc};
 
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use xitca_io::io::AsyncIo;
 use xitca_tls::rustls::{
     self,
-    client::danger::HandshakeSignatureValid,
+    client::{
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        WebPkiServerVerifier,
+    },
     crypto::{verify_tls12_signature, verify_tls13_signature},
-    pki_types::{CertificateDer, ServerName, UnixTime},
-    ClientConfig, ClientConnection, DigitallySignedStruct, RootCertStore, TlsStream,
+    pki_types::{pem::PemObject, CertificateDer, ServerName, UnixTime},
+    CertificateError, ClientConfig, ClientConnection, DigitallySignedStruct, RootCertStore, TlsStream,
 };
 
 use crate::{
-    config::{Config, SslNegotiation},
+    config::{Config, SslMode, SslNegotiation},
     error::Error,
 };
 
@@ -24,7 +27,10 @@ where
     Io: AsyncIo,
 {
     let name = ServerName::try_from(host).map_err(|_| Error::todo())?.to_owned();
-    let mut config = dangerous_config(Vec::new());
+    let mut config = match cfg.get_ssl_mode() {
+        mode @ (SslMode::VerifyCa | SslMode::VerifyFull) => verified_config(cfg, mode)?,
+        _ => dangerous_config(Vec::new()),
+    };
 
     if cfg.get_ssl_negotiation() == SslNegotiation::Direct {
         config.alpn_protocols = vec![b"postgresql".to_vec()];
@@ -35,18 +41,111 @@ where
 
     let stream = TlsStream::handshake(io, session).await?;
 
-    if let Some(sha256) = stream
+    if let Some(hash) = stream
         .session()
         .peer_certificates()
         .and_then(|certs| certs.first())
-        .map(|cert| Sha256::digest(cert.as_ref()))
+        .map(|cert| tls_server_end_point(cert.as_ref()))
     {
-        cfg.tls_server_end_point(sha256);
+        cfg.tls_server_end_point(hash);
     }
 
     Ok(stream)
 }
 
+// hash of server certificate for tls-server-end-point channel binding. (RFC 5929 section 4.1)
+// hash function follows certificate's signature algorithm and sha256 is used when it's md5, sha1 or
+// any algorithm can't be recognized.
+fn tls_server_end_point(cert: &[u8]) -> Vec<u8> {
+    const SHA384_RSA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0C];
+    const SHA512_RSA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0D];
+    const SHA384_ECDSA: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x03];
+    const SHA512_ECDSA: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x04];
+
+    match signature_algorithm(cert) {
+        Some(SHA384_RSA | SHA384_ECDSA) => Sha384::digest(cert).to_vec(),
+        Some(SHA512_RSA | SHA512_ECDSA) => Sha512::digest(cert).to_vec(),
+        _ => Sha256::digest(cert).to_vec(),
+    }
+}
+
+// extract object identifier of signatureAlgorithm field from DER encoded x509 certificate.
+fn signature_algorithm(cert: &[u8]) -> Option<&[u8]> {
+    // read one DER tag-length-value and return (tag, value, rest)
+    fn tlv(buf: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+        let (&tag, buf) = buf.split_first()?;
+        let (&len, mut buf) = buf.split_first()?;
+        let len = if len < 0x80 {
+            len as usize
+        } else {
+            let n = (len & 0x7f) as usize;
+            if n == 0 || n > 4 || buf.len() < n {
+                return None;
+            }
+            let (bytes, rest) = buf.split_at(n);
+            buf = rest;
+            bytes.iter().fold(0, |len, b| (len << 8) | *b as usize)
+        };
+        if buf.len() < len {
+            return None;
+        }
+        let (value, rest) = buf.split_at(len);
+        Some((tag, value, rest))
+    }
+
+    const SEQUENCE: u8 = 0x30;
+    const OID: u8 = 0x06;
+
+    let (SEQUENCE, cert, _) = tlv(cert)? else { return None };
+    // skip tbsCertificate
+    let (SEQUENCE, _, rest) = tlv(cert)? else { return None };
+    let (SEQUENCE, alg, _) = tlv(rest)? else { return None };
+    let (OID, oid, _) = tlv(alg)? else { return None };
+    Some(oid)
+}
+
+// config verifying server certificate against root certificates from config or built in webpki roots.
+fn verified_config(cfg: &Config, mode: SslMode) -> Result<ClientConfig, Error> {
+    let mut root_store = RootCertStore::empty();
+
+    match cfg.get_ssl_root_cert() {
+        Some(path) => {
+            let pem = std::fs::read(path)?;
+            for cert in CertificateDer::pem_slice_iter(&pem) {
+                let cert = cert.map_err(invalid_data)?;
+                root_store.add(cert).map_err(invalid_data)?;
+            }
+        }
+        None => root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+    }
+
+    let config = ClientConfig::builder();
+
+    let config = match mode {
+        SslMode::VerifyCa => {
+            let verifier = WebPkiServerVerifier::builder_with_provider(
+                Arc::new(root_store),
+                Arc::new(rustls::crypto::ring::default_provider()),
+            )
+            .build()
+            .map_err(invalid_data)?;
+            config
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(VerifyCaOnly(verifier)))
+        }
+        _ => config.with_root_certificates(root_store),
+    };
+
+    Ok(config.with_no_client_auth())
+}
+
+fn invalid_data<E>(e: E) -> Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    Error::from(io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
 pub(crate) fn dangerous_config(alpn: Vec<Vec<u8>>) -> xitca_tls::rustls::ClientConfig {
     let mut root_store = RootCertStore::empty();
 
@@ -62,6 +161,53 @@ pub(crate) fn dangerous_config(alpn: Vec<Vec<u8>>) -> xitca_tls::rustls::ClientC
     cfg
 }
 
+// verify certificate chain of server and skip host name check. (sslmode=verify-ca)
+#[derive(Debug)]
+struct VerifyCaOnly(Arc<WebPkiServerVerifier>);
+
+impl ServerCertVerifier for VerifyCaOnly {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        match self
+            .0
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp, now)
+        {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            )) => Ok(ServerCertVerified::assertion()),
+            res => res,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.0.supported_verify_schemes()
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct SkipServerVerification;
 
@@ -71,7 +217,7 @@ impl SkipServerVerification {
     }
 }
 
-impl rustls::client::danger::ServerCertVerifier for SkipServerVerification {
+impl ServerCertVerifier for SkipServerVerification {
     fn verify_server_cert(
         &self,
         _end_entity: &CertificateDer<'_>,
@@ -79,8 +225,8 @@ impl rustls::client::danger::ServerCertVerifier for SkipServerVerification {
         _server_name: &ServerName<'_>,
         _ocsp: &[u8],
         _now: UnixTime,
-    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
-        Ok(rustls::client::danger::ServerCertVerified::assertion())
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
     }
 
     fn verify_tls12_signature(
@@ -117,3 +263,25 @@ impl rustls::client::danger::ServerCertVerifier for SkipServerVerification {
             .supported_schemes()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn end_point_hash() {
+        for (alg, len) in [
+            (&rcgen::PKCS_ECDSA_P256_SHA256, 32),
+            (&rcgen::PKCS_ECDSA_P384_SHA384, 48),
+        ] {
+            let key = rcgen::KeyPair::generate_for(alg).unwrap();
+            let cert = rcgen::CertificateParams::new(vec!["localhost".to_string()])
+                .unwrap()
+                .self_signed(&key)
+                .unwrap();
+            assert_eq!(tls_server_end_point(cert.der()).len(), len);
+        }
+
+        assert_eq!(signature_algorithm(b"not a certificate"), None);
+    }
+}
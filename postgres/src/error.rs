@@ -17,7 +17,7 @@ use std::{backtrace::Backtrace, error, io};
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::ErrorFields;
 
-use super::from_sql::FromSqlError;
+use super::{from_sql::FromSqlError, session::TargetSessionAttrs};
 
 pub use self::sql_state::SqlState;
 
@@ -305,6 +305,7 @@ pub enum ConfigError {
     MissingUserName,
     MissingPassWord,
     WrongPassWord,
+    MissingChannelBinding,
 }
 
 impl fmt::Display for ConfigError {
@@ -316,6 +317,7 @@ impl fmt::Display for ConfigError {
             Self::MissingUserName => f.write_str("username is missing"),
             Self::MissingPassWord => f.write_str("password is missing"),
             Self::WrongPassWord => f.write_str("password is wrong"),
+            Self::MissingChannelBinding => f.write_str("channel binding is required but can't be used"),
         }
     }
 }
@@ -364,6 +366,98 @@ impl error::Error for FeatureError {}
 
 from_impl!(FeatureError);
 
+/// error indicate [`Pool`] failed to hand out a connection before acquire timeout is reached.
+///
+/// [`Pool`]: crate::pool::Pool
+#[derive(Debug)]
+pub struct PoolTimeout;
+
+impl fmt::Display for PoolTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Pool failed to hand out connection before acquire timeout")
+    }
+}
+
+impl error::Error for PoolTimeout {}
+
+from_impl!(PoolTimeout);
+
+/// error indicate connected server does not satisfy [`TargetSessionAttrs`] requirement.
+///
+/// [`TargetSessionAttrs`]: crate::TargetSessionAttrs
+#[derive(Debug)]
+pub struct TargetSessionMismatch(pub TargetSessionAttrs);
+
+impl fmt::Display for TargetSessionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "server does not satisfy target session attribute: {:?}", self.0)
+    }
+}
+
+impl error::Error for TargetSessionMismatch {}
+
+from_impl!(TargetSessionMismatch);
+
+/// error indicate [`Interval`] can not be converted from/to fixed length duration type.
+///
+/// [`Interval`]: crate::types::Interval
+#[derive(Debug)]
+pub struct IntervalOutOfRange;
+
+impl fmt::Display for IntervalOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Interval is out of range or contains months of variable length")
+    }
+}
+
+impl error::Error for IntervalOutOfRange {}
+
+from_impl!(IntervalOutOfRange);
+
+/// error indicate [`Cidr`] is constructed with prefix length longer than address or with bits set to the right
+/// of prefix.
+///
+/// [`Cidr`]: crate::types::Cidr
+#[derive(Debug)]
+pub struct InvalidCidr;
+
+impl fmt::Display for InvalidCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid cidr value: prefix length out of range or bits set to right of mask")
+    }
+}
+
+impl error::Error for InvalidCidr {}
+
+from_impl!(InvalidCidr);
+
+/// error indicate query produced unexpected number of rows.
+/// returned by [`Execute::query_one`], [`Execute::query_opt`] and [`Execute::query_scalar`].
+///
+/// [`Execute::query_one`]: crate::Execute::query_one
+/// [`Execute::query_opt`]: crate::Execute::query_opt
+/// [`Execute::query_scalar`]: crate::Execute::query_scalar
+#[derive(Debug, PartialEq, Eq)]
+pub enum RowCountMismatch {
+    /// query produced no row while exactly one row is expected.
+    Zero,
+    /// query produced more than one row.
+    Many,
+}
+
+impl fmt::Display for RowCountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zero => f.write_str("query returned no row while exactly one row is expected"),
+            Self::Many => f.write_str("query returned more than one row while at most one row is expected"),
+        }
+    }
+}
+
+impl error::Error for RowCountMismatch {}
+
+from_impl!(RowCountMismatch);
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum RuntimeError {
     RequireNoTokio,
@@ -430,7 +524,7 @@ pub struct DbError {
 }
 
 impl DbError {
-    fn parse(fields: &mut ErrorFields<'_>) -> io::Result<DbError> {
+    pub(crate) fn parse(fields: &mut ErrorFields<'_>) -> io::Result<DbError> {
         let mut severity = None;
         let mut parsed_severity = None;
         let mut code = None;
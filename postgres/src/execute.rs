@@ -1,6 +1,16 @@
 mod async_impl;
 mod sync_impl;
 
+use core::future::Future;
+
+use postgres_types::FromSql;
+
+use crate::{
+    error::{Error, RowCountMismatch},
+    query::{QueryAs, RowStreamFuture, RowStreamMap},
+    row::FromRow,
+};
+
 /// Defining how a query is executed. can be used for customizing encoding, executing and database
 /// data decoding.
 ///
@@ -40,6 +50,120 @@ where
 
     /// define how a statement is queried with repeated response
     fn query(self, cli: C) -> Self::QueryOutput;
+
+    /// [`Execute::query`] with rows converted to type implementing [`FromRow`] trait.
+    ///
+    /// # Examples
+    /// ```
+    /// # use xitca_postgres::{iter::AsyncLendingIterator, row::{FromRow, Row}, Client, Error, Execute, Statement};
+    /// struct Id(i32);
+    ///
+    /// impl FromRow for Id {
+    ///     fn from_row(row: &Row<'_>) -> Result<Self, Error> {
+    ///         row.try_get("id").map(Id)
+    ///     }
+    /// }
+    ///
+    /// # async fn query_as(client: &Client) -> Result<(), Error> {
+    /// let stmt = Statement::named("SELECT id FROM users", &[]).execute(client).await?;
+    /// let mut stream = stmt.query_as::<Id>(client).await?;
+    /// while let Some(Id(id)) = stream.try_next().await? {
+    ///     println!("{id}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`FromRow`]: crate::row::FromRow
+    #[inline]
+    fn query_as<T>(self, cli: C) -> QueryAs<Self::QueryOutput, T> {
+        QueryAs::new(self.query(cli))
+    }
+
+    /// [`Execute::query`] expecting exactly one row and convert it to type implementing [`FromRow`] trait.
+    /// [`RowCountMismatch`] error is returned when query produces no row or more than one row.
+    ///
+    /// # Examples
+    /// ```
+    /// # use xitca_postgres::{Client, Error, Execute, Statement};
+    /// # async fn query_one(client: &Client) -> Result<(), Error> {
+    /// let stmt = Statement::named("SELECT id, name FROM users WHERE id = $1", &[]).execute(client).await?;
+    /// // tuple is mapped to columns by index.
+    /// let (id, name) = stmt.bind([1]).query_one::<(i32, String)>(client).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`FromRow`]: crate::row::FromRow
+    /// [`RowCountMismatch`]: crate::error::RowCountMismatch
+    fn query_one<T>(self, cli: C) -> impl Future<Output = Result<T, Error>> + Send
+    where
+        Self::QueryOutput: RowStreamFuture,
+        T: FromRow + Send,
+    {
+        let fut = self.query(cli);
+        async move {
+            let mut stream = fut.await?;
+            let t = stream.try_next_map(T::from_row).await?.ok_or(RowCountMismatch::Zero)?;
+            ensure_no_more(&mut stream).await?;
+            Ok(t)
+        }
+    }
+
+    /// [`Execute::query`] expecting at most one row and convert it to type implementing [`FromRow`] trait.
+    /// resolve to `None` when query produces no row. [`RowCountMismatch`] error is returned when query produces
+    /// more than one row.
+    ///
+    /// [`FromRow`]: crate::row::FromRow
+    /// [`RowCountMismatch`]: crate::error::RowCountMismatch
+    fn query_opt<T>(self, cli: C) -> impl Future<Output = Result<Option<T>, Error>> + Send
+    where
+        Self::QueryOutput: RowStreamFuture,
+        T: FromRow + Send,
+    {
+        let fut = self.query(cli);
+        async move {
+            let mut stream = fut.await?;
+            let t = stream.try_next_map(T::from_row).await?;
+            if t.is_some() {
+                ensure_no_more(&mut stream).await?;
+            }
+            Ok(t)
+        }
+    }
+
+    /// [`Execute::query`] expecting exactly one row and decode it's first column to given type.
+    /// [`RowCountMismatch`] error is returned when query produces no row or more than one row.
+    ///
+    /// # Examples
+    /// ```
+    /// # use xitca_postgres::{Client, Error, Execute, Statement};
+    /// # async fn query_scalar(client: &Client) -> Result<(), Error> {
+    /// let stmt = Statement::named("SELECT count(*) FROM users", &[]).execute(client).await?;
+    /// let count = stmt.query_scalar::<i64>(client).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`RowCountMismatch`]: crate::error::RowCountMismatch
+    fn query_scalar<T>(self, cli: C) -> impl Future<Output = Result<T, Error>> + Send
+    where
+        Self::QueryOutput: RowStreamFuture,
+        T: for<'r> FromSql<'r> + Send,
+    {
+        let fut = self.query_one::<(T,)>(cli);
+        async move { fut.await.map(|(t,)| t) }
+    }
+}
+
+async fn ensure_no_more<S>(stream: &mut S) -> Result<(), Error>
+where
+    S: RowStreamMap,
+{
+    match stream.try_next_map(|_| Ok(())).await? {
+        Some(_) => Err(RowCountMismatch::Many.into()),
+        None => Ok(()),
+    }
 }
 
 /// blocking version of [`Execute`] for synchronous environment
@@ -0,0 +1,408 @@
+//! large object api wrapping `lo_*` server side functions.
+//!
+//! large object is stored in `pg_largeobject` system table and referenced by it's [`Oid`]. content of large object
+//! is accessed through a descriptor opened by [`LargeObject::open`] which is only valid inside the transaction it's
+//! opened in.
+//!
+//! [`LargeObject`] offers async methods mapping to server side functions and [`LargeObjectIo`] adapts it to
+//! [`AsyncRead`], [`AsyncWrite`] and [`AsyncSeek`] traits of tokio.
+//!
+//! # Examples
+//! ```
+//! # use xitca_postgres::{Client, Error};
+//! # async fn large_object(client: &mut Client) -> Result<(), Error> {
+//! use xitca_postgres::large_object::{self, LargeObject, Mode};
+//!
+//! let tx = client.transaction().await?;
+//!
+//! let oid = large_object::create(&tx).await?;
+//! let mut lo = LargeObject::open(&tx, oid, Mode::ReadWrite).await?;
+//! lo.write(b"hello,world").await?;
+//! lo.seek(std::io::SeekFrom::Start(0)).await?;
+//! assert_eq!(lo.read(5).await?, b"hello");
+//! lo.close().await?;
+//!
+//! tx.commit().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use core::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use std::io::{self, SeekFrom};
+
+use postgres_types::FromSql;
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+use xitca_io::bytes::{Buf, Bytes};
+
+use crate::{
+    client::ClientBorrowMut,
+    error::Error,
+    execute::Execute,
+    prepare::Prepare,
+    statement::Statement,
+    transaction::Transaction,
+    types::{Oid, ToSql, Type},
+    BoxedFuture,
+};
+
+// flags of lo_open. defined in libpq-fs.h
+const INV_WRITE: i32 = 0x0002_0000;
+const INV_READ: i32 = 0x0004_0000;
+
+// upper bound of data read or written by a single query of io adapter.
+const MAX_CHUNK: usize = 1024 * 1024;
+
+/// create a new empty large object and return it's [`Oid`].
+pub async fn create<C>(cli: &C) -> Result<Oid, Error>
+where
+    C: Prepare + Sync,
+{
+    select(cli, "SELECT lo_create($1)", &[Type::OID], [&0u32]).await
+}
+
+/// delete large object with given [`Oid`].
+pub async fn unlink<C>(cli: &C, oid: Oid) -> Result<(), Error>
+where
+    C: Prepare + Sync,
+{
+    select::<_, i32, 1>(cli, "SELECT lo_unlink($1)", &[Type::OID], [&oid])
+        .await
+        .map(|_| ())
+}
+
+/// access mode of opened large object.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// read only. content is a snapshot of large object at the time transaction started.
+    Read,
+    /// write only. in practice postgres allows reading from large object opened in this mode and the content
+    /// reflects writes made by current transaction.
+    Write,
+    /// read and write.
+    ReadWrite,
+}
+
+impl Mode {
+    const fn flags(self) -> i32 {
+        match self {
+            Self::Read => INV_READ,
+            Self::Write => INV_WRITE,
+            Self::ReadWrite => INV_READ | INV_WRITE,
+        }
+    }
+}
+
+/// descriptor of an opened large object.
+///
+/// descriptor is bound to the transaction it's opened in and it's closed by database when transaction ends.
+/// [`LargeObject::close`] can be used to close it early.
+pub struct LargeObject<'t, C> {
+    cli: &'t C,
+    fd: i32,
+}
+
+impl<'t, 'a, C> LargeObject<'t, Transaction<'a, C>>
+where
+    C: Prepare + ClientBorrowMut + Sync,
+{
+    /// open large object with given [`Oid`] inside transaction.
+    pub async fn open(tx: &'t Transaction<'a, C>, oid: Oid, mode: Mode) -> Result<Self, Error> {
+        let fd = select(
+            tx,
+            "SELECT lo_open($1, $2)",
+            &[Type::OID, Type::INT4],
+            [&oid, &mode.flags()],
+        )
+        .await?;
+        Ok(Self { cli: tx, fd })
+    }
+}
+
+impl<'t, C> LargeObject<'t, C>
+where
+    C: Prepare + Sync,
+{
+    /// read up to len bytes from current position. an empty buffer is returned when position is at the end of
+    /// large object.
+    pub fn read(&mut self, len: usize) -> impl Future<Output = Result<Vec<u8>, Error>> + Send + 't {
+        read(self.cli, self.fd, len)
+    }
+
+    /// write data at current position and return the amount of bytes written.
+    pub fn write(&mut self, data: &[u8]) -> impl Future<Output = Result<usize, Error>> + Send + 't {
+        write(self.cli, self.fd, data.to_vec())
+    }
+
+    /// change current position and return the new position from start of large object.
+    pub fn seek(&mut self, pos: SeekFrom) -> impl Future<Output = Result<u64, Error>> + Send + 't {
+        seek(self.cli, self.fd, pos)
+    }
+
+    /// return current position from start of large object.
+    pub async fn tell(&mut self) -> Result<u64, Error> {
+        let pos = select::<_, i64, 1>(self.cli, "SELECT lo_tell64($1)", &[Type::INT4], [&self.fd]).await?;
+        Ok(pos as u64)
+    }
+
+    /// truncate or extend large object to given length. extended part is filled with zero.
+    pub async fn truncate(&mut self, len: u64) -> Result<(), Error> {
+        let len = i64::try_from(len).map_err(|_| invalid_input("length of large object overflows"))?;
+        select::<_, i32, 2>(
+            self.cli,
+            "SELECT lo_truncate64($1, $2)",
+            &[Type::INT4, Type::INT8],
+            [&self.fd, &len],
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// close descriptor of large object.
+    pub async fn close(self) -> Result<(), Error> {
+        select::<_, i32, 1>(self.cli, "SELECT lo_close($1)", &[Type::INT4], [&self.fd])
+            .await
+            .map(|_| ())
+    }
+
+    /// convert to an adapter implementing tokio's [`AsyncRead`], [`AsyncWrite`] and [`AsyncSeek`] traits.
+    pub fn into_io(self) -> LargeObjectIo<'t, C> {
+        LargeObjectIo {
+            lo: self,
+            op: None,
+            buf: Bytes::new(),
+        }
+    }
+}
+
+/// [`LargeObject`] adapter implementing tokio's [`AsyncRead`], [`AsyncWrite`] and [`AsyncSeek`] traits.
+///
+/// every read and write is a query to database and it's advised to wrap the adapter with buffered reader and
+/// writer like [`tokio::io::BufReader`] and [`tokio::io::BufWriter`]. Read data is buffered when it exceeds the
+/// buffer given by caller and the position of large object is adjusted accordingly before write and seek.
+///
+/// An operation in progress must be driven to completion by the same kind of poll method that started it.
+/// result of write interrupted by read or seek is discarded.
+pub struct LargeObjectIo<'t, C> {
+    lo: LargeObject<'t, C>,
+    op: Option<BoxedFuture<'t, Result<Op, Error>>>,
+    buf: Bytes,
+}
+
+enum Op {
+    Read(Vec<u8>),
+    Write(usize),
+    Seek(u64),
+}
+
+impl<'t, C> LargeObjectIo<'t, C>
+where
+    C: Prepare + Sync,
+{
+    /// take back [`LargeObject`]. position of large object may not match what has been read from adapter when
+    /// read data is buffered.
+    pub fn into_inner(self) -> LargeObject<'t, C> {
+        self.lo
+    }
+
+    fn start_read(&mut self, len: usize) {
+        let fut = read(self.lo.cli, self.lo.fd, len.min(MAX_CHUNK));
+        self.op = Some(Box::pin(async move { fut.await.map(Op::Read) }));
+    }
+
+    fn start_write(&mut self, data: &[u8]) {
+        let fut = write(self.lo.cli, self.lo.fd, data[..data.len().min(MAX_CHUNK)].to_vec());
+        self.op = Some(Box::pin(async move { fut.await.map(Op::Write) }));
+    }
+
+    fn start_seek(&mut self, pos: SeekFrom) {
+        let fut = seek(self.lo.cli, self.lo.fd, pos);
+        self.op = Some(Box::pin(async move { fut.await.map(Op::Seek) }));
+    }
+
+    // drive operation in progress to completion. data of read operation is buffered.
+    fn poll_op(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Option<Op>>> {
+        let Some(ref mut fut) = self.op else {
+            return Poll::Ready(Ok(None));
+        };
+        let res = ready!(fut.as_mut().poll(cx));
+        self.op = None;
+        let mut op = res.map_err(io::Error::other)?;
+        if let Op::Read(ref mut data) = op {
+            self.buf = Bytes::from(mem::take(data));
+        }
+        Poll::Ready(Ok(Some(op)))
+    }
+
+    // move position of large object back to where caller has read to.
+    fn unread(&mut self) -> bool {
+        if self.buf.is_empty() {
+            return false;
+        }
+        let len = self.buf.len() as i64;
+        self.buf.clear();
+        self.start_seek(SeekFrom::Current(-len));
+        true
+    }
+}
+
+impl<C> AsyncRead for LargeObjectIo<'_, C>
+where
+    C: Prepare + Sync,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if !this.buf.is_empty() {
+                let len = this.buf.len().min(buf.remaining());
+                buf.put_slice(&this.buf[..len]);
+                this.buf.advance(len);
+                return Poll::Ready(Ok(()));
+            }
+
+            // read operation yields empty buffer at the end of large object.
+            if let Some(Op::Read(_)) = ready!(this.poll_op(cx))? {
+                if this.buf.is_empty() {
+                    return Poll::Ready(Ok(()));
+                }
+                continue;
+            }
+
+            if buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+
+            this.start_read(buf.remaining());
+        }
+    }
+}
+
+impl<C> AsyncWrite for LargeObjectIo<'_, C>
+where
+    C: Prepare + Sync,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(Op::Write(n)) = ready!(this.poll_op(cx))? {
+                return Poll::Ready(Ok(n));
+            }
+
+            if this.unread() {
+                continue;
+            }
+
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            this.start_write(buf);
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // write is finished when it's operation is completed. there is nothing to flush.
+        ready!(self.get_mut().poll_op(cx))?;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<C> AsyncSeek for LargeObjectIo<'_, C>
+where
+    C: Prepare + Sync,
+{
+    fn start_seek(self: Pin<&mut Self>, pos: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+        if this.op.is_some() {
+            return Err(io::Error::other("other operation of large object is in progress"));
+        }
+        // position of large object is ahead of caller by the amount of buffered data.
+        let pos = match pos {
+            SeekFrom::Current(offset) => SeekFrom::Current(offset - this.buf.len() as i64),
+            pos => pos,
+        };
+        this.buf.clear();
+        this.start_seek(pos);
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(Op::Seek(pos)) = ready!(this.poll_op(cx))? {
+                return Poll::Ready(Ok(pos));
+            }
+
+            // no seek in progress. report current position.
+            if !this.unread() {
+                this.start_seek(SeekFrom::Current(0));
+            }
+        }
+    }
+}
+
+async fn read<C>(cli: &C, fd: i32, len: usize) -> Result<Vec<u8>, Error>
+where
+    C: Prepare + Sync,
+{
+    let len = i32::try_from(len).unwrap_or(i32::MAX);
+    select(cli, "SELECT loread($1, $2)", &[Type::INT4, Type::INT4], [&fd, &len]).await
+}
+
+async fn write<C>(cli: &C, fd: i32, data: Vec<u8>) -> Result<usize, Error>
+where
+    C: Prepare + Sync,
+{
+    let n = select::<_, i32, 2>(cli, "SELECT lowrite($1, $2)", &[Type::INT4, Type::BYTEA], [&fd, &data]).await?;
+    Ok(n as usize)
+}
+
+async fn seek<C>(cli: &C, fd: i32, pos: SeekFrom) -> Result<u64, Error>
+where
+    C: Prepare + Sync,
+{
+    // whence of lo_lseek64. same as SEEK_SET, SEEK_CUR and SEEK_END in C.
+    let (offset, whence) = match pos {
+        SeekFrom::Start(offset) => (
+            i64::try_from(offset).map_err(|_| invalid_input("seek offset overflows"))?,
+            0i32,
+        ),
+        SeekFrom::Current(offset) => (offset, 1),
+        SeekFrom::End(offset) => (offset, 2),
+    };
+    let pos = select::<_, i64, 3>(
+        cli,
+        "SELECT lo_lseek64($1, $2, $3)",
+        &[Type::INT4, Type::INT8, Type::INT4],
+        [&fd, &offset, &whence],
+    )
+    .await?;
+    Ok(pos as u64)
+}
+
+// select a single value returned by server side function.
+async fn select<C, T, const N: usize>(
+    cli: &C,
+    sql: &str,
+    types: &[Type],
+    params: [&(dyn ToSql + Sync); N],
+) -> Result<T, Error>
+where
+    C: Prepare + Sync,
+    T: for<'r> FromSql<'r> + Send,
+{
+    Statement::unnamed(sql, types).bind(params).query_scalar(cli).await
+}
+
+fn invalid_input(msg: &'static str) -> Error {
+    Error::from(io::Error::new(io::ErrorKind::InvalidInput, msg))
+}
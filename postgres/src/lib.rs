@@ -11,12 +11,18 @@ mod from_sql;
 mod prepare;
 mod query;
 mod session;
+mod util;
 
+pub mod bulk;
 pub mod copy;
 pub mod error;
 pub mod iter;
+pub mod large_object;
+pub mod notify;
 pub mod pipeline;
 pub mod pool;
+pub mod reconnect;
+pub mod replication;
 pub mod row;
 pub mod statement;
 pub mod transaction;
@@ -27,15 +33,27 @@ pub mod proxy;
 #[cfg(feature = "quic")]
 pub use driver::quic::QuicStream;
 
+#[cfg(feature = "io-uring")]
+pub use driver::io_uring::UringDriver;
+
+#[cfg(feature = "tracing")]
+mod trace;
+
+#[cfg(feature = "codegen")]
+pub use xitca_postgres_codegen::query;
+
 pub use self::{
     client::Client,
-    config::Config,
+    config::{ChannelBinding, Config, LoadBalanceHosts, ReplicationMode, SslMode, SslNegotiation},
     driver::Driver,
     error::Error,
     execute::{Execute, ExecuteBlocking},
     from_sql::FromSqlExt,
-    query::{RowSimpleStream, RowSimpleStreamOwned, RowStream, RowStreamOwned},
-    session::Session,
+    query::{
+        RowSimpleStream, RowSimpleStreamOwned, RowStream, RowStreamAs, RowStreamOwned, SimpleQueryMessage,
+        SimpleQueryStream,
+    },
+    session::{Session, TargetSessionAttrs},
     statement::Statement,
 };
 
@@ -91,7 +109,7 @@ pub mod dev {
     pub use crate::copy::r#Copy;
     pub use crate::driver::codec::{encode::Encode, response::IntoResponse, Response};
     pub use crate::prepare::Prepare;
-    pub use crate::query::Query;
+    pub use crate::query::{Query, RowStreamFuture, RowStreamMap};
 }
 
 use core::{future::Future, pin::Pin, sync::atomic::AtomicUsize};
@@ -153,6 +171,17 @@ impl Postgres {
         driver::connect_io(io, &mut cfg).await
     }
 
+    /// Connect to database with plain tcp connection and drive it with io-uring, returning [Client] and
+    /// [UringDriver] on success. must be called inside tokio-uring runtime.
+    ///
+    /// connection is established with tokio's tcp stream and then handed over to io-uring. tls and unix socket
+    /// connection are not supported.
+    #[cfg(feature = "io-uring")]
+    pub async fn connect_io_uring(self) -> Result<(Client, UringDriver), Error> {
+        let (cli, drv) = self.connect().await?;
+        UringDriver::try_from_driver(drv).map(|drv| (cli, drv))
+    }
+
     #[cfg(feature = "quic")]
     pub async fn connect_quic(self) -> Result<(Client, Driver), Error> {
         use config::Host;
@@ -7,6 +7,14 @@
 //! - flexible transform between sync and un-sync pipeline. See [Pipeline::new] for detail
 //! - ordered response handling with a single stream type. reduce memory footprint and possibility of deadlock
 //!
+//! # Error handling
+//! how an error of one query affects other queries in the same pipeline depends on pipeline's sync mode:
+//! - sync pipeline (see [Pipeline::new]) runs every query in it's own implicit transaction. a failed query only
+//!   produce an error for itself and [PipelineStream] can continue to be iterated for responses of following queries.
+//! - un-sync pipeline (see [Pipeline::unsync]) runs all queries in one implicit transaction. a failed query aborts
+//!   all queries after it and rolls back changes made by queries before it. [PipelineStream] produce the error and
+//!   then finishes. the number of aborted queries can be observed with [PipelineStream::aborted].
+//!
 //! [`tokio-postgres`]: https://docs.rs/tokio-postgres/latest/tokio_postgres/#pipelining
 use core::ops::{Deref, DerefMut, Range};
 
@@ -258,6 +266,7 @@ where
 
 pub struct PipelineQuery<'a, 'b> {
     pub(crate) count: usize,
+    pub(crate) sync_mode: bool,
     pub(crate) columns: Vec<&'a [Column]>,
     pub(crate) buf: &'b [u8],
 }
@@ -296,6 +305,7 @@ where
 
         cli._query(PipelineQuery {
             count,
+            sync_mode: SYNC_MODE,
             columns,
             buf: buf.as_ref(),
         })
@@ -316,9 +326,11 @@ where
 
         loop {
             match res.res.blocking_recv()? {
+                backend::Message::ParseComplete => {}
                 backend::Message::BindComplete => {
+                    res.pending = true;
                     let item = PipelineItem {
-                        finished: false,
+                        pending: &mut res.pending,
                         res: &mut res.res,
                         ranges: &mut res.ranges,
                         columns: res.columns.pop_front(),
@@ -326,7 +338,7 @@ where
                     row_affected += item.row_affected_blocking()?;
                 }
                 backend::Message::ReadyForQuery(_) => {
-                    if res.columns.is_empty() {
+                    if res.columns.is_empty() || !res.sync_mode {
                         return Ok(row_affected);
                     }
                 }
@@ -346,16 +358,46 @@ pub struct PipelineStream<'a> {
     res: Response,
     columns: Columns<'a>,
     ranges: Ranges,
+    sync_mode: bool,
+    // an item is emitted and it's response is not fully received yet.
+    pending: bool,
+    aborted: usize,
 }
 
 impl<'a> PipelineStream<'a> {
-    pub(crate) const fn new(res: Response, columns: Vec<&'a [Column]>) -> Self {
+    pub(crate) const fn new(res: Response, columns: Vec<&'a [Column]>, sync_mode: bool) -> Self {
         Self {
             res,
             columns: Columns { columns, next: 0 },
             ranges: Vec::new(),
+            sync_mode,
+            pending: false,
+            aborted: 0,
         }
     }
+
+    /// number of queries aborted by database because of error from previous query in un-sync pipeline.
+    /// it's only meaningful after stream produces an error. sync pipeline always returns 0.
+    #[inline]
+    pub fn aborted(&self) -> usize {
+        self.aborted
+    }
+
+    fn on_error(&mut self) {
+        // error of a query that has not emitted it's item.
+        if !self.pending && !self.columns.is_empty() {
+            self.columns.pop_front();
+        }
+        self.pending = false;
+        if !self.sync_mode {
+            self.abort();
+        }
+    }
+
+    fn abort(&mut self) {
+        self.aborted += self.columns.len();
+        self.columns.next = self.columns.columns.len();
+    }
 }
 
 type Ranges = Vec<Range<usize>>;
@@ -392,20 +434,36 @@ impl AsyncLendingIterator for PipelineStream<'_> {
 
     async fn try_next(&mut self) -> Result<Option<Self::Ok<'_>>, Self::Err> {
         loop {
-            match self.res.recv().await? {
+            let msg = match self.res.recv().await {
+                Ok(msg) => msg,
+                Err(e) => {
+                    self.on_error();
+                    return Err(e);
+                }
+            };
+
+            match msg {
+                backend::Message::ParseComplete => {}
                 backend::Message::BindComplete => {
+                    self.pending = true;
                     return Ok(Some(PipelineItem {
-                        finished: false,
+                        pending: &mut self.pending,
                         res: &mut self.res,
                         ranges: &mut self.ranges,
                         columns: self.columns.pop_front(),
                     }));
                 }
-                backend::Message::DataRow(_) | backend::Message::CommandComplete(_) => {
+                backend::Message::DataRow(_) => {
                     // last PipelineItem dropped before finish. do some catch up until next
                     // item arrives.
                 }
+                backend::Message::CommandComplete(_) => self.pending = false,
                 backend::Message::ReadyForQuery(_) => {
+                    // un-sync pipeline has only one sync point at the end. remaining queries are aborted by error
+                    // from item.
+                    if !self.sync_mode {
+                        self.abort();
+                    }
                     if self.columns.is_empty() {
                         return Ok(None);
                     }
@@ -425,7 +483,7 @@ impl AsyncLendingIterator for PipelineStream<'_> {
 /// streaming item of certain query inside pipeline's [PipelineStream].
 /// impl [AsyncLendingIterator] and can be used to collect [Row] from item.
 pub struct PipelineItem<'a> {
-    finished: bool,
+    pending: &'a mut bool,
     res: &'a mut Response,
     ranges: &'a mut Ranges,
     columns: &'a [Column],
@@ -433,16 +491,16 @@ pub struct PipelineItem<'a> {
 
 impl PipelineItem<'_> {
     /// collect rows affected by this pipelined query. [Row] information will be ignored.
-    pub async fn row_affected(mut self) -> Result<u64, Error> {
-        if self.finished {
+    pub async fn row_affected(self) -> Result<u64, Error> {
+        if !*self.pending {
             return Err(Completed.into());
         }
 
         loop {
-            match self.res.recv().await? {
+            match self.res.recv().await.inspect_err(|_| *self.pending = false)? {
                 backend::Message::DataRow(_) => {}
                 backend::Message::CommandComplete(body) => {
-                    self.finished = true;
+                    *self.pending = false;
                     return codec::body_to_affected_rows(&body);
                 }
                 _ => return Err(Error::unexpected()),
@@ -451,16 +509,16 @@ impl PipelineItem<'_> {
     }
 
     /// blocking version of [`PipelineItem::row_affected`]
-    pub fn row_affected_blocking(mut self) -> Result<u64, Error> {
-        if self.finished {
+    pub fn row_affected_blocking(self) -> Result<u64, Error> {
+        if !*self.pending {
             return Err(Completed.into());
         }
 
         loop {
-            match self.res.blocking_recv()? {
+            match self.res.blocking_recv().inspect_err(|_| *self.pending = false)? {
                 backend::Message::DataRow(_) => {}
                 backend::Message::CommandComplete(body) => {
-                    self.finished = true;
+                    *self.pending = false;
                     return codec::body_to_affected_rows(&body);
                 }
                 _ => return Err(Error::unexpected()),
@@ -477,12 +535,12 @@ impl AsyncLendingIterator for PipelineItem<'_> {
     type Err = Error;
 
     async fn try_next(&mut self) -> Result<Option<Self::Ok<'_>>, Self::Err> {
-        if !self.finished {
-            match self.res.recv().await? {
+        if *self.pending {
+            match self.res.recv().await.inspect_err(|_| *self.pending = false)? {
                 backend::Message::DataRow(body) => {
                     return Row::try_new(self.columns, body, self.ranges).map(Some);
                 }
-                backend::Message::CommandComplete(_) => self.finished = true,
+                backend::Message::CommandComplete(_) => *self.pending = false,
                 _ => return Err(Error::unexpected()),
             }
         }
@@ -18,6 +18,13 @@ pub trait Prepare: Query + Sync {
 
     // blocking version of [`Prepare::_get_type`].
     fn _get_type_blocking(&self, oid: Oid) -> Result<Type, Error>;
+
+    // when true statement is prepared as unnamed statement and parsed again on every binding.
+    // see [`Config::pgbouncer`](crate::config::Config::pgbouncer) for detail.
+    // custom client types wrapping a client should forward it to the inner client.
+    fn _unnamed_statement(&self) -> bool {
+        false
+    }
 }
 
 impl Prepare for Client {
@@ -112,6 +119,11 @@ impl Prepare for Client {
 
         Ok(type_)
     }
+
+    #[inline]
+    fn _unnamed_statement(&self) -> bool {
+        self.cache.pgbouncer
+    }
 }
 
 const TYPEINFO_QUERY: StatementNamed = Statement::named(
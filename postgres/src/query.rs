@@ -1,9 +1,14 @@
+mod simple;
 mod stream;
 
 #[cfg(feature = "compat")]
 pub(crate) mod compat;
 
-pub use stream::{RowAffected, RowSimpleStream, RowSimpleStreamOwned, RowStream, RowStreamGuarded, RowStreamOwned};
+pub use simple::{SimpleQueryMessage, SimpleQueryStream};
+pub use stream::{
+    QueryAs, RowAffected, RowSimpleStream, RowSimpleStreamOwned, RowStream, RowStreamAs, RowStreamFuture,
+    RowStreamGuarded, RowStreamMap, RowStreamOwned,
+};
 
 use super::{
     driver::codec::{encode::Encode, response::IntoResponse, Response},
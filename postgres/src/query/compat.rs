@@ -20,7 +20,8 @@ impl Stream for RowStreamOwned {
                 backend::Message::DataRow(body) => {
                     return Poll::Ready(Some(RowOwned::try_new(this.col.clone(), body, Vec::new())))
                 }
-                backend::Message::BindComplete
+                backend::Message::ParseComplete
+                | backend::Message::BindComplete
                 | backend::Message::EmptyQueryResponse
                 | backend::Message::CommandComplete(_)
                 | backend::Message::PortalSuspended => {}
@@ -0,0 +1,116 @@
+use core::ops::Range;
+
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::message::backend;
+
+use crate::{
+    column::Column,
+    driver::codec::{body_to_affected_rows, Response},
+    error::Error,
+    iter::AsyncLendingIterator,
+    row::RowSimple,
+    types::Type,
+};
+
+use super::RowSimpleStream;
+
+/// message of [`SimpleQueryStream`].
+#[derive(Debug)]
+pub enum SimpleQueryMessage<'a> {
+    /// start of rows produced by a statement with their column metadata. statement that produces no row like
+    /// `INSERT` without `RETURNING` clause doesn't emit this message.
+    RowDescription(&'a [Column]),
+    /// row produced by current statement.
+    Row(RowSimple<'a>),
+    /// end of a statement with it's command tag. for example `SELECT 2` or `CREATE TABLE`.
+    CommandComplete {
+        /// command tag of statement.
+        tag: &'a str,
+        /// amount of rows affected or produced by statement. it's 0 when command tag doesn't carry a row count.
+        rows: u64,
+    },
+    /// an empty statement is executed.
+    EmptyQuery,
+}
+
+/// A stream of simple query result where rows of each statement are grouped and followed by statement's command
+/// tag. it's useful for executing multiple statements where caller wants to know which statement produced what.
+///
+/// # Examples
+/// ```
+/// # use xitca_postgres::{Client, Error};
+/// # async fn simple(client: &Client) -> Result<(), Error> {
+/// use xitca_postgres::{iter::AsyncLendingIterator, Execute, SimpleQueryMessage};
+///
+/// let mut stream = "CREATE TEMPORARY TABLE foo (id INT); INSERT INTO foo VALUES (1), (2); SELECT id FROM foo"
+///     .query(client)
+///     .await?
+///     .into_messages();
+///
+/// while let Some(msg) = stream.try_next().await? {
+///     match msg {
+///         SimpleQueryMessage::RowDescription(cols) => println!("columns: {:?}", cols),
+///         SimpleQueryMessage::Row(row) => println!("id: {:?}", row.get(0)),
+///         SimpleQueryMessage::CommandComplete { tag, rows } => println!("{tag} finished with {rows} rows"),
+///         SimpleQueryMessage::EmptyQuery => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SimpleQueryStream {
+    res: Response,
+    col: Vec<Column>,
+    ranges: Vec<Range<usize>>,
+    tag: String,
+}
+
+impl RowSimpleStream {
+    /// convert to [`SimpleQueryStream`] where statement boundaries and command tags are observable.
+    ///
+    /// rows already consumed from current stream are not emitted again.
+    pub fn into_messages(self) -> SimpleQueryStream {
+        SimpleQueryStream {
+            res: self.res,
+            col: self.col,
+            ranges: self.ranges,
+            tag: String::new(),
+        }
+    }
+}
+
+impl AsyncLendingIterator for SimpleQueryStream {
+    type Ok<'i>
+        = SimpleQueryMessage<'i>
+    where
+        Self: 'i;
+    type Err = Error;
+
+    async fn try_next(&mut self) -> Result<Option<Self::Ok<'_>>, Self::Err> {
+        let msg = match self.res.recv().await? {
+            backend::Message::RowDescription(body) => {
+                // text type is used to match RowSimpleStream's columns.
+                self.col = body
+                    .fields()
+                    .map(|f| Ok(Column::new(f.name(), Type::TEXT)))
+                    .collect::<Vec<_>>()?;
+                SimpleQueryMessage::RowDescription(&self.col)
+            }
+            backend::Message::DataRow(body) => {
+                SimpleQueryMessage::Row(RowSimple::try_new(&self.col, body, &mut self.ranges)?)
+            }
+            backend::Message::CommandComplete(body) => {
+                let rows = body_to_affected_rows(&body)?;
+                self.tag.clear();
+                self.tag.push_str(body.tag().map_err(|_| Error::todo())?);
+                self.col.clear();
+                SimpleQueryMessage::CommandComplete { tag: &self.tag, rows }
+            }
+            backend::Message::EmptyQueryResponse => SimpleQueryMessage::EmptyQuery,
+            backend::Message::ReadyForQuery(_) => return Ok(None),
+            _ => return Err(Error::unexpected()),
+        };
+        Ok(Some(msg))
+    }
+}
@@ -17,7 +17,7 @@ use crate::{
     error::Error,
     iter::AsyncLendingIterator,
     prepare::Prepare,
-    row::{marker, Row, RowOwned, RowSimple, RowSimpleOwned},
+    row::{marker, FromRow, Row, RowOwned, RowSimple, RowSimpleOwned},
     types::Type,
 };
 
@@ -64,7 +64,8 @@ async fn try_next<'r>(
     loop {
         match res.recv().await? {
             backend::Message::DataRow(body) => return Row::try_new(col, body, ranges).map(Some),
-            backend::Message::BindComplete
+            backend::Message::ParseComplete
+            | backend::Message::BindComplete
             | backend::Message::EmptyQueryResponse
             | backend::Message::CommandComplete(_)
             | backend::Message::PortalSuspended => {}
@@ -132,6 +133,26 @@ impl AsyncLendingIterator for RowStreamOwned {
     }
 }
 
+impl RowStreamOwned {
+    // async counterpart of Iterator::next.
+    pub(crate) async fn try_next_owned(&mut self) -> Result<Option<RowOwned>, Error> {
+        loop {
+            match self.res.recv().await? {
+                backend::Message::DataRow(body) => {
+                    return RowOwned::try_new(self.col.clone(), body, Vec::new()).map(Some)
+                }
+                backend::Message::ParseComplete
+                | backend::Message::BindComplete
+                | backend::Message::EmptyQueryResponse
+                | backend::Message::CommandComplete(_)
+                | backend::Message::PortalSuspended => {}
+                backend::Message::ReadyForQuery(_) => return Ok(None),
+                _ => return Err(Error::unexpected()),
+            }
+        }
+    }
+}
+
 impl IntoIterator for RowStream<'_> {
     type Item = Result<RowOwned, Error>;
     type IntoIter = RowStreamOwned;
@@ -151,7 +172,8 @@ impl Iterator for RowStreamOwned {
                     backend::Message::DataRow(body) => {
                         return Some(RowOwned::try_new(self.col.clone(), body, Vec::new()))
                     }
-                    backend::Message::BindComplete
+                    backend::Message::ParseComplete
+                    | backend::Message::BindComplete
                     | backend::Message::EmptyQueryResponse
                     | backend::Message::CommandComplete(_)
                     | backend::Message::PortalSuspended => {}
@@ -374,6 +396,136 @@ where
     }
 }
 
+/// future returned by [`Execute::query_as`]. resolve to [`RowStreamAs`]
+///
+/// [`Execute::query_as`]: crate::Execute::query_as
+pub struct QueryAs<F, T> {
+    fut: F,
+    _t: PhantomData<fn() -> T>,
+}
+
+impl<F, T> QueryAs<F, T> {
+    pub(crate) fn new(fut: F) -> Self {
+        Self { fut, _t: PhantomData }
+    }
+}
+
+impl<F, S, T> Future for QueryAs<F, T>
+where
+    F: Future<Output = Result<S, Error>> + Unpin,
+{
+    type Output = Result<RowStreamAs<S, T>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().fut).poll(cx).map_ok(|stream| RowStreamAs {
+            stream,
+            _t: PhantomData,
+        })
+    }
+}
+
+/// a stream of typed values converted from table rows with [`FromRow`] trait.
+pub struct RowStreamAs<S, T> {
+    stream: S,
+    _t: PhantomData<fn() -> T>,
+}
+
+impl<T> AsyncLendingIterator for RowStreamAs<RowStream<'_>, T>
+where
+    T: FromRow,
+{
+    type Ok<'i>
+        = T
+    where
+        Self: 'i;
+    type Err = Error;
+
+    async fn try_next(&mut self) -> Result<Option<Self::Ok<'_>>, Self::Err> {
+        match self.stream.try_next().await? {
+            Some(row) => T::from_row(&row).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<C, T> AsyncLendingIterator for RowStreamAs<RowStreamGuarded<'_, C>, T>
+where
+    C: Prepare + Sync,
+    T: FromRow,
+{
+    type Ok<'i>
+        = T
+    where
+        Self: 'i;
+    type Err = Error;
+
+    async fn try_next(&mut self) -> Result<Option<Self::Ok<'_>>, Self::Err> {
+        match self.stream.try_next().await? {
+            Some(row) => T::from_row(&row).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// row stream of typed statement where rows can be mapped with a closure.
+/// used by [`Execute::query_one`], [`Execute::query_opt`] and [`Execute::query_scalar`].
+///
+/// [`Execute::query_one`]: crate::Execute::query_one
+/// [`Execute::query_opt`]: crate::Execute::query_opt
+/// [`Execute::query_scalar`]: crate::Execute::query_scalar
+pub trait RowStreamMap: Send {
+    /// advance the stream and map the next row with given function.
+    fn try_next_map<F, T>(&mut self, func: F) -> impl Future<Output = Result<Option<T>, Error>> + Send
+    where
+        F: FnOnce(&Row<'_>) -> Result<T, Error> + Send;
+}
+
+impl RowStreamMap for RowStream<'_> {
+    async fn try_next_map<F, T>(&mut self, func: F) -> Result<Option<T>, Error>
+    where
+        F: FnOnce(&Row<'_>) -> Result<T, Error> + Send,
+    {
+        self.try_next().await?.as_ref().map(func).transpose()
+    }
+}
+
+impl RowStreamMap for RowStreamOwned {
+    async fn try_next_map<F, T>(&mut self, func: F) -> Result<Option<T>, Error>
+    where
+        F: FnOnce(&Row<'_>) -> Result<T, Error> + Send,
+    {
+        self.try_next().await?.as_ref().map(func).transpose()
+    }
+}
+
+impl<C> RowStreamMap for RowStreamGuarded<'_, C>
+where
+    C: Prepare + Sync,
+{
+    async fn try_next_map<F, T>(&mut self, func: F) -> Result<Option<T>, Error>
+    where
+        F: FnOnce(&Row<'_>) -> Result<T, Error> + Send,
+    {
+        self.try_next().await?.as_ref().map(func).transpose()
+    }
+}
+
+/// future resolving to a [`RowStreamMap`]. it's implemented for all output of [`Execute::query`] producing row
+/// stream of typed statement.
+///
+/// [`Execute::query`]: crate::Execute::query
+pub trait RowStreamFuture: Future<Output = Result<Self::Stream, Error>> + Send {
+    type Stream: RowStreamMap;
+}
+
+impl<F, S> RowStreamFuture for F
+where
+    F: Future<Output = Result<S, Error>> + Send,
+    S: RowStreamMap,
+{
+    type Stream = S;
+}
+
 pub struct RowAffected {
     res: Response,
     rows_affected: u64,
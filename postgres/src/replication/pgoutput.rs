@@ -0,0 +1,473 @@
+//! decoder for messages of `pgoutput` logical decoding plugin.
+//!
+//! see <https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html> for message formats.
+
+use std::{io, time::SystemTime};
+
+use postgres_types::Oid;
+use xitca_io::bytes::{Buf, Bytes};
+
+use crate::{error::Error, types::PgLsn};
+
+use super::from_pg_timestamp;
+
+const BEGIN_TAG: u8 = b'B';
+const COMMIT_TAG: u8 = b'C';
+const ORIGIN_TAG: u8 = b'O';
+const RELATION_TAG: u8 = b'R';
+const TYPE_TAG: u8 = b'Y';
+const INSERT_TAG: u8 = b'I';
+const UPDATE_TAG: u8 = b'U';
+const DELETE_TAG: u8 = b'D';
+const TRUNCATE_TAG: u8 = b'T';
+
+const TUPLE_NEW_TAG: u8 = b'N';
+const TUPLE_KEY_TAG: u8 = b'K';
+const TUPLE_OLD_TAG: u8 = b'O';
+
+const TUPLE_DATA_NULL_TAG: u8 = b'n';
+const TUPLE_DATA_TOAST_TAG: u8 = b'u';
+const TUPLE_DATA_TEXT_TAG: u8 = b't';
+const TUPLE_DATA_BINARY_TAG: u8 = b'b';
+
+/// message of `pgoutput` logical decoding plugin.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LogicalReplicationMessage {
+    Begin(BeginBody),
+    Commit(CommitBody),
+    Origin(OriginBody),
+    Relation(RelationBody),
+    Type(TypeBody),
+    Insert(InsertBody),
+    Update(UpdateBody),
+    Delete(DeleteBody),
+    Truncate(TruncateBody),
+}
+
+impl LogicalReplicationMessage {
+    /// parse message from data of [`XLogData`].
+    ///
+    /// [`XLogData`]: super::XLogData
+    pub fn parse(mut buf: Bytes) -> Result<Self, Error> {
+        let mut reader = Reader::new(&mut buf);
+        let msg = match reader.u8()? {
+            BEGIN_TAG => Self::Begin(BeginBody {
+                final_lsn: PgLsn::from(reader.u64()?),
+                timestamp: from_pg_timestamp(reader.i64()?),
+                xid: reader.u32()?,
+            }),
+            COMMIT_TAG => Self::Commit(CommitBody {
+                flags: reader.u8()?,
+                commit_lsn: PgLsn::from(reader.u64()?),
+                end_lsn: PgLsn::from(reader.u64()?),
+                timestamp: from_pg_timestamp(reader.i64()?),
+            }),
+            ORIGIN_TAG => Self::Origin(OriginBody {
+                commit_lsn: PgLsn::from(reader.u64()?),
+                name: reader.str()?,
+            }),
+            RELATION_TAG => {
+                let rel_id = reader.u32()?;
+                let namespace = reader.str()?;
+                let name = reader.str()?;
+                let replica_identity = match reader.u8()? {
+                    b'd' => ReplicaIdentity::Default,
+                    b'n' => ReplicaIdentity::Nothing,
+                    b'f' => ReplicaIdentity::Full,
+                    b'i' => ReplicaIdentity::Index,
+                    _ => return Err(invalid_data("unknown replica identity")),
+                };
+                let len = reader.i16()?;
+                let columns = (0..len)
+                    .map(|_| {
+                        Ok(Column {
+                            flags: reader.u8()?,
+                            name: reader.str()?,
+                            type_id: reader.u32()?,
+                            type_modifier: reader.i32()?,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?;
+                Self::Relation(RelationBody {
+                    rel_id,
+                    namespace,
+                    name,
+                    replica_identity,
+                    columns,
+                })
+            }
+            TYPE_TAG => Self::Type(TypeBody {
+                id: reader.u32()?,
+                namespace: reader.str()?,
+                name: reader.str()?,
+            }),
+            INSERT_TAG => {
+                let rel_id = reader.u32()?;
+                match reader.u8()? {
+                    TUPLE_NEW_TAG => Self::Insert(InsertBody {
+                        rel_id,
+                        tuple: reader.tuple()?,
+                    }),
+                    _ => return Err(invalid_data("unexpected tuple tag")),
+                }
+            }
+            UPDATE_TAG => {
+                let rel_id = reader.u32()?;
+                let (key_tuple, old_tuple) = match reader.u8()? {
+                    TUPLE_KEY_TAG => (Some(reader.tuple()?), None),
+                    TUPLE_OLD_TAG => (None, Some(reader.tuple()?)),
+                    TUPLE_NEW_TAG => {
+                        return Ok(Self::Update(UpdateBody {
+                            rel_id,
+                            key_tuple: None,
+                            old_tuple: None,
+                            new_tuple: reader.tuple()?,
+                        }))
+                    }
+                    _ => return Err(invalid_data("unexpected tuple tag")),
+                };
+                match reader.u8()? {
+                    TUPLE_NEW_TAG => Self::Update(UpdateBody {
+                        rel_id,
+                        key_tuple,
+                        old_tuple,
+                        new_tuple: reader.tuple()?,
+                    }),
+                    _ => return Err(invalid_data("unexpected tuple tag")),
+                }
+            }
+            DELETE_TAG => {
+                let rel_id = reader.u32()?;
+                let (key_tuple, old_tuple) = match reader.u8()? {
+                    TUPLE_KEY_TAG => (Some(reader.tuple()?), None),
+                    TUPLE_OLD_TAG => (None, Some(reader.tuple()?)),
+                    _ => return Err(invalid_data("unexpected tuple tag")),
+                };
+                Self::Delete(DeleteBody {
+                    rel_id,
+                    key_tuple,
+                    old_tuple,
+                })
+            }
+            TRUNCATE_TAG => {
+                let len = reader.u32()?;
+                let options = reader.u8()?;
+                let rel_ids = (0..len).map(|_| reader.u32()).collect::<Result<_, _>>()?;
+                Self::Truncate(TruncateBody { options, rel_ids })
+            }
+            _ => return Err(invalid_data("unknown pgoutput message tag")),
+        };
+        Ok(msg)
+    }
+}
+
+/// start of a transaction.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BeginBody {
+    /// final lsn of the transaction.
+    pub final_lsn: PgLsn,
+    /// commit timestamp of the transaction.
+    pub timestamp: SystemTime,
+    /// xid of the transaction.
+    pub xid: u32,
+}
+
+/// end of a transaction.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CommitBody {
+    pub flags: u8,
+    /// lsn of the commit.
+    pub commit_lsn: PgLsn,
+    /// end lsn of the transaction.
+    pub end_lsn: PgLsn,
+    /// commit timestamp of the transaction.
+    pub timestamp: SystemTime,
+}
+
+/// origin of a transaction replicated from other node.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct OriginBody {
+    /// lsn of the commit on the origin server.
+    pub commit_lsn: PgLsn,
+    /// name of the origin.
+    pub name: Box<str>,
+}
+
+/// description of a relation. it's always sent before the first [`InsertBody`], [`UpdateBody`] and
+/// [`DeleteBody`] of the relation and when the relation's definition changed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RelationBody {
+    pub rel_id: Oid,
+    pub namespace: Box<str>,
+    pub name: Box<str>,
+    pub replica_identity: ReplicaIdentity,
+    pub columns: Box<[Column]>,
+}
+
+/// replica identity setting of a relation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplicaIdentity {
+    /// primary key columns.
+    Default,
+    /// no old value is logged.
+    Nothing,
+    /// all columns.
+    Full,
+    /// columns of an index.
+    Index,
+}
+
+/// column of a [`RelationBody`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Column {
+    /// 1 when the column is part of the key.
+    pub flags: u8,
+    pub name: Box<str>,
+    pub type_id: Oid,
+    pub type_modifier: i32,
+}
+
+impl Column {
+    /// column is part of replica identity key.
+    pub fn is_key(&self) -> bool {
+        self.flags & 1 == 1
+    }
+}
+
+/// description of a custom type referenced by a [`RelationBody`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TypeBody {
+    pub id: Oid,
+    pub namespace: Box<str>,
+    pub name: Box<str>,
+}
+
+/// inserted row.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InsertBody {
+    pub rel_id: Oid,
+    pub tuple: Tuple,
+}
+
+/// updated row. the old row is present depending on the replica identity of relation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct UpdateBody {
+    pub rel_id: Oid,
+    /// key columns of old row when replica identity is default or index and key is changed.
+    pub key_tuple: Option<Tuple>,
+    /// old row when replica identity is full.
+    pub old_tuple: Option<Tuple>,
+    pub new_tuple: Tuple,
+}
+
+/// deleted row. one of key or old row is present depending on the replica identity of relation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DeleteBody {
+    pub rel_id: Oid,
+    pub key_tuple: Option<Tuple>,
+    pub old_tuple: Option<Tuple>,
+}
+
+/// truncated relations.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TruncateBody {
+    /// 1 for `CASCADE` and 2 for `RESTART IDENTITY`.
+    pub options: u8,
+    pub rel_ids: Box<[Oid]>,
+}
+
+/// row data. columns are in the same order as [`RelationBody::columns`].
+#[derive(Debug)]
+pub struct Tuple(pub Box<[TupleData]>);
+
+/// value of a column in [`Tuple`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TupleData {
+    Null,
+    /// unchanged toasted value. the actual value is not sent.
+    UnchangedToast,
+    /// value in text format.
+    Text(Bytes),
+    /// value in binary format. only sent when `binary` option is enabled for replication.
+    Binary(Bytes),
+}
+
+pub(super) struct Reader<'a>(&'a mut Bytes);
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(buf: &'a mut Bytes) -> Self {
+        Self(buf)
+    }
+
+    fn check(&self, len: usize) -> Result<(), Error> {
+        if self.0.remaining() < len {
+            return Err(invalid_data("unexpected end of message"));
+        }
+        Ok(())
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, Error> {
+        self.check(1)?;
+        Ok(self.0.get_u8())
+    }
+
+    fn i16(&mut self) -> Result<i16, Error> {
+        self.check(2)?;
+        Ok(self.0.get_i16())
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        self.check(4)?;
+        Ok(self.0.get_u32())
+    }
+
+    fn i32(&mut self) -> Result<i32, Error> {
+        self.check(4)?;
+        Ok(self.0.get_i32())
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64, Error> {
+        self.check(8)?;
+        Ok(self.0.get_u64())
+    }
+
+    pub(super) fn i64(&mut self) -> Result<i64, Error> {
+        self.check(8)?;
+        Ok(self.0.get_i64())
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<Bytes, Error> {
+        self.check(len)?;
+        Ok(self.0.split_to(len))
+    }
+
+    // null terminated string.
+    fn str(&mut self) -> Result<Box<str>, Error> {
+        let end = self
+            .0
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| invalid_data("unexpected end of message"))?;
+        let bytes = self.0.split_to(end);
+        self.0.advance(1);
+        core::str::from_utf8(&bytes)
+            .map(Into::into)
+            .map_err(|_| invalid_data("invalid utf-8 string"))
+    }
+
+    fn tuple(&mut self) -> Result<Tuple, Error> {
+        let len = self.i16()?;
+        (0..len)
+            .map(|_| match self.u8()? {
+                TUPLE_DATA_NULL_TAG => Ok(TupleData::Null),
+                TUPLE_DATA_TOAST_TAG => Ok(TupleData::UnchangedToast),
+                TUPLE_DATA_TEXT_TAG => {
+                    let len = self.i32()?;
+                    self.bytes(len as usize).map(TupleData::Text)
+                }
+                TUPLE_DATA_BINARY_TAG => {
+                    let len = self.i32()?;
+                    self.bytes(len as usize).map(TupleData::Binary)
+                }
+                _ => Err(invalid_data("unknown tuple data tag")),
+            })
+            .collect::<Result<_, _>>()
+            .map(Tuple)
+    }
+
+    pub(super) fn remaining(self) -> Bytes {
+        core::mem::take(self.0)
+    }
+}
+
+fn invalid_data(msg: &'static str) -> Error {
+    Error::from(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
+#[cfg(test)]
+mod test {
+    use xitca_io::bytes::{BufMut, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn relation() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(RELATION_TAG);
+        buf.put_u32(16384);
+        buf.put_slice(b"public\0foo\0");
+        buf.put_u8(b'd');
+        buf.put_i16(2);
+        buf.put_u8(1);
+        buf.put_slice(b"id\0");
+        buf.put_u32(23);
+        buf.put_i32(-1);
+        buf.put_u8(0);
+        buf.put_slice(b"name\0");
+        buf.put_u32(25);
+        buf.put_i32(-1);
+
+        let LogicalReplicationMessage::Relation(body) = LogicalReplicationMessage::parse(buf.freeze()).unwrap() else {
+            panic!("expect Relation");
+        };
+        assert_eq!(body.rel_id, 16384);
+        assert_eq!(&*body.namespace, "public");
+        assert_eq!(&*body.name, "foo");
+        assert_eq!(body.replica_identity, ReplicaIdentity::Default);
+        assert_eq!(body.columns.len(), 2);
+        assert!(body.columns[0].is_key());
+        assert_eq!(&*body.columns[1].name, "name");
+        assert_eq!(body.columns[1].type_id, 25);
+    }
+
+    #[test]
+    fn update() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(UPDATE_TAG);
+        buf.put_u32(16384);
+        buf.put_u8(TUPLE_KEY_TAG);
+        buf.put_i16(2);
+        buf.put_u8(TUPLE_DATA_TEXT_TAG);
+        buf.put_i32(1);
+        buf.put_slice(b"1");
+        buf.put_u8(TUPLE_DATA_NULL_TAG);
+        buf.put_u8(TUPLE_NEW_TAG);
+        buf.put_i16(2);
+        buf.put_u8(TUPLE_DATA_TEXT_TAG);
+        buf.put_i32(1);
+        buf.put_slice(b"2");
+        buf.put_u8(TUPLE_DATA_TOAST_TAG);
+
+        let LogicalReplicationMessage::Update(body) = LogicalReplicationMessage::parse(buf.freeze()).unwrap() else {
+            panic!("expect Update");
+        };
+        assert_eq!(body.rel_id, 16384);
+        assert!(body.old_tuple.is_none());
+        assert_eq!(
+            &*body.key_tuple.unwrap().0,
+            [TupleData::Text(Bytes::from_static(b"1")), TupleData::Null]
+        );
+        assert_eq!(
+            &*body.new_tuple.0,
+            [TupleData::Text(Bytes::from_static(b"2")), TupleData::UnchangedToast]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(LogicalReplicationMessage::parse(Bytes::from_static(b"I\0\0")).is_err());
+        assert!(LogicalReplicationMessage::parse(Bytes::from_static(b"Y\0\0\0\x01public")).is_err());
+        assert!(LogicalReplicationMessage::parse(Bytes::from_static(b"?")).is_err());
+    }
+}
@@ -5,6 +5,79 @@ mod types;
 
 pub use types::{Row, RowOwned, RowSimple, RowSimpleOwned};
 
+#[cfg(feature = "codegen")]
+/// derive [`FromRow`] trait for struct. see the [trait](trait@FromRow) for detail.
+pub use xitca_postgres_codegen::FromRow;
+
+use postgres_types::FromSql;
+
+use crate::error::Error;
+
+/// trait for constructing type from [`Row`]. it's usually used together with [`Execute::query_as`] for querying
+/// typed values instead of rows.
+///
+/// # Derive
+/// with `codegen` feature enabled the trait can be derived for struct where fields are mapped to columns by name.
+/// tuple struct fields are mapped to columns by index. field attributes can be used to alter the mapping:
+/// - `#[row(rename = "name")]` map field to column with given name.
+/// - `#[row(flatten)]` construct field from the same row with it's own [`FromRow`] impl.
+/// - `#[row(default)]` use [`Default`] value when column is not found in row.
+///
+/// # Tuple
+/// tuples of up to 8 elements implement the trait where elements are mapped to columns by index.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "codegen")]
+/// # async fn query(client: &xitca_postgres::Client) -> Result<(), xitca_postgres::Error> {
+/// use xitca_postgres::{iter::AsyncLendingIterator, row::FromRow, Execute, Statement};
+///
+/// #[derive(FromRow)]
+/// struct User {
+///     id: i32,
+///     #[row(rename = "user_name")]
+///     name: String,
+///     #[row(default)]
+///     age: Option<i32>,
+/// }
+///
+/// let stmt = Statement::named("SELECT id, user_name FROM users", &[]).execute(client).await?;
+/// let mut users = stmt.query_as::<User>(client).await?;
+/// while let Some(user) = users.try_next().await? {
+///     println!("{}: {}", user.id, user.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Execute::query_as`]: crate::Execute::query_as
+pub trait FromRow: Sized {
+    fn from_row(row: &Row<'_>) -> Result<Self, Error>;
+}
+
+// tuples are constructed from columns by index.
+macro_rules! tuple_impl {
+    ($($t: ident: $idx: tt),*) => {
+        impl<$($t),*> FromRow for ($($t,)*)
+        where
+            $($t: for<'r> FromSql<'r>),*
+        {
+            fn from_row(row: &Row<'_>) -> Result<Self, Error> {
+                Ok(($(row.try_get::<$t>($idx)?,)*))
+            }
+        }
+    };
+}
+
+tuple_impl!(A: 0);
+tuple_impl!(A: 0, B: 1);
+tuple_impl!(A: 0, B: 1, C: 2);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+tuple_impl!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
 // Marker types for specialized impl on row types
 pub(crate) mod marker {
     #[derive(Debug)]
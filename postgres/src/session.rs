@@ -10,9 +10,9 @@ use postgres_protocol::{
 use xitca_io::{bytes::BytesMut, io::AsyncIo};
 
 use super::{
-    config::{Config, SslMode, SslNegotiation},
+    config::{ChannelBinding, Config, ReplicationMode, SslMode, SslNegotiation},
     driver::generic::GenericDriver,
-    error::{ConfigError, Error},
+    error::{ConfigError, Error, TargetSessionMismatch},
 };
 
 /// Properties required of a session.
@@ -25,6 +25,12 @@ pub enum TargetSessionAttrs {
     ReadWrite,
     /// The session only allows read.
     ReadOnly,
+    /// The server must not be in hot standby mode.
+    Primary,
+    /// The server must be in hot standby mode.
+    Standby,
+    /// Prefer server in hot standby mode. When no such server is available connect to any server.
+    PreferStandby,
 }
 
 /// information about session. used for canceling query
@@ -40,16 +46,31 @@ pub(crate) struct ConnectInfo {
     pub(crate) addr: Addr,
     pub(crate) ssl_mode: SslMode,
     pub(crate) ssl_negotiation: SslNegotiation,
+    #[cfg(feature = "tls")]
+    pub(crate) ssl_root_cert: Option<std::path::PathBuf>,
 }
 
 impl ConnectInfo {
-    pub(crate) fn new(addr: Addr, ssl_mode: SslMode, ssl_negotiation: SslNegotiation) -> Self {
+    pub(crate) fn new(addr: Addr, cfg: &Config) -> Self {
         Self {
             addr,
-            ssl_mode,
-            ssl_negotiation,
+            ssl_mode: cfg.get_ssl_mode(),
+            ssl_negotiation: cfg.get_ssl_negotiation(),
+            #[cfg(feature = "tls")]
+            ssl_root_cert: cfg.ssl_root_cert.clone(),
         }
     }
+
+    // config for re-establishing tls connection with the same server.
+    #[cfg(feature = "tls")]
+    pub(crate) fn tls_config(&self) -> Config {
+        let mut cfg = Config::new();
+        cfg.ssl_mode(self.ssl_mode).ssl_negotiation(self.ssl_negotiation);
+        if let Some(ref path) = self.ssl_root_cert {
+            cfg.ssl_root_cert(path);
+        }
+        cfg
+    }
 }
 
 #[derive(Clone, Default)]
@@ -95,39 +116,38 @@ impl Session {
                     session.id = body.process_id();
                     session.key = body.secret_key();
                 }
-                backend::Message::ParameterStatus(body) => {
-                    // TODO: handling params?
-                    let _name = body.name()?;
-                    let _value = body.value()?;
-                }
+                backend::Message::ParameterStatus(body) => drv.set_parameter(body.name()?, body.value()?),
                 backend::Message::ErrorResponse(body) => return Err(Error::db(body.fields())),
-                backend::Message::NoticeResponse(_) => {
-                    // TODO: collect notice and let Driver emit it when polled?
-                }
+                msg @ backend::Message::NoticeResponse(_) => drv.push_pending(msg),
                 _ => return Err(Error::unexpected()),
             }
         }
 
-        if !matches!(cfg.get_target_session_attrs(), TargetSessionAttrs::Any) {
-            frontend::query("SHOW transaction_read_only", &mut buf)?;
-            let msg = buf.split();
-            drv.send(msg).await?;
-            // TODO: use RowSimple for parsing?
-            loop {
-                match drv.recv().await? {
-                    backend::Message::DataRow(body) => {
-                        let range = body.ranges().next()?.flatten().ok_or(Error::todo())?;
-                        let slice = &body.buffer()[range.start..range.end];
-                        match (slice, cfg.get_target_session_attrs()) {
-                            (b"on", TargetSessionAttrs::ReadWrite) => return Err(Error::todo()),
-                            (b"off", TargetSessionAttrs::ReadOnly) => return Err(Error::todo()),
-                            _ => {}
-                        }
+        let (query, expect): (_, &[u8]) = match cfg.get_target_session_attrs() {
+            TargetSessionAttrs::Any => return Ok(session),
+            TargetSessionAttrs::ReadWrite => ("SHOW transaction_read_only", b"off"),
+            TargetSessionAttrs::ReadOnly => ("SHOW transaction_read_only", b"on"),
+            TargetSessionAttrs::Primary => ("SELECT pg_is_in_recovery()", b"f"),
+            TargetSessionAttrs::Standby | TargetSessionAttrs::PreferStandby => ("SELECT pg_is_in_recovery()", b"t"),
+        };
+
+        frontend::query(query, &mut buf)?;
+        let msg = buf.split();
+        drv.send(msg).await?;
+        // TODO: use RowSimple for parsing?
+        loop {
+            match drv.recv().await? {
+                backend::Message::DataRow(body) => {
+                    let range = body.ranges().next()?.flatten().ok_or(Error::todo())?;
+                    let slice = &body.buffer()[range.start..range.end];
+                    if slice != expect {
+                        return Err(TargetSessionMismatch(cfg.get_target_session_attrs()).into());
                     }
-                    backend::Message::RowDescription(_) | backend::Message::CommandComplete(_) => {}
-                    backend::Message::EmptyQueryResponse | backend::Message::ReadyForQuery(_) => break,
-                    _ => return Err(Error::unexpected()),
                 }
+                backend::Message::RowDescription(_) | backend::Message::CommandComplete(_) => {}
+                backend::Message::EmptyQueryResponse | backend::Message::ReadyForQuery(_) => break,
+                backend::Message::ErrorResponse(body) => return Err(Error::db(body.fields())),
+                _ => return Err(Error::unexpected()),
             }
         }
 
@@ -135,6 +155,14 @@ impl Session {
     }
 }
 
+// password based authentication can't be channel bound.
+fn require_no_channel_binding(cfg: &Config) -> Result<(), Error> {
+    match cfg.get_channel_binding() {
+        ChannelBinding::Require => Err(ConfigError::MissingChannelBinding.into()),
+        _ => Ok(()),
+    }
+}
+
 #[cold]
 #[inline(never)]
 async fn auth<Io>(drv: &mut GenericDriver<Io>, cfg: &Config, buf: &mut BytesMut) -> Result<(), Error>
@@ -154,6 +182,17 @@ where
     if let Some(application_name) = &cfg.application_name {
         params.push(("application_name", &**application_name));
     }
+    if let Some(mode) = cfg.replication_mode {
+        let mode = match mode {
+            ReplicationMode::Physical => "true",
+            ReplicationMode::Logical => "database",
+        };
+        params.push(("replication", mode));
+    }
+    let statement_timeout = cfg.statement_timeout.map(|timeout| timeout.as_millis().to_string());
+    if let Some(statement_timeout) = &statement_timeout {
+        params.push(("statement_timeout", statement_timeout));
+    }
 
     frontend::startup_message(params, buf)?;
     let msg = buf.split();
@@ -163,10 +202,12 @@ where
         match drv.recv().await? {
             backend::Message::AuthenticationOk => return Ok(()),
             backend::Message::AuthenticationCleartextPassword => {
+                require_no_channel_binding(cfg)?;
                 let pass = cfg.get_password().ok_or(ConfigError::MissingPassWord)?;
                 send_pass(drv, pass, buf).await?;
             }
             backend::Message::AuthenticationMd5Password(body) => {
+                require_no_channel_binding(cfg)?;
                 let pass = cfg.get_password().ok_or(ConfigError::MissingPassWord)?;
                 let user = cfg.get_user().ok_or(ConfigError::MissingUserName)?.as_bytes();
                 let pass = authentication::md5_hash(user, pass, body.salt());
@@ -187,23 +228,26 @@ where
                     }
                 }
 
-                let (channel_binding, mechanism) = match (is_scram_plus, is_scram) {
-                    (true, is_scram) => {
-                        let buf = cfg.get_tls_server_end_point();
-                        match (buf, is_scram) {
-                            (Some(buf), _) => (
-                                sasl::ChannelBinding::tls_server_end_point(buf.to_owned()),
-                                sasl::SCRAM_SHA_256_PLUS,
-                            ),
-                            (None, true) => (sasl::ChannelBinding::unrequested(), sasl::SCRAM_SHA_256),
-                            // server ask for channel binding but no tls_server_end_point can be
-                            // found.
-                            _ => return Err(Error::todo()),
-                        }
+                let end_point = match cfg.get_channel_binding() {
+                    ChannelBinding::Disable => None,
+                    _ => cfg.get_tls_server_end_point(),
+                };
+
+                let (channel_binding, mechanism) = match (is_scram_plus, end_point) {
+                    (true, Some(buf)) => (
+                        sasl::ChannelBinding::tls_server_end_point(buf.to_owned()),
+                        sasl::SCRAM_SHA_256_PLUS,
+                    ),
+                    _ if cfg.get_channel_binding() == ChannelBinding::Require => {
+                        return Err(ConfigError::MissingChannelBinding.into())
                     }
-                    (false, true) => (sasl::ChannelBinding::unrequested(), sasl::SCRAM_SHA_256),
+                    // client supports channel binding but server does not offer it.
+                    (false, Some(_)) if is_scram => (sasl::ChannelBinding::unrequested(), sasl::SCRAM_SHA_256),
+                    (_, None) if is_scram => (sasl::ChannelBinding::unsupported(), sasl::SCRAM_SHA_256),
+                    // server ask for channel binding but no tls_server_end_point can be found or
+                    // no supported mechanism is offered.
                     // TODO: return "unsupported SASL mechanism" error.
-                    (false, false) => return Err(Error::todo()),
+                    _ => return Err(Error::todo()),
                 };
 
                 let mut scram = sasl::ScramSha256::new(pass, channel_binding);
@@ -1,11 +1,15 @@
 //! Statement module is mostly copy/paste from `tokio_postgres::statement`
 
-use core::{ops::Deref, sync::atomic::Ordering};
+use core::{future::Future, marker::PhantomData, ops::Deref, sync::atomic::Ordering};
 
 use super::{
     column::Column,
     driver::codec::{encode::StatementCancel, AsParams},
-    query::Query,
+    error::Error,
+    execute::Execute,
+    prepare::Prepare,
+    query::{Query, QueryAs, RowStreamFuture},
+    row::FromRow,
     types::{ToSql, Type},
 };
 
@@ -48,7 +52,8 @@ where
     C: Query,
 {
     fn drop(&mut self) {
-        if let Some(stmt) = self.stmt.take() {
+        // unnamed statement has nothing to close on database.
+        if let Some(stmt) = self.stmt.take().filter(|stmt| stmt.sql().is_none()) {
             let _ = self.cli._send_encode_query(StatementCancel { name: stmt.name() });
         }
     }
@@ -82,6 +87,8 @@ pub struct Statement {
     name: Box<str>,
     params: Box<[Type]>,
     columns: Box<[Column]>,
+    // sql text of statement prepared as unnamed statement. see Config::pgbouncer for detail.
+    sql: Option<Box<str>>,
 }
 
 impl Statement {
@@ -90,14 +97,21 @@ impl Statement {
             name: name.into_boxed_str(),
             params: params.into_boxed_slice(),
             columns: columns.into_boxed_slice(),
+            sql: None,
         }
     }
 
+    pub(crate) fn with_sql(mut self, sql: Option<Box<str>>) -> Self {
+        self.sql = sql;
+        self
+    }
+
     pub(crate) fn duplicate(&self) -> Self {
         Self {
             name: self.name.clone(),
             params: self.params.clone(),
             columns: self.columns.clone(),
+            sql: self.sql.clone(),
         }
     }
 
@@ -105,6 +119,11 @@ impl Statement {
         &self.name
     }
 
+    // sql text must be parsed into unnamed statement before binding when it's Some.
+    pub(crate) fn sql(&self) -> Option<&str> {
+        self.sql.as_deref()
+    }
+
     /// construct a new named statement.
     /// must be called with [`Execute::execute`] method for making a prepared statement.
     ///
@@ -189,7 +208,11 @@ pub struct StatementNamed<'a> {
 }
 
 impl StatementNamed<'_> {
-    fn name() -> String {
+    // unnamed statement is described with empty name.
+    fn name(cli: &impl Prepare) -> String {
+        if cli._unnamed_statement() {
+            return String::new();
+        }
         let id = crate::NEXT_ID.fetch_add(1, Ordering::Relaxed);
         format!("s{id}")
     }
@@ -202,10 +225,13 @@ pub(crate) struct StatementCreate<'a, 'c, C> {
     pub(crate) cli: &'c C,
 }
 
-impl<'a, 'c, C> From<(StatementNamed<'a>, &'c C)> for StatementCreate<'a, 'c, C> {
+impl<'a, 'c, C> From<(StatementNamed<'a>, &'c C)> for StatementCreate<'a, 'c, C>
+where
+    C: Prepare,
+{
     fn from((stmt, cli): (StatementNamed<'a>, &'c C)) -> Self {
         Self {
-            name: StatementNamed::name(),
+            name: StatementNamed::name(cli),
             stmt: stmt.stmt,
             types: stmt.types,
             cli,
@@ -220,10 +246,13 @@ pub(crate) struct StatementCreateBlocking<'a, 'c, C> {
     pub(crate) cli: &'c C,
 }
 
-impl<'a, 'c, C> From<(StatementNamed<'a>, &'c C)> for StatementCreateBlocking<'a, 'c, C> {
+impl<'a, 'c, C> From<(StatementNamed<'a>, &'c C)> for StatementCreateBlocking<'a, 'c, C>
+where
+    C: Prepare,
+{
     fn from((stmt, cli): (StatementNamed<'a>, &'c C)) -> Self {
         Self {
-            name: StatementNamed::name(),
+            name: StatementNamed::name(cli),
             stmt: stmt.stmt,
             types: stmt.types,
             cli,
@@ -273,6 +302,83 @@ pub struct StatementUnnamedBind<'a, P> {
     params: P,
 }
 
+/// a statement with it's row type known ahead of time. produced by `query!` macro where sql is checked against
+/// database schema at compile time and rows are decoded into generated struct.
+///
+/// # Examples
+/// ```ignore
+/// # use xitca_postgres::{Client, Error};
+/// # async fn typed(cli: &Client) -> Result<(), Error> {
+/// let id = 9527;
+/// let user = xitca_postgres::query!("SELECT id, name FROM users WHERE id = $1", &id)
+///     .query_one(cli)
+///     .await?;
+/// println!("{}: {}", user.id, user.name);
+/// # Ok(())
+/// # }
+/// ```
+pub struct StatementTyped<S, T> {
+    stmt: S,
+    _row: PhantomData<fn() -> T>,
+}
+
+impl<S, T> StatementTyped<S, T> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(stmt: S) -> Self {
+        Self {
+            stmt,
+            _row: PhantomData,
+        }
+    }
+
+    /// take the inner statement and discard row type.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.stmt
+    }
+
+    /// function the same as [`Execute::execute`]
+    #[inline]
+    pub fn execute<C>(self, cli: C) -> S::ExecuteOutput
+    where
+        S: Execute<C>,
+    {
+        self.stmt.execute(cli)
+    }
+
+    /// function the same as [`Execute::query_as`] with row type of statement.
+    #[inline]
+    pub fn query<C>(self, cli: C) -> QueryAs<S::QueryOutput, T>
+    where
+        S: Execute<C>,
+    {
+        self.stmt.query_as(cli)
+    }
+
+    /// function the same as [`Execute::query_one`] with row type of statement.
+    #[inline]
+    pub fn query_one<C>(self, cli: C) -> impl Future<Output = Result<T, Error>> + Send
+    where
+        S: Execute<C>,
+        S::QueryOutput: RowStreamFuture,
+        T: FromRow + Send,
+    {
+        self.stmt.query_one(cli)
+    }
+
+    /// function the same as [`Execute::query_opt`] with row type of statement.
+    #[inline]
+    pub fn query_opt<C>(self, cli: C) -> impl Future<Output = Result<Option<T>, Error>> + Send
+    where
+        S: Execute<C>,
+        S::QueryOutput: RowStreamFuture,
+        T: FromRow + Send,
+    {
+        self.stmt.query_opt(cli)
+    }
+}
+
 pub(crate) struct StatementUnnamedQuery<'a, 'c, P, C> {
     pub(crate) stmt: &'a str,
     pub(crate) types: &'a [Type],
@@ -325,7 +431,9 @@ pub(crate) mod compat {
         C: Query,
     {
         fn drop(&mut self) {
-            let _ = self.cli._send_encode_query(StatementCancel { name: self.stmt.name() });
+            if self.stmt.sql().is_none() {
+                let _ = self.cli._send_encode_query(StatementCancel { name: self.stmt.name() });
+            }
         }
     }
 
@@ -12,6 +12,7 @@ use super::{
     query::Query,
     statement::Statement,
     types::{Oid, ToSql, Type},
+    util::quote_ident,
     BoxedFuture,
 };
 
@@ -51,7 +52,7 @@ impl SavePoint {
         match self {
             Self::None => Cow::Borrowed("SAVEPOINT"),
             Self::Auto { depth } => Cow::Owned(format!("SAVEPOINT sp_{depth}")),
-            Self::Custom { name, .. } => Cow::Owned(format!("SAVEPOINT {name}")),
+            Self::Custom { name, .. } => Cow::Owned(format!("SAVEPOINT {}", quote_ident(name))),
         }
     }
 
@@ -59,7 +60,7 @@ impl SavePoint {
         match self {
             Self::None => Cow::Borrowed("COMMIT"),
             Self::Auto { depth } => Cow::Owned(format!("RELEASE sp_{depth}")),
-            Self::Custom { name, .. } => Cow::Owned(format!("RELEASE {name}")),
+            Self::Custom { name, .. } => Cow::Owned(format!("RELEASE {}", quote_ident(name))),
         }
     }
 
@@ -67,7 +68,7 @@ impl SavePoint {
         match self {
             Self::None => Cow::Borrowed("ROLLBACK"),
             Self::Auto { depth } => Cow::Owned(format!("ROLLBACK TO sp_{depth}")),
-            Self::Custom { name, .. } => Cow::Owned(format!("ROLLBACK TO {name}")),
+            Self::Custom { name, .. } => Cow::Owned(format!("ROLLBACK TO {}", quote_ident(name))),
         }
     }
 }
@@ -121,6 +122,7 @@ where
     }
 
     /// Like [`Client::transaction`], but creates a nested transaction via a savepoint with the specified name.
+    /// name is quoted and therefore case sensitive.
     ///
     /// [`Client::transaction`]: crate::client::Client::transaction
     pub async fn save_point<I>(&mut self, name: I) -> Result<Transaction<C>, Error>
@@ -183,6 +185,11 @@ where
     fn _get_type_blocking(&self, oid: Oid) -> Result<Type, Error> {
         self.client._get_type_blocking(oid)
     }
+
+    #[inline]
+    fn _unnamed_statement(&self) -> bool {
+        self.client._unnamed_statement()
+    }
 }
 
 impl<C> Query for Transaction<'_, C>
@@ -1,4 +1,12 @@
-use crate::{client::ClientBorrowMut, error::Error, execute::Execute, prepare::Prepare, query::Query};
+use core::time::Duration;
+
+use crate::{
+    client::ClientBorrowMut,
+    error::{DbError, Error, SqlState},
+    execute::Execute,
+    prepare::Prepare,
+    query::Query,
+};
 
 use super::Transaction;
 
@@ -38,6 +46,7 @@ impl IsolationLevel {
 }
 
 /// A builder for database transactions.
+#[derive(Clone, Copy)]
 pub struct TransactionBuilder {
     isolation_level: Option<IsolationLevel>,
     read_only: Option<bool>,
@@ -75,6 +84,10 @@ impl TransactionBuilder {
         self
     }
 
+    pub(crate) fn is_read_only(&self) -> bool {
+        matches!(self.read_only, Some(true))
+    }
+
     /// Begins the transaction.
     ///
     /// The transaction will roll back by default - use the `commit` method to commit it.
@@ -113,4 +126,56 @@ impl TransactionBuilder {
 
         query.as_str().execute(cli).await.map(|_| Transaction::new(cli))
     }
+
+    /// Begins the transaction, runs given async closure with it and commits when closure returns `Ok`.
+    ///
+    /// When transaction fails with serialization failure (`40001`) or deadlock (`40P01`) error it's rolled back and
+    /// the whole process is retried up to `max_retries` times with exponential backoff starting from 10 milliseconds.
+    /// The closure can be called multiple times and it should not have side effects outside of the transaction.
+    ///
+    /// # Examples
+    /// ```
+    /// # use xitca_postgres::{Client, Error, Execute};
+    /// use xitca_postgres::transaction::{IsolationLevel, TransactionBuilder};
+    ///
+    /// # async fn retry(client: &mut Client) -> Result<(), Error> {
+    /// let rows = TransactionBuilder::new()
+    ///     .isolation_level(IsolationLevel::Serializable)
+    ///     .run(client, 3, async |tx| "UPDATE users SET age = age + 1".execute(&*tx).await)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run<C, F, T>(self, cli: &mut C, max_retries: usize, mut func: F) -> Result<T, Error>
+    where
+        C: Prepare + Query + ClientBorrowMut,
+        F: AsyncFnMut(&mut Transaction<'_, C>) -> Result<T, Error>,
+    {
+        let mut retries = 0;
+        loop {
+            let res = async {
+                let mut tx = self.begin(cli).await?;
+                let t = func(&mut tx).await?;
+                tx.commit().await?;
+                Ok(t)
+            }
+            .await;
+
+            match res {
+                Err(e) if retries < max_retries && is_retryable(&e) => {
+                    let backoff = Duration::from_millis(10 << retries.min(6));
+                    retries += 1;
+                    tokio::time::sleep(backoff).await;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+fn is_retryable(e: &Error) -> bool {
+    e.downcast_ref::<DbError>().is_some_and(|e| {
+        let code = e.code();
+        *code == SqlState::T_R_SERIALIZATION_FAILURE || *code == SqlState::T_R_DEADLOCK_DETECTED
+    })
 }
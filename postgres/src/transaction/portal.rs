@@ -9,7 +9,8 @@ use crate::{
         AsParams,
     },
     error::Error,
-    query::Query,
+    query::{Query, RowStreamOwned},
+    row::RowOwned,
     statement::Statement,
 };
 
@@ -17,6 +18,26 @@ use crate::{
 ///
 /// Portals can only be used with the connection that created them, and only exist for the duration of the transaction
 /// in which they were created.
+///
+/// # Examples
+/// ```
+/// # use xitca_postgres::{Client, Error, Execute, Statement};
+/// # async fn portal(client: &mut Client) -> Result<(), Error> {
+/// let tx = client.transaction().await?;
+/// let stmt = Statement::named("SELECT * FROM huge_table", &[]).execute(&tx).await?;
+///
+/// // bind statement to a portal and fetch rows in batches.
+/// let portal = tx.bind(&stmt, &[]).await?;
+/// loop {
+///     let rows = portal.fetch_next(100).await?;
+///     if rows.is_empty() {
+///         break;
+///     }
+///     // handle batch of up to 100 rows.
+/// }
+/// # Ok(())
+/// # }
+/// ```
 pub struct Portal<'a, C>
 where
     C: Query,
@@ -47,19 +68,23 @@ where
 
         let (_, mut res) = cli._send_encode_query(PortalCreate {
             name: &name,
-            stmt: stmt.name(),
-            types: stmt.params(),
+            stmt,
             params,
         })?;
 
-        match res.recv().await? {
-            backend::Message::BindComplete => {}
-            _ => return Err(Error::unexpected()),
+        loop {
+            match res.recv().await? {
+                backend::Message::ParseComplete => {}
+                backend::Message::BindComplete => break,
+                _ => return Err(Error::unexpected()),
+            }
         }
 
         Ok(Portal { cli, name, stmt })
     }
 
+    /// execute portal and return a stream of at most `max_rows` rows. following calls continue from where the
+    /// previous one stopped. `max_rows` of 0 means no limit.
     pub fn query_portal(
         &self,
         max_rows: i32,
@@ -70,4 +95,14 @@ where
             max_rows,
         })
     }
+
+    /// fetch next batch of at most `max_rows` rows from portal. an empty batch is returned when portal is exhausted.
+    pub async fn fetch_next(&self, max_rows: i32) -> Result<Vec<RowOwned>, Error> {
+        let mut stream = RowStreamOwned::from(self.query_portal(max_rows)?);
+        let mut rows = Vec::new();
+        while let Some(row) = stream.try_next_owned().await? {
+            rows.push(row);
+        }
+        Ok(rows)
+    }
 }
@@ -1,6 +1,167 @@
 //! Types.
 //!
-//! This module is a reexport of the `postgres_types` crate.
+//! This module is a reexport of the `postgres_types` crate with additional types not covered by it.
+//!
+//! # Date and time
+//! With `chrono` or `time` feature enabled [`ToSql`] and [`FromSql`] are implemented for following types:
+//!
+//! | Postgres type | `chrono` | `time` |
+//! |---------------|----------|--------|
+//! | `TIMESTAMP`   | `NaiveDateTime` | `PrimitiveDateTime` |
+//! | `TIMESTAMPTZ` | `DateTime<Utc>`, `DateTime<Local>`, `DateTime<FixedOffset>` | `OffsetDateTime` |
+//! | `DATE`        | `NaiveDate` | `Date` |
+//! | `TIME`        | `NaiveTime` | `Time` |
+//! | `INTERVAL`    | [`Interval`] convertible from/to `TimeDelta` | [`Interval`] convertible from/to `Duration` |
+//!
+//! Postgres `infinity` and `-infinity` are out of range for all of above types and decoding them would produce
+//! error. Wrap the type with [`Timestamp`] or [`Date`] to represent them:
+//! ```rust
+//! # #[cfg(feature = "chrono")]
+//! # async fn infinity(cli: &xitca_postgres::Client) -> Result<(), xitca_postgres::Error> {
+//! use xitca_postgres::{iter::AsyncLendingIterator, types::Timestamp, Execute, Statement};
+//!
+//! let stmt = Statement::named("SELECT 'infinity'::timestamp", &[]).execute(cli).await?;
+//! let mut stream = stmt.query(cli).await?;
+//! let row = stream.try_next().await?.unwrap();
+//! let ts = row.get::<Timestamp<chrono::NaiveDateTime>>(0);
+//! assert_eq!(ts, Timestamp::PosInfinity);
+//! # Ok(())
+//! # }
+//! ```
+
+//! # JSON
+//! With `json` feature enabled `serde_json::Value` and [`Json`] wrapper type can be used for `JSON` and `JSONB`
+//! types:
+//! ```rust
+//! # #[cfg(feature = "json")]
+//! # async fn json(cli: &xitca_postgres::Client) -> Result<(), xitca_postgres::Error> {
+//! use xitca_postgres::{iter::AsyncLendingIterator, types::{Json, Type}, Execute, Statement};
+//!
+//! #[derive(Debug, serde::Serialize, serde::Deserialize)]
+//! struct Doc {
+//!     id: i32,
+//! }
+//!
+//! let stmt = Statement::named("SELECT $1::JSONB", &[Type::JSONB]).execute(cli).await?;
+//! let mut stream = stmt.bind([Json(Doc { id: 1 })]).query(cli).await?;
+//! let row = stream.try_next().await?.unwrap();
+//! let Json(doc) = row.get::<Json<Doc>>(0);
+//! assert_eq!(doc.id, 1);
+//! # Ok(())
+//! # }
+//! ```
+
+//! # Numeric
+//! With `decimal` feature enabled `rust_decimal::Decimal` can be used for `NUMERIC` type. [`Numeric`] wraps it for
+//! representing `NaN`, `Infinity` and `-Infinity`.
+
+//! # Network address
+//! `std::net::IpAddr` can be used for `INET` type. [`Inet`] carries netmask of `INET` value and [`Cidr`] is used for
+//! `CIDR` type where bits to the right of network prefix must be zero. [`MacAddr`] and [`MacAddr8`] are used for
+//! `MACADDR` and `MACADDR8` types.
+//! ```rust
+//! # async fn net(cli: &xitca_postgres::Client) -> Result<(), xitca_postgres::Error> {
+//! use std::net::IpAddr;
+//! use xitca_postgres::{iter::AsyncLendingIterator, types::{Cidr, Type}, Execute, Statement};
+//!
+//! let stmt = Statement::named("SELECT $1::CIDR", &[Type::CIDR]).execute(cli).await?;
+//! let cidr = Cidr::new("10.0.0.0".parse::<IpAddr>().unwrap(), 8)?;
+//! let mut stream = stmt.bind([cidr]).query(cli).await?;
+//! let row = stream.try_next().await?.unwrap();
+//! assert_eq!(row.get::<Cidr>(0), cidr);
+//! # Ok(())
+//! # }
+//! ```
+
+//! # Array
+//! One dimensional array of any type implementing [`ToSql`] can be used as parameter in the form of `Vec<T>`,
+//! `&[T]` and `Box<[T]>`. Arrays can be decoded to `Vec<T>` and `Box<[T]>` where `T` must be `Option` when
+//! array contains `NULL` element. Element type is checked against array type's element [`Type`]
+//! which for user defined type is looked up from database when preparing statement. Decoding multi dimensional
+//! array would produce error.
+//! ```rust
+//! # async fn array(cli: &xitca_postgres::Client) -> Result<(), xitca_postgres::Error> {
+//! use xitca_postgres::{iter::AsyncLendingIterator, types::Type, Execute, Statement};
+//!
+//! let stmt = Statement::named("SELECT $1::TEXT[]", &[Type::TEXT_ARRAY]).execute(cli).await?;
+//! let mut stream = stmt.bind([&[Some("foo"), None][..]]).query(cli).await?;
+//! let row = stream.try_next().await?.unwrap();
+//! assert_eq!(row.get::<Vec<Option<String>>>(0), [Some("foo".to_owned()), None]);
+//! # Ok(())
+//! # }
+//! ```
+
+//! # Hstore
+//! `HashMap<String, Option<String>>` can be used for `hstore` type of `hstore` extension. Oid of extension type
+//! differs between databases and [`Client::resolve_type`] can be used to look it up at runtime. Statement prepared
+//! without parameter types resolves it from database too.
+//! ```rust
+//! # async fn hstore(cli: &xitca_postgres::Client) -> Result<(), xitca_postgres::Error> {
+//! use std::collections::HashMap;
+//!
+//! use xitca_postgres::{iter::AsyncLendingIterator, Execute, Statement};
+//!
+//! let hstore = cli.resolve_type("hstore").await?;
+//! let stmt = Statement::named("SELECT $1", &[hstore]).execute(cli).await?;
+//!
+//! let map = HashMap::from([(String::from("key"), Some(String::from("value"))), (String::from("null"), None)]);
+//! let mut stream = stmt.bind([&map]).query(cli).await?;
+//! let row = stream.try_next().await?.unwrap();
+//! assert_eq!(row.get::<HashMap<String, Option<String>>>(0), map);
+//! # Ok(())
+//! # }
+//! ```
+
+//! # User defined type
+//! With `codegen` feature enabled [`PgType`](derive@PgType) can be derived for mapping Rust enum with unit variants
+//! to postgres enum type and struct with named fields to postgres composite type. Type name defaults to the name
+//! of Rust type and can be changed with `#[pg_type(name = "..")]`. Variant and field names can be changed with
+//! `#[pg_type(rename = "..")]`.
+//!
+//! Oid of user defined type is only known at runtime. [`Client::resolve_type`] can be used to look up the type by
+//! name and use it for preparing statement.
+//! ```rust
+//! # #[cfg(feature = "codegen")]
+//! # async fn user_type(cli: &xitca_postgres::Client) -> Result<(), xitca_postgres::Error> {
+//! use xitca_postgres::{iter::AsyncLendingIterator, types::PgType, Execute, Statement};
+//!
+//! #[derive(Debug, PartialEq, PgType)]
+//! #[pg_type(name = "mood")]
+//! enum Mood {
+//!     #[pg_type(rename = "sad")]
+//!     Sad,
+//!     #[pg_type(rename = "happy")]
+//!     Happy,
+//! }
+//!
+//! "CREATE TYPE pg_temp.mood AS ENUM ('sad', 'happy')".execute(cli).await?;
+//!
+//! let mood = cli.resolve_type("mood").await?;
+//! let stmt = Statement::named("SELECT $1", &[mood]).execute(cli).await?;
+//! let mut stream = stmt.bind([Mood::Happy]).query(cli).await?;
+//! let row = stream.try_next().await?.unwrap();
+//! assert_eq!(row.get::<Mood>(0), Mood::Happy);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Client::resolve_type`]: crate::Client::resolve_type
+
+mod interval;
+mod net;
+#[cfg(feature = "decimal")]
+mod numeric;
+mod range;
 
 #[doc(inline)]
 pub use postgres_types::*;
+
+#[cfg(feature = "codegen")]
+/// derive [`ToSql`] and [`FromSql`] traits for user defined enum and composite type. see [module](self) for detail.
+pub use xitca_postgres_codegen::PgType;
+
+pub use self::interval::Interval;
+pub use self::net::{Cidr, Inet, MacAddr, MacAddr8};
+#[cfg(feature = "decimal")]
+pub use self::numeric::Numeric;
+pub use self::range::{Multirange, Range};
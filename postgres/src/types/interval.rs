@@ -0,0 +1,155 @@
+use core::error;
+
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use xitca_io::bytes::{BufMut, BytesMut};
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::error::IntervalOutOfRange;
+
+/// Postgres `INTERVAL` type.
+///
+/// Interval is stored as separate months, days and microseconds fields where the length of a month and a day
+/// depends on the date it's applied to. Conversion to fixed length duration types like `chrono::TimeDelta` and
+/// `time::Duration` treats a day as 24 hours and fails when months is not zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl Interval {
+    pub const fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn try_to_microseconds(self) -> Result<i64, IntervalOutOfRange> {
+        if self.months != 0 {
+            return Err(IntervalOutOfRange);
+        }
+        i64::from(self.days)
+            .checked_mul(MICROSECONDS_PER_DAY)
+            .and_then(|days| days.checked_add(self.microseconds))
+            .ok_or(IntervalOutOfRange)
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+const MICROSECONDS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        let raw: &[u8; 16] = raw
+            .try_into()
+            .map_err(|_| "invalid message length: interval size mismatch")?;
+        let (micros, rest) = raw.split_at(8);
+        let (days, months) = rest.split_at(4);
+        Ok(Self {
+            months: i32::from_be_bytes(months.try_into().unwrap()),
+            days: i32::from_be_bytes(days.try_into().unwrap()),
+            microseconds: i64::from_be_bytes(micros.try_into().unwrap()),
+        })
+    }
+
+    accepts!(INTERVAL);
+}
+
+impl ToSql for Interval {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        out.put_i64(self.microseconds);
+        out.put_i32(self.days);
+        out.put_i32(self.months);
+        Ok(IsNull::No)
+    }
+
+    accepts!(INTERVAL);
+
+    to_sql_checked!();
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impl {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    impl TryFrom<TimeDelta> for Interval {
+        type Error = IntervalOutOfRange;
+
+        fn try_from(delta: TimeDelta) -> Result<Self, Self::Error> {
+            delta
+                .num_microseconds()
+                .map(|micros| Self::new(0, 0, micros))
+                .ok_or(IntervalOutOfRange)
+        }
+    }
+
+    impl TryFrom<Interval> for TimeDelta {
+        type Error = IntervalOutOfRange;
+
+        fn try_from(interval: Interval) -> Result<Self, Self::Error> {
+            interval.try_to_microseconds().map(TimeDelta::microseconds)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impl {
+    use time::Duration;
+
+    use super::*;
+
+    impl TryFrom<Duration> for Interval {
+        type Error = IntervalOutOfRange;
+
+        fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+            i64::try_from(duration.whole_microseconds())
+                .map(|micros| Self::new(0, 0, micros))
+                .map_err(|_| IntervalOutOfRange)
+        }
+    }
+
+    impl TryFrom<Interval> for Duration {
+        type Error = IntervalOutOfRange;
+
+        fn try_from(interval: Interval) -> Result<Self, Self::Error> {
+            interval.try_to_microseconds().map(Duration::microseconds)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let interval = Interval::new(1, -2, 3_000_004);
+
+        let mut buf = BytesMut::new();
+        interval.to_sql(&Type::INTERVAL, &mut buf).unwrap();
+        assert_eq!(
+            buf.as_ref(),
+            [0, 0, 0, 0, 0, 45, 198, 196, 255, 255, 255, 254, 0, 0, 0, 1]
+        );
+        assert_eq!(Interval::from_sql(&Type::INTERVAL, &buf).unwrap(), interval);
+
+        assert!(Interval::from_sql(&Type::INTERVAL, &buf[..15]).is_err());
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn to_microseconds() {
+        assert_eq!(
+            Interval::new(0, 1, 1).try_to_microseconds().unwrap(),
+            MICROSECONDS_PER_DAY + 1
+        );
+        assert!(Interval::new(1, 0, 0).try_to_microseconds().is_err());
+        assert!(Interval::new(0, 1, i64::MAX).try_to_microseconds().is_err());
+    }
+}
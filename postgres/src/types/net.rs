@@ -0,0 +1,320 @@
+use core::{error, fmt};
+
+use std::net::IpAddr;
+
+use postgres_protocol::types as protocol;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use xitca_io::bytes::{BufMut, BytesMut};
+
+use crate::error::InvalidCidr;
+
+/// Postgres `INET` type. Host address with optional netmask where bits to the right of netmask can be non zero.
+///
+/// Netmask defaults to the full length of address which postgres omits when formatting value to text.
+///
+/// # Examples
+/// ```rust
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use xitca_postgres::types::Inet;
+///
+/// let inet = Inet::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 5)), 24).unwrap();
+/// assert_eq!(inet.to_string(), "192.168.1.5/24");
+/// assert_eq!(inet.network().to_string(), "192.168.1.0/24");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Inet {
+    addr: IpAddr,
+    netmask: u8,
+}
+
+impl Inet {
+    /// construct from address and netmask length. `None` is returned when netmask is longer than address.
+    pub fn new(addr: IpAddr, netmask: u8) -> Option<Self> {
+        (netmask <= max_prefix(&addr)).then_some(Self { addr, netmask })
+    }
+
+    pub const fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    pub const fn netmask(&self) -> u8 {
+        self.netmask
+    }
+
+    /// network of address with bits to the right of netmask cleared.
+    pub fn network(&self) -> Cidr {
+        Cidr {
+            addr: mask(self.addr, self.netmask),
+            prefix: self.netmask,
+        }
+    }
+}
+
+impl From<IpAddr> for Inet {
+    fn from(addr: IpAddr) -> Self {
+        Self {
+            netmask: max_prefix(&addr),
+            addr,
+        }
+    }
+}
+
+impl fmt::Display for Inet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.netmask == max_prefix(&self.addr) {
+            fmt::Display::fmt(&self.addr, f)
+        } else {
+            write!(f, "{}/{}", self.addr, self.netmask)
+        }
+    }
+}
+
+impl<'a> FromSql<'a> for Inet {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        let inet = protocol::inet_from_sql(raw)?;
+        Ok(Self {
+            addr: inet.addr(),
+            netmask: inet.netmask(),
+        })
+    }
+
+    accepts!(INET, CIDR);
+}
+
+impl ToSql for Inet {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        protocol::inet_to_sql(self.addr, self.netmask, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(INET);
+
+    to_sql_checked!();
+}
+
+/// Postgres `CIDR` type. Network address where bits to the right of prefix must be zero.
+///
+/// # Examples
+/// ```rust
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use xitca_postgres::types::Cidr;
+///
+/// let cidr = Cidr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8).unwrap();
+/// assert!(cidr.contains(&IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))));
+/// assert!(!cidr.contains(&IpAddr::V4(Ipv4Addr::new(11, 0, 0, 0))));
+///
+/// // host bits must not be set.
+/// assert!(Cidr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 8).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    /// construct from network address and prefix length.
+    pub fn new(addr: IpAddr, prefix: u8) -> Result<Self, InvalidCidr> {
+        if prefix > max_prefix(&addr) || mask(addr, prefix) != addr {
+            return Err(InvalidCidr);
+        }
+        Ok(Self { addr, prefix })
+    }
+
+    pub const fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    pub const fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// check if given address belongs to network. address of different family is never contained.
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        addr.is_ipv4() == self.addr.is_ipv4() && mask(*addr, self.prefix) == self.addr
+    }
+}
+
+impl From<IpAddr> for Cidr {
+    fn from(addr: IpAddr) -> Self {
+        Self {
+            prefix: max_prefix(&addr),
+            addr,
+        }
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+impl<'a> FromSql<'a> for Cidr {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        let inet = protocol::inet_from_sql(raw)?;
+        Self::new(inet.addr(), inet.netmask()).map_err(Into::into)
+    }
+
+    accepts!(CIDR);
+}
+
+impl ToSql for Cidr {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        protocol::inet_to_sql(self.addr, self.prefix, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(CIDR);
+
+    to_sql_checked!();
+}
+
+fn max_prefix(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn mask(addr: IpAddr, prefix: u8) -> IpAddr {
+    match addr {
+        IpAddr::V4(addr) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            IpAddr::V4((u32::from(addr) & mask).into())
+        }
+        IpAddr::V6(addr) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            IpAddr::V6((u128::from(addr) & mask).into())
+        }
+    }
+}
+
+/// Postgres `MACADDR` type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacAddr(pub [u8; 6]);
+
+/// Postgres `MACADDR8` type.
+///
+/// [`MacAddr`] converts to it in EUI-64 format by inserting `FF:FE` in the middle like postgres' `macaddr8` cast.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacAddr8(pub [u8; 8]);
+
+impl From<MacAddr> for MacAddr8 {
+    fn from(MacAddr(a): MacAddr) -> Self {
+        Self([a[0], a[1], a[2], 0xff, 0xfe, a[3], a[4], a[5]])
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(&self.0, f)
+    }
+}
+
+impl fmt::Display for MacAddr8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(&self.0, f)
+    }
+}
+
+fn fmt_hex(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (idx, byte) in bytes.iter().enumerate() {
+        if idx > 0 {
+            f.write_str(":")?;
+        }
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+impl<'a> FromSql<'a> for MacAddr {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        protocol::macaddr_from_sql(raw).map(Self)
+    }
+
+    accepts!(MACADDR);
+}
+
+impl ToSql for MacAddr {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        protocol::macaddr_to_sql(self.0, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(MACADDR);
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for MacAddr8 {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        raw.try_into()
+            .map(Self)
+            .map_err(|_| "invalid message length: macaddr8 length mismatch".into())
+    }
+
+    accepts!(MACADDR8);
+}
+
+impl ToSql for MacAddr8 {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        out.put_slice(&self.0);
+        Ok(IsNull::No)
+    }
+
+    accepts!(MACADDR8);
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+
+    #[test]
+    fn cidr() {
+        let v4 = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 0));
+        assert!(Cidr::new(v4, 16).is_ok());
+        assert!(Cidr::new(v4, 15).is_ok());
+        assert!(Cidr::new(v4, 12).is_err());
+        assert!(Cidr::new(v4, 33).is_err());
+        assert_eq!(Cidr::from(v4).prefix(), 32);
+
+        let v6 = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+        let cidr = Cidr::new(v6, 32).unwrap();
+        assert!(cidr.contains(&IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 1, 0, 0, 0, 0, 1))));
+        assert!(!cidr.contains(&v4));
+        assert_eq!(cidr.to_string(), "2001:db8::/32");
+
+        let all = Cidr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0).unwrap();
+        assert!(all.contains(&v4));
+
+        let mut buf = BytesMut::new();
+        cidr.to_sql(&Type::CIDR, &mut buf).unwrap();
+        assert_eq!(Cidr::from_sql(&Type::CIDR, &buf).unwrap(), cidr);
+
+        // inet with host bits is not valid cidr.
+        let inet = Inet::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 8).unwrap();
+        let mut buf = BytesMut::new();
+        inet.to_sql(&Type::INET, &mut buf).unwrap();
+        assert!(Cidr::from_sql(&Type::CIDR, &buf).is_err());
+        assert_eq!(Inet::from_sql(&Type::INET, &buf).unwrap(), inet);
+        assert_eq!(inet.network().to_string(), "10.0.0.0/8");
+        assert_eq!(Inet::from(v4).to_string(), "192.168.0.0");
+    }
+
+    #[test]
+    fn macaddr() {
+        let mac = MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]);
+        assert_eq!(mac.to_string(), "08:00:2b:01:02:03");
+        assert_eq!(MacAddr8::from(mac).to_string(), "08:00:2b:ff:fe:01:02:03");
+
+        let mut buf = BytesMut::new();
+        mac.to_sql(&Type::MACADDR, &mut buf).unwrap();
+        assert_eq!(MacAddr::from_sql(&Type::MACADDR, &buf).unwrap(), mac);
+        assert!(MacAddr8::from_sql(&Type::MACADDR8, &buf).is_err());
+    }
+}
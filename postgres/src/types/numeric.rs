@@ -0,0 +1,100 @@
+use core::error;
+
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use rust_decimal::Decimal;
+use xitca_io::bytes::{BufMut, BytesMut};
+
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+
+/// Postgres `NUMERIC` type with special values.
+///
+/// [`Decimal`] implements [`ToSql`] and [`FromSql`] on it's own but it can not represent `NaN`, `Infinity` and
+/// `-Infinity` and decoding them would produce error. Numeric can be used in place of it when these values are
+/// expected. Decoding a value with more scale or digits than [`Decimal`] can hold would produce error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Numeric {
+    /// Represents `NaN`. Unlike float types postgres consider `NaN` equal to itself and greater than all other
+    /// numeric values.
+    NaN,
+    /// Represents `Infinity`, a value that is greater than all other numeric values except `NaN`.
+    PosInfinity,
+    /// Represents `-Infinity`, a value that is less than all other numeric values.
+    NegInfinity,
+    /// The wrapped decimal.
+    Value(Decimal),
+}
+
+impl From<Decimal> for Numeric {
+    fn from(value: Decimal) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl<'a> FromSql<'a> for Numeric {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        let sign = raw
+            .get(4..6)
+            .map(|sign| u16::from_be_bytes([sign[0], sign[1]]))
+            .ok_or("invalid message length: numeric header mismatch")?;
+        match sign {
+            NUMERIC_NAN => Ok(Self::NaN),
+            NUMERIC_PINF => Ok(Self::PosInfinity),
+            NUMERIC_NINF => Ok(Self::NegInfinity),
+            _ => Decimal::from_sql(ty, raw).map(Self::Value),
+        }
+    }
+
+    accepts!(NUMERIC);
+}
+
+impl ToSql for Numeric {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        let sign = match *self {
+            Self::NaN => NUMERIC_NAN,
+            Self::PosInfinity => NUMERIC_PINF,
+            Self::NegInfinity => NUMERIC_NINF,
+            Self::Value(ref value) => return value.to_sql(ty, out),
+        };
+        // ndigits, weight, sign and dscale. special values have no digits.
+        out.put_i16(0);
+        out.put_i16(0);
+        out.put_u16(sign);
+        out.put_u16(0);
+        Ok(IsNull::No)
+    }
+
+    accepts!(NUMERIC);
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn special_value() {
+        for (value, sign) in [
+            (Numeric::NaN, NUMERIC_NAN),
+            (Numeric::PosInfinity, NUMERIC_PINF),
+            (Numeric::NegInfinity, NUMERIC_NINF),
+        ] {
+            let mut buf = BytesMut::new();
+            value.to_sql(&Type::NUMERIC, &mut buf).unwrap();
+            assert_eq!(buf.as_ref()[4..6], sign.to_be_bytes());
+            assert_eq!(Numeric::from_sql(&Type::NUMERIC, &buf).unwrap(), value);
+        }
+
+        assert!(Numeric::from_sql(&Type::NUMERIC, &[0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn decimal_value() {
+        let value = Numeric::from(Decimal::new(-12345, 3));
+        let mut buf = BytesMut::new();
+        value.to_sql(&Type::NUMERIC, &mut buf).unwrap();
+        assert_eq!(Numeric::from_sql(&Type::NUMERIC, &buf).unwrap(), value);
+    }
+}
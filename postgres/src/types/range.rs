@@ -0,0 +1,369 @@
+use core::{error, ops::Bound};
+
+use postgres_protocol::{
+    types::{self as protocol, RangeBound},
+    IsNull as ProtocolIsNull,
+};
+use postgres_types::{to_sql_checked, FromSql, IsNull, Kind, ToSql, Type};
+use xitca_io::bytes::{BufMut, BytesMut};
+
+/// Postgres range type like `int4range`, `int8range`, `numrange`, `tsrange`, `tstzrange` and `daterange`.
+///
+/// Bounds are represented with [`Bound`] where [`Bound::Included`] and [`Bound::Excluded`] maps to `[`/`]` and
+/// `(`/`)` bound of range and [`Bound::Unbounded`] maps to infinite bound. Postgres normalizes discrete range types
+/// and value decoded from database may have different bounds than the one encoded. For example `[1, 3]` of
+/// `int4range` is stored as `[1, 4)`.
+///
+/// # Examples
+/// ```rust
+/// use core::ops::Bound;
+/// use xitca_postgres::types::Range;
+///
+/// let range = Range::from(1..4);
+/// assert_eq!(range, Range::new(Bound::Included(1), Bound::Excluded(4)));
+/// assert!(range.contains(&3));
+/// assert!(!range.contains(&4));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Range<T> {
+    /// Represents `empty`, a range that contains no value.
+    Empty,
+    /// A range with lower and upper bound.
+    Nonempty(Bound<T>, Bound<T>),
+}
+
+impl<T> Range<T> {
+    /// construct a non empty range from lower and upper bound.
+    pub const fn new(lower: Bound<T>, upper: Bound<T>) -> Self {
+        Self::Nonempty(lower, upper)
+    }
+
+    /// construct an empty range.
+    pub const fn empty() -> Self {
+        Self::Empty
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// lower bound of range. `None` when range is empty.
+    pub fn lower(&self) -> Option<Bound<&T>> {
+        match self {
+            Self::Empty => None,
+            Self::Nonempty(lower, _) => Some(lower.as_ref()),
+        }
+    }
+
+    /// upper bound of range. `None` when range is empty.
+    pub fn upper(&self) -> Option<Bound<&T>> {
+        match self {
+            Self::Empty => None,
+            Self::Nonempty(_, upper) => Some(upper.as_ref()),
+        }
+    }
+
+    /// check if value is contained by range.
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialOrd,
+    {
+        let Self::Nonempty(lower, upper) = self else {
+            return false;
+        };
+        let lower = match lower {
+            Bound::Included(lower) => lower <= value,
+            Bound::Excluded(lower) => lower < value,
+            Bound::Unbounded => true,
+        };
+        let upper = match upper {
+            Bound::Included(upper) => value <= upper,
+            Bound::Excluded(upper) => value < upper,
+            Bound::Unbounded => true,
+        };
+        lower && upper
+    }
+}
+
+impl<T> From<core::ops::Range<T>> for Range<T> {
+    fn from(range: core::ops::Range<T>) -> Self {
+        Self::new(Bound::Included(range.start), Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<core::ops::RangeInclusive<T>> for Range<T> {
+    fn from(range: core::ops::RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Self::new(Bound::Included(start), Bound::Included(end))
+    }
+}
+
+impl<T> From<core::ops::RangeFrom<T>> for Range<T> {
+    fn from(range: core::ops::RangeFrom<T>) -> Self {
+        Self::new(Bound::Included(range.start), Bound::Unbounded)
+    }
+}
+
+impl<T> From<core::ops::RangeTo<T>> for Range<T> {
+    fn from(range: core::ops::RangeTo<T>) -> Self {
+        Self::new(Bound::Unbounded, Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<core::ops::RangeToInclusive<T>> for Range<T> {
+    fn from(range: core::ops::RangeToInclusive<T>) -> Self {
+        Self::new(Bound::Unbounded, Bound::Included(range.end))
+    }
+}
+
+impl<T> From<core::ops::RangeFull> for Range<T> {
+    fn from(_: core::ops::RangeFull) -> Self {
+        Self::new(Bound::Unbounded, Bound::Unbounded)
+    }
+}
+
+impl<'a, T> FromSql<'a> for Range<T>
+where
+    T: FromSql<'a>,
+{
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        let Kind::Range(ref inner) = *ty.kind() else {
+            unreachable!("accepts only range type");
+        };
+        range_from_sql(inner, raw)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Range(ref inner) => T::accepts(inner),
+            _ => false,
+        }
+    }
+}
+
+impl<T> ToSql for Range<T>
+where
+    T: ToSql,
+{
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        let Kind::Range(ref inner) = *ty.kind() else {
+            unreachable!("accepts only range type");
+        };
+        range_to_sql(self, inner, out)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Range(ref inner) => T::accepts(inner),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+/// Postgres multirange type like `int4multirange` and `tstzmultirange`. available since postgres 14.
+///
+/// A multirange is an ordered list of non overlapping and non empty ranges. Postgres normalizes multirange and
+/// value decoded from database may be different from the one encoded.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Multirange<T>(pub Vec<Range<T>>);
+
+impl<T> From<Vec<Range<T>>> for Multirange<T> {
+    fn from(ranges: Vec<Range<T>>) -> Self {
+        Self(ranges)
+    }
+}
+
+impl<'a, T> FromSql<'a> for Multirange<T>
+where
+    T: FromSql<'a>,
+{
+    fn from_sql(ty: &Type, mut raw: &'a [u8]) -> Result<Self, Box<dyn error::Error + Sync + Send>> {
+        let Kind::Multirange(ref inner) = *ty.kind() else {
+            unreachable!("accepts only multirange type");
+        };
+
+        let count = read_i32(&mut raw)?;
+        let mut ranges = Vec::with_capacity(count.max(0) as usize);
+        for _ in 0..count {
+            let len = usize::try_from(read_i32(&mut raw)?).map_err(|_| "invalid multirange length")?;
+            let (range, rest) = raw
+                .split_at_checked(len)
+                .ok_or("invalid message length: multirange size mismatch")?;
+            ranges.push(range_from_sql(inner, range)?);
+            raw = rest;
+        }
+
+        if !raw.is_empty() {
+            return Err("invalid message length: multirange size mismatch".into());
+        }
+
+        Ok(Self(ranges))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Multirange(ref inner) => T::accepts(inner),
+            _ => false,
+        }
+    }
+}
+
+impl<T> ToSql for Multirange<T>
+where
+    T: ToSql,
+{
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        let Kind::Multirange(ref inner) = *ty.kind() else {
+            unreachable!("accepts only multirange type");
+        };
+
+        let count = i32::try_from(self.0.len()).map_err(|_| "value too large to transmit")?;
+        out.put_i32(count);
+
+        for range in self.0.iter() {
+            let base = out.len();
+            out.put_i32(0);
+            range_to_sql(range, inner, out)?;
+            let len = i32::try_from(out.len() - base - 4).map_err(|_| "value too large to transmit")?;
+            out[base..base + 4].copy_from_slice(&len.to_be_bytes());
+        }
+
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Multirange(ref inner) => T::accepts(inner),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+fn range_from_sql<'a, T>(inner: &Type, raw: &'a [u8]) -> Result<Range<T>, Box<dyn error::Error + Sync + Send>>
+where
+    T: FromSql<'a>,
+{
+    fn bound_from_sql<'a, T>(
+        inner: &Type,
+        bound: RangeBound<Option<&'a [u8]>>,
+    ) -> Result<Bound<T>, Box<dyn error::Error + Sync + Send>>
+    where
+        T: FromSql<'a>,
+    {
+        match bound {
+            RangeBound::Inclusive(value) => T::from_sql_nullable(inner, value).map(Bound::Included),
+            RangeBound::Exclusive(value) => T::from_sql_nullable(inner, value).map(Bound::Excluded),
+            RangeBound::Unbounded => Ok(Bound::Unbounded),
+        }
+    }
+
+    match protocol::range_from_sql(raw)? {
+        protocol::Range::Empty => Ok(Range::Empty),
+        protocol::Range::Nonempty(lower, upper) => Ok(Range::Nonempty(
+            bound_from_sql(inner, lower)?,
+            bound_from_sql(inner, upper)?,
+        )),
+    }
+}
+
+fn range_to_sql<T>(
+    range: &Range<T>,
+    inner: &Type,
+    out: &mut BytesMut,
+) -> Result<(), Box<dyn error::Error + Sync + Send>>
+where
+    T: ToSql,
+{
+    fn bound_to_sql<T>(
+        bound: &Bound<T>,
+        inner: &Type,
+        out: &mut BytesMut,
+    ) -> Result<RangeBound<ProtocolIsNull>, Box<dyn error::Error + Sync + Send>>
+    where
+        T: ToSql,
+    {
+        let is_null = |value: &T, out: &mut BytesMut| {
+            value.to_sql(inner, out).map(|is_null| match is_null {
+                IsNull::Yes => ProtocolIsNull::Yes,
+                IsNull::No => ProtocolIsNull::No,
+            })
+        };
+        match bound {
+            Bound::Included(value) => is_null(value, out).map(RangeBound::Inclusive),
+            Bound::Excluded(value) => is_null(value, out).map(RangeBound::Exclusive),
+            Bound::Unbounded => Ok(RangeBound::Unbounded),
+        }
+    }
+
+    match range {
+        Range::Empty => {
+            protocol::empty_range_to_sql(out);
+            Ok(())
+        }
+        Range::Nonempty(lower, upper) => protocol::range_to_sql(
+            |out| bound_to_sql(lower, inner, out),
+            |out| bound_to_sql(upper, inner, out),
+            out,
+        ),
+    }
+}
+
+fn read_i32(buf: &mut &[u8]) -> Result<i32, Box<dyn error::Error + Sync + Send>> {
+    let (head, rest) = buf
+        .split_first_chunk::<4>()
+        .ok_or("invalid message length: multirange size mismatch")?;
+    *buf = rest;
+    Ok(i32::from_be_bytes(*head))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn contains() {
+        let range = Range::from(1..=3);
+        assert!(range.contains(&1));
+        assert!(range.contains(&3));
+        assert!(!range.contains(&4));
+
+        let range = Range::<i32>::from(..);
+        assert!(range.contains(&i32::MIN));
+
+        assert!(!Range::empty().contains(&0));
+    }
+
+    #[test]
+    fn encode_decode() {
+        for range in [
+            Range::empty(),
+            Range::from(1..4),
+            Range::new(Bound::Excluded(1), Bound::Included(4)),
+            Range::from(1..),
+            Range::from(..=4),
+            Range::from(..),
+        ] {
+            let mut buf = BytesMut::new();
+            range.to_sql(&Type::INT4_RANGE, &mut buf).unwrap();
+            assert_eq!(Range::<i32>::from_sql(&Type::INT4_RANGE, &buf).unwrap(), range);
+        }
+
+        let multirange = Multirange(vec![Range::from(1..4), Range::from(8..)]);
+        let mut buf = BytesMut::new();
+        multirange.to_sql(&Type::INT4MULTI_RANGE, &mut buf).unwrap();
+        assert_eq!(
+            Multirange::<i32>::from_sql(&Type::INT4MULTI_RANGE, &buf).unwrap(),
+            multirange
+        );
+        assert!(Multirange::<i32>::from_sql(&Type::INT4MULTI_RANGE, &buf[..buf.len() - 1]).is_err());
+
+        assert!(<Range<i32> as ToSql>::accepts(&Type::INT4_RANGE));
+        assert!(!<Range<i64> as ToSql>::accepts(&Type::INT4_RANGE));
+        assert!(!<Range<i32> as ToSql>::accepts(&Type::INT4MULTI_RANGE));
+    }
+}
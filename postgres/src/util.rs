@@ -0,0 +1,15 @@
+// quote identifier like channel, savepoint and replication slot names. quoted identifier is case-sensitive.
+pub(crate) fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quote() {
+        assert_eq!(quote_ident("foo"), "\"foo\"");
+        assert_eq!(quote_ident("fo\"o"), "\"fo\"\"o\"");
+    }
+}
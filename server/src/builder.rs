@@ -21,6 +21,9 @@ pub struct Builder {
     pub(crate) listeners: HashMap<String, Vec<ListenerFn>>,
     pub(crate) factories: HashMap<String, ServiceObj>,
     pub(crate) enable_signal: bool,
+    pub(crate) graceful_signal: bool,
+    pub(crate) on_signal: Option<Box<dyn FnOnce() + Send>>,
+    pub(crate) on_reload: Option<Box<dyn FnMut() + Send>>,
     pub(crate) shutdown_timeout: Duration,
     pub(crate) on_worker_start: Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>,
     backlog: u32,
@@ -42,6 +45,9 @@ impl Builder {
             listeners: HashMap::new(),
             factories: HashMap::new(),
             enable_signal: true,
+            graceful_signal: false,
+            on_signal: None,
+            on_reload: None,
             shutdown_timeout: Duration::from_secs(30),
             on_worker_start: Box::new(|| Box::pin(async {})),
             backlog: 2048,
@@ -100,6 +106,59 @@ impl Builder {
         self
     }
 
+    /// Shutdown server gracefully on every stop signal.
+    ///
+    /// By default SIGINT and SIGQUIT(ctrl-c on windows) force stop the server and only SIGTERM(ctrl-break on
+    /// windows) stops it gracefully. With this option all of them trigger graceful shutdown where workers have
+    /// given drain timeout to finish serving in-flight requests before being force dropped.
+    ///
+    /// Drain timeout overrides the value set by [Builder::shutdown_timeout].
+    ///
+    /// # Examples:
+    /// ```
+    /// # use std::time::Duration;
+    /// # use xitca_server::Builder;
+    /// let builder = Builder::new()
+    ///     .graceful_signal(Duration::from_secs(10))
+    ///     .on_signal(|| println!("shutting down"));
+    /// ```
+    pub fn graceful_signal(mut self, drain_timeout: Duration) -> Self {
+        self.enable_signal = true;
+        self.graceful_signal = true;
+        self.shutdown_timeout = drain_timeout;
+        self
+    }
+
+    /// Callback called once when a stop signal is received and before server starts to shutdown.
+    ///
+    /// Useful for flipping application readiness state and flushing buffered data. The callback is
+    /// called from server's thread and must not block for long.
+    ///
+    /// Callback is not called when signal listening is disabled or server is stopped through
+    /// [ServerHandle](crate::ServerHandle).
+    pub fn on_signal<F>(mut self, on_signal: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.on_signal = Some(Box::new(on_signal));
+        self
+    }
+
+    /// Callback called on every SIGHUP signal.
+    ///
+    /// By default SIGHUP stops server from listening to signals and keeps it running. With this
+    /// callback server keeps listening and the callback is called for reloading application state
+    /// like tls certificates. The callback is called from server's thread and must not block for long.
+    ///
+    /// Callback is not called when signal listening is disabled or on non unix platform.
+    pub fn on_reload<F>(mut self, on_reload: F) -> Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.on_reload = Some(Box::new(on_reload));
+        self
+    }
+
     /// Timeout for graceful workers shutdown in seconds.
     ///
     /// After receiving a stop signal, workers have this much time to finish serving requests.
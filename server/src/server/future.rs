@@ -127,21 +127,30 @@ impl ServerFutureInner {
 
     #[inline(never)]
     fn poll_cmd(&mut self, cx: &mut Context<'_>) -> Poll<Command> {
-        if let Some(signals) = self.signals.as_mut() {
-            if let Poll::Ready(sig) = Pin::new(signals).poll(cx) {
-                tracing::info!("Signal {:?} received.", sig);
-                let cmd = match sig {
-                    Signal::Int | Signal::Quit => Command::ForceStop,
-                    Signal::Term => Command::GracefulStop,
-                    // Remove signal listening and keep Server running when
-                    // terminal closed which xitca-server process belong.
-                    Signal::Hup => {
-                        self.signals = None;
-                        return Poll::Pending;
-                    }
-                };
-                return Poll::Ready(cmd);
+        while let Some(signals) = self.signals.as_mut() {
+            let Poll::Ready(sig) = Pin::new(signals).poll(cx) else {
+                break;
+            };
+            tracing::info!("Signal {:?} received.", sig);
+            let cmd = match sig {
+                Signal::Int | Signal::Quit if !self.server.graceful_signal => Command::ForceStop,
+                Signal::Int | Signal::Quit | Signal::Term => Command::GracefulStop,
+                // call reload callback and poll signals again to keep listening.
+                Signal::Hup if self.server.on_reload.is_some() => {
+                    (self.server.on_reload.as_mut().unwrap())();
+                    continue;
+                }
+                // Remove signal listening and keep Server running when
+                // terminal closed which xitca-server process belong.
+                Signal::Hup => {
+                    self.signals = None;
+                    return Poll::Pending;
+                }
+            };
+            if let Some(on_signal) = self.server.on_signal.take() {
+                on_signal();
             }
+            return Poll::Ready(cmd);
         }
 
         match ready!(Pin::new(&mut self.server.rx_cmd).poll_recv(cx)) {
@@ -28,6 +28,9 @@ pub struct Server {
     rx_cmd: UnboundedReceiver<Command>,
     rt: Option<Runtime>,
     worker_join_handles: Vec<thread::JoinHandle<io::Result<()>>>,
+    graceful_signal: bool,
+    on_signal: Option<Box<dyn FnOnce() + Send>>,
+    on_reload: Option<Box<dyn FnMut() + Send>>,
 }
 
 impl Server {
@@ -89,6 +92,9 @@ impl Server {
             worker_max_blocking_threads,
             listeners,
             factories,
+            graceful_signal,
+            on_signal,
+            on_reload,
             shutdown_timeout,
             on_worker_start,
             ..
@@ -178,6 +184,9 @@ impl Server {
             rx_cmd,
             rt: Some(rt),
             worker_join_handles: vec![worker_handles],
+            graceful_signal,
+            on_signal,
+            on_reload,
         })
     }
 
@@ -6,9 +6,9 @@ use std::{future::Future, pin::Pin};
 pub(crate) enum Signal {
     /// SIGHUP
     Hup,
-    /// SIGINT
+    /// SIGINT. ctrl-c on windows
     Int,
-    /// SIGTERM
+    /// SIGTERM. ctrl-break on windows
     Term,
     /// SIGQUIT
     Quit,
@@ -49,7 +49,31 @@ pub(crate) fn start() -> SignalFuture {
         }))
     }
 
-    #[cfg(not(any(unix, target_family = "wasm")))]
+    #[cfg(windows)]
+    {
+        use std::{future::poll_fn, task::Poll};
+
+        use tokio::signal::windows;
+
+        let mut ctrl_c = windows::ctrl_c()
+            .map_err(|e| tracing::error!("Can not initialize stream handler for ctrl-c err: {}", e))
+            .ok();
+        let mut ctrl_break = windows::ctrl_break()
+            .map_err(|e| tracing::error!("Can not initialize stream handler for ctrl-break err: {}", e))
+            .ok();
+
+        Box::pin(poll_fn(move |cx| {
+            if let Some(Poll::Ready(_)) = ctrl_c.as_mut().map(|sig| sig.poll_recv(cx)) {
+                return Poll::Ready(Signal::Int);
+            }
+            if let Some(Poll::Ready(_)) = ctrl_break.as_mut().map(|sig| sig.poll_recv(cx)) {
+                return Poll::Ready(Signal::Term);
+            }
+            Poll::Pending
+        }))
+    }
+
+    #[cfg(not(any(unix, windows, target_family = "wasm")))]
     {
         Box::pin(async {
             let _ = tokio::signal::ctrl_c().await;
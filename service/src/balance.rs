@@ -0,0 +1,310 @@
+//! load balancing service distributing requests across a dynamic set of inner services.
+//!
+//! [Balance] picks two random services for each request and calls the less loaded one. (power of two choices)
+//! load of service is estimated from the output of it's [ReadyService::ready] through [Load] trait and the output
+//! is held until the call is finished.
+//!
+//! # Examples
+//! ```rust
+//! # use core::{cell::Cell, convert::Infallible};
+//! # use xitca_service::{balance::Balance, ready::ReadyService, Service};
+//! // a service tracking it's in flight calls.
+//! struct Endpoint(Cell<usize>);
+//!
+//! impl Service<()> for Endpoint {
+//!     type Response = ();
+//!     type Error = Infallible;
+//!
+//!     async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! impl ReadyService for Endpoint {
+//!     // amount of in flight calls is used as load.
+//!     type Ready = usize;
+//!
+//!     async fn ready(&self) -> Self::Ready {
+//!         self.0.get()
+//!     }
+//! }
+//!
+//! # async fn balance() {
+//! let balance = Balance::new();
+//!
+//! // endpoints can be added and removed at runtime.
+//! balance.insert("127.0.0.1:8080", Endpoint(Cell::new(0)));
+//! balance.insert("127.0.0.1:8081", Endpoint(Cell::new(0)));
+//!
+//! balance.call(()).await.unwrap();
+//!
+//! balance.remove(&"127.0.0.1:8080");
+//! # }
+//! ```
+
+use core::{cell::RefCell, fmt};
+
+use alloc::{rc::Rc, vec::Vec};
+
+use crate::{ready::ReadyService, service::Service};
+
+/// load estimate of service. lower value means less loaded service.
+pub trait Load {
+    fn load(&self) -> usize;
+}
+
+impl Load for usize {
+    #[inline]
+    fn load(&self) -> usize {
+        *self
+    }
+}
+
+impl Load for () {
+    #[inline]
+    fn load(&self) -> usize {
+        0
+    }
+}
+
+/// power of two choices load balancer over services keyed by K.
+pub struct Balance<K, S> {
+    services: RefCell<Vec<(K, Rc<S>)>>,
+    rng: RefCell<Rng>,
+}
+
+impl<K, S> Default for Balance<K, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, S> Balance<K, S> {
+    /// construct an empty balancer.
+    pub const fn new() -> Self {
+        Self {
+            services: RefCell::new(Vec::new()),
+            rng: RefCell::new(Rng(0x2545_F491_4F6C_DD1D)),
+        }
+    }
+
+    /// amount of services in balancer.
+    pub fn len(&self) -> usize {
+        self.services.borrow().len()
+    }
+
+    /// balancer is empty and any call would fail with [BalanceError::Empty].
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, S> Balance<K, S>
+where
+    K: Eq,
+{
+    /// add service with given key. existing service with the same key is replaced and returned.
+    ///
+    /// in flight calls of replaced service are not affected.
+    pub fn insert(&self, key: K, service: S) -> Option<Rc<S>> {
+        let mut services = self.services.borrow_mut();
+        match services.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, s)) => Some(core::mem::replace(s, Rc::new(service))),
+            None => {
+                services.push((key, Rc::new(service)));
+                None
+            }
+        }
+    }
+
+    /// remove service with given key.
+    ///
+    /// in flight calls of removed service are not affected.
+    pub fn remove(&self, key: &K) -> Option<Rc<S>> {
+        let mut services = self.services.borrow_mut();
+        let idx = services.iter().position(|(k, _)| k == key)?;
+        Some(services.swap_remove(idx).1)
+    }
+}
+
+impl<K, S> Balance<K, S> {
+    // pick two random services. None when balancer is empty.
+    fn pick(&self) -> Option<(Rc<S>, Option<Rc<S>>)> {
+        let services = self.services.borrow();
+        match services.len() {
+            0 => None,
+            1 => Some((services[0].1.clone(), None)),
+            len => {
+                let mut rng = self.rng.borrow_mut();
+                let a = rng.next_below(len);
+                // pick b from the rest services so it never equals to a.
+                let b = (a + 1 + rng.next_below(len - 1)) % len;
+                Some((services[a].1.clone(), Some(services[b].1.clone())))
+            }
+        }
+    }
+}
+
+impl<K, S, Req> Service<Req> for Balance<K, S>
+where
+    S: Service<Req> + ReadyService,
+    S::Ready: Load,
+{
+    type Response = S::Response;
+    type Error = BalanceError<S::Error>;
+
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        let (a, b) = self.pick().ok_or(BalanceError::Empty)?;
+
+        let ready_a = a.ready().await;
+
+        let (service, _ready) = match b {
+            Some(b) => {
+                let ready_b = b.ready().await;
+                if ready_b.load() < ready_a.load() {
+                    (b, ready_b)
+                } else {
+                    (a, ready_a)
+                }
+            }
+            None => (a, ready_a),
+        };
+
+        service.call(req).await.map_err(BalanceError::Service)
+    }
+}
+
+// xorshift random number generator.
+struct Rng(u64);
+
+impl Rng {
+    fn next_below(&mut self, n: usize) -> usize {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        (x % n as u64) as usize
+    }
+}
+
+/// error type of [Balance].
+#[derive(Debug, Eq, PartialEq)]
+pub enum BalanceError<E> {
+    /// balancer has no service.
+    Empty,
+    /// error produced by service.
+    Service(E),
+}
+
+impl<E> fmt::Display for BalanceError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("balancer has no service"),
+            Self::Service(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl<E> core::error::Error for BalanceError<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Empty => None,
+            Self::Service(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{cell::Cell, convert::Infallible};
+
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use super::*;
+
+    struct Endpoint {
+        load: usize,
+        calls: Cell<usize>,
+    }
+
+    impl Endpoint {
+        fn new(load: usize) -> Self {
+            Self {
+                load,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl Service<()> for Endpoint {
+        type Response = usize;
+        type Error = Infallible;
+
+        async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.load)
+        }
+    }
+
+    impl ReadyService for Endpoint {
+        type Ready = usize;
+
+        async fn ready(&self) -> Self::Ready {
+            self.load
+        }
+    }
+
+    #[test]
+    fn p2c() {
+        let balance = Balance::new();
+        assert_eq!(balance.call(()).now_or_panic(), Err(BalanceError::Empty));
+
+        balance.insert(0, Endpoint::new(0));
+        balance.insert(1, Endpoint::new(1));
+
+        // with two services the less loaded one is always picked.
+        for _ in 0..8 {
+            assert_eq!(balance.call(()).now_or_panic(), Ok(0));
+        }
+
+        let removed = balance.remove(&0).unwrap();
+        assert_eq!(removed.calls.get(), 8);
+        assert!(balance.remove(&0).is_none());
+
+        assert_eq!(balance.call(()).now_or_panic(), Ok(1));
+    }
+
+    #[test]
+    fn distribute() {
+        let balance = Balance::new();
+        for key in 0..4 {
+            balance.insert(key, Endpoint::new(key));
+        }
+        assert_eq!(balance.len(), 4);
+
+        let replaced = balance.insert(3, Endpoint::new(3)).unwrap();
+        assert_eq!(replaced.load, 3);
+        assert_eq!(balance.len(), 4);
+
+        for _ in 0..64 {
+            balance.call(()).now_or_panic().unwrap();
+        }
+
+        let calls = (0..4)
+            .map(|key| balance.remove(&key).unwrap().calls.get())
+            .collect::<Vec<_>>();
+
+        // the most loaded service is never picked as it always loses to the other choice.
+        assert_eq!(calls[3], 0);
+        assert!(calls[0] > calls[2]);
+        assert_eq!(calls.iter().sum::<usize>(), 64);
+        assert!(balance.is_empty());
+    }
+}
@@ -0,0 +1,395 @@
+//! buffered service handle backed by a worker task.
+//!
+//! [Buffer] is a clonable handle of a service. requests are sent to a bounded queue and the service is called by
+//! [BufferWorker] one request at a time. when queue is full caller of [Buffer] waits for free capacity.
+//!
+//! crate is runtime agnostic and [BufferWorker::run] must be spawned as a task by caller. handle and worker are
+//! not thread safe and they are meant to be used inside the same thread. (e.g: with `tokio::task::spawn_local`)
+//!
+//! # Examples
+//! ```rust
+//! # use core::convert::Infallible;
+//! # use xitca_service::{buffer::Buffer, fn_service, Service};
+//! # async fn buffer() {
+//! let service = fn_service(|req: usize| async move { Ok::<_, Infallible>(req + 1) })
+//!     .call(())
+//!     .await
+//!     .unwrap();
+//!
+//! let (buffer, worker) = Buffer::new(service, 16);
+//!
+//! // worker must be polled for handle to make progress. in real world application it's spawned as task.
+//! let worker = worker.run();
+//! # let _ = worker;
+//!
+//! // handle can be cloned and shared.
+//! let buffer2 = buffer.clone();
+//! # }
+//! ```
+
+use core::{
+    cell::RefCell,
+    fmt,
+    future::{Future, poll_fn},
+    task::{Poll, Waker},
+};
+
+use alloc::{collections::VecDeque, rc::Rc, vec::Vec};
+
+use crate::{ready::ReadyService, service::Service};
+
+/// clonable handle of service driven by [BufferWorker].
+pub struct Buffer<Req, Res, Err> {
+    shared: SharedRef<Req, Res, Err>,
+}
+
+/// worker calling service with requests sent from [Buffer].
+pub struct BufferWorker<S, Req>
+where
+    S: Service<Req>,
+{
+    service: S,
+    shared: SharedRef<Req, S::Response, S::Error>,
+}
+
+type SharedRef<Req, Res, Err> = Rc<RefCell<Shared<Req, Res, Err>>>;
+
+type Item<Req, Res, Err> = (Req, Sender<Result<Res, Err>>);
+
+struct Shared<Req, Res, Err> {
+    queue: VecDeque<Item<Req, Res, Err>>,
+    cap: usize,
+    closed: bool,
+    worker: Option<Waker>,
+    // callers waiting for free capacity of queue.
+    waiters: Vec<Waker>,
+}
+
+impl<Req, Res, Err> Shared<Req, Res, Err> {
+    // register waker waiting for free capacity. waker of the same task is only registered once so repeated
+    // polling of pending call does not grow the waiter list.
+    fn wait(&mut self, waker: &Waker) {
+        if !self.waiters.iter().any(|w| w.will_wake(waker)) {
+            self.waiters.push(waker.clone());
+        }
+    }
+}
+
+impl<Req, Res, Err> Buffer<Req, Res, Err> {
+    /// construct a handle and it's worker with given service and capacity of request queue.
+    ///
+    /// # Panics
+    /// when capacity is 0.
+    pub fn new<S>(service: S, cap: usize) -> (Self, BufferWorker<S, Req>)
+    where
+        S: Service<Req, Response = Res, Error = Err>,
+    {
+        assert!(cap > 0, "buffer capacity must be greater than 0");
+        let shared = Rc::new(RefCell::new(Shared {
+            queue: VecDeque::with_capacity(cap),
+            cap,
+            closed: false,
+            worker: None,
+            waiters: Vec::new(),
+        }));
+        let worker = BufferWorker {
+            service,
+            shared: shared.clone(),
+        };
+        (Self { shared }, worker)
+    }
+}
+
+impl<Req, Res, Err> Clone for Buffer<Req, Res, Err> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<Req, Res, Err> Drop for Buffer<Req, Res, Err> {
+    fn drop(&mut self) {
+        // wake up worker so it can observe all handles are gone.
+        if let Some(waker) = self.shared.borrow_mut().worker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<Req, Res, Err> Service<Req> for Buffer<Req, Res, Err> {
+    type Response = Res;
+    type Error = BufferError<Err>;
+
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        let slot = Rc::new(RefCell::new(Slot {
+            value: None,
+            waker: None,
+            closed: false,
+        }));
+
+        let mut req = Some((req, Sender(slot.clone())));
+
+        poll_fn(|cx| {
+            let mut shared = self.shared.borrow_mut();
+            if shared.closed {
+                return Poll::Ready(Err(BufferError::Closed));
+            }
+            if shared.queue.len() < shared.cap {
+                shared.queue.push_back(req.take().unwrap());
+                if let Some(waker) = shared.worker.take() {
+                    waker.wake();
+                }
+                Poll::Ready(Ok(()))
+            } else {
+                shared.wait(cx.waker());
+                Poll::Pending
+            }
+        })
+        .await?;
+
+        poll_fn(|cx| {
+            let mut slot = slot.borrow_mut();
+            match slot.value.take() {
+                Some(res) => Poll::Ready(res.map_err(BufferError::Service)),
+                None if slot.closed => Poll::Ready(Err(BufferError::Closed)),
+                None => {
+                    slot.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+}
+
+impl<Req, Res, Err> ReadyService for Buffer<Req, Res, Err> {
+    type Ready = ();
+
+    /// wait until queue has free capacity or worker is gone.
+    async fn ready(&self) -> Self::Ready {
+        poll_fn(|cx| {
+            let mut shared = self.shared.borrow_mut();
+            if shared.closed || shared.queue.len() < shared.cap {
+                Poll::Ready(())
+            } else {
+                shared.wait(cx.waker());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+impl<S, Req> BufferWorker<S, Req>
+where
+    S: Service<Req>,
+{
+    /// run worker until all [Buffer] handles are dropped.
+    pub async fn run(self) {
+        while let Some((req, tx)) = self.next().await {
+            let res = self.service.call(req).await;
+            tx.send(res);
+        }
+    }
+
+    fn next(&self) -> impl Future<Output = Option<Item<Req, S::Response, S::Error>>> + '_ {
+        poll_fn(|cx| {
+            let mut shared = self.shared.borrow_mut();
+            if let Some(item) = shared.queue.pop_front() {
+                // wake all waiters as some of them may be cancelled and not going to take the free capacity.
+                shared.waiters.drain(..).for_each(Waker::wake);
+                return Poll::Ready(Some(item));
+            }
+            if Rc::strong_count(&self.shared) == 1 {
+                return Poll::Ready(None);
+            }
+            shared.worker = Some(cx.waker().clone());
+            Poll::Pending
+        })
+    }
+}
+
+impl<S, Req> Drop for BufferWorker<S, Req>
+where
+    S: Service<Req>,
+{
+    fn drop(&mut self) {
+        let (queue, waiters) = {
+            let mut shared = self.shared.borrow_mut();
+            shared.closed = true;
+            (core::mem::take(&mut shared.queue), core::mem::take(&mut shared.waiters))
+        };
+        // pending requests are dropped and their callers are notified by sender's drop.
+        drop(queue);
+        waiters.into_iter().for_each(Waker::wake);
+    }
+}
+
+struct Slot<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+struct Sender<T>(Rc<RefCell<Slot<T>>>);
+
+impl<T> Sender<T> {
+    fn send(self, value: T) {
+        self.0.borrow_mut().value = Some(value);
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut slot = self.0.borrow_mut();
+        slot.closed = true;
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// error type of [Buffer].
+#[derive(Debug, Eq, PartialEq)]
+pub enum BufferError<E> {
+    /// [BufferWorker] is dropped before request is handled.
+    Closed,
+    /// error produced by service.
+    Service(E),
+}
+
+impl<E> fmt::Display for BufferError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => f.write_str("buffer worker is closed"),
+            Self::Service(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl<E> core::error::Error for BufferError<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Closed => None,
+            Self::Service(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        convert::Infallible,
+        pin::pin,
+        task::{Context, Poll},
+    };
+
+    use xitca_unsafe_collection::futures::{NowOrPanic, Select, SelectOutput};
+
+    use crate::fn_service;
+
+    use super::*;
+
+    // poll future for limited times. handle and worker wake each other and a single poll is not enough.
+    fn poll_ready<F: Future>(fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        let cx = &mut Context::from_waker(Waker::noop());
+        for _ in 0..8 {
+            if let Poll::Ready(res) = fut.as_mut().poll(cx) {
+                return res;
+            }
+        }
+        panic!("future can not be polled to complete")
+    }
+
+    fn service() -> impl Service<usize, Response = usize, Error = Infallible> {
+        fn_service(|req: usize| async move { Ok::<_, Infallible>(req + 1) })
+            .call(())
+            .now_or_panic()
+            .unwrap()
+    }
+
+    #[test]
+    fn buffer() {
+        let (buffer, worker) = Buffer::new(service(), 1);
+        let buffer2 = buffer.clone();
+
+        let mut worker = pin!(worker.run());
+
+        let res = poll_ready(buffer.call(1).select(worker.as_mut()));
+        assert!(matches!(res, SelectOutput::A(Ok(2))));
+
+        let res = poll_ready(buffer2.call(2).select(worker.as_mut()));
+        assert!(matches!(res, SelectOutput::A(Ok(3))));
+
+        drop((buffer, buffer2));
+        worker.now_or_panic();
+    }
+
+    #[test]
+    fn backpressure() {
+        let (buffer, worker) = Buffer::new(service(), 1);
+
+        // first request takes the only capacity of queue.
+        let mut first = pin!(buffer.call(1));
+        assert!(matches!(
+            first.as_mut().select(async {}).now_or_panic(),
+            SelectOutput::B(_)
+        ));
+
+        // second request waits for capacity.
+        let mut second = pin!(buffer.call(2));
+        assert!(matches!(
+            second.as_mut().select(async {}).now_or_panic(),
+            SelectOutput::B(_)
+        ));
+
+        let mut worker = pin!(worker.run());
+        assert!(matches!(
+            poll_ready(first.select(worker.as_mut())),
+            SelectOutput::A(Ok(2))
+        ));
+        assert!(matches!(
+            poll_ready(second.select(worker.as_mut())),
+            SelectOutput::A(Ok(3))
+        ));
+    }
+
+    #[test]
+    fn waiter_dedup() {
+        let (buffer, _worker) = Buffer::new(service(), 1);
+
+        let mut first = pin!(buffer.call(1));
+        let mut second = pin!(buffer.call(2));
+
+        struct NoopWake;
+
+        impl alloc::task::Wake for NoopWake {
+            fn wake(self: alloc::sync::Arc<Self>) {}
+        }
+
+        let waker = Waker::from(alloc::sync::Arc::new(NoopWake));
+        let cx = &mut Context::from_waker(&waker);
+        assert!(first.as_mut().poll(cx).is_pending());
+
+        // repeated polling of pending call from the same task registers it's waker once.
+        for _ in 0..4 {
+            assert!(second.as_mut().poll(cx).is_pending());
+        }
+        assert_eq!(buffer.shared.borrow().waiters.len(), 1);
+    }
+
+    #[test]
+    fn closed() {
+        let (buffer, worker) = Buffer::new(service(), 1);
+        drop(worker);
+        assert_eq!(buffer.call(1).now_or_panic(), Err(BufferError::Closed));
+    }
+}
@@ -37,16 +37,25 @@ pub mod pipeline;
 pub mod ready;
 
 pub use self::{
-    middleware::{EnclosedBuilder, EnclosedFnBuilder, MapBuilder, MapErrorBuilder},
+    middleware::{EnclosedBuilder, EnclosedFnBuilder, MapBuilder, MapErrorBuilder, MapRequestBuilder},
     service::{FnService, Service, ServiceExt, fn_build, fn_service},
 };
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+pub mod balance;
+
+#[cfg(feature = "alloc")]
+pub mod buffer;
+
 #[cfg(feature = "alloc")]
 pub mod object;
 
+#[cfg(feature = "alloc")]
+pub mod steer;
+
 #[cfg(feature = "alloc")]
 /// boxed [core::future::Future] trait object with no extra auto trait bound(`!Send` and `!Sync`).
 pub type BoxFuture<'a, Res, Err> =
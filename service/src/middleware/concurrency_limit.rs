@@ -0,0 +1,264 @@
+use core::{
+    cell::RefCell,
+    future::poll_fn,
+    task::{Poll, Waker},
+};
+
+use alloc::vec::Vec;
+
+use crate::{ready::ReadyService, service::Service};
+
+/// A middleware limiting the amount of in flight calls to inner service. when limit is reached caller waits until
+/// one of the in flight calls is finished.
+///
+/// limit is applied to each service instance built by middleware. For multi-threaded server where service is
+/// built per thread the total amount of in flight calls is `limit * threads`.
+///
+/// # Examples
+/// ```rust
+/// # use core::convert::Infallible;
+/// # use xitca_service::{fn_service, middleware::ConcurrencyLimit, Service, ServiceExt};
+/// # async fn limit() {
+/// let service = fn_service(|_: ()| async { Ok::<_, Infallible>(()) })
+///     // allow up to 64 concurrent calls.
+///     .enclosed(ConcurrencyLimit::new(64))
+///     .call(())
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(service.available(), 64);
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct ConcurrencyLimit(usize);
+
+impl ConcurrencyLimit {
+    /// # Panics
+    /// when limit is 0.
+    pub const fn new(limit: usize) -> Self {
+        assert!(limit > 0, "concurrency limit must be greater than 0");
+        Self(limit)
+    }
+}
+
+impl<S, E> Service<Result<S, E>> for ConcurrencyLimit {
+    type Response = ConcurrencyLimitService<S>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| ConcurrencyLimitService {
+            service,
+            limit: self.0,
+            semaphore: Semaphore::new(self.0),
+        })
+    }
+}
+
+pub struct ConcurrencyLimitService<S> {
+    service: S,
+    limit: usize,
+    semaphore: Semaphore,
+}
+
+impl<S> ConcurrencyLimitService<S> {
+    /// max amount of in flight calls.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// amount of calls can be made before service is saturated.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.semaphore.state.borrow().permits
+    }
+
+    /// service is saturated when all permits are taken by in flight calls.
+    #[inline]
+    pub fn is_saturated(&self) -> bool {
+        self.available() == 0
+    }
+}
+
+impl<S, Req> Service<Req> for ConcurrencyLimitService<S>
+where
+    S: Service<Req>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        let _permit = self.semaphore.acquire().await;
+        self.service.call(req).await
+    }
+}
+
+impl<S> ReadyService for ConcurrencyLimitService<S>
+where
+    S: ReadyService,
+{
+    type Ready = S::Ready;
+
+    /// wait until service is not saturated and then wait for inner service's ready state.
+    ///
+    /// no permit is reserved and a following call can still wait when other callers take the permit first.
+    async fn ready(&self) -> Self::Ready {
+        self.semaphore.wait_available().await;
+        self.service.ready().await
+    }
+}
+
+struct Semaphore {
+    state: RefCell<State>,
+}
+
+struct State {
+    permits: usize,
+    waiters: Vec<Waker>,
+}
+
+impl State {
+    // register waker of pending caller. a caller polled multiple times before a permit is released
+    // registers it's waker once.
+    fn wait(&mut self, waker: &Waker) {
+        if !self.waiters.iter().any(|w| w.will_wake(waker)) {
+            self.waiters.push(waker.clone());
+        }
+    }
+}
+
+impl Semaphore {
+    const fn new(permits: usize) -> Self {
+        Self {
+            state: RefCell::new(State {
+                permits,
+                waiters: Vec::new(),
+            }),
+        }
+    }
+
+    async fn acquire(&self) -> Permit<'_> {
+        poll_fn(|cx| {
+            let mut state = self.state.borrow_mut();
+            if state.permits > 0 {
+                state.permits -= 1;
+                Poll::Ready(Permit(self))
+            } else {
+                state.wait(cx.waker());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    async fn wait_available(&self) {
+        poll_fn(|cx| {
+            let mut state = self.state.borrow_mut();
+            if state.permits > 0 {
+                Poll::Ready(())
+            } else {
+                state.wait(cx.waker());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+struct Permit<'a>(&'a Semaphore);
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let waiters = {
+            let mut state = self.0.state.borrow_mut();
+            state.permits += 1;
+            core::mem::take(&mut state.waiters)
+        };
+        // wake all waiters as some of them may be cancelled and not going to take the permit.
+        waiters.into_iter().for_each(Waker::wake);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        future::{Future, pending, poll_fn},
+        pin::pin,
+        task::Context,
+    };
+
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{ServiceExt, fn_service};
+
+    use super::*;
+
+    #[test]
+    fn limit() {
+        let service = fn_service(|block: bool| async move {
+            if block {
+                pending::<()>().await;
+            }
+            Ok::<_, ()>(())
+        })
+        .enclosed(ConcurrencyLimit::new(1))
+        .call(())
+        .now_or_panic()
+        .unwrap();
+
+        assert_eq!(service.limit(), 1);
+        assert!(!service.is_saturated());
+
+        {
+            let mut blocked = pin!(service.call(true));
+            poll_fn(|cx| {
+                assert!(blocked.as_mut().poll(cx).is_pending());
+                Poll::Ready(())
+            })
+            .now_or_panic();
+
+            assert!(service.is_saturated());
+
+            let mut next = pin!(service.call(false));
+            poll_fn(|cx| {
+                assert!(next.as_mut().poll(cx).is_pending());
+                Poll::Ready(())
+            })
+            .now_or_panic();
+        }
+
+        // permit is released when in flight call is dropped.
+        assert!(!service.is_saturated());
+        service.call(false).now_or_panic().unwrap();
+    }
+
+    #[test]
+    fn waiter_dedup() {
+        let service = fn_service(|_: ()| pending::<Result<(), ()>>())
+            .enclosed(ConcurrencyLimit::new(1))
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        struct NoopWake;
+
+        impl alloc::task::Wake for NoopWake {
+            fn wake(self: alloc::sync::Arc<Self>) {}
+        }
+
+        let waker = Waker::from(alloc::sync::Arc::new(NoopWake));
+        let cx = &mut Context::from_waker(&waker);
+
+        let mut blocked = pin!(service.call(()));
+        assert!(blocked.as_mut().poll(cx).is_pending());
+
+        // repeated polling of pending call and ready check registers waker once.
+        let mut next = pin!(service.call(()));
+        let mut ready = pin!(service.ready());
+        for _ in 0..4 {
+            assert!(next.as_mut().poll(cx).is_pending());
+            assert!(ready.as_mut().poll(cx).is_pending());
+        }
+        assert_eq!(service.semaphore.state.borrow().waiters.len(), 1);
+    }
+}
@@ -0,0 +1,313 @@
+use core::{
+    cell::RefCell,
+    future::{Future, poll_fn},
+    pin::pin,
+    task::Poll,
+    time::Duration,
+};
+
+use crate::{ready::ReadyService, service::Service};
+
+/// A middleware issuing a second speculative call to inner service when the first one is not finished after a
+/// latency threshold. the response of whichever call finishes first is returned and the other one is dropped.
+///
+/// threshold is the given percentile of latency of previous calls. latency is recorded to a rolling histogram with
+/// power of two microseconds buckets and the threshold is the upper bound of bucket where percentile lands.
+///
+/// crate is runtime agnostic and timer is constructed by given async function which takes the duration as
+/// argument and resolves after it's elapsed. e.g: `tokio::time::sleep`. latency is measured by given clock
+/// function returning monotonic time elapsed since an arbitrary point.
+///
+/// request type must be [Clone] for issuing the speculative call.
+///
+/// # Examples
+/// ```rust
+/// # use core::{convert::Infallible, time::Duration};
+/// # use xitca_service::{fn_service, middleware::Hedge, Service, ServiceExt};
+/// # async fn sleep(_: Duration) {}
+/// # async fn hedge() {
+/// let start = std::time::Instant::now();
+///
+/// let service = fn_service(|req: usize| async move { Ok::<_, Infallible>(req) })
+///     // hedge calls slower than 95% of previous calls.
+///     // in real world application sleep function would be offered by async runtime.
+///     .enclosed(Hedge::new(0.95, sleep, move || start.elapsed()))
+///     .call(())
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(service.call(996).await, Ok(996));
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Hedge<F, C> {
+    percentile: f64,
+    min_samples: u64,
+    rotate_period: Duration,
+    sleep: F,
+    clock: C,
+}
+
+impl<F, C> Hedge<F, C> {
+    /// construct middleware with percentile in range of `(0, 1]`, async sleep function and clock function.
+    ///
+    /// # Panics
+    /// when percentile is out of range.
+    pub fn new(percentile: f64, sleep: F, clock: C) -> Self {
+        assert!(
+            percentile > 0.0 && percentile <= 1.0,
+            "hedge percentile must be in range of (0, 1]"
+        );
+        Self {
+            percentile,
+            min_samples: 10,
+            rotate_period: Duration::from_secs(1),
+            sleep,
+            clock,
+        }
+    }
+
+    /// minimal amount of recorded latency before any call can be hedged.
+    ///
+    /// # Default
+    /// 10
+    pub fn min_samples(mut self, min_samples: u64) -> Self {
+        self.min_samples = min_samples;
+        self
+    }
+
+    /// period of rotating latency histogram. threshold is calculated from latency recorded in last period.
+    ///
+    /// # Default
+    /// 1 second
+    pub fn rotate_period(mut self, period: Duration) -> Self {
+        self.rotate_period = period;
+        self
+    }
+}
+
+impl<S, E, F, C> Service<Result<S, E>> for Hedge<F, C>
+where
+    F: Clone,
+    C: Fn() -> Duration + Clone,
+{
+    type Response = HedgeService<S, F, C>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| HedgeService {
+            service,
+            percentile: self.percentile,
+            min_samples: self.min_samples,
+            rotate_period: self.rotate_period,
+            histogram: RefCell::new(Histogram::new((self.clock)())),
+            sleep: self.sleep.clone(),
+            clock: self.clock.clone(),
+        })
+    }
+}
+
+pub struct HedgeService<S, F, C> {
+    service: S,
+    percentile: f64,
+    min_samples: u64,
+    rotate_period: Duration,
+    histogram: RefCell<Histogram>,
+    sleep: F,
+    clock: C,
+}
+
+impl<S, F, C> HedgeService<S, F, C>
+where
+    C: Fn() -> Duration,
+{
+    /// latency threshold of hedging next call. None when there is not enough recorded latency.
+    pub fn threshold(&self) -> Option<Duration> {
+        let mut histogram = self.histogram.borrow_mut();
+        histogram.try_rotate((self.clock)(), self.rotate_period);
+        histogram.percentile(self.percentile, self.min_samples)
+    }
+
+    fn record(&self, start: Duration) {
+        let now = (self.clock)();
+        let mut histogram = self.histogram.borrow_mut();
+        histogram.try_rotate(now, self.rotate_period);
+        histogram.record(now.saturating_sub(start));
+    }
+}
+
+impl<S, F, C, Req> Service<Req> for HedgeService<S, F, C>
+where
+    S: Service<Req>,
+    F: core::ops::AsyncFn(Duration),
+    C: Fn() -> Duration,
+    Req: Clone,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        let start = (self.clock)();
+
+        let Some(threshold) = self.threshold() else {
+            let res = self.service.call(req).await;
+            self.record(start);
+            return res;
+        };
+
+        let req2 = req.clone();
+
+        let mut first = pin!(self.service.call(req));
+        let mut sleep = pin!((self.sleep)(threshold));
+
+        let res = poll_fn(|cx| {
+            if let Poll::Ready(res) = first.as_mut().poll(cx) {
+                return Poll::Ready(Some(res));
+            }
+            sleep.as_mut().poll(cx).map(|_| None)
+        })
+        .await;
+
+        let res = match res {
+            Some(res) => res,
+            None => {
+                let mut second = pin!(self.service.call(req2));
+                poll_fn(|cx| {
+                    if let Poll::Ready(res) = first.as_mut().poll(cx) {
+                        return Poll::Ready(res);
+                    }
+                    second.as_mut().poll(cx)
+                })
+                .await
+            }
+        };
+
+        self.record(start);
+        res
+    }
+}
+
+impl<S, F, C> ReadyService for HedgeService<S, F, C>
+where
+    S: ReadyService,
+{
+    type Ready = S::Ready;
+
+    #[inline]
+    async fn ready(&self) -> Self::Ready {
+        self.service.ready().await
+    }
+}
+
+const BUCKETS: usize = 32;
+
+// rolling histogram of latency. threshold is read from latency of last period while new latency is written to
+// current period.
+struct Histogram {
+    read: [u64; BUCKETS],
+    write: [u64; BUCKETS],
+    rotated_at: Duration,
+}
+
+impl Histogram {
+    const fn new(now: Duration) -> Self {
+        Self {
+            read: [0; BUCKETS],
+            write: [0; BUCKETS],
+            rotated_at: now,
+        }
+    }
+
+    fn try_rotate(&mut self, now: Duration, period: Duration) {
+        if now.saturating_sub(self.rotated_at) >= period {
+            self.read = core::mem::replace(&mut self.write, [0; BUCKETS]);
+            self.rotated_at = now;
+        }
+    }
+
+    fn record(&mut self, latency: Duration) {
+        // bucket n holds latency in range of [2^n, 2^(n+1)) microseconds.
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX).max(1);
+        let idx = (micros.ilog2() as usize).min(BUCKETS - 1);
+        self.write[idx] += 1;
+    }
+
+    fn percentile(&self, percentile: f64, min_samples: u64) -> Option<Duration> {
+        let total = self.read.iter().sum::<u64>();
+        if total == 0 || total < min_samples {
+            return None;
+        }
+
+        let target = ((total as f64 * percentile) as u64).max(1);
+
+        let mut count = 0;
+        self.read.iter().enumerate().find_map(|(idx, n)| {
+            count += n;
+            (count >= target).then(|| Duration::from_micros(1 << (idx + 1)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{cell::Cell, future::pending};
+
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{ServiceExt, fn_service};
+
+    use super::*;
+
+    #[test]
+    fn histogram() {
+        let mut histogram = Histogram::new(Duration::ZERO);
+
+        for micros in [1, 3, 5, 9, 100] {
+            histogram.record(Duration::from_micros(micros));
+        }
+
+        // latency is not readable until rotated.
+        assert_eq!(histogram.percentile(0.5, 1), None);
+
+        histogram.try_rotate(Duration::from_secs(1), Duration::from_secs(1));
+        assert_eq!(histogram.percentile(0.5, 10), None);
+        assert_eq!(histogram.percentile(0.5, 1), Some(Duration::from_micros(4)));
+        assert_eq!(histogram.percentile(1.0, 1), Some(Duration::from_micros(128)));
+    }
+
+    #[test]
+    fn hedge() {
+        // a timer fires immediately.
+        async fn instant(_: Duration) {}
+
+        let now = Cell::new(Duration::ZERO);
+        let calls = Cell::new(0);
+
+        let (now, calls) = (&now, &calls);
+
+        let service = fn_service(|slow: bool| async move {
+            calls.set(calls.get() + 1);
+            // first call of slow request never finishes.
+            if slow && calls.get() % 2 == 1 {
+                pending::<()>().await;
+            }
+            Ok::<_, ()>(slow)
+        })
+        .enclosed(Hedge::new(0.9, instant, || now.get()).min_samples(1))
+        .call(())
+        .now_or_panic()
+        .unwrap();
+
+        // no call is hedged before there is latency for threshold.
+        assert_eq!(service.threshold(), None);
+        assert!(!service.call(false).now_or_panic().unwrap());
+        assert_eq!(calls.get(), 1);
+
+        now.set(Duration::from_secs(1));
+        assert!(service.threshold().is_some());
+
+        calls.set(0);
+        assert!(service.call(true).now_or_panic().unwrap());
+        assert_eq!(calls.get(), 2);
+    }
+}
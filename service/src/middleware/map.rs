@@ -40,6 +40,43 @@ where
     }
 }
 
+pub struct MapRequest<F>(pub F);
+
+impl<F> Clone for MapRequest<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S, E, F> Service<Result<S, E>> for MapRequest<F>
+where
+    F: Clone,
+{
+    type Response = PipelineT<S, F, marker::MapRequest>;
+    type Error = E;
+
+    async fn call(&self, arg: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        arg.map(|service| PipelineT::new(service, self.0.clone()))
+    }
+}
+
+impl<S, Req, F, ReqMap> Service<Req> for PipelineT<S, F, marker::MapRequest>
+where
+    F: Fn(Req) -> ReqMap,
+    S: Service<ReqMap>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+
+    #[inline]
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        self.first.call((self.second)(req)).await
+    }
+}
+
 #[derive(Clone)]
 pub struct MapErr<F>(pub F);
 
@@ -3,15 +3,25 @@
 //! [ServiceExt::enclosed]: crate::service::ServiceExt::enclosed
 
 mod async_fn;
+#[cfg(feature = "alloc")]
+mod concurrency_limit;
 mod group;
+mod hedge;
 mod map;
+mod retry;
+mod timeout;
 mod unchecked_ready;
 
 pub use async_fn::AsyncFn;
+#[cfg(feature = "alloc")]
+pub use concurrency_limit::ConcurrencyLimit;
 pub use group::Group;
+pub use hedge::Hedge;
+pub use retry::{Retry, RetryLimit, RetryPolicy};
+pub use timeout::{Timeout, TimeoutError};
 pub use unchecked_ready::UncheckedReady;
 
-pub(crate) use map::{Map, MapErr};
+pub(crate) use map::{Map, MapErr, MapRequest};
 
 use crate::pipeline::{PipelineT, marker::BuildEnclosed};
 
@@ -24,5 +34,8 @@ pub type EnclosedFnBuilder<F, S> = EnclosedBuilder<F, AsyncFn<S>>;
 /// Type alias for specialized [PipelineT] type.
 pub type MapBuilder<F, S> = EnclosedBuilder<F, Map<S>>;
 
+/// Type alias for specialized [PipelineT] type.
+pub type MapRequestBuilder<F, S> = EnclosedBuilder<F, MapRequest<S>>;
+
 /// Type alias for specialized [PipelineT] type.
 pub type MapErrorBuilder<F, S> = EnclosedBuilder<F, MapErr<S>>;
@@ -0,0 +1,236 @@
+use core::future::Future;
+
+use crate::{ready::ReadyService, service::Service};
+
+/// policy of [Retry] middleware. it decides if and how a failed request would be retried.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_service::{fn_service, middleware::{Retry, RetryPolicy}, Service, ServiceExt};
+/// enum Error {
+///     Timeout,
+///     BadRequest,
+/// }
+///
+/// // retry timed out request with it's clone for up to 3 times.
+/// #[derive(Clone)]
+/// struct RetryTimeout;
+///
+/// impl RetryPolicy<String, Error> for RetryTimeout {
+///     fn clone_request(&self, req: &String) -> Option<String> {
+///         Some(req.clone())
+///     }
+///
+///     async fn retry(&self, attempt: usize, err: &Error) -> bool {
+///         matches!(err, Error::Timeout) && attempt <= 3
+///     }
+/// }
+///
+/// fn_service(|_: String| async { Err::<(), _>(Error::Timeout) })
+///     .enclosed(Retry::new(RetryTimeout));
+/// ```
+pub trait RetryPolicy<Req, E> {
+    /// clone request before passing it to service so it can be retried on failure. return None when request can
+    /// not be cloned and it would be passed to service without retry.
+    fn clone_request(&self, req: &Req) -> Option<Req>;
+
+    /// decide if request should be retried after service call failed with given error. attempt is the count of
+    /// failed calls and it starts from 1.
+    ///
+    /// the returned future is awaited before retrying and policy can wait in it for backoff between attempts.
+    /// it's required to be [Send] so the policy can be used by multi-threaded services like `xitca-client`.
+    fn retry(&self, attempt: usize, err: &E) -> impl Future<Output = bool> + Send;
+}
+
+/// a [RetryPolicy] retrying any error with clone of request for a limited amount of times.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryLimit(usize);
+
+impl RetryLimit {
+    /// construct policy retrying request up to given times. total amount of service calls is `times + 1`.
+    pub const fn new(times: usize) -> Self {
+        Self(times)
+    }
+}
+
+impl<Req, E> RetryPolicy<Req, E> for RetryLimit
+where
+    Req: Clone,
+{
+    #[inline]
+    fn clone_request(&self, req: &Req) -> Option<Req> {
+        Some(req.clone())
+    }
+
+    #[inline]
+    fn retry(&self, attempt: usize, _: &E) -> impl Future<Output = bool> + Send {
+        core::future::ready(attempt <= self.0)
+    }
+}
+
+/// A middleware retrying failed service call according to given [RetryPolicy].
+///
+/// # Examples
+/// ```rust
+/// # use xitca_service::{fn_service, middleware::{Retry, RetryLimit}, Service, ServiceExt};
+/// # async fn retry() {
+/// let service = fn_service(|req: u16| async move { Err::<u16, _>(req) })
+///     .enclosed(Retry::new(RetryLimit::new(2)))
+///     .call(())
+///     .await
+///     .unwrap();
+///
+/// // service is called 3 times and the last error is returned.
+/// assert_eq!(service.call(996).await, Err(996));
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Retry<P>(P);
+
+impl<P> Retry<P> {
+    pub const fn new(policy: P) -> Self {
+        Self(policy)
+    }
+}
+
+impl<S, E, P> Service<Result<S, E>> for Retry<P>
+where
+    P: Clone,
+{
+    type Response = RetryService<S, P>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| RetryService {
+            service,
+            policy: self.0.clone(),
+        })
+    }
+}
+
+pub struct RetryService<S, P> {
+    service: S,
+    policy: P,
+}
+
+impl<S, P, Req> Service<Req> for RetryService<S, P>
+where
+    S: Service<Req>,
+    P: RetryPolicy<Req, S::Error>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+
+    async fn call(&self, mut req: Req) -> Result<Self::Response, Self::Error> {
+        let mut attempt = 0;
+        loop {
+            let Some(next) = self.policy.clone_request(&req) else {
+                return self.service.call(req).await;
+            };
+
+            match self.service.call(req).await {
+                Ok(res) => return Ok(res),
+                Err(e) => {
+                    attempt += 1;
+                    if !self.policy.retry(attempt, &e).await {
+                        return Err(e);
+                    }
+                }
+            }
+
+            req = next;
+        }
+    }
+}
+
+impl<S, P> ReadyService for RetryService<S, P>
+where
+    S: ReadyService,
+{
+    type Ready = S::Ready;
+
+    #[inline]
+    async fn ready(&self) -> Self::Ready {
+        self.service.ready().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::cell::Cell;
+
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{ServiceExt, fn_service};
+
+    use super::*;
+
+    #[test]
+    fn retry_limit() {
+        let count = Cell::new(0);
+
+        let service = fn_service(|_: &str| async {
+            count.set(count.get() + 1);
+            Err::<(), _>(count.get())
+        })
+        .enclosed(Retry::new(RetryLimit::new(2)))
+        .call(())
+        .now_or_panic()
+        .unwrap();
+
+        let err = service.call("996").now_or_panic().err().unwrap();
+        assert_eq!(err, 3);
+    }
+
+    #[test]
+    fn retry_until_ok() {
+        let count = Cell::new(0);
+
+        let count = &count;
+
+        let service = fn_service(|req: &'static str| async move {
+            count.set(count.get() + 1);
+            if count.get() < 2 { Err(()) } else { Ok(req) }
+        })
+        .enclosed(Retry::new(RetryLimit::new(3)))
+        .call(())
+        .now_or_panic()
+        .unwrap();
+
+        let res = service.call("996").now_or_panic().unwrap();
+        assert_eq!(res, "996");
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn retry_by_error() {
+        #[derive(Clone)]
+        struct Policy;
+
+        impl RetryPolicy<u8, bool> for Policy {
+            fn clone_request(&self, req: &u8) -> Option<u8> {
+                Some(*req)
+            }
+
+            // only retry error marked as retryable.
+            async fn retry(&self, _: usize, err: &bool) -> bool {
+                *err
+            }
+        }
+
+        let count = Cell::new(0);
+
+        let service = fn_service(|_: u8| async {
+            count.set(count.get() + 1);
+            Err::<(), _>(count.get() < 3)
+        })
+        .enclosed(Retry::new(Policy))
+        .call(())
+        .now_or_panic()
+        .unwrap();
+
+        let err = service.call(0).now_or_panic().err().unwrap();
+        assert!(!err);
+        assert_eq!(count.get(), 3);
+    }
+}
@@ -0,0 +1,175 @@
+use core::{
+    fmt,
+    future::{Future, poll_fn},
+    pin::pin,
+    task::Poll,
+    time::Duration,
+};
+
+use crate::{ready::ReadyService, service::Service};
+
+/// A middleware racing inner service call against a timer. when timer fires first the call is dropped and
+/// [TimeoutError::Elapsed] is returned.
+///
+/// crate is runtime agnostic and timer is constructed by given async function which takes the duration as
+/// argument and resolves after it's elapsed. e.g: `tokio::time::sleep`.
+///
+/// # Examples
+/// ```rust
+/// # use core::{convert::Infallible, future::pending, time::Duration};
+/// # use xitca_service::{fn_service, middleware::{Timeout, TimeoutError}, Service, ServiceExt};
+/// # async fn sleep(_: Duration) {}
+/// # async fn timeout() {
+/// let service = fn_service(|_: ()| async { pending::<Result<(), Infallible>>().await })
+///     // in real world application sleep function would be offered by async runtime.
+///     .enclosed(Timeout::new(Duration::from_secs(3), sleep))
+///     .call(())
+///     .await
+///     .unwrap();
+///
+/// assert!(matches!(service.call(()).await, Err(TimeoutError::Elapsed)));
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Timeout<F> {
+    dur: Duration,
+    sleep: F,
+}
+
+impl<F> Timeout<F> {
+    pub const fn new(dur: Duration, sleep: F) -> Self {
+        Self { dur, sleep }
+    }
+}
+
+impl<S, E, F> Service<Result<S, E>> for Timeout<F>
+where
+    F: Clone,
+{
+    type Response = TimeoutService<S, F>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| TimeoutService {
+            service,
+            dur: self.dur,
+            sleep: self.sleep.clone(),
+        })
+    }
+}
+
+pub struct TimeoutService<S, F> {
+    service: S,
+    dur: Duration,
+    sleep: F,
+}
+
+impl<S, F, Req> Service<Req> for TimeoutService<S, F>
+where
+    S: Service<Req>,
+    F: core::ops::AsyncFn(Duration),
+{
+    type Response = S::Response;
+    type Error = TimeoutError<S::Error>;
+
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        let mut call = pin!(self.service.call(req));
+        let mut sleep = pin!((self.sleep)(self.dur));
+
+        poll_fn(|cx| {
+            if let Poll::Ready(res) = call.as_mut().poll(cx) {
+                return Poll::Ready(res.map_err(TimeoutError::Service));
+            }
+            sleep.as_mut().poll(cx).map(|_| Err(TimeoutError::Elapsed))
+        })
+        .await
+    }
+}
+
+impl<S, F> ReadyService for TimeoutService<S, F>
+where
+    S: ReadyService,
+{
+    type Ready = S::Ready;
+
+    #[inline]
+    async fn ready(&self) -> Self::Ready {
+        self.service.ready().await
+    }
+}
+
+/// error type of service enclosed by [Timeout] middleware.
+#[derive(Debug, Eq, PartialEq)]
+pub enum TimeoutError<E> {
+    /// service call is not finished in time.
+    Elapsed,
+    /// error produced by service.
+    Service(E),
+}
+
+impl<E> fmt::Display for TimeoutError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Elapsed => f.write_str("service call timed out"),
+            Self::Service(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl<E> core::error::Error for TimeoutError<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Elapsed => None,
+            Self::Service(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::future::{pending, ready};
+
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{ServiceExt, fn_service};
+
+    use super::*;
+
+    #[test]
+    fn timeout() {
+        // a timer never fires.
+        async fn never(_: Duration) {
+            pending().await
+        }
+
+        let service = fn_service(|req: &'static str| ready(Ok::<_, ()>(req)))
+            .enclosed(Timeout::new(Duration::from_secs(1), never))
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let res = service.call("996").now_or_panic().unwrap();
+        assert_eq!(res, "996");
+    }
+
+    #[test]
+    fn elapsed() {
+        // a timer fires immediately.
+        async fn instant(_: Duration) {}
+
+        let service = fn_service(|_: &'static str| pending::<Result<(), ()>>())
+            .enclosed(Timeout::new(Duration::from_secs(1), instant))
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let err = service.call("996").now_or_panic().err().unwrap();
+        assert_eq!(err, TimeoutError::Elapsed);
+    }
+}
@@ -1,5 +1,7 @@
 //! trait and types making [Service] object safe.
 
+use core::marker::PhantomData;
+
 use alloc::boxed::Box;
 
 use super::{BoxFuture, service::Service};
@@ -49,3 +51,203 @@ pub type BoxedServiceObject<Req, Res, Err> = Box<dyn ServiceObject<Req, Response
 
 /// sync version of [BoxedServiceObject]
 pub type BoxedSyncServiceObject<Req, Res, Err> = Box<dyn ServiceObject<Req, Response = Res, Error = Err> + Send + Sync>;
+
+/// type erased [Service] with no extra auto trait bound. services of different types can be stored in collections
+/// after boxed.
+///
+/// # Examples
+/// ```rust
+/// # use core::convert::Infallible;
+/// # use xitca_service::{fn_service, object::BoxService, Service, ServiceExt};
+/// # async fn boxed() {
+/// let add = fn_service(|req: usize| async move { Ok::<_, Infallible>(req + 1) })
+///     .call(())
+///     .await
+///     .unwrap();
+/// let mul = fn_service(|req: usize| async move { Ok::<_, Infallible>(req * 2) })
+///     .map(|res| res + 1)
+///     .call(())
+///     .await
+///     .unwrap();
+///
+/// let services = [BoxService::new(add), BoxService::new(mul)];
+///
+/// for (service, res) in services.iter().zip([2, 3]) {
+///     assert_eq!(service.call(1).await, Ok(res));
+/// }
+/// # }
+/// # use xitca_unsafe_collection::futures::NowOrPanic;
+/// # boxed().now_or_panic();
+/// ```
+pub struct BoxService<Req, Res, Err>(BoxedServiceObject<Req, Res, Err>);
+
+impl<Req, Res, Err> BoxService<Req, Res, Err> {
+    pub fn new<S>(service: S) -> Self
+    where
+        S: Service<Req, Response = Res, Error = Err> + 'static,
+    {
+        Self(Box::new(service))
+    }
+}
+
+impl<Req, Res, Err> Service<Req> for BoxService<Req, Res, Err> {
+    type Response = Res;
+    type Error = Err;
+
+    #[inline]
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        ServiceObject::call(&*self.0, req).await
+    }
+}
+
+/// Object-safe counterpart of [Service] + [Clone].
+pub trait CloneServiceObject<Req>: ServiceObject<Req> {
+    fn clone_object(&self) -> Box<dyn CloneServiceObject<Req, Response = Self::Response, Error = Self::Error>>;
+}
+
+impl<S, Req> CloneServiceObject<Req> for S
+where
+    S: Service<Req> + Clone + 'static,
+{
+    fn clone_object(&self) -> Box<dyn CloneServiceObject<Req, Response = Self::Response, Error = Self::Error>> {
+        Box::new(self.clone())
+    }
+}
+
+/// clonable version of [BoxService].
+pub struct BoxCloneService<Req, Res, Err>(Box<dyn CloneServiceObject<Req, Response = Res, Error = Err>>);
+
+impl<Req, Res, Err> BoxCloneService<Req, Res, Err> {
+    pub fn new<S>(service: S) -> Self
+    where
+        S: Service<Req, Response = Res, Error = Err> + Clone + 'static,
+    {
+        Self(Box::new(service))
+    }
+}
+
+impl<Req, Res, Err> Clone for BoxCloneService<Req, Res, Err> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_object())
+    }
+}
+
+impl<Req, Res, Err> Service<Req> for BoxCloneService<Req, Res, Err> {
+    type Response = Res;
+    type Error = Err;
+
+    #[inline]
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        ServiceObject::call(&*self.0, req).await
+    }
+}
+
+/// type erased service factory producing [BoxService].
+///
+/// # Examples
+/// ```rust
+/// # use core::convert::Infallible;
+/// # use xitca_service::{fn_service, object::BoxServiceFactory, Service, ServiceExt};
+/// # async fn boxed() {
+/// let factory = BoxServiceFactory::new(fn_service(|req: usize| async move { Ok::<_, Infallible>(req) }));
+/// let factory2 = BoxServiceFactory::new(
+///     fn_service(|req: usize| async move { Ok::<_, Infallible>(req) }).map(|res| res + 1),
+/// );
+///
+/// let service = factory.call(()).await.unwrap();
+/// assert_eq!(service.call(1).await, Ok(1));
+///
+/// let service = factory2.call(()).await.unwrap();
+/// assert_eq!(service.call(1).await, Ok(2));
+/// # }
+/// # use xitca_unsafe_collection::futures::NowOrPanic;
+/// # boxed().now_or_panic();
+/// ```
+pub struct BoxServiceFactory<Arg, Req, Res, Err, BErr>(
+    Box<dyn ServiceObject<Arg, Response = BoxService<Req, Res, Err>, Error = BErr>>,
+);
+
+impl<Arg, Req, Res, Err, BErr> BoxServiceFactory<Arg, Req, Res, Err, BErr> {
+    pub fn new<F>(factory: F) -> Self
+    where
+        Req: 'static,
+        F: Service<Arg, Error = BErr> + 'static,
+        F::Response: Service<Req, Response = Res, Error = Err> + 'static,
+    {
+        struct Factory<F, Req>(F, PhantomData<fn(Req)>);
+
+        impl<F, Arg, Req> Service<Arg> for Factory<F, Req>
+        where
+            F: Service<Arg>,
+            F::Response: Service<Req> + 'static,
+        {
+            type Response =
+                BoxService<Req, <F::Response as Service<Req>>::Response, <F::Response as Service<Req>>::Error>;
+            type Error = F::Error;
+
+            async fn call(&self, arg: Arg) -> Result<Self::Response, Self::Error> {
+                self.0.call(arg).await.map(BoxService::new)
+            }
+        }
+
+        Self(Box::new(Factory(factory, PhantomData)))
+    }
+}
+
+impl<Arg, Req, Res, Err, BErr> Service<Arg> for BoxServiceFactory<Arg, Req, Res, Err, BErr> {
+    type Response = BoxService<Req, Res, Err>;
+    type Error = BErr;
+
+    #[inline]
+    async fn call(&self, arg: Arg) -> Result<Self::Response, Self::Error> {
+        ServiceObject::call(&*self.0, arg).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{Service, ServiceExt, fn_service};
+
+    use super::{BoxCloneService, BoxService, BoxServiceFactory};
+
+    async fn index(s: &'static str) -> Result<&'static str, ()> {
+        Ok(s)
+    }
+
+    #[test]
+    fn box_service() {
+        let service = fn_service(index).call(()).now_or_panic().unwrap();
+        let service2 = fn_service(index).map(|_| "251").call(()).now_or_panic().unwrap();
+
+        let services = [BoxService::new(service), BoxService::new(service2)];
+
+        let res = services
+            .iter()
+            .map(|s| s.call("996").now_or_panic().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(res, ["996", "251"]);
+    }
+
+    #[test]
+    fn box_clone_service() {
+        let service = fn_service(index).call(()).now_or_panic().unwrap();
+        let service = BoxCloneService::new(service);
+        let service2 = service.clone();
+
+        drop(service);
+        let res = service2.call("996").now_or_panic().unwrap();
+        assert_eq!(res, "996");
+    }
+
+    #[test]
+    fn box_service_factory() {
+        let factory = BoxServiceFactory::new(fn_service(index).map_err(|_| "error"));
+        let service = factory.call(()).now_or_panic().unwrap();
+        let res = service.call("996").now_or_panic().unwrap();
+        assert_eq!(res, "996");
+    }
+}
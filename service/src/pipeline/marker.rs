@@ -1,6 +1,7 @@
 //! Marker types for different variant of [crate::pipeline::PipelineT]
 
 pub struct Map;
+pub struct MapRequest;
 pub struct MapErr;
 pub struct BuildAndThen;
 pub struct AndThen;
@@ -1,4 +1,7 @@
-use crate::pipeline::{PipelineT, marker::Map};
+use crate::pipeline::{
+    PipelineT,
+    marker::{Map, MapRequest},
+};
 
 use super::ReadyService;
 
@@ -13,3 +16,15 @@ where
         self.first.ready().await
     }
 }
+
+impl<S, F> ReadyService for PipelineT<S, F, MapRequest>
+where
+    S: ReadyService,
+{
+    type Ready = S::Ready;
+
+    #[inline]
+    async fn ready(&self) -> Self::Ready {
+        self.first.ready().await
+    }
+}
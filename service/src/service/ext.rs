@@ -1,5 +1,8 @@
 use crate::{
-    middleware::{AsyncFn, EnclosedBuilder, EnclosedFnBuilder, Map, MapBuilder, MapErr, MapErrorBuilder},
+    middleware::{
+        AsyncFn, EnclosedBuilder, EnclosedFnBuilder, Map, MapBuilder, MapErr, MapErrorBuilder, MapRequest,
+        MapRequestBuilder,
+    },
     pipeline::{PipelineT, marker},
 };
 
@@ -36,6 +39,16 @@ pub trait ServiceExt<Arg>: Service<Arg> {
         self.enclosed(Map(mapper))
     }
 
+    /// Mutate `Req` type of `<Self::Response as Service<Req>>` with given closure. the returned service takes
+    /// closure's argument type as it's request type.
+    fn map_request<F, Req, ReqMap>(self, mapper: F) -> MapRequestBuilder<Self, F>
+    where
+        F: Fn(Req) -> ReqMap + Clone,
+        Self: Sized,
+    {
+        self.enclosed(MapRequest(mapper))
+    }
+
     /// Mutate `<Self::Response as Service<Req>>::Error` type with given closure.
     fn map_err<F, Err, ErrMap>(self, err: F) -> MapErrorBuilder<Self, F>
     where
@@ -117,6 +130,18 @@ mod test {
         assert_eq!(err, "251");
     }
 
+    #[test]
+    fn map_request() {
+        let service = fn_service(index)
+            .map_request(|req: usize| if req == 996 { "996" } else { "251" })
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let res = service.call(996).now_or_panic().unwrap();
+        assert_eq!(res, "996");
+    }
+
     #[test]
     fn map_err() {
         let service = fn_service(|_: &str| async { Err::<(), _>(()) })
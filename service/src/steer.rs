@@ -0,0 +1,143 @@
+//! routing service dispatching request to one of it's inner services.
+//!
+//! # Examples
+//! ```rust
+//! # use core::convert::Infallible;
+//! # use xitca_service::{fn_service, steer::Steer, Service};
+//! # async fn steer() {
+//! let shard = |id: usize| async move {
+//!     fn_service(move |key: u64| async move { Ok::<_, Infallible>((id, key)) })
+//!         .call(())
+//!         .await
+//!         .unwrap()
+//! };
+//!
+//! // shard request by key.
+//! let service = Steer::new([shard(0).await, shard(1).await], |key: &u64, services: &[_]| {
+//!     *key as usize % services.len()
+//! });
+//!
+//! assert_eq!(service.call(3).await, Ok((1, 3)));
+//! assert_eq!(service.call(4).await, Ok((0, 4)));
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{ready::ReadyService, service::Service};
+
+/// A service dispatching each request to one of inner services. index of service is picked by given function with
+/// reference of request and inner services as arguments.
+pub struct Steer<S, F> {
+    services: Vec<S>,
+    picker: F,
+}
+
+impl<S, F> Steer<S, F> {
+    /// # Panics
+    /// when services is empty.
+    pub fn new(services: impl IntoIterator<Item = S>, picker: F) -> Self {
+        let services = services.into_iter().collect::<Vec<_>>();
+        assert!(!services.is_empty(), "steer must have at least one service");
+        Self { services, picker }
+    }
+
+    /// inner services in order of their index.
+    #[inline]
+    pub fn services(&self) -> &[S] {
+        &self.services
+    }
+}
+
+impl<S, F, Req> Service<Req> for Steer<S, F>
+where
+    S: Service<Req>,
+    F: Fn(&Req, &[S]) -> usize,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+
+    /// # Panics
+    /// when picked index is out of bound of inner services.
+    async fn call(&self, req: Req) -> Result<Self::Response, Self::Error> {
+        let idx = (self.picker)(&req, &self.services);
+        self.services[idx].call(req).await
+    }
+}
+
+impl<S, F> ReadyService for Steer<S, F>
+where
+    S: ReadyService,
+{
+    type Ready = Vec<S::Ready>;
+
+    /// wait for ready state of all inner services as any of them can be picked by next call. ready states are
+    /// collected in order of service index.
+    async fn ready(&self) -> Self::Ready {
+        let mut ready = Vec::with_capacity(self.services.len());
+        for service in self.services.iter() {
+            ready.push(service.ready().await);
+        }
+        ready
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::convert::Infallible;
+
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::fn_service;
+
+    use super::*;
+
+    struct Shard(usize);
+
+    impl Service<&'static str> for Shard {
+        type Response = (usize, &'static str);
+        type Error = Infallible;
+
+        async fn call(&self, req: &'static str) -> Result<Self::Response, Self::Error> {
+            Ok((self.0, req))
+        }
+    }
+
+    impl ReadyService for Shard {
+        type Ready = usize;
+
+        async fn ready(&self) -> Self::Ready {
+            self.0
+        }
+    }
+
+    #[test]
+    fn steer() {
+        let service = Steer::new((0..3).map(Shard), |req: &&str, services: &[Shard]| {
+            req.len() % services.len()
+        });
+
+        assert_eq!(service.services().len(), 3);
+        assert_eq!(service.call("996").now_or_panic().unwrap(), (0, "996"));
+        assert_eq!(service.call("9960").now_or_panic().unwrap(), (1, "9960"));
+        assert_eq!(service.call("99600").now_or_panic().unwrap(), (2, "99600"));
+
+        assert_eq!(service.ready().now_or_panic(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn steer_fn_service() {
+        let service = |flip: bool| {
+            fn_service(move |req: bool| async move { Ok::<_, ()>(req ^ flip) })
+                .call(())
+                .now_or_panic()
+                .unwrap()
+        };
+
+        // A/B routing by request.
+        let service = Steer::new([service(false), service(true)], |req: &bool, _: &[_]| *req as usize);
+
+        assert!(!service.call(false).now_or_panic().unwrap());
+        assert!(!service.call(true).now_or_panic().unwrap());
+    }
+}
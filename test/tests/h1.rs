@@ -1,5 +1,6 @@
 use futures_util::StreamExt;
 use std::{
+    fs::{self, File},
     io::{Read, Write},
     net::TcpStream,
     time::Duration,
@@ -7,7 +8,7 @@ use std::{
 
 use xitca_client::Client;
 use xitca_http::{
-    body::{BoxBody, ResponseBody},
+    body::{BoxBody, FileBody, ResponseBody},
     bytes::{Bytes, BytesMut},
     h1,
     http::{
@@ -270,6 +271,51 @@ async fn h1_keepalive() -> Result<(), Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn h1_send_file() -> Result<(), Error> {
+    let path = std::env::temp_dir().join(format!("xitca-test-h1-send-file-{}", std::process::id()));
+    let content = (0..1024 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    fs::write(&path, &content)?;
+
+    let file_path = path.clone();
+    let mut handle = test_h1_server(fn_service(move |req: Request<RequestExt<h1::RequestBody>>| {
+        let path = file_path.clone();
+        async move {
+            let file = File::open(path)?;
+            let body = match req.uri().path() {
+                "/range" => FileBody::with_range(file, 1, 1024),
+                _ => FileBody::new(file)?,
+            };
+            let mut res = Response::new(());
+            res.extensions_mut().insert(body.send_file());
+            Ok::<_, Error>(res.map(|_| ResponseBody::stream(body)))
+        }
+    }))?;
+
+    let c = Client::new();
+
+    // multiple requests on the same connection to make sure zero copy transfer does not break
+    // framing of following response.
+    for _ in 0..2 {
+        let url = format!("http://{}/", handle.ip_port_string());
+        let res = c.get(&url).version(Version::HTTP_11).send().await?;
+        assert_eq!(res.status().as_u16(), 200);
+        assert_eq!(res.body().await?, content);
+
+        let url = format!("http://{}/range", handle.ip_port_string());
+        let res = c.get(&url).version(Version::HTTP_11).send().await?;
+        assert_eq!(res.status().as_u16(), 200);
+        assert_eq!(res.body().await?, &content[1..1025]);
+    }
+
+    handle.try_handle()?.stop(false);
+    handle.await?;
+
+    fs::remove_file(path)?;
+
+    Ok(())
+}
+
 async fn handle(req: Request<RequestExt<h1::RequestBody>>) -> Result<Response<ResponseBody>, Error> {
     match (req.method(), req.uri().path()) {
         (&Method::GET, "/") | (&Method::HEAD, "/") => Ok(Response::new(Bytes::from("GET Response").into())),
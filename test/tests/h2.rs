@@ -126,6 +126,39 @@ async fn h2_connect() -> Result<(), Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn h2_retry_on_closed_connection() -> Result<(), Error> {
+    let mut handle = test_h2_server(fn_service(handle))?;
+
+    let server_url = format!("https://{}/", handle.ip_port_string());
+    let close_url = format!("https://{}/close", handle.ip_port_string());
+
+    let c = Client::new();
+
+    // server would send GOAWAY and close the connection after this response.
+    let res = c.get(&close_url).version(Version::HTTP_2).send().await?;
+    assert_eq!(res.status().as_u16(), 200);
+    let body = res.string().await?;
+    assert_eq!("GET Response", body);
+
+    // give the pooled connection some time to observe the shutdown.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // pooled connection is closed. client should retry once with a new connection.
+    for _ in 0..3 {
+        let res = c.get(&server_url).version(Version::HTTP_2).send().await?;
+        assert_eq!(res.status().as_u16(), 200);
+        let body = res.string().await?;
+        assert_eq!("GET Response", body);
+    }
+
+    handle.try_handle()?.stop(false);
+
+    handle.await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn h2_keepalive() -> Result<(), Error> {
     let mut handle = test_h2_server(fn_service(handle))?;
@@ -181,6 +214,12 @@ async fn handle(req: Request<RequestExt<h2::RequestBody>>) -> Result<Response<Re
 
     match (req.method(), req.uri().path()) {
         (&Method::GET, "/") => Ok(Response::new(Bytes::from("GET Response").into())),
+        (&Method::GET, "/close") => {
+            let mut res = Response::new(Bytes::from("GET Response").into());
+            res.headers_mut()
+                .insert(header::CONNECTION, header::HeaderValue::from_static("close"));
+            Ok(res)
+        }
         (&Method::GET, "/host") => Ok(Response::new(
             Bytes::from(
                 req.headers()
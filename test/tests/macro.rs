@@ -117,6 +117,42 @@ fn state_borrow() {
     assert_eq!(num, &251);
 }
 
+#[derive(xitca_codegen::BorrowState)]
+struct MyAllState<T> {
+    field1: String,
+    field2: Vec<T>,
+    #[borrow(skip)]
+    _field3: String,
+}
+
+#[derive(xitca_codegen::BorrowState)]
+struct MyTupleState(String, #[borrow(skip)] u32, u64);
+
+#[test]
+fn state_borrow_all() {
+    use xitca_web::handler::state::BorrowState;
+
+    let state = MyAllState {
+        field1: String::from("996"),
+        field2: vec![251u32],
+        _field3: String::new(),
+    };
+
+    let string: &String = state.borrow();
+    let nums: &Vec<u32> = state.borrow();
+
+    assert_eq!(string.as_str(), "996");
+    assert_eq!(nums, &[251]);
+
+    let state = MyTupleState(String::from("996"), 0, 251);
+
+    let string: &String = state.borrow();
+    let num: &u64 = state.borrow();
+
+    assert_eq!(string.as_str(), "996");
+    assert_eq!(num, &251);
+}
+
 #[derive(Debug)]
 struct MyError;
 
@@ -159,3 +195,246 @@ async fn web_handler() {
         .at_typed(test3)
         .finish();
 }
+
+#[tokio::test]
+async fn web_handler_shortcut() {
+    use xitca_web::{
+        codegen::{get, post, route, routes},
+        http::{Method, Request},
+        service::Service,
+        test::collect_string_body,
+        App,
+    };
+
+    #[get("/")]
+    async fn index() -> &'static str {
+        "index"
+    }
+
+    #[post("/echo")]
+    async fn echo(body: String) -> String {
+        body
+    }
+
+    #[route("/both", method = get, method = post)]
+    async fn both() -> &'static str {
+        "both"
+    }
+
+    let service = App::new()
+        .at_routes(routes![index, echo, both])
+        .finish()
+        .call(())
+        .await
+        .unwrap();
+
+    let res = service.call(Request::default()).await.unwrap();
+    assert_eq!(collect_string_body(res.into_body()).await.unwrap(), "index");
+
+    let mut req = Request::default();
+    *req.method_mut() = Method::POST;
+    *req.uri_mut() = "/".parse().unwrap();
+    let res = service.call(req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    for method in [Method::GET, Method::POST] {
+        let mut req = Request::default();
+        *req.method_mut() = method;
+        *req.uri_mut() = "/both".parse().unwrap();
+        let res = service.call(req).await.unwrap();
+        assert_eq!(collect_string_body(res.into_body()).await.unwrap(), "both");
+    }
+}
+
+#[tokio::test]
+async fn from_request_derive() {
+    use xitca_web::{
+        codegen::FromRequest,
+        handler::{handler_service, state::StateRef, uri::UriRef},
+        http::{Method, Request},
+        route::get,
+        service::Service,
+        test::collect_string_body,
+        App,
+    };
+
+    #[derive(FromRequest)]
+    struct Extract<'a> {
+        method: &'a Method,
+        uri: UriRef<'a>,
+        state: StateRef<'a, String>,
+    }
+
+    #[derive(FromRequest)]
+    struct ExtractOwn(Method, String);
+
+    async fn handler(extract: Extract<'_>, ExtractOwn(method, body): ExtractOwn) -> String {
+        assert_eq!(extract.method, method);
+        format!("{}-{}-{}-{}", extract.method, extract.uri.0, extract.state.0, body)
+    }
+
+    let service = App::new()
+        .with_state(String::from("996"))
+        .at("/", get(handler_service(handler)))
+        .finish()
+        .call(())
+        .await
+        .unwrap();
+
+    let res = service.call(Request::default()).await.unwrap();
+    assert_eq!(collect_string_body(res.into_body()).await.unwrap(), "GET-/-996-");
+}
+
+#[tokio::test]
+async fn error_response_derive() {
+    use xitca_web::{
+        codegen::ErrorResponse,
+        error::Error,
+        handler::handler_service,
+        http::{header::CONTENT_TYPE, Request},
+        route::get,
+        service::Service,
+        test::collect_string_body,
+        App,
+    };
+
+    #[derive(Debug, ErrorResponse)]
+    #[response(body = json)]
+    enum ApiError {
+        #[response(status = NOT_FOUND)]
+        NotFound,
+        #[response(status = 422, body = text)]
+        Invalid(&'static str),
+        #[response(body = empty)]
+        Internal,
+    }
+
+    impl fmt::Display for ApiError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::NotFound => f.write_str("\"path\" not found"),
+                Self::Invalid(field) => write!(f, "invalid {field}"),
+                Self::Internal => f.write_str("internal"),
+            }
+        }
+    }
+
+    impl std::error::Error for ApiError {}
+
+    async fn handler(req: &Request<xitca_web::http::RequestExt<()>>) -> Result<&'static str, Error> {
+        match req.uri().path() {
+            "/404" => Err(ApiError::NotFound.into()),
+            "/422" => Err(ApiError::Invalid("name").into()),
+            _ => Err(ApiError::Internal.into()),
+        }
+    }
+
+    let service = App::new()
+        .at("/*path", get(handler_service(handler)))
+        .finish()
+        .call(())
+        .await
+        .unwrap();
+
+    for (path, status, content_type, body) in [
+        (
+            "/404",
+            404,
+            Some("application/json"),
+            r#"{"error":"\"path\" not found"}"#,
+        ),
+        ("/422", 422, Some("text/plain; charset=utf-8"), "invalid name"),
+        ("/500", 500, None, ""),
+    ] {
+        let mut req = Request::default();
+        *req.uri_mut() = path.parse().unwrap();
+        let res = service.call(req).await.unwrap();
+        assert_eq!(res.status().as_u16(), status);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).map(|v| v.to_str().unwrap()),
+            content_type
+        );
+        assert_eq!(collect_string_body(res.into_body()).await.unwrap(), body);
+    }
+}
+
+#[test]
+fn api_schema_derive() {
+    use xitca_web::{codegen::ApiSchema, schema::ApiSchema as _};
+
+    /// a registered user.
+    #[derive(ApiSchema)]
+    #[allow(dead_code)]
+    struct User<T> {
+        /// name of user.
+        #[schema(example = "\"foo\"")]
+        name: String,
+        #[schema(rename = "user_role")]
+        role: Role,
+        nickname: Option<String>,
+        extra: Vec<T>,
+    }
+
+    #[derive(ApiSchema)]
+    #[allow(dead_code)]
+    enum Role {
+        Admin,
+        #[schema(rename = "member")]
+        Member,
+    }
+
+    #[derive(ApiSchema)]
+    #[allow(dead_code)]
+    enum Event {
+        Ping,
+        Message(String),
+        /// user joined.
+        Join {
+            id: u64,
+        },
+    }
+
+    #[derive(ApiSchema)]
+    #[allow(dead_code)]
+    struct Id(u64);
+
+    assert_eq!(
+        User::<bool>::schema().to_string(),
+        concat!(
+            r#"{"type":"object","description":"a registered user.","properties":{"#,
+            r#""name":{"type":"string","description":"name of user.","example":"foo"},"#,
+            r#""user_role":{"type":"string","enum":["Admin","member"]},"#,
+            r#""nickname":{"type":["string","null"]},"#,
+            r#""extra":{"type":"array","items":{"type":"boolean"}}},"#,
+            r#""required":["name","user_role","extra"]}"#
+        )
+    );
+
+    assert_eq!(
+        Event::schema().to_string(),
+        concat!(
+            r#"{"oneOf":[{"type":"string","enum":["Ping"]},"#,
+            r#"{"type":"object","properties":{"Message":{"type":"string"}},"required":["Message"]},"#,
+            r#"{"type":"object","properties":{"Join":{"type":"object","description":"user joined.","#,
+            r#""properties":{"id":{"type":"integer"}},"required":["id"]}},"required":["Join"]}]}"#
+        )
+    );
+
+    assert_eq!(Id::schema().to_string(), r#"{"type":"integer"}"#);
+}
+
+#[xitca_web::test]
+async fn test_entry() {
+    use xitca_web::{handler::handler_service, http::StatusCode, route::get, test::TestApp, App};
+
+    let app = TestApp::new(App::new().at("/", get(handler_service(|| async { "996" }))).finish()).await;
+
+    let res = app.get("/").await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.get("/none").await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    // runtime is capable of time driven task.
+    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+}
@@ -26,6 +26,8 @@ use crate::{
     service::{EnclosedBuilder, EnclosedFnBuilder, MapBuilder, Service, ServiceExt, ready::ReadyService},
 };
 
+pub use self::router::TypedRoutes;
+
 use self::{object::WebObject, router::AppRouter};
 
 /// composed application type with router, stateful context and default middlewares.
@@ -310,6 +312,16 @@ impl<Obj, CF> App<AppRouter<Obj>, CF> {
         self.router = self.router.insert_typed(typed);
         self
     }
+
+    /// insert a collection of typed route services to application.
+    /// typed routes can be collected by [routes](crate::codegen::routes) macro.
+    pub fn at_routes<T, C>(mut self, routes: T) -> Self
+    where
+        T: TypedRoutes<C, Obj>,
+    {
+        self.router = routes.insert_typed(self.router);
+        self
+    }
 }
 
 impl<R, CF> App<R, CF> {
@@ -33,6 +33,28 @@ impl<Obj> AppRouter<Obj> {
     }
 }
 
+/// trait for collection of [TypedRoute] types. implemented for nested tuple in form of `(T1, (T2, ()))`.
+pub trait TypedRoutes<M, Obj> {
+    fn insert_typed(self, router: AppRouter<Obj>) -> AppRouter<Obj>;
+}
+
+impl<M, Obj> TypedRoutes<M, Obj> for () {
+    #[inline]
+    fn insert_typed(self, router: AppRouter<Obj>) -> AppRouter<Obj> {
+        router
+    }
+}
+
+impl<T, R, M, Obj> TypedRoutes<M, Obj> for (T, R)
+where
+    T: TypedRoute<M, Route = Obj>,
+    R: TypedRoutes<M, Obj>,
+{
+    fn insert_typed(self, router: AppRouter<Obj>) -> AppRouter<Obj> {
+        self.1.insert_typed(router.insert_typed(self.0))
+    }
+}
+
 impl<Obj> PathGen for AppRouter<Obj>
 where
     Router<Obj>: PathGen,
@@ -0,0 +1,684 @@
+//! configuration file driven [HttpServer] construction.
+//!
+//! # Examples
+//! ```toml
+//! # server.toml
+//! worker_threads = 4
+//! # graceful shutdown with drain timeout on every stop signal.
+//! shutdown_timeout = "30s"
+//!
+//! [http]
+//! keep_alive_timeout = "5s"
+//! request_head_timeout = "5s"
+//! tls_accept_timeout = "3s"
+//!
+//! [limits]
+//! request_body_size = 4194304
+//!
+//! [[bind]]
+//! addr = "0.0.0.0:8080"
+//!
+//! [[bind]]
+//! addr = "0.0.0.0:8443"
+//! tls = { cert = "cert.pem", key = "key.pem" }
+//! ```
+//!
+//! ```rust,no_run
+//! # use xitca_web::{config::ConfigFile, handler::handler_service, App, HttpServer};
+//! # fn main() -> std::io::Result<()> {
+//! let config = ConfigFile::load("server.toml")?;
+//!
+//! // reload configuration file when it's modified on disk. SIGHUP signal triggers reload too.
+//! config.watch(std::time::Duration::from_secs(5))?;
+//!
+//! let app = App::new()
+//!     .at("/", handler_service(|| async { "hello,world!" }))
+//!     // reloadable request body size limit.
+//!     .enclosed(config.limit());
+//!
+//! HttpServer::serve(app.finish())
+//!     .configure(&config)?
+//!     .run()
+//!     .wait()
+//! # }
+//! ```
+//!
+//! # Reload
+//! Settings of worker threads, bind addresses and http timeouts are only applied when server starts.
+//! tls certificates and limits are reloadable and changed by [ConfigFile::reload] without restarting server.
+//! [ConfigFile::config] always returns settings of the last successful load.
+
+use core::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use std::{
+    error, fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock, Weak},
+    thread,
+    time::SystemTime,
+};
+
+use futures_core::stream::Stream;
+use serde::{Deserialize, Deserializer};
+use xitca_http::body::RequestBody;
+
+use crate::{
+    HttpServer,
+    bytes::Bytes,
+    http::{Request, RequestExt, Response},
+    service::{Service, ready::ReadyService},
+};
+
+/// settings of [HttpServer] parsed from TOML or YAML configuration file.
+///
+/// duration value can be integer in seconds or string with `ms`, `s`, `m` and `h` unit suffix.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// see [HttpServer::worker_threads]
+    pub worker_threads: Option<usize>,
+    /// see [HttpServer::server_threads]
+    pub server_threads: Option<usize>,
+    /// see [HttpServer::worker_max_blocking_threads]
+    pub worker_max_blocking_threads: Option<usize>,
+    /// see [HttpServer::backlog]
+    pub backlog: Option<u32>,
+    /// shutdown server gracefully on every stop signal with given drain timeout.
+    /// see [HttpServer::graceful_signal]
+    #[serde(deserialize_with = "duration")]
+    pub shutdown_timeout: Option<Duration>,
+    pub http: HttpConfig,
+    pub limits: LimitsConfig,
+    pub bind: Vec<BindConfig>,
+}
+
+/// http protocol settings.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    /// see [HttpServer::keep_alive_timeout]
+    #[serde(deserialize_with = "duration")]
+    pub keep_alive_timeout: Option<Duration>,
+    /// see [HttpServer::request_head_timeout]
+    #[serde(deserialize_with = "duration")]
+    pub request_head_timeout: Option<Duration>,
+    /// see [HttpServer::tls_accept_timeout]
+    #[serde(deserialize_with = "duration")]
+    pub tls_accept_timeout: Option<Duration>,
+    /// false value disables vectored write. see [HttpServer::disable_vectored_write]
+    pub vectored_write: Option<bool>,
+}
+
+/// reloadable limits enforced by [ConfigLimit] middleware.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// max size in byte unit the request body can be. no limit when absent.
+    pub request_body_size: Option<usize>,
+}
+
+/// listening address and optional tls setting.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BindConfig {
+    pub addr: String,
+    pub tls: Option<TlsConfig>,
+}
+
+/// path to PEM encoded certificate chain and private key files. tls is served by `rustls` and
+/// requires `rustls` feature and a process default `CryptoProvider` installed.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl ServerConfig {
+    /// parse config from TOML string.
+    pub fn from_toml(s: &str) -> Result<Self, ConfigError> {
+        toml::from_str(s).map_err(ConfigError::Toml)
+    }
+
+    /// parse config from YAML string.
+    pub fn from_yaml(s: &str) -> Result<Self, ConfigError> {
+        serde_norway::from_str(s).map_err(ConfigError::Yaml)
+    }
+
+    /// read and parse config file. format is decided by file extension where `.toml` is TOML and
+    /// `.yaml` or `.yml` is YAML.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&fs::read_to_string(path)?),
+            Some("yaml" | "yml") => Self::from_yaml(&fs::read_to_string(path)?),
+            _ => Err(ConfigError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+}
+
+/// error type of loading configuration file.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Yaml(serde_norway::Error),
+    /// file extension is not one of supported formats.
+    UnknownFormat(PathBuf),
+    /// failed to load tls certificate or private key.
+    Tls(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => fmt::Display::fmt(e, f),
+            Self::Toml(e) => fmt::Display::fmt(e, f),
+            Self::Yaml(e) => fmt::Display::fmt(e, f),
+            Self::UnknownFormat(path) => write!(f, "unknown format of config file: {}", path.display()),
+            Self::Tls(e) => write!(f, "tls config error: {e}"),
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ConfigError> for io::Error {
+    fn from(e: ConfigError) -> Self {
+        match e {
+            ConfigError::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidInput, e),
+        }
+    }
+}
+
+/// configuration file loaded from disk. cheap to clone and share between server and middlewares.
+#[derive(Clone)]
+pub struct ConfigFile {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    path: PathBuf,
+    config: RwLock<Arc<ServerConfig>>,
+    request_body_size: AtomicUsize,
+    #[cfg(feature = "rustls")]
+    certs: Mutex<Vec<(String, Arc<tls::CertResolver>)>>,
+    // serialize reloads from signal and file watcher.
+    reload: Mutex<()>,
+}
+
+impl ConfigFile {
+    /// load configuration file from given path. see [ServerConfig::from_file] for supported formats.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+        let path = path.into();
+        let config = ServerConfig::from_file(&path)?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                path,
+                request_body_size: AtomicUsize::new(body_limit(&config.limits)),
+                config: RwLock::new(Arc::new(config)),
+                #[cfg(feature = "rustls")]
+                certs: Mutex::new(Vec::new()),
+                reload: Mutex::new(()),
+            }),
+        })
+    }
+
+    /// settings of last successful load. updated by [ConfigFile::reload].
+    pub fn config(&self) -> Arc<ServerConfig> {
+        self.inner.config.read().unwrap().clone()
+    }
+
+    /// re-read configuration file and apply reloadable settings. On error previous settings are kept.
+    pub fn reload(&self) -> Result<(), ConfigError> {
+        self.inner.reload()
+    }
+
+    /// spawn a thread checking modified time of configuration file with given interval and reload it on
+    /// change. The thread exits when all copies of [ConfigFile] are dropped.
+    pub fn watch(&self, interval: Duration) -> io::Result<()> {
+        let inner = Arc::downgrade(&self.inner);
+        let modified = modified(&self.inner.path);
+        thread::Builder::new()
+            .name(String::from("xitca-web-config-watch"))
+            .spawn(move || watch(inner, interval, modified))
+            .map(|_| ())
+    }
+
+    /// construct middleware enforcing reloadable [LimitsConfig].
+    pub fn limit(&self) -> ConfigLimit {
+        ConfigLimit(self.clone())
+    }
+}
+
+impl Inner {
+    fn reload(&self) -> Result<(), ConfigError> {
+        let _guard = self.reload.lock().unwrap();
+
+        let config = ServerConfig::from_file(&self.path)?;
+
+        // load every certificate before swapping so partial reload would not happen.
+        #[cfg(feature = "rustls")]
+        {
+            let certs = self.certs.lock().unwrap();
+            let keys = certs
+                .iter()
+                .filter_map(|(addr, resolver)| {
+                    let tls = config.bind.iter().find(|b| &b.addr == addr)?.tls.as_ref()?;
+                    Some(tls::load(tls).map(|key| (resolver, key)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            for (resolver, key) in keys {
+                resolver.swap(key);
+            }
+        }
+
+        self.request_body_size
+            .store(body_limit(&config.limits), Ordering::Relaxed);
+
+        *self.config.write().unwrap() = Arc::new(config);
+
+        Ok(())
+    }
+}
+
+fn body_limit(limits: &LimitsConfig) -> usize {
+    limits.request_body_size.unwrap_or(usize::MAX)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn watch(inner: Weak<Inner>, interval: Duration, mut last: Option<SystemTime>) {
+    loop {
+        thread::sleep(interval);
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+        let modified = modified(&inner.path);
+        if modified != last {
+            last = modified;
+            if let Err(e) = inner.reload() {
+                tracing::error!("failed to reload config file {}: {e}", inner.path.display());
+            }
+        }
+    }
+}
+
+impl<S, const HEADER_LIMIT: usize, const READ_BUF_LIMIT: usize, const WRITE_BUF_LIMIT: usize>
+    HttpServer<S, HEADER_LIMIT, READ_BUF_LIMIT, WRITE_BUF_LIMIT>
+where
+    S: Send + Sync + 'static,
+{
+    /// apply settings and bind addresses of [ConfigFile] to server. reload of configuration file is
+    /// triggered on every SIGHUP signal.
+    pub fn configure<ResB, BE>(mut self, config: &ConfigFile) -> io::Result<Self>
+    where
+        S: Service + 'static,
+        S::Response: ReadyService + Service<Request<RequestExt<RequestBody>>, Response = Response<ResB>> + 'static,
+        S::Error: fmt::Debug,
+        <S::Response as Service<Request<RequestExt<RequestBody>>>>::Error: fmt::Debug,
+        ResB: Stream<Item = Result<Bytes, BE>> + 'static,
+        BE: fmt::Debug + 'static,
+    {
+        let cfg = config.config();
+
+        if let Some(num) = cfg.worker_threads {
+            self = self.worker_threads(num);
+        }
+        if let Some(num) = cfg.server_threads {
+            self = self.server_threads(num);
+        }
+        if let Some(num) = cfg.worker_max_blocking_threads {
+            self = self.worker_max_blocking_threads(num);
+        }
+        if let Some(num) = cfg.backlog {
+            self = self.backlog(num);
+        }
+        if let Some(dur) = cfg.shutdown_timeout {
+            self = self.graceful_signal(dur);
+        }
+        if let Some(dur) = cfg.http.keep_alive_timeout {
+            self = self.keep_alive_timeout(dur);
+        }
+        if let Some(dur) = cfg.http.request_head_timeout {
+            self = self.request_head_timeout(dur);
+        }
+        if let Some(dur) = cfg.http.tls_accept_timeout {
+            self = self.tls_accept_timeout(dur);
+        }
+        if cfg.http.vectored_write == Some(false) {
+            self = self.disable_vectored_write();
+        }
+
+        for bind in cfg.bind.iter() {
+            self = match bind.tls {
+                None => self.bind(bind.addr.as_str())?,
+                #[cfg(feature = "rustls")]
+                Some(ref tls) => {
+                    let resolver = Arc::new(tls::CertResolver::new(tls::load(tls)?));
+                    config
+                        .inner
+                        .certs
+                        .lock()
+                        .unwrap()
+                        .push((bind.addr.clone(), resolver.clone()));
+                    self.bind_rustls(bind.addr.as_str(), tls::server_config(resolver))?
+                }
+                #[cfg(not(feature = "rustls"))]
+                Some(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "tls binding requires rustls feature",
+                    ));
+                }
+            };
+        }
+
+        let config = config.clone();
+        Ok(self.on_reload(move || {
+            if let Err(e) = config.reload() {
+                tracing::error!("failed to reload config file {}: {e}", config.inner.path.display());
+            }
+        }))
+    }
+}
+
+/// middleware enforcing reloadable [LimitsConfig] of [ConfigFile]. limits are read for every request.
+///
+/// # Type mutation
+/// like [Limit](crate::middleware::limit::Limit) it mutates request body type from `B` to
+/// [LimitBody<B>](crate::middleware::limit::LimitBody).
+#[derive(Clone)]
+pub struct ConfigLimit(ConfigFile);
+
+impl<S, E> Service<Result<S, E>> for ConfigLimit {
+    type Response = service::ConfigLimitService<S>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| service::ConfigLimitService {
+            service,
+            config: self.0.clone(),
+        })
+    }
+}
+
+mod service {
+    use core::cell::RefCell;
+
+    use crate::{body::BodyStream, context::WebContext, middleware::limit::LimitBody};
+
+    use super::*;
+
+    pub struct ConfigLimitService<S> {
+        pub(super) service: S,
+        pub(super) config: ConfigFile,
+    }
+
+    impl<'r, S, C, B, Res, Err> Service<WebContext<'r, C, B>> for ConfigLimitService<S>
+    where
+        B: BodyStream + Default,
+        S: for<'r2> Service<WebContext<'r2, C, LimitBody<B>>, Response = Res, Error = Err>,
+    {
+        type Response = Res;
+        type Error = Err;
+
+        async fn call(&self, mut ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+            let limit = self.config.inner.request_body_size.load(Ordering::Relaxed);
+
+            let (parts, ext) = ctx.take_request().into_parts();
+            let state = ctx.ctx;
+            let (ext, body) = ext.replace_body(());
+            let mut body = RefCell::new(LimitBody::new(body, limit));
+            let mut req = Request::from_parts(parts, ext);
+
+            self.service
+                .call(WebContext::new(&mut req, &mut body, state))
+                .await
+                .inspect_err(|_| {
+                    let body = body.into_inner().into_inner();
+                    *ctx.body_borrow_mut() = body;
+                })
+        }
+    }
+
+    impl<S> ReadyService for ConfigLimitService<S>
+    where
+        S: ReadyService,
+    {
+        type Ready = S::Ready;
+
+        #[inline]
+        async fn ready(&self) -> Self::Ready {
+            self.service.ready().await
+        }
+    }
+}
+
+#[cfg(feature = "rustls")]
+mod tls {
+    use std::sync::{Arc, RwLock};
+
+    use xitca_tls::rustls::{
+        ServerConfig,
+        crypto::CryptoProvider,
+        pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+        server::{ClientHello, ResolvesServerCert},
+        sign::CertifiedKey,
+    };
+
+    use super::{ConfigError, TlsConfig};
+
+    // certificate resolver shared with rustls server config. certificate is swapped on reload and
+    // new handshakes use it while established connections are not affected.
+    #[derive(Debug)]
+    pub(super) struct CertResolver(RwLock<Arc<CertifiedKey>>);
+
+    impl CertResolver {
+        pub(super) fn new(key: CertifiedKey) -> Self {
+            Self(RwLock::new(Arc::new(key)))
+        }
+
+        pub(super) fn swap(&self, key: CertifiedKey) {
+            *self.0.write().unwrap() = Arc::new(key);
+        }
+    }
+
+    impl ResolvesServerCert for CertResolver {
+        fn resolve(&self, _: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+            Some(self.0.read().unwrap().clone())
+        }
+    }
+
+    pub(super) fn load(tls: &TlsConfig) -> Result<CertifiedKey, ConfigError> {
+        let provider = CryptoProvider::get_default()
+            .ok_or_else(|| ConfigError::Tls(String::from("process default CryptoProvider is not installed")))?;
+
+        let certs = CertificateDer::pem_file_iter(&tls.cert)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| ConfigError::Tls(format!("{}: {e}", tls.cert.display())))?;
+
+        let key = PrivateKeyDer::from_pem_file(&tls.key)
+            .map_err(|e| ConfigError::Tls(format!("{}: {e}", tls.key.display())))?;
+
+        CertifiedKey::from_der(certs, key, provider).map_err(|e| ConfigError::Tls(e.to_string()))
+    }
+
+    pub(super) fn server_config(resolver: Arc<CertResolver>) -> ServerConfig {
+        ServerConfig::builder()
+            .with_no_client_auth()
+            .with_cert_resolver(resolver)
+    }
+}
+
+fn duration<'de, D>(de: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Secs(u64),
+        Str(String),
+    }
+
+    match Option::<Value>::deserialize(de)? {
+        None => Ok(None),
+        Some(Value::Secs(secs)) => Ok(Some(Duration::from_secs(secs))),
+        Some(Value::Str(s)) => parse_duration(&s)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid duration: {s}"))),
+    }
+}
+
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (num, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let num = num.parse::<u64>().ok()?;
+    match unit.trim() {
+        "ms" => Some(Duration::from_millis(num)),
+        "" | "s" => Some(Duration::from_secs(num)),
+        "m" => num.checked_mul(60).map(Duration::from_secs),
+        "h" => num.checked_mul(3600).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{
+        App,
+        handler::handler_service,
+        http::{StatusCode, WebRequest},
+    };
+
+    use super::*;
+
+    #[test]
+    fn duration_unit() {
+        assert_eq!(parse_duration("5"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1 h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("ms"), None);
+        assert_eq!(parse_duration("1d"), None);
+    }
+
+    #[test]
+    fn parse() {
+        let toml = ServerConfig::from_toml(
+            r#"
+            worker_threads = 2
+            shutdown_timeout = "10s"
+
+            [http]
+            keep_alive_timeout = 5
+            request_head_timeout = "500ms"
+
+            [limits]
+            request_body_size = 1024
+
+            [[bind]]
+            addr = "127.0.0.1:8080"
+
+            [[bind]]
+            addr = "127.0.0.1:8443"
+            tls = { cert = "cert.pem", key = "key.pem" }
+            "#,
+        )
+        .unwrap();
+
+        let yaml = ServerConfig::from_yaml(
+            r#"
+            worker_threads: 2
+            shutdown_timeout: 10s
+            http:
+              keep_alive_timeout: 5
+              request_head_timeout: 500ms
+            limits:
+              request_body_size: 1024
+            bind:
+              - addr: 127.0.0.1:8080
+              - addr: 127.0.0.1:8443
+                tls:
+                  cert: cert.pem
+                  key: key.pem
+            "#,
+        )
+        .unwrap();
+
+        for cfg in [toml, yaml] {
+            assert_eq!(cfg.worker_threads, Some(2));
+            assert_eq!(cfg.server_threads, None);
+            assert_eq!(cfg.shutdown_timeout, Some(Duration::from_secs(10)));
+            assert_eq!(cfg.http.keep_alive_timeout, Some(Duration::from_secs(5)));
+            assert_eq!(cfg.http.request_head_timeout, Some(Duration::from_millis(500)));
+            assert_eq!(cfg.http.tls_accept_timeout, None);
+            assert_eq!(cfg.limits.request_body_size, Some(1024));
+            assert_eq!(cfg.bind.len(), 2);
+            assert!(cfg.bind[0].tls.is_none());
+            assert_eq!(cfg.bind[1].tls.as_ref().unwrap().key, Path::new("key.pem"));
+        }
+
+        assert!(ServerConfig::from_yaml("unknown: 1").is_err());
+
+        assert!(ServerConfig::from_toml("unknown = 1").is_err());
+        assert!(matches!(
+            ServerConfig::from_file("server.json"),
+            Err(ConfigError::UnknownFormat(_))
+        ));
+    }
+
+    #[test]
+    fn reload_limit() {
+        let path = std::env::temp_dir().join(format!("xitca-web-config-{}.toml", std::process::id()));
+        fs::write(&path, "[limits]\nrequest_body_size = 4").unwrap();
+
+        let config = ConfigFile::load(&path).unwrap();
+
+        let service = App::new()
+            .at("/", handler_service(|body: String| async move { body }))
+            .enclosed(config.limit())
+            .finish()
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let req = || WebRequest::default().map(|ext| ext.map_body(|_: ()| b"hello".as_slice().into()));
+
+        let res = service.call(req()).now_or_panic().unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        fs::write(&path, "[limits]\nrequest_body_size = 8").unwrap();
+        config.reload().unwrap();
+
+        let res = service.call(req()).now_or_panic().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(config.config().limits.request_body_size, Some(8));
+
+        // invalid file keeps previous settings.
+        fs::write(&path, "[limits]\nrequest_body_size = \"8\"").unwrap();
+        assert!(config.reload().is_err());
+
+        let res = service.call(req()).now_or_panic().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(config.config().limits.request_body_size, Some(8));
+
+        fs::remove_file(path).unwrap();
+    }
+}
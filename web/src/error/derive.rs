@@ -0,0 +1,72 @@
+//! helper types for error types generated by `codegen::ErrorResponse` derive macro.
+
+use core::fmt::{self, Write};
+
+use crate::{
+    WebContext,
+    body::ResponseBody,
+    http::{
+        StatusCode, WebResponse,
+        const_header_value::{JSON, TEXT_UTF8},
+        header::CONTENT_TYPE,
+    },
+    json::JsonEscape,
+};
+
+/// body format of error response.
+pub enum ErrorBody {
+    /// empty body.
+    Empty,
+    /// error's display format as plain text body.
+    Text,
+    /// error's display format as json body in form of `{"error":"<display format>"}`.
+    Json,
+}
+
+/// generate http response with given status code and body format of error.
+pub fn error_response<C, B>(
+    ctx: WebContext<'_, C, B>,
+    status: StatusCode,
+    body: ErrorBody,
+    err: &dyn fmt::Display,
+) -> WebResponse {
+    let mut res = match body {
+        ErrorBody::Empty => ctx.into_response(ResponseBody::empty()),
+        ErrorBody::Text => {
+            let mut res = ctx.into_response(err.to_string());
+            res.headers_mut().insert(CONTENT_TYPE, TEXT_UTF8);
+            res
+        }
+        ErrorBody::Json => {
+            let mut body = String::from("{\"error\":\"");
+            // writing to string is infallible.
+            let _ = write!(JsonEscape(&mut body), "{err}");
+            body.push_str("\"}");
+            let mut res = ctx.into_response(body);
+            res.headers_mut().insert(CONTENT_TYPE, JSON);
+            res
+        }
+    };
+    *res.status_mut() = status;
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::test::collect_string_body;
+
+    use super::*;
+
+    #[test]
+    fn json_escape() {
+        let mut ctx = WebContext::new_test(());
+        let res = error_response(ctx.as_web_ctx(), StatusCode::BAD_REQUEST, ErrorBody::Json, &"\"996\"\n");
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), JSON);
+
+        let body = collect_string_body(res.into_body()).now_or_panic().unwrap();
+        assert_eq!(body, r#"{"error":"\"996\"\n"}"#);
+    }
+}
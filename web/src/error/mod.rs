@@ -71,6 +71,8 @@
 //! ```
 
 mod body;
+#[cfg(feature = "codegen")]
+pub(crate) mod derive;
 mod extension;
 mod header;
 mod router;
@@ -0,0 +1,477 @@
+//! GraphQL integration with [async_graphql].
+//!
+//! - [GraphQLRequest] and [GraphQLBatchRequest] extract GraphQL request from query string of GET request, json
+//!   body or multipart body with file uploads.
+//! - [GraphQLResponse] sends GraphQL response as json body together with it's http headers and cache control.
+//! - [GraphQLSubscription] serves subscriptions over websocket (`graphql-ws` and `graphql-transport-ws`
+//!   protocols) and falls back to server sent events when request is not a websocket upgrade.
+//!
+//! # Examples
+//! ```rust
+//! # use xitca_web::{
+//! #   graphql::{GraphQLBatchRequest, GraphQLResponse, GraphQLSubscription},
+//! #   handler::{handler_service, state::StateRef},
+//! #   route::get,
+//! #   App, WebContext,
+//! # };
+//! use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn add(&self, a: i32, b: i32) -> i32 {
+//!         a + b
+//!     }
+//! }
+//!
+//! type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+//!
+//! async fn graphql(StateRef(schema): StateRef<'_, AppSchema>, req: GraphQLBatchRequest) -> GraphQLResponse {
+//!     schema.execute_batch(req.0).await.into()
+//! }
+//!
+//! let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+//!
+//! App::new()
+//!     .with_state(schema.clone())
+//!     .at("/graphql", get(handler_service(graphql)).post(handler_service(graphql)))
+//!     .at("/graphql/ws", GraphQLSubscription::new(schema))
+//! #   .at("/nah", handler_service(nah));
+//!
+//! # async fn nah(_: &WebContext<'_, AppSchema>) -> &'static str {
+//! #   // needed to infer the state and body type of request
+//! #   ""
+//! # }
+//! ```
+
+use core::{
+    convert::Infallible,
+    future::poll_fn,
+    pin::{Pin, pin},
+    str::FromStr,
+    task::{Context, Poll, ready},
+};
+
+use async_graphql::{
+    BatchRequest, BatchResponse, Executor, ParseRequestError, Request, Response,
+    http::{MultipartOptions, WebSocket, WebSocketProtocols, WsMessage},
+};
+use futures_core::stream::{BoxStream, Stream};
+use http_ws::{CloseCode, Message, ProtocolError, RequestStream, ResponseSender};
+use tokio::sync::mpsc::{Receiver, channel};
+use xitca_http::util::service::router::{PathGen, RouteGen, RouterMapErr};
+use xitca_unsafe_collection::futures::{Select, SelectOutput};
+
+use crate::{
+    body::{BodyStream, ResponseBody},
+    bytes::Bytes,
+    context::WebContext,
+    error::{Error, HeaderNotFound, error_from_service, forward_blank_bad_request},
+    handler::{FromRequest, Responder, body::Limit},
+    http::{
+        Method, WebResponse,
+        const_header_value::JSON,
+        header::{CACHE_CONTROL, CONTENT_TYPE, HeaderValue, SEC_WEBSOCKET_PROTOCOL, SEC_WEBSOCKET_VERSION},
+    },
+    service::Service,
+};
+
+pub use async_graphql;
+
+/// default max size of GraphQL request body in bytes.
+pub const DEFAULT_LIMIT: usize = 4 * 1024 * 1024;
+
+/// Extract type for single GraphQL request. const generic param LIMIT is for max size of request body in bytes.
+///
+/// request is parsed from query string for GET method and from json or multipart body for other methods.
+/// batched request is rejected. see [GraphQLBatchRequest] for batch support.
+pub struct GraphQLRequest<const LIMIT: usize = DEFAULT_LIMIT>(pub Request);
+
+impl<const LIMIT: usize> GraphQLRequest<LIMIT> {
+    /// consume self and return the inner GraphQL request.
+    #[inline]
+    pub fn into_inner(self) -> Request {
+        self.0
+    }
+}
+
+impl<'a, 'r, C, B, const LIMIT: usize> FromRequest<'a, WebContext<'r, C, B>> for GraphQLRequest<LIMIT>
+where
+    B: BodyStream + Default,
+{
+    type Type<'b> = GraphQLRequest<LIMIT>;
+    type Error = Error;
+
+    async fn from_request(ctx: &'a WebContext<'r, C, B>) -> Result<Self, Self::Error> {
+        let GraphQLBatchRequest(req) = GraphQLBatchRequest::<LIMIT>::from_request(ctx).await?;
+        req.into_single().map(GraphQLRequest).map_err(Into::into)
+    }
+}
+
+/// Extract type for single or batched GraphQL request. const generic param LIMIT is for max size of request body
+/// in bytes.
+pub struct GraphQLBatchRequest<const LIMIT: usize = DEFAULT_LIMIT>(pub BatchRequest);
+
+impl<const LIMIT: usize> GraphQLBatchRequest<LIMIT> {
+    /// consume self and return the inner GraphQL batch request.
+    #[inline]
+    pub fn into_inner(self) -> BatchRequest {
+        self.0
+    }
+}
+
+impl<'a, 'r, C, B, const LIMIT: usize> FromRequest<'a, WebContext<'r, C, B>> for GraphQLBatchRequest<LIMIT>
+where
+    B: BodyStream + Default,
+{
+    type Type<'b> = GraphQLBatchRequest<LIMIT>;
+    type Error = Error;
+
+    async fn from_request(ctx: &'a WebContext<'r, C, B>) -> Result<Self, Self::Error> {
+        if ctx.req().method() == Method::GET {
+            let query = ctx.req().uri().query().unwrap_or_default();
+            return async_graphql::http::parse_query_string(query)
+                .map(|req| GraphQLBatchRequest(BatchRequest::Single(req)))
+                .map_err(Into::into);
+        }
+
+        let content_type = ctx.req().headers().get(CONTENT_TYPE).and_then(|v| v.to_str().ok());
+        let (body, _) = <(Vec<u8>, Limit<LIMIT>)>::from_request(ctx).await?;
+
+        async_graphql::http::receive_batch_body(content_type, body.as_slice(), MultipartOptions::default())
+            .await
+            .map(GraphQLBatchRequest)
+            .map_err(Into::into)
+    }
+}
+
+error_from_service!(ParseRequestError);
+forward_blank_bad_request!(ParseRequestError);
+
+/// Response type for single or batched GraphQL response.
+pub struct GraphQLResponse(pub BatchResponse);
+
+impl From<Response> for GraphQLResponse {
+    fn from(res: Response) -> Self {
+        Self(BatchResponse::Single(res))
+    }
+}
+
+impl From<BatchResponse> for GraphQLResponse {
+    fn from(res: BatchResponse) -> Self {
+        Self(res)
+    }
+}
+
+impl<'r, C, B> Responder<WebContext<'r, C, B>> for GraphQLResponse {
+    type Response = WebResponse;
+    type Error = Error;
+
+    async fn respond(self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        let body = serde_json::to_vec(&self.0)?;
+
+        let mut res = ctx.into_response(body);
+        res.headers_mut().insert(CONTENT_TYPE, JSON);
+
+        // cache control is only sent for successful response.
+        if let Some(value) = self
+            .0
+            .cache_control()
+            .value()
+            .filter(|_| self.0.is_ok())
+            .and_then(|v| HeaderValue::from_str(&v).ok())
+        {
+            res.headers_mut().insert(CACHE_CONTROL, value);
+        }
+
+        for (name, value) in self.0.http_headers_iter() {
+            res.headers_mut().append(name, value);
+        }
+
+        Ok(res)
+    }
+}
+
+/// Service type for GraphQL subscription.
+///
+/// websocket upgrade request is served with protocol negotiated from `sec-websocket-protocol` header. Other
+/// request is parsed the same way as [GraphQLRequest] and it's response stream is sent as server sent events.
+#[derive(Clone)]
+pub struct GraphQLSubscription<E> {
+    executor: E,
+}
+
+impl<E> GraphQLSubscription<E> {
+    /// construct a new subscription service with given executor. (usually a [async_graphql::Schema])
+    pub fn new(executor: E) -> Self
+    where
+        E: Executor,
+    {
+        Self { executor }
+    }
+}
+
+impl<E> PathGen for GraphQLSubscription<E> {}
+
+impl<E> RouteGen for GraphQLSubscription<E> {
+    type Route<R> = RouterMapErr<R>;
+
+    fn route_gen<R>(route: R) -> Self::Route<R> {
+        RouterMapErr(route)
+    }
+}
+
+impl<E> Service for GraphQLSubscription<E>
+where
+    E: Clone,
+{
+    type Response = Self;
+    type Error = Infallible;
+
+    async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+        Ok(self.clone())
+    }
+}
+
+impl<'r, C, B, E> Service<WebContext<'r, C, B>> for GraphQLSubscription<E>
+where
+    E: Executor,
+    B: BodyStream + Default + 'static,
+{
+    type Response = WebResponse;
+    type Error = Error;
+
+    async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        if !ctx.req().headers().contains_key(SEC_WEBSOCKET_VERSION) {
+            let GraphQLRequest(req) = GraphQLRequest::<DEFAULT_LIMIT>::from_request(&ctx).await?;
+            let stream = self.executor.execute_stream(req, None);
+
+            let mut res = ctx.into_response(ResponseBody::box_stream(EventStream(stream)));
+            res.headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+            res.headers_mut()
+                .insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+            return Ok(res);
+        }
+
+        let protocol = ctx
+            .req()
+            .headers()
+            .get(SEC_WEBSOCKET_PROTOCOL)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').find_map(|p| WebSocketProtocols::from_str(p.trim()).ok()))
+            .ok_or(HeaderNotFound(SEC_WEBSOCKET_PROTOCOL))?;
+
+        let body = ctx.take_body_ref();
+        let (decode, mut res, tx) = http_ws::ws(ctx.req(), body).map_err(Error::from_service)?;
+
+        res.headers_mut().insert(
+            SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static(protocol.sec_websocket_protocol()),
+        );
+
+        tokio::task::spawn_local(subscribe(self.executor.clone(), protocol, decode, tx));
+
+        Ok(res.map(ResponseBody::box_stream))
+    }
+}
+
+// max count of incoming messages waiting for GraphQL executor. websocket is not read when it's reached.
+const INCOMING_CAP: usize = 16;
+
+// bridge websocket messages between client and GraphQL executor.
+async fn subscribe<E, B>(
+    executor: E,
+    protocol: WebSocketProtocols,
+    decode: RequestStream<B>,
+    tx: ResponseSender,
+) -> Result<(), ProtocolError>
+where
+    E: Executor,
+    B: BodyStream,
+{
+    let (tx_in, rx_in) = channel(INCOMING_CAP);
+
+    let mut stream = pin!(WebSocket::new(executor, Incoming(rx_in), protocol));
+    let mut decode = pin!(decode);
+
+    loop {
+        // reserve room for incoming message before reading from websocket so slow executor applies
+        // backpressure to client.
+        let next = async {
+            let permit = tx_in.reserve().await;
+            let msg = poll_fn(|cx| decode.as_mut().poll_next(cx)).await;
+            (permit, msg)
+        };
+
+        match next.select(poll_fn(|cx| stream.as_mut().poll_next(cx))).await {
+            SelectOutput::A((permit, Some(Ok(msg)))) => match msg {
+                Message::Text(bytes) | Message::Binary(bytes) => {
+                    if let Ok(permit) = permit {
+                        permit.send(bytes);
+                    }
+                }
+                Message::Ping(ping) => tx.send(Message::Pong(ping)).await?,
+                Message::Close(reason) => return tx.send(Message::Close(reason)).await,
+                _ => {}
+            },
+            SelectOutput::A(_) => return Ok(()),
+            SelectOutput::B(Some(WsMessage::Text(text))) => tx.text(text).await?,
+            SelectOutput::B(Some(WsMessage::Close(code, reason))) => {
+                let reason = (CloseCode::from(code), reason).into();
+                return tx.send(Message::Close(Some(reason))).await;
+            }
+            SelectOutput::B(None) => return tx.send(Message::Close(None)).await,
+        }
+    }
+}
+
+struct Incoming(Receiver<Bytes>);
+
+impl Stream for Incoming {
+    type Item = Bytes;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().0.poll_recv(cx)
+    }
+}
+
+// GraphQL response stream encoded as server sent events.
+struct EventStream(BoxStream<'static, Response>);
+
+impl Stream for EventStream {
+    type Item = Result<Bytes, serde_json::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let res = match ready!(self.get_mut().0.as_mut().poll_next(cx)) {
+            Some(res) => res,
+            None => return Poll::Ready(None),
+        };
+
+        let mut buf = b"data: ".to_vec();
+        let res = serde_json::to_writer(&mut buf, &res).map(|_| {
+            buf.extend_from_slice(b"\n\n");
+            Bytes::from(buf)
+        });
+
+        Poll::Ready(Some(res))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema, Subscription};
+    use futures_util::stream::{self, StreamExt};
+    use http_ws::Codec;
+
+    use crate::{
+        App,
+        bytes::BytesMut,
+        handler::{handler_service, state::StateRef},
+        http::StatusCode,
+        route::get,
+        test::{TestApp, collect_string_body},
+    };
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn add(&self, a: i32, b: i32) -> i32 {
+            a + b
+        }
+    }
+
+    type AppSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+    struct Sub;
+
+    #[Subscription]
+    impl Sub {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            stream::iter(0..2)
+        }
+    }
+
+    async fn graphql(StateRef(schema): StateRef<'_, AppSchema>, req: GraphQLBatchRequest) -> GraphQLResponse {
+        schema.execute_batch(req.0).await.into()
+    }
+
+    #[tokio::test]
+    async fn query() {
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+        let app = TestApp::new(
+            App::new()
+                .with_state(schema)
+                .at("/graphql", get(handler_service(graphql)))
+                .finish(),
+        )
+        .await;
+
+        let res = app.get("/graphql?query=%7Badd(a%3A1%2Cb%3A2)%7D").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), JSON);
+        assert_eq!(
+            collect_string_body(res.into_body()).await.unwrap(),
+            "{\"data\":{\"add\":3}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn subscription() {
+        let schema = Schema::new(Query, EmptyMutation, Sub);
+
+        let mut codec = Codec::new().client_mode();
+        let mut frames = BytesMut::new();
+        for msg in [
+            r#"{"type":"connection_init"}"#,
+            r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { values }"}}"#,
+        ] {
+            codec.encode(Message::Text(Bytes::from(msg)), &mut frames).unwrap();
+        }
+
+        // client keeps websocket open after sending messages.
+        let body = stream::iter([Ok::<_, Infallible>(frames.freeze())]).chain(stream::pending());
+        let decode = RequestStream::new(body);
+        let (res, tx) = decode.response_stream();
+
+        let read = async {
+            let mut res = pin!(RequestStream::with_codec(res, Codec::new().client_mode()));
+            let mut msgs = Vec::new();
+            loop {
+                let Message::Text(text) = res.next().await.unwrap().unwrap() else {
+                    panic!("unexpected websocket message")
+                };
+                let text = String::from_utf8(text.to_vec()).unwrap();
+                let complete = text.contains("complete");
+                msgs.push(text);
+                if complete {
+                    return msgs;
+                }
+            }
+        };
+
+        let msgs = match subscribe(schema, WebSocketProtocols::GraphQLWS, decode, tx)
+            .select(read)
+            .await
+        {
+            SelectOutput::A(res) => panic!("subscription ended early: {res:?}"),
+            SelectOutput::B(msgs) => msgs,
+        };
+
+        assert_eq!(
+            msgs,
+            [
+                r#"{"type":"connection_ack"}"#,
+                r#"{"type":"next","id":"1","payload":{"data":{"values":0}}}"#,
+                r#"{"type":"next","id":"1","payload":{"data":{"values":1}}}"#,
+                r#"{"type":"complete","id":"1"}"#,
+            ]
+        );
+    }
+}
@@ -0,0 +1,691 @@
+//! gRPC over http/2.
+//!
+//! gRPC request and response are mapped to http/2 stream with length prefixed message framing and status
+//! code carried by `grpc-status` and `grpc-message` headers/trailers.
+//!
+//! message encoding is pluggable through [GrpcMessage] trait. (e.g: implement it for types generated by `prost`)
+//!
+//! # Limitation
+//! only unary call is supported and compressed message is rejected with [Code::Unimplemented] status.
+//!
+//! # Examples
+//! ```rust
+//! # use xitca_web::{
+//! #   bytes::{Bytes, BytesMut},
+//! #   grpc::{Code, Grpc, GrpcMessage, Status},
+//! #   handler::handler_service,
+//! #   route::post,
+//! #   App, WebContext,
+//! # };
+//! // a message type. in real world application it's usually generated from protobuf definition.
+//! struct Hello(String);
+//!
+//! impl GrpcMessage for Hello {
+//!     fn decode(buf: Bytes) -> Result<Self, Status> {
+//!         String::from_utf8(buf.to_vec())
+//!             .map(Hello)
+//!             .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))
+//!     }
+//!
+//!     fn encode(&self, buf: &mut BytesMut) {
+//!         buf.extend_from_slice(self.0.as_bytes());
+//!     }
+//! }
+//!
+//! // unary gRPC handler. Status error would be sent to client as gRPC status.
+//! async fn say_hello(Grpc(Hello(name)): Grpc<Hello>) -> Result<Grpc<Hello>, Status> {
+//!     if name.is_empty() {
+//!         return Err(Status::new(Code::InvalidArgument, "name is empty"));
+//!     }
+//!     Ok(Grpc(Hello(format!("hello {name}"))))
+//! }
+//!
+//! App::new()
+//!     .at("/helloworld.Greeter/SayHello", post(handler_service(say_hello)))
+//!     # .at("/infer", handler_service(|_: &WebContext<'_>| async { "infer type" }));
+//! ```
+
+use core::{convert::Infallible, fmt, future::poll_fn, pin::pin, task::Poll, time::Duration};
+
+use std::{borrow::Cow, error};
+
+use xitca_http::util::service::router::{PathGen, RouteGen, RouterMapErr};
+
+use crate::{
+    body::{BodyStream, ResponseBody},
+    bytes::{BufMut, Bytes, BytesMut},
+    context::WebContext,
+    error::{Error, error_from_service},
+    handler::{FromRequest, Responder, body::Limit},
+    http::{
+        StatusCode, WebResponse,
+        const_header_value::GRPC,
+        header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, TRAILER},
+    },
+    service::{Service, ready::ReadyService},
+};
+
+/// default max size of gRPC message in bytes.
+pub const DEFAULT_LIMIT: usize = 4 * 1024 * 1024;
+
+#[allow(clippy::declare_interior_mutable_const)]
+pub const GRPC_STATUS: HeaderName = HeaderName::from_static("grpc-status");
+#[allow(clippy::declare_interior_mutable_const)]
+pub const GRPC_MESSAGE: HeaderName = HeaderName::from_static("grpc-message");
+#[allow(clippy::declare_interior_mutable_const)]
+pub const GRPC_TIMEOUT: HeaderName = HeaderName::from_static("grpc-timeout");
+
+/// trait for encoding and decoding gRPC message.
+pub trait GrpcMessage: Sized {
+    /// decode message from bytes with length prefix removed.
+    fn decode(buf: Bytes) -> Result<Self, Status>;
+
+    /// encode message and append it to buffer.
+    fn encode(&self, buf: &mut BytesMut);
+}
+
+impl GrpcMessage for Bytes {
+    fn decode(buf: Bytes) -> Result<Self, Status> {
+        Ok(buf)
+    }
+
+    fn encode(&self, buf: &mut BytesMut) {
+        buf.extend_from_slice(self);
+    }
+}
+
+/// status code of gRPC call.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Code {
+    Ok = 0,
+    Cancelled = 1,
+    Unknown = 2,
+    InvalidArgument = 3,
+    DeadlineExceeded = 4,
+    NotFound = 5,
+    AlreadyExists = 6,
+    PermissionDenied = 7,
+    ResourceExhausted = 8,
+    FailedPrecondition = 9,
+    Aborted = 10,
+    OutOfRange = 11,
+    Unimplemented = 12,
+    Internal = 13,
+    Unavailable = 14,
+    DataLoss = 15,
+    Unauthenticated = 16,
+}
+
+impl Code {
+    fn header_value(self) -> HeaderValue {
+        HeaderValue::from(self as u16)
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// status of failed gRPC call. it can be used as error type of handler function and would be sent to client as
+/// `grpc-status` and `grpc-message` headers.
+#[derive(Debug)]
+pub struct Status {
+    code: Code,
+    message: Cow<'static, str>,
+}
+
+impl Status {
+    pub fn new(code: Code, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn code(&self) -> Code {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn insert_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(GRPC_STATUS, self.code.header_value());
+        if !self.message.is_empty() {
+            // percent encoded message is always valid header value.
+            let msg = HeaderValue::from_maybe_shared(percent_encode(&self.message)).unwrap();
+            headers.insert(GRPC_MESSAGE, msg);
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "grpc status: {}, message: {}", self.code, self.message)
+    }
+}
+
+impl error::Error for Status {}
+
+error_from_service!(Status);
+
+impl<'r, C, B> Service<WebContext<'r, C, B>> for Status {
+    type Response = WebResponse;
+    type Error = Infallible;
+
+    async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        // trailers only response where status is sent with headers.
+        let mut res = ctx.into_response(ResponseBody::empty());
+        res.headers_mut().insert(CONTENT_TYPE, GRPC);
+        self.insert_headers(res.headers_mut());
+        Ok(res)
+    }
+}
+
+/// type extractor and response generator for unary gRPC message. const generic param LIMIT is for max size of
+/// message in bytes.
+///
+/// Default limit is [DEFAULT_LIMIT] in bytes.
+pub struct Grpc<T, const LIMIT: usize = DEFAULT_LIMIT>(pub T);
+
+impl<T, const LIMIT: usize> fmt::Debug for Grpc<T, LIMIT>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Grpc")
+            .field("value", &self.0)
+            .field("limit", &LIMIT)
+            .finish()
+    }
+}
+
+impl<'a, 'r, C, B, T, const LIMIT: usize> FromRequest<'a, WebContext<'r, C, B>> for Grpc<T, LIMIT>
+where
+    B: BodyStream + Default,
+    T: GrpcMessage,
+{
+    type Type<'b> = Grpc<T, LIMIT>;
+    type Error = Error;
+
+    async fn from_request(ctx: &'a WebContext<'r, C, B>) -> Result<Self, Self::Error> {
+        let is_grpc = ctx
+            .req()
+            .headers()
+            .get(CONTENT_TYPE)
+            .is_some_and(|v| v.as_bytes().starts_with(GRPC.as_bytes()));
+
+        if !is_grpc {
+            return Err(StatusCode::UNSUPPORTED_MEDIA_TYPE.into());
+        }
+
+        let (bytes, _) = <(BytesMut, Limit<LIMIT>)>::from_request(ctx).await?;
+        let msg = decode_frame(bytes)?;
+        T::decode(msg).map(Grpc).map_err(Into::into)
+    }
+}
+
+impl<'r, C, B, T> Responder<WebContext<'r, C, B>> for Grpc<T>
+where
+    T: GrpcMessage,
+{
+    type Response = WebResponse;
+    type Error = Error;
+
+    #[inline]
+    async fn respond(self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        Ok(self._respond(|bytes| ctx.into_response(bytes)))
+    }
+
+    #[inline]
+    fn map(self, res: Self::Response) -> Result<Self::Response, Self::Error> {
+        Ok(self._respond(|bytes| res.map(|_| bytes.into())))
+    }
+}
+
+impl<T> Grpc<T>
+where
+    T: GrpcMessage,
+{
+    fn _respond<F>(self, func: F) -> WebResponse
+    where
+        F: FnOnce(Bytes) -> WebResponse,
+    {
+        let mut res = func(encode_frame(&self.0));
+        res.headers_mut().insert(CONTENT_TYPE, GRPC);
+        // status is moved to trailers by http/2 dispatcher.
+        res.headers_mut().insert(GRPC_STATUS, Code::Ok.header_value());
+        res.headers_mut()
+            .insert(TRAILER, HeaderValue::from_static("grpc-status"));
+        res
+    }
+}
+
+impl<T> PathGen for Grpc<T> {}
+
+impl<T> RouteGen for Grpc<T> {
+    type Route<R> = RouterMapErr<R>;
+
+    fn route_gen<R>(route: R) -> Self::Route<R> {
+        RouterMapErr(route)
+    }
+}
+
+/// type extractor for deadline of gRPC call from `grpc-timeout` header. it's None when client does not set a
+/// deadline.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GrpcTimeout(pub Option<Duration>);
+
+impl<'a, 'r, C, B> FromRequest<'a, WebContext<'r, C, B>> for GrpcTimeout {
+    type Type<'b> = GrpcTimeout;
+    type Error = Error;
+
+    #[inline]
+    async fn from_request(ctx: &'a WebContext<'r, C, B>) -> Result<Self, Self::Error> {
+        grpc_timeout(ctx.req().headers()).map(GrpcTimeout).map_err(Into::into)
+    }
+}
+
+/// A middleware propagating deadline of gRPC call. when deadline from `grpc-timeout` header is reached before
+/// inner service finish the call is dropped and [Code::DeadlineExceeded] status is sent to client.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_web::{bytes::Bytes, grpc::{Deadline, Grpc}, handler::handler_service, route::post, App, WebContext};
+/// App::new()
+///     .at("/echo.Echo/Echo", post(handler_service(|msg: Grpc<Bytes>| async { msg })))
+///     # .at("/infer", handler_service(|_: &WebContext<'_>| async { "infer type" }))
+///     .enclosed(Deadline);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Deadline;
+
+impl<S, E> Service<Result<S, E>> for Deadline {
+    type Response = DeadlineService<S>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(DeadlineService)
+    }
+}
+
+pub struct DeadlineService<S>(S);
+
+impl<'r, S, C, B, Err> Service<WebContext<'r, C, B>> for DeadlineService<S>
+where
+    S: for<'r2> Service<WebContext<'r2, C, B>, Response = WebResponse, Error = Err>,
+{
+    type Response = WebResponse;
+    type Error = Err;
+
+    async fn call(&self, mut ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        let dur = match grpc_timeout(ctx.req().headers()) {
+            Ok(Some(dur)) => dur,
+            Ok(None) => return self.0.call(ctx).await,
+            Err(status) => return Ok(status.call(ctx).await.unwrap()),
+        };
+
+        let res = {
+            let mut call = pin!(self.0.call(ctx.reborrow()));
+            let mut sleep = pin!(tokio::time::sleep(dur));
+
+            poll_fn(|cx| {
+                if let Poll::Ready(res) = call.as_mut().poll(cx) {
+                    return Poll::Ready(Some(res));
+                }
+                sleep.as_mut().poll(cx).map(|_| None)
+            })
+            .await
+        };
+
+        match res {
+            Some(res) => res,
+            None => {
+                let status = Status::new(Code::DeadlineExceeded, "deadline exceeded");
+                Ok(status.call(ctx).await.unwrap())
+            }
+        }
+    }
+}
+
+impl<S> ReadyService for DeadlineService<S>
+where
+    S: ReadyService,
+{
+    type Ready = S::Ready;
+
+    #[inline]
+    async fn ready(&self) -> Self::Ready {
+        self.0.ready().await
+    }
+}
+
+/// parse deadline from `grpc-timeout` header.
+pub fn grpc_timeout(headers: &HeaderMap) -> Result<Option<Duration>, Status> {
+    let Some(value) = headers.get(GRPC_TIMEOUT) else {
+        return Ok(None);
+    };
+
+    let err = || Status::new(Code::InvalidArgument, "invalid grpc-timeout header");
+
+    let value = value.as_bytes();
+
+    // timeout value is at most 8 digits followed by a unit.
+    let (unit, digits) = value.split_last().ok_or_else(err)?;
+    if digits.is_empty() || digits.len() > 8 || !digits.iter().all(u8::is_ascii_digit) {
+        return Err(err());
+    }
+
+    let num = digits.iter().fold(0, |num, d| num * 10 + u64::from(d - b'0'));
+
+    let dur = match unit {
+        b'H' => Duration::from_secs(num * 60 * 60),
+        b'M' => Duration::from_secs(num * 60),
+        b'S' => Duration::from_secs(num),
+        b'm' => Duration::from_millis(num),
+        b'u' => Duration::from_micros(num),
+        b'n' => Duration::from_nanos(num),
+        _ => return Err(err()),
+    };
+
+    Ok(Some(dur))
+}
+
+/// encode message with length prefix.
+pub fn encode_frame<T>(msg: &T) -> Bytes
+where
+    T: GrpcMessage,
+{
+    let mut buf = BytesMut::new();
+    // reserve space for compressed flag and message length.
+    buf.put_bytes(0, 5);
+    msg.encode(&mut buf);
+    let len = u32::try_from(buf.len() - 5).expect("gRPC message exceeds u32::MAX in size");
+    buf[1..5].copy_from_slice(&len.to_be_bytes());
+    buf.freeze()
+}
+
+/// decode a single length prefixed message and return it with prefix removed.
+pub fn decode_frame(mut buf: BytesMut) -> Result<Bytes, Status> {
+    if buf.len() < 5 {
+        return Err(Status::new(Code::Internal, "incomplete gRPC message"));
+    }
+
+    if buf[0] != 0 {
+        return Err(Status::new(
+            Code::Unimplemented,
+            "compressed gRPC message is not supported",
+        ));
+    }
+
+    let len = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]) as usize;
+
+    if buf.len() - 5 != len {
+        return Err(Status::new(Code::Internal, "gRPC message length mismatch"));
+    }
+
+    Ok(buf.split_off(5).freeze())
+}
+
+fn percent_encode(msg: &str) -> Bytes {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut buf = BytesMut::with_capacity(msg.len());
+    for &b in msg.as_bytes() {
+        if (0x20..=0x7e).contains(&b) && b != b'%' {
+            buf.put_u8(b);
+        } else {
+            buf.put_slice(&[b'%', HEX[(b >> 4) as usize], HEX[(b & 0xf) as usize]]);
+        }
+    }
+    buf.freeze()
+}
+
+#[cfg(feature = "tower-http-compat")]
+pub use compat::GrpcCompat;
+
+#[cfg(feature = "tower-http-compat")]
+mod compat {
+    use http_body::Body;
+
+    use crate::{
+        bytes::Buf,
+        http::{Request, RequestExt, Response},
+        service::tower_http_compat::{CompatReqBody, TowerCompatService},
+    };
+
+    use super::*;
+
+    /// A service type mounting [tower_service::Service] generated by gRPC framework (e.g: `tonic`) to xitca-web.
+    ///
+    /// response body of tower service is collected and it's trailers are sent with http/2 trailers. therefore
+    /// only unary call is supported.
+    pub struct GrpcCompat<S>(S);
+
+    impl<S> GrpcCompat<S> {
+        pub const fn new(service: S) -> Self
+        where
+            S: Clone,
+        {
+            Self(service)
+        }
+    }
+
+    impl<S> Service for GrpcCompat<S>
+    where
+        S: Clone,
+    {
+        type Response = GrpcCompatService<S>;
+        type Error = Infallible;
+
+        async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+            Ok(GrpcCompatService(TowerCompatService::new(self.0.clone())))
+        }
+    }
+
+    impl<S> PathGen for GrpcCompat<S> {}
+
+    impl<S> RouteGen for GrpcCompat<S> {
+        type Route<R> = RouterMapErr<R>;
+
+        fn route_gen<R>(route: R) -> Self::Route<R> {
+            RouterMapErr(route)
+        }
+    }
+
+    pub struct GrpcCompatService<S>(TowerCompatService<S>);
+
+    impl<'r, C, ReqB, S, ResB> Service<WebContext<'r, C, ReqB>> for GrpcCompatService<S>
+    where
+        S: tower_service::Service<Request<CompatReqBody<RequestExt<ReqB>, C>>, Response = Response<ResB>>,
+        ResB: Body,
+        ResB::Error: fmt::Display,
+        C: Clone + 'static,
+        ReqB: Default,
+    {
+        type Response = WebResponse;
+        type Error = S::Error;
+
+        async fn call(&self, mut ctx: WebContext<'r, C, ReqB>) -> Result<Self::Response, Self::Error> {
+            let res = self.0.call(ctx.reborrow()).await?;
+            let (mut parts, body) = res.into_parts();
+
+            let mut body = pin!(body.into_inner());
+            let mut buf = BytesMut::new();
+            let mut trailers = HeaderMap::new();
+
+            while let Some(frame) = poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+                let frame = match frame {
+                    Ok(frame) => frame,
+                    Err(e) => return Ok(Status::new(Code::Internal, e.to_string()).call(ctx).await.unwrap()),
+                };
+
+                match frame.into_data() {
+                    Ok(mut data) => {
+                        while data.has_remaining() {
+                            let chunk = data.chunk();
+                            let len = chunk.len();
+                            buf.extend_from_slice(chunk);
+                            data.advance(len);
+                        }
+                    }
+                    Err(frame) => {
+                        if let Ok(t) = frame.into_trailers() {
+                            trailers.extend(t);
+                        }
+                    }
+                }
+            }
+
+            // trailers are sent with headers when there is no message in response.
+            if !buf.is_empty() {
+                for name in trailers.keys() {
+                    parts
+                        .headers
+                        .append(TRAILER, HeaderValue::from_str(name.as_str()).unwrap());
+                }
+            }
+            parts.headers.extend(trailers);
+
+            Ok(Response::from_parts(parts, ResponseBody::from(buf.freeze())))
+        }
+    }
+
+    impl<S> ReadyService for GrpcCompatService<S> {
+        type Ready = ();
+
+        #[inline]
+        async fn ready(&self) -> Self::Ready {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{
+        App,
+        handler::handler_service,
+        http::{Method, WebRequest, header::CONTENT_LENGTH},
+        route::post,
+        test::collect_body,
+    };
+
+    use super::*;
+
+    #[test]
+    fn timeout() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(grpc_timeout(&headers).unwrap(), None);
+
+        for (value, dur) in [
+            ("1H", Duration::from_secs(3600)),
+            ("2M", Duration::from_secs(120)),
+            ("3S", Duration::from_secs(3)),
+            ("996m", Duration::from_millis(996)),
+            ("5u", Duration::from_micros(5)),
+            ("99999999n", Duration::from_nanos(99999999)),
+        ] {
+            headers.insert(GRPC_TIMEOUT, HeaderValue::from_static(value));
+            assert_eq!(grpc_timeout(&headers).unwrap(), Some(dur));
+        }
+
+        for value in ["", "S", "1", "123456789S", "1s", "-1S"] {
+            headers.insert(GRPC_TIMEOUT, HeaderValue::from_static(value));
+            assert_eq!(grpc_timeout(&headers).err().unwrap().code(), Code::InvalidArgument);
+        }
+    }
+
+    #[test]
+    fn frame() {
+        let frame = encode_frame(&Bytes::from_static(b"996"));
+        assert_eq!(frame.as_ref(), b"\0\0\0\0\x03996");
+        assert_eq!(decode_frame(BytesMut::from(frame.as_ref())).unwrap(), "996");
+
+        let err = decode_frame(BytesMut::from(&b"\x01\0\0\0\x03996"[..])).err().unwrap();
+        assert_eq!(err.code(), Code::Unimplemented);
+
+        let err = decode_frame(BytesMut::from(&b"\0\0\0\0\x04996"[..])).err().unwrap();
+        assert_eq!(err.code(), Code::Internal);
+    }
+
+    #[test]
+    fn message_encode() {
+        assert_eq!(percent_encode("not found"), "not found");
+        assert_eq!(percent_encode("100%\n"), "100%25%0A");
+        assert_eq!(percent_encode("μ"), "%CE%BC");
+    }
+
+    fn request(body: &[u8]) -> WebRequest {
+        let frame = encode_frame(&Bytes::copy_from_slice(body));
+        let mut req = WebRequest::default();
+        *req.method_mut() = Method::POST;
+        *req.uri_mut() = "/echo".parse().unwrap();
+        req.headers_mut().insert(CONTENT_TYPE, GRPC);
+        req.headers_mut().insert(CONTENT_LENGTH, frame.len().into());
+        req.map(|ext| ext.map_body(|_: ()| frame.into()))
+    }
+
+    #[tokio::test]
+    async fn unary() {
+        async fn echo(Grpc(msg): Grpc<Bytes>) -> Result<Grpc<Bytes>, Status> {
+            if msg.is_empty() {
+                return Err(Status::new(Code::InvalidArgument, "empty message"));
+            }
+            Ok(Grpc(msg))
+        }
+
+        let service = App::new()
+            .at("/echo", post(handler_service(echo)))
+            .enclosed(Deadline)
+            .finish()
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let res = service.call(request(b"996")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), GRPC);
+        assert_eq!(res.headers().get(GRPC_STATUS).unwrap(), "0");
+        assert_eq!(res.headers().get(TRAILER).unwrap(), "grpc-status");
+        let body = collect_body(res.into_body()).await.unwrap();
+        assert_eq!(body, b"\0\0\0\0\x03996");
+
+        let res = service.call(request(b"")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(GRPC_STATUS).unwrap(), "3");
+        assert_eq!(res.headers().get(GRPC_MESSAGE).unwrap(), "empty message");
+        assert!(res.headers().get(TRAILER).is_none());
+
+        let mut req = request(b"996");
+        req.headers_mut().remove(CONTENT_TYPE);
+        let res = service.call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn deadline() {
+        async fn slow(_: Grpc<Bytes>) -> Grpc<Bytes> {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Grpc(Bytes::new())
+        }
+
+        let service = App::new()
+            .at("/echo", post(handler_service(slow)))
+            .enclosed(Deadline)
+            .finish()
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let mut req = request(b"996");
+        req.headers_mut().insert(GRPC_TIMEOUT, HeaderValue::from_static("1m"));
+
+        let res = service.call(req).await.unwrap();
+        assert_eq!(res.headers().get(GRPC_STATUS).unwrap(), "4");
+    }
+}
@@ -0,0 +1,48 @@
+//! json string escaping shared by types producing json output without serde.
+
+use core::fmt::{self, Write};
+
+/// writer escaping written string as content of json string.
+pub(crate) struct JsonEscape<W>(pub(crate) W);
+
+impl<W> Write for JsonEscape<W>
+where
+    W: Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                c if c.is_control() => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// write string as quoted and escaped json string.
+pub(crate) fn write_str<W>(w: &mut W, s: &str) -> fmt::Result
+where
+    W: Write,
+{
+    w.write_char('"')?;
+    JsonEscape(&mut *w).write_str(s)?;
+    w.write_char('"')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escape() {
+        let mut s = String::new();
+        write_str(&mut s, "\"a\\b\"\n\r\t\u{1}ü").unwrap();
+        assert_eq!(s, r#""\"a\\b\"\n\r\t\u0001ü""#);
+    }
+}
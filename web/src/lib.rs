@@ -258,13 +258,21 @@
 
 mod app;
 mod context;
+mod json;
 #[cfg(feature = "__server")]
 mod server;
 
 pub mod body;
+#[cfg(all(feature = "config", not(target_family = "wasm")))]
+pub mod config;
 pub mod error;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod handler;
 pub mod middleware;
+pub mod schema;
 pub mod service;
 pub mod test;
 
@@ -306,14 +314,204 @@ pub mod codegen {
     /// ```
     pub use xitca_codegen::State;
 
+    /// Derive macro for every struct field type extractable through [StateRef](crate::handler::state::StateRef)
+    /// and [StateOwn](crate::handler::state::StateOwn). field can be opted out with `#[borrow(skip)]` attribute.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use xitca_web::{codegen::BorrowState, handler::state::BorrowState as _};
+    /// #[derive(BorrowState)]
+    /// struct MyState {
+    ///     num: u128,
+    ///     name: String,
+    ///     #[borrow(skip)]
+    ///     _other_name: String,
+    /// }
+    ///
+    /// let state = MyState { num: 996, name: String::from("996"), _other_name: String::new() };
+    /// let num: &u128 = state.borrow();
+    /// let name: &String = state.borrow();
+    /// assert_eq!(num.to_string(), *name);
+    /// ```
+    pub use xitca_codegen::BorrowState;
+
+    /// Derive macro for struct composed by extractor types. all fields are extracted concurrently and the struct
+    /// can be used as one handler argument.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use xitca_web::{
+    /// #   codegen::FromRequest,
+    /// #   handler::{handler_service, state::StateRef, uri::UriRef},
+    /// #   http::Method,
+    /// #   route::get,
+    /// #   App,
+    /// # };
+    /// #[derive(FromRequest)]
+    /// struct Extract<'a> {
+    ///     method: &'a Method,
+    ///     uri: UriRef<'a>,
+    ///     state: StateRef<'a, String>,
+    /// }
+    ///
+    /// async fn handler(extract: Extract<'_>) -> String {
+    ///     format!("{} {} {}", extract.method, extract.uri.0, extract.state.0)
+    /// }
+    ///
+    /// # fn app() {
+    /// App::new()
+    ///     .with_state(String::from("996"))
+    ///     .at("/", get(handler_service(handler)))
+    ///     .finish();
+    /// # }
+    /// ```
+    pub use xitca_codegen::FromRequest;
+
     pub use xitca_codegen::route;
 
+    /// shortcut attribute macros of [route] with given http method.
+    pub use xitca_codegen::{connect, delete, get, head, options, patch, post, put, trace};
+
+    /// collect routes generated by [route] and it's shortcut macros and register them to application at once.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use xitca_web::{codegen::{get, post, routes}, App};
+    /// #[get("/")]
+    /// async fn index() -> &'static str {
+    ///     "Hello,World!"
+    /// }
+    ///
+    /// #[post("/echo")]
+    /// async fn echo(body: String) -> String {
+    ///     body
+    /// }
+    ///
+    /// # fn app() {
+    /// App::new().at_routes(routes![index, echo]).finish();
+    /// # }
+    /// ```
+    pub use xitca_codegen::routes;
+
     pub use xitca_codegen::error_impl;
 
+    /// Derive macro for error type generating http response with status code and body format given by
+    /// `#[response(status = <status>, body = <empty|text|json>)]` attribute. the type can be converted to
+    /// [Error](crate::error::Error) and it's display format is used as response body.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use std::fmt;
+    /// # use xitca_web::{codegen::ErrorResponse, error::Error};
+    /// #[derive(Debug, ErrorResponse)]
+    /// // default attribute for all variants.
+    /// #[response(body = json)]
+    /// enum MyError {
+    ///     #[response(status = NOT_FOUND)]
+    ///     NotFound,
+    ///     #[response(status = 400, body = text)]
+    ///     BadInput(String),
+    ///     // 500 status code with json body.
+    ///     Internal { reason: String },
+    /// }
+    ///
+    /// impl fmt::Display for MyError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         match self {
+    ///             Self::NotFound => f.write_str("not found"),
+    ///             Self::BadInput(input) => write!(f, "bad input: {input}"),
+    ///             Self::Internal { reason } => write!(f, "internal error: {reason}"),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for MyError {}
+    ///
+    /// async fn handler() -> Result<&'static str, Error> {
+    ///     Err(MyError::NotFound.into())
+    /// }
+    /// ```
+    pub use xitca_codegen::ErrorResponse;
+
+    /// Derive macro for [ApiSchema](crate::schema::ApiSchema) trait. doc comments are used as description of schema
+    /// and `#[schema(example = "<json>", description = "<description>", rename = "<name>")]` attribute can be
+    /// used for adding extra metadata.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use xitca_web::{codegen::ApiSchema, schema::ApiSchema as _};
+    /// /// a registered user.
+    /// #[derive(ApiSchema)]
+    /// struct User {
+    ///     /// name of user.
+    ///     #[schema(example = "\"foo\"")]
+    ///     name: String,
+    ///     role: Role,
+    ///     nickname: Option<String>,
+    /// }
+    ///
+    /// #[derive(ApiSchema)]
+    /// enum Role {
+    ///     Admin,
+    ///     #[schema(rename = "member")]
+    ///     Member,
+    /// }
+    ///
+    /// let schema = User::schema().to_string();
+    /// assert!(schema.starts_with(r#"{"type":"object","description":"a registered user.""#));
+    /// ```
+    pub use xitca_codegen::ApiSchema;
+
+    /// Attribute macro for async main function. function is run by a single threaded async runtime and it's
+    /// the preferred runtime configuration as [HttpServer](crate::HttpServer) runs every worker in it's own thread.
+    ///
+    /// `#[main(log = <level>)]` attribute can be used for initializing global tracing subscriber with given level.
+    /// it requires `logger` feature.
+    ///
+    /// # Example:
+    /// ```rust,no_run
+    /// # use xitca_web::{handler::handler_service, route::get, App};
+    /// #[xitca_web::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     App::new()
+    ///         .at("/", get(handler_service(|| async { "hello,world!" })))
+    ///         .serve()
+    ///         .bind("localhost:8080")?
+    ///         .run()
+    ///         .await
+    /// }
+    /// ```
+    pub use xitca_codegen::main;
+
+    /// Attribute macro for async test function. it accepts the same attributes as [macro@main].
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use xitca_web::{handler::handler_service, http::StatusCode, route::get, test::TestApp, App};
+    /// #[xitca_web::test]
+    /// async fn hello() {
+    ///     let app = TestApp::new(App::new().at("/", get(handler_service(|| async { "hello,world!" }))).finish()).await;
+    ///     let res = app.get("/").await.unwrap();
+    ///     assert_eq!(res.status(), StatusCode::OK);
+    /// }
+    /// ```
+    pub use xitca_codegen::test;
+
     #[doc(hidden)]
     /// a hidden module for macro to access public types that are not framework user facing.
     pub mod __private {
         pub use xitca_http::util::service::router::{IntoObject, RouteObject, RouterMapErr, TypedRoute};
+
+        pub use crate::app::TypedRoutes;
+
+        pub use crate::error::derive::{ErrorBody, error_response};
+
+        pub use tokio;
+
+        #[cfg(feature = "logger")]
+        pub use crate::middleware::logger::init_log;
+        #[cfg(feature = "logger")]
+        pub use tracing::Level;
     }
 }
 
@@ -359,6 +557,8 @@ pub mod route {
 
 pub use app::{App, AppObject, NestApp};
 pub use body::BodyStream;
+#[cfg(feature = "codegen")]
+pub use codegen::{main, test};
 pub use context::WebContext;
 #[cfg(feature = "__server")]
 pub use server::HttpServer;
@@ -0,0 +1,378 @@
+//! integrity digest (RFC 9530) middleware.
+
+use core::fmt;
+
+use std::error;
+
+use crate::{
+    error::{error_from_service, forward_blank_bad_request},
+    service::Service,
+};
+
+/// digest algorithms supported by [ContentDigest] middleware.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    const fn key(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha-256",
+            Self::Sha512 => "sha-512",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "sha-256" => Some(Self::Sha256),
+            "sha-512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    fn digest(&self, bytes: &[u8]) -> Vec<u8> {
+        use sha2::Digest;
+
+        match self {
+            Self::Sha256 => sha2::Sha256::digest(bytes).to_vec(),
+            Self::Sha512 => sha2::Sha512::digest(bytes).to_vec(),
+        }
+    }
+}
+
+/// middleware validating `Content-Digest` and `Repr-Digest` headers of request against it's body and
+/// optionally computing `Content-Digest` header for response with buffered body.
+///
+/// Request carrying digest header(s) is buffered and every `sha-256` and `sha-512` digest is checked.
+/// Digest of other algorithms is ignored. Request with mismatched or malformed digest is rejected
+/// with 400 Bad Request status code.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_web::{
+/// #   handler::handler_service,
+/// #   middleware::content_digest::{ContentDigest, DigestAlgorithm},
+/// #   route::post,
+/// #   App, WebContext
+/// # };
+/// App::new()
+///     .at("/ingest", post(handler_service(|body: String| async move { body })))
+///     # .at("/infer", handler_service(|_: &WebContext<'_>| async{ "infer type" }))
+///     .enclosed(
+///         ContentDigest::new()
+///             // reject request without digest.
+///             .require(true)
+///             // add sha-256 digest to response.
+///             .response_digest(DigestAlgorithm::Sha256)
+///     );
+/// ```
+#[derive(Clone, Copy)]
+pub struct ContentDigest {
+    require: bool,
+    response_digest: Option<DigestAlgorithm>,
+    request_body_size: usize,
+}
+
+impl Default for ContentDigest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContentDigest {
+    pub const fn new() -> Self {
+        Self {
+            require: false,
+            response_digest: None,
+            request_body_size: 1024 * 1024 * 4,
+        }
+    }
+
+    /// reject request without digest header of supported algorithm.
+    ///
+    /// # Default
+    /// false. request without digest header is passed through without buffering.
+    pub fn require(mut self, require: bool) -> Self {
+        self.require = require;
+        self
+    }
+
+    /// compute `Content-Digest` header with given algorithm for response with buffered body.
+    /// response with streaming body or existing `Content-Digest` header is not touched.
+    ///
+    /// # Default
+    /// no digest is computed for response.
+    pub fn response_digest(mut self, alg: DigestAlgorithm) -> Self {
+        self.response_digest = Some(alg);
+        self
+    }
+
+    /// set max size in byte unit of request body can be buffered for validation.
+    ///
+    /// # Default
+    /// 4 MiB
+    pub fn set_request_body_max_size(mut self, size: usize) -> Self {
+        self.request_body_size = size;
+        self
+    }
+}
+
+impl<S, E> Service<Result<S, E>> for ContentDigest {
+    type Response = service::ContentDigestService<S>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| service::ContentDigestService { service, config: *self })
+    }
+}
+
+/// error type of rejected request by [ContentDigest] middleware.
+#[derive(Debug)]
+pub enum DigestError {
+    /// request does not carry digest header of supported algorithm.
+    Missing,
+    /// digest header is malformed.
+    Malformed,
+    /// digest does not match request body.
+    Mismatch(DigestAlgorithm),
+}
+
+impl fmt::Display for DigestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => f.write_str("request digest is missing"),
+            Self::Malformed => f.write_str("request digest header is malformed"),
+            Self::Mismatch(alg) => write!(f, "request {} digest does not match body", alg.key()),
+        }
+    }
+}
+
+impl error::Error for DigestError {}
+
+error_from_service!(DigestError);
+forward_blank_bad_request!(DigestError);
+
+mod service {
+    use core::{future::poll_fn, pin::pin};
+
+    use base64::{Engine, engine::general_purpose::STANDARD};
+
+    use crate::{
+        body::{BodyStream, ResponseBody},
+        bytes::{Bytes, BytesMut},
+        context::WebContext,
+        error::{BodyOverFlow, Error},
+        http::{
+            WebResponse,
+            header::{HeaderMap, HeaderName, HeaderValue},
+        },
+        service::ready::ReadyService,
+    };
+
+    use super::*;
+
+    const CONTENT_DIGEST: HeaderName = HeaderName::from_static("content-digest");
+    const REPR_DIGEST: HeaderName = HeaderName::from_static("repr-digest");
+
+    pub struct ContentDigestService<S> {
+        pub(super) service: S,
+        pub(super) config: ContentDigest,
+    }
+
+    impl<'r, C, B, S, ResB> Service<WebContext<'r, C, B>> for ContentDigestService<S>
+    where
+        B: BodyStream + Default + From<Bytes>,
+        S: for<'r2> Service<WebContext<'r2, C, B>, Response = WebResponse<ResponseBody<ResB>>, Error = Error>,
+    {
+        type Response = WebResponse<ResponseBody<ResB>>;
+        type Error = Error;
+
+        async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+            let digests = parse_digests(ctx.req().headers())?;
+
+            if digests.is_empty() {
+                if self.config.require {
+                    return Err(Error::from(DigestError::Missing));
+                }
+            } else {
+                let body = collect(ctx.take_body_ref(), self.config.request_body_size).await?;
+
+                for (alg, digest) in digests {
+                    if alg.digest(&body) != digest {
+                        return Err(Error::from(DigestError::Mismatch(alg)));
+                    }
+                }
+
+                *ctx.body_borrow_mut() = B::from(body);
+            }
+
+            let mut res = self.service.call(ctx).await?;
+
+            let digest = self
+                .config
+                .response_digest
+                .filter(|_| !res.headers().contains_key(CONTENT_DIGEST))
+                .and_then(|alg| {
+                    let bytes = res.body().as_bytes()?;
+                    Some(format!("{}=:{}:", alg.key(), STANDARD.encode(alg.digest(bytes))))
+                });
+
+            if let Some(digest) = digest {
+                let value = HeaderValue::try_from(digest).expect("base64 encoded digest must be valid header value");
+                res.headers_mut().insert(CONTENT_DIGEST, value);
+            }
+
+            Ok(res)
+        }
+    }
+
+    impl<S> ReadyService for ContentDigestService<S>
+    where
+        S: ReadyService,
+    {
+        type Ready = S::Ready;
+
+        #[inline]
+        async fn ready(&self) -> Self::Ready {
+            self.service.ready().await
+        }
+    }
+
+    // collect digests of supported algorithms from Content-Digest and Repr-Digest headers.
+    // for request without range the content and representation data are the same bytes.
+    fn parse_digests(headers: &HeaderMap) -> Result<Vec<(DigestAlgorithm, Vec<u8>)>, DigestError> {
+        let mut digests = Vec::new();
+
+        for value in headers
+            .get_all(CONTENT_DIGEST)
+            .iter()
+            .chain(headers.get_all(REPR_DIGEST))
+        {
+            let value = value.to_str().map_err(|_| DigestError::Malformed)?;
+
+            for member in value.split(',') {
+                let (key, digest) = member.trim().split_once('=').ok_or(DigestError::Malformed)?;
+                let Some(alg) = DigestAlgorithm::from_key(key) else {
+                    continue;
+                };
+                let digest = digest
+                    .strip_prefix(':')
+                    .and_then(|d| d.strip_suffix(':'))
+                    .and_then(|d| STANDARD.decode(d).ok())
+                    .ok_or(DigestError::Malformed)?;
+                digests.push((alg, digest));
+            }
+        }
+
+        Ok(digests)
+    }
+
+    async fn collect<B>(body: B, limit: usize) -> Result<Bytes, Error>
+    where
+        B: BodyStream,
+    {
+        let mut body = pin!(body);
+        let mut buf = BytesMut::new();
+
+        while let Some(chunk) = poll_fn(|cx| body.as_mut().poll_next(cx)).await {
+            let chunk = chunk.map_err(Into::into)?;
+            buf.extend_from_slice(chunk.as_ref());
+            if buf.len() > limit {
+                return Err(Error::from(BodyOverFlow { limit }));
+            }
+        }
+
+        Ok(buf.freeze())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{
+        App,
+        handler::handler_service,
+        http::{StatusCode, WebRequest},
+        test::collect_string_body,
+    };
+
+    use super::*;
+
+    async fn echo(body: String) -> String {
+        body
+    }
+
+    fn req(body: &'static str, digest: Option<String>) -> WebRequest {
+        let mut req = WebRequest::default().map(|ext| ext.map_body(|_: ()| body.as_bytes().into()));
+        if let Some(digest) = digest {
+            req.headers_mut().insert("content-digest", digest.parse().unwrap());
+        }
+        req
+    }
+
+    #[test]
+    fn digest() {
+        let service = App::new()
+            .at("/", handler_service(echo))
+            .enclosed(
+                ContentDigest::new()
+                    .require(true)
+                    .response_digest(DigestAlgorithm::Sha256),
+            )
+            .finish()
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let sha256 = |b: &str| {
+            format!(
+                "sha-256=:{}:",
+                STANDARD.encode(DigestAlgorithm::Sha256.digest(b.as_bytes()))
+            )
+        };
+        let sha512 = |b: &str| {
+            format!(
+                "sha-512=:{}:",
+                STANDARD.encode(DigestAlgorithm::Sha512.digest(b.as_bytes()))
+            )
+        };
+
+        let res = service
+            .call(req("hello", Some(format!("{}, {}", sha256("hello"), sha512("hello")))))
+            .now_or_panic()
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get("content-digest").unwrap().to_str().unwrap(),
+            sha256("hello")
+        );
+        let body = collect_string_body(res.into_body()).now_or_panic().unwrap();
+        assert_eq!(body, "hello");
+
+        let res = service
+            .call(req("hello", Some(sha512("world"))))
+            .now_or_panic()
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = service
+            .call(req("hello", Some(String::from("sha-256=hello"))))
+            .now_or_panic()
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = service.call(req("hello", None)).now_or_panic().unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        // unsupported algorithm is ignored and request is treated as without digest.
+        let res = service
+            .call(req("hello", Some(String::from("md5=:XUFAKrxLKna5cZ2REBfFkg==:"))))
+            .now_or_panic()
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
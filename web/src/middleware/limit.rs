@@ -121,11 +121,11 @@ impl<B: Default> Default for LimitBody<B> {
 }
 
 impl<B> LimitBody<B> {
-    const fn new(body: B, limit: usize) -> Self {
+    pub(crate) const fn new(body: B, limit: usize) -> Self {
         Self { limit, record: 0, body }
     }
 
-    fn into_inner(self) -> B {
+    pub(crate) fn into_inner(self) -> B {
         self.body
     }
 }
@@ -34,10 +34,7 @@ impl Logger {
     /// construct a new logger middleware builder with given [Level] of verbosity it generate and captures.
     /// would try to initialize global trace dispatcher.
     pub fn with_level(level: Level) -> Self {
-        if let Err(e) = tracing_subscriber::fmt().with_max_level(level).try_init() {
-            // the most likely case is trace dispatcher has already been set by user. log the warning and move on.
-            warn!("failed to initialize global trace dispatcher: {}", e);
-        }
+        init_log(level);
 
         Self {
             logger: middleware::Logger::with_level(level),
@@ -45,6 +42,14 @@ impl Logger {
     }
 }
 
+/// try to initialize global trace dispatcher with given [Level] of verbosity.
+pub fn init_log(level: Level) {
+    if let Err(e) = tracing_subscriber::fmt().with_max_level(level).try_init() {
+        // the most likely case is trace dispatcher has already been set by user. log the warning and move on.
+        warn!("failed to initialize global trace dispatcher: {}", e);
+    }
+}
+
 impl<Arg> Service<Arg> for Logger
 where
     middleware::Logger: Service<Arg>,
@@ -295,11 +295,19 @@
 
 #[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
 pub mod compress;
+#[cfg(feature = "content-digest")]
+pub mod content_digest;
 #[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
 pub mod decompress;
 #[cfg(feature = "rate-limit")]
 pub mod rate_limit;
 #[cfg(not(target_family = "wasm"))]
+pub mod server_timing;
+#[cfg(feature = "proxy")]
+pub mod shadow;
+#[cfg(feature = "signature")]
+pub mod signature;
+#[cfg(not(target_family = "wasm"))]
 pub mod sync;
 #[cfg(feature = "tower-http-compat")]
 pub mod tower_http_compat;
@@ -308,7 +316,7 @@ pub mod eraser;
 pub mod limit;
 
 #[cfg(feature = "logger")]
-mod logger;
+pub(crate) mod logger;
 #[cfg(feature = "logger")]
 pub use logger::Logger;
 
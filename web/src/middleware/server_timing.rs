@@ -0,0 +1,273 @@
+//! `Server-Timing` response header middleware.
+
+use core::{fmt::Write, time::Duration};
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use crate::service::Service;
+
+/// middleware emitting `Server-Timing` header on response with metrics recorded through [Timings].
+///
+/// A [Timings] handle is inserted into request extensions before calling inner services. Handlers and
+/// middlewares can obtain it and record named segments of request processing.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_web::{
+/// #   handler::{extension::ExtensionRef, handler_service},
+/// #   middleware::server_timing::{ServerTiming, Timings},
+/// #   App, WebContext
+/// # };
+/// async fn handler(ExtensionRef(timings): ExtensionRef<'_, Timings>) -> &'static str {
+///     {
+///         // segment is recorded when timer guard goes out of scope.
+///         let _timer = timings.start("db").desc("user lookup");
+///         // query database.
+///     }
+///     let _timer = timings.start("render");
+///     "hello,world!"
+/// }
+///
+/// App::new()
+///     .at("/", handler_service(handler))
+///     # .at("/infer", handler_service(|_: &WebContext<'_>| async{ "infer type" }))
+///     .enclosed(ServerTiming::new());
+/// ```
+#[derive(Clone, Copy)]
+pub struct ServerTiming {
+    total: bool,
+}
+
+impl Default for ServerTiming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServerTiming {
+    pub const fn new() -> Self {
+        Self { total: true }
+    }
+
+    /// set if a `total` metric measuring the duration of inner services should be emitted.
+    ///
+    /// # Default
+    /// true
+    pub fn total(mut self, total: bool) -> Self {
+        self.total = total;
+        self
+    }
+}
+
+impl<S, E> Service<Result<S, E>> for ServerTiming {
+    type Response = service::ServerTimingService<S>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| service::ServerTimingService { service, config: *self })
+    }
+}
+
+/// handle for recording timing metrics of current request.
+#[derive(Clone, Default)]
+pub struct Timings(Arc<Mutex<Vec<Metric>>>);
+
+struct Metric {
+    name: String,
+    desc: Option<String>,
+    dur: Option<Duration>,
+}
+
+impl Timings {
+    /// record a named segment with given duration.
+    pub fn record(&self, name: impl Into<String>, dur: Duration) {
+        self.push(name.into(), None, Some(dur));
+    }
+
+    /// record a named segment with description and given duration.
+    pub fn record_with_desc(&self, name: impl Into<String>, desc: impl Into<String>, dur: Duration) {
+        self.push(name.into(), Some(desc.into()), Some(dur));
+    }
+
+    /// record a named marker without duration.
+    pub fn mark(&self, name: impl Into<String>) {
+        self.push(name.into(), None, None);
+    }
+
+    /// start a timer of named segment. the segment is recorded when returned [Timer] is dropped or
+    /// [Timer::stop] is called.
+    pub fn start(&self, name: impl Into<String>) -> Timer {
+        Timer {
+            timings: self.clone(),
+            name: name.into(),
+            desc: None,
+            start: Instant::now(),
+        }
+    }
+
+    fn push(&self, name: String, desc: Option<String>, dur: Option<Duration>) {
+        self.0.lock().unwrap().push(Metric { name, desc, dur });
+    }
+
+    fn header_value(&self) -> String {
+        let mut value = String::new();
+        for (idx, metric) in self.0.lock().unwrap().iter().enumerate() {
+            if idx > 0 {
+                value.push_str(", ");
+            }
+            // metric name must be a token. invalid characters are replaced.
+            value.extend(metric.name.chars().map(|c| if is_tchar(c) { c } else { '_' }));
+            if let Some(ref desc) = metric.desc {
+                value.push_str(";desc=\"");
+                for c in desc.chars().filter(|c| matches!(c, ' '..='~')) {
+                    if matches!(c, '"' | '\\') {
+                        value.push('\\');
+                    }
+                    value.push(c);
+                }
+                value.push('"');
+            }
+            if let Some(dur) = metric.dur {
+                let _ = write!(value, ";dur={:.3}", dur.as_secs_f64() * 1000.0);
+            }
+        }
+        value
+    }
+}
+
+/// guard type recording elapsed time of a named segment when dropped.
+pub struct Timer {
+    timings: Timings,
+    name: String,
+    desc: Option<String>,
+    start: Instant,
+}
+
+impl Timer {
+    /// attach description to segment.
+    pub fn desc(mut self, desc: impl Into<String>) -> Self {
+        self.desc = Some(desc.into());
+        self
+    }
+
+    /// stop timer and record segment.
+    pub fn stop(self) {}
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let dur = self.start.elapsed();
+        let name = core::mem::take(&mut self.name);
+        self.timings.push(name, self.desc.take(), Some(dur));
+    }
+}
+
+fn is_tchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+mod service {
+    use crate::{
+        WebContext,
+        http::{
+            WebResponse,
+            header::{HeaderName, HeaderValue},
+        },
+        service::ready::ReadyService,
+    };
+
+    use super::*;
+
+    const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+    pub struct ServerTimingService<S> {
+        pub(super) service: S,
+        pub(super) config: ServerTiming,
+    }
+
+    impl<'r, C, B, S, ResB, Err> Service<WebContext<'r, C, B>> for ServerTimingService<S>
+    where
+        S: for<'r2> Service<WebContext<'r2, C, B>, Response = WebResponse<ResB>, Error = Err>,
+    {
+        type Response = WebResponse<ResB>;
+        type Error = Err;
+
+        async fn call(&self, mut ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+            let timings = Timings::default();
+            ctx.req_mut().extensions_mut().insert(timings.clone());
+
+            let start = Instant::now();
+            let mut res = self.service.call(ctx).await?;
+
+            if self.config.total {
+                timings.record("total", start.elapsed());
+            }
+
+            let value = timings.header_value();
+            if !value.is_empty() {
+                // metric names and descriptions are sanitized to visible ascii.
+                let value = HeaderValue::try_from(value).expect("server timing must be valid header value");
+                res.headers_mut().append(SERVER_TIMING, value);
+            }
+
+            Ok(res)
+        }
+    }
+
+    impl<S> ReadyService for ServerTimingService<S>
+    where
+        S: ReadyService,
+    {
+        type Ready = S::Ready;
+
+        #[inline]
+        async fn ready(&self) -> Self::Ready {
+            self.service.ready().await
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{
+        App,
+        handler::{extension::ExtensionRef, handler_service},
+        http::WebRequest,
+    };
+
+    use super::*;
+
+    async fn handler(ExtensionRef(timings): ExtensionRef<'_, Timings>) -> &'static str {
+        timings.record_with_desc("db", "a \"quoted\" query", Duration::from_millis(5));
+        timings.start("render").stop();
+        timings.mark("cache hit");
+        "996"
+    }
+
+    #[test]
+    fn server_timing() {
+        let res = App::new()
+            .at("/", handler_service(handler))
+            .enclosed(ServerTiming::new())
+            .finish()
+            .call(())
+            .now_or_panic()
+            .unwrap()
+            .call(WebRequest::default())
+            .now_or_panic()
+            .unwrap();
+
+        let value = res.headers().get("server-timing").unwrap().to_str().unwrap();
+        let mut metrics = value.split(", ");
+        assert_eq!(metrics.next(), Some(r#"db;desc="a \"quoted\" query";dur=5.000"#));
+        assert!(metrics.next().unwrap().starts_with("render;dur="));
+        assert_eq!(metrics.next(), Some("cache_hit"));
+        assert!(metrics.next().unwrap().starts_with("total;dur="));
+        assert!(metrics.next().is_none());
+    }
+}
@@ -0,0 +1,399 @@
+//! traffic shadowing middleware mirroring requests to secondary upstream.
+
+use std::rc::Rc;
+
+use xitca_client::Client;
+
+use crate::{service::Service, service::proxy::Upstream};
+
+/// builder type for traffic shadowing middleware.
+///
+/// a sample of incoming requests is mirrored to secondary upstream with [xitca_client::Client] in a
+/// detached task. response of the mirrored request is discarded and failure of it does not affect the
+/// primary request which is always passed to inner services. Mirrored request carries the method, path
+/// and headers of original request with hop-by-hop headers removed and `Forwarded`, `X-Forwarded-For`,
+/// `X-Forwarded-Host` and `X-Forwarded-Proto` headers set.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_web::{
+/// #     handler::handler_service,
+/// #     http::Uri,
+/// #     middleware::shadow::Shadow,
+/// #     App, WebContext
+/// # };
+/// App::new()
+///     .at("/", handler_service(|| async { "hello,world!" }))
+///     # .at("/infer", handler_service(|_: &WebContext<'_>| async{ "infer type" }))
+///     .enclosed(
+///         Shadow::new(Uri::from_static("http://127.0.0.1:8081"))
+///             // mirror 10% of requests.
+///             .sample_rate(0.1)
+///             // mirror request body up to 64KiB.
+///             .mirror_body(64 * 1024)
+///     );
+/// ```
+///
+/// # Default
+/// - each worker thread uses it's own [Client] constructed by [Client::new]. see [Shadow::client] for changing it.
+#[derive(Clone)]
+pub struct Shadow<U> {
+    upstream: U,
+    client: fn() -> Client,
+    sample_rate: f64,
+    body_size: Option<usize>,
+    max_in_flight: usize,
+}
+
+impl<U> Shadow<U>
+where
+    U: Upstream,
+{
+    /// construct a new shadowing middleware builder with given [Upstream] type as secondary upstream.
+    pub fn new(upstream: U) -> Self {
+        Self {
+            upstream,
+            client: Client::new,
+            sample_rate: 1.0,
+            body_size: None,
+            max_in_flight: 64,
+        }
+    }
+
+    /// change constructor of [Client] used for mirroring request.
+    pub fn client(mut self, client: fn() -> Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// set ratio of requests being mirrored. value is clamped to range of `0.0..=1.0`.
+    ///
+    /// # Default
+    /// 1.0. every request is mirrored.
+    pub fn sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// mirror request body with max size in byte unit. request body is buffered before passing to inner
+    /// services when it's size is known through `Content-Length` header and within given size. Otherwise
+    /// request is mirrored with empty body. When buffering fails request is not mirrored and inner services
+    /// receive the part of body buffered before failure.
+    ///
+    /// # Default
+    /// request body is not mirrored.
+    pub fn mirror_body(mut self, max_size: usize) -> Self {
+        self.body_size = Some(max_size);
+        self
+    }
+
+    /// set max count of mirrored requests in flight per worker thread. request would not be mirrored
+    /// when the limit is reached.
+    ///
+    /// # Default
+    /// 64
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = max;
+        self
+    }
+}
+
+impl<U, S, E> Service<Result<S, E>> for Shadow<U>
+where
+    U: Clone,
+{
+    type Response = service::ShadowService<U, S>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| service::ShadowService {
+            service,
+            upstream: self.upstream.clone(),
+            client: Rc::new((self.client)()),
+            threshold: (self.sample_rate * u64::MAX as f64) as u64,
+            body_size: self.body_size,
+            max_in_flight: self.max_in_flight,
+            in_flight: Rc::new(core::cell::Cell::new(0)),
+            rng: core::cell::Cell::new(seed()),
+        })
+    }
+}
+
+fn seed() -> u64 {
+    use std::hash::{BuildHasher, RandomState};
+    // xorshift state must not be zero.
+    RandomState::new().hash_one(0u8) | 1
+}
+
+mod service {
+    use core::{cell::Cell, future::poll_fn, pin::pin};
+
+    use xitca_http::body::Once;
+
+    use crate::{
+        body::BodyStream,
+        bytes::{Bytes, BytesMut},
+        context::WebContext,
+        error::Error,
+        http::{
+            Request,
+            header::{CONTENT_LENGTH, HeaderValue},
+        },
+        service::{
+            proxy::{forward_headers, remove_hop_headers, upstream_uri},
+            ready::ReadyService,
+        },
+    };
+
+    use super::*;
+
+    pub struct ShadowService<U, S> {
+        pub(super) service: S,
+        pub(super) upstream: U,
+        pub(super) client: Rc<Client>,
+        pub(super) threshold: u64,
+        pub(super) body_size: Option<usize>,
+        pub(super) max_in_flight: usize,
+        pub(super) in_flight: Rc<Cell<usize>>,
+        pub(super) rng: Cell<u64>,
+    }
+
+    impl<'r, C, B, U, S, Res> Service<WebContext<'r, C, B>> for ShadowService<U, S>
+    where
+        U: Upstream,
+        B: BodyStream + Default + From<Bytes>,
+        S: for<'r2> Service<WebContext<'r2, C, B>, Response = Res, Error = Error>,
+    {
+        type Response = Res;
+        type Error = Error;
+
+        async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+            if self.in_flight.get() < self.max_in_flight && self.sample() {
+                self.mirror(&ctx).await;
+            }
+            self.service.call(ctx).await
+        }
+    }
+
+    impl<U, S> ShadowService<U, S>
+    where
+        U: Upstream,
+    {
+        // xorshift64 random number compared against sample rate threshold.
+        fn sample(&self) -> bool {
+            let mut x = self.rng.get();
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            self.rng.set(x);
+            x <= self.threshold
+        }
+
+        // mirroring is best effort and it's failure is never observed by primary request.
+        async fn mirror<C, B>(&self, ctx: &WebContext<'_, C, B>)
+        where
+            B: BodyStream + Default + From<Bytes>,
+        {
+            let req = ctx.req();
+
+            let Some(uri) = self
+                .upstream
+                .select(req)
+                .and_then(|base| upstream_uri(&base, req.uri()))
+            else {
+                return;
+            };
+
+            let len = req
+                .headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<usize>().ok());
+
+            let body = match (self.body_size, len) {
+                (Some(max), Some(len)) if len > 0 && len <= max => {
+                    let (body, complete) = match collect(ctx.take_body_ref(), max).await {
+                        Ok(body) => (body, true),
+                        Err(body) => (body, false),
+                    };
+                    // buffered body is passed to inner services regardless of outcome of buffering.
+                    *ctx.body_borrow_mut() = B::from(body.clone());
+                    if !complete {
+                        return;
+                    }
+                    body
+                }
+                _ => Bytes::new(),
+            };
+
+            let addr = req.body().socket_addr().ip();
+            let proto = match req.uri().scheme_str() {
+                Some("https") => "https",
+                _ => "http",
+            };
+
+            let mut headers = req.headers().clone();
+            remove_hop_headers(&mut headers);
+            forward_headers(&mut headers, addr, proto);
+            // body is either fully buffered or dropped. content length must reflect the mirrored body.
+            headers.insert(CONTENT_LENGTH, HeaderValue::from(body.len()));
+
+            let mut mirror = Request::new(Once::new(body));
+            *mirror.method_mut() = req.method().clone();
+            *mirror.uri_mut() = uri;
+            *mirror.headers_mut() = headers;
+
+            self.spawn(mirror);
+        }
+
+        fn spawn(&self, req: Request<Once<Bytes>>) {
+            let client = self.client.clone();
+            let in_flight = self.in_flight.clone();
+            in_flight.set(in_flight.get() + 1);
+
+            // outcome of mirrored request is ignored. response body is drained so connection can be reused.
+            tokio::task::spawn_local(async move {
+                if let Ok(res) = client.request(req).send().await {
+                    let _ = res.body().await;
+                }
+                in_flight.set(in_flight.get() - 1);
+            });
+        }
+    }
+
+    // buffer body up to limit. on failure the part of body buffered so far is returned as error.
+    async fn collect<B>(body: B, limit: usize) -> Result<Bytes, Bytes>
+    where
+        B: BodyStream,
+    {
+        let mut body = pin!(body);
+        let mut buf = BytesMut::new();
+
+        while let Some(chunk) = poll_fn(|cx| body.as_mut().poll_next(cx)).await {
+            let Ok(chunk) = chunk else {
+                return Err(buf.freeze());
+            };
+            buf.extend_from_slice(chunk.as_ref());
+            if buf.len() > limit {
+                return Err(buf.freeze());
+            }
+        }
+
+        Ok(buf.freeze())
+    }
+
+    impl<U, S> ReadyService for ShadowService<U, S>
+    where
+        S: ReadyService,
+    {
+        type Ready = S::Ready;
+
+        #[inline]
+        async fn ready(&self) -> Self::Ready {
+            self.service.ready().await
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use crate::{
+        App,
+        handler::handler_service,
+        http::{Uri, WebRequest, header::CONTENT_LENGTH},
+        test::collect_string_body,
+    };
+
+    use super::*;
+
+    async fn handler(body: String) -> String {
+        body
+    }
+
+    #[tokio::test]
+    async fn shadow() {
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                let addr = listener.local_addr().unwrap();
+
+                let (tx, rx) = mpsc::channel();
+
+                let upstream = thread::spawn(move || {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut buf = Vec::new();
+                    let mut chunk = [0; 1024];
+                    while !buf.ends_with(b"hello") {
+                        let n = stream.read(&mut chunk).unwrap();
+                        buf.extend_from_slice(&chunk[..n]);
+                    }
+                    stream
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 6\r\n\r\nshadow")
+                        .unwrap();
+                    tx.send(String::from_utf8(buf).unwrap()).unwrap();
+                });
+
+                let uri = Uri::try_from(format!("http://{addr}")).unwrap();
+
+                let service = App::new()
+                    .at("/foo", handler_service(handler))
+                    .enclosed(Shadow::new(uri).mirror_body(1024))
+                    .finish()
+                    .call(())
+                    .await
+                    .unwrap();
+
+                let mut req = WebRequest::default().map(|ext| ext.map_body(|_: ()| b"hello".as_slice().into()));
+                *req.uri_mut() = Uri::from_static("/foo?bar=1");
+                req.headers_mut().insert(CONTENT_LENGTH, 5.into());
+                req.headers_mut().insert("x-custom", "1".parse().unwrap());
+
+                let res = service.call(req).await.unwrap();
+                let body = collect_string_body(res.into_body()).await.unwrap();
+                assert_eq!(body, "hello");
+
+                let mirrored = tokio::task::spawn_blocking(move || rx.recv().unwrap()).await.unwrap();
+                assert!(mirrored.starts_with("GET /foo?bar=1 HTTP/1.1\r\n"));
+                assert!(mirrored.contains("x-custom: 1\r\n"));
+                assert!(mirrored.contains("content-length: 5\r\n"));
+                assert!(mirrored.ends_with("\r\n\r\nhello"));
+
+                upstream.join().unwrap();
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn shadow_body_failure() {
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                // upstream is never contacted when buffering body fails.
+                let uri = Uri::from_static("http://127.0.0.1:1");
+
+                let service = App::new()
+                    .at("/foo", handler_service(handler))
+                    .enclosed(Shadow::new(uri).mirror_body(8))
+                    .finish()
+                    .call(())
+                    .await
+                    .unwrap();
+
+                // body larger than it's content length overflows buffer limit.
+                let mut req = WebRequest::default().map(|ext| ext.map_body(|_: ()| b"hello,world".as_slice().into()));
+                *req.uri_mut() = Uri::from_static("/foo");
+                req.headers_mut().insert(CONTENT_LENGTH, 5.into());
+
+                let res = service.call(req).await.unwrap();
+                let body = collect_string_body(res.into_body()).await.unwrap();
+                assert_eq!(body, "hello,world");
+            })
+            .await;
+    }
+}
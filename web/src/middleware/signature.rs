@@ -0,0 +1,166 @@
+//! http message signature (RFC 9421) verifying middleware.
+
+pub use http_signature::{
+    Algorithm, Error as SignatureError, KeyResolver, SignatureParams, Verified, Verifier, VerifyingKey,
+};
+
+use crate::service::Service;
+
+/// builder for http message signature verifying middleware.
+///
+/// request without valid signature is rejected with 401 Unauthorized status code. On success
+/// [Verified] is inserted into request extensions and can be extracted by handlers.
+///
+/// # Examples
+/// ```rust
+/// # use std::time::Duration;
+/// # use xitca_web::{
+/// #   handler::{extension::ExtensionRef, handler_service},
+/// #   middleware::signature::{Verified, Verifier, VerifyingKey, VerifySignature},
+/// #   route::post,
+/// #   App, WebContext
+/// # };
+/// let verifier = Verifier::new(|key_id: &str| match key_id {
+///         "partner-1" => Some(VerifyingKey::hmac_sha256(b"shared secret")),
+///         _ => None,
+///     })
+///     .required_components(["@method", "@path", "content-digest"])
+///     .max_age(Some(Duration::from_secs(60)));
+///
+/// App::new()
+///     .at("/webhook", post(handler_service(webhook)))
+///     # .at("/infer", handler_service(|_: &WebContext<'_>| async{ "infer type" }))
+///     .enclosed(VerifySignature::new(verifier));
+///
+/// async fn webhook(ExtensionRef(verified): ExtensionRef<'_, Verified>) -> String {
+///     format!("hello {}", verified.params().key_id().unwrap())
+/// }
+/// ```
+pub struct VerifySignature(Verifier);
+
+impl VerifySignature {
+    /// construct middleware with given verifier.
+    pub fn new(verifier: Verifier) -> Self {
+        Self(verifier)
+    }
+}
+
+impl<S, E> Service<Result<S, E>> for VerifySignature {
+    type Response = service::VerifySignatureService<S>;
+    type Error = E;
+
+    async fn call(&self, res: Result<S, E>) -> Result<Self::Response, Self::Error> {
+        res.map(|service| service::VerifySignatureService {
+            service,
+            verifier: self.0.clone(),
+        })
+    }
+}
+
+mod service {
+    use core::convert::Infallible;
+
+    use http_signature::{Error as SignatureError, Verifier};
+
+    use crate::{
+        WebContext,
+        error::Error,
+        http::{StatusCode, WebResponse},
+        service::{Service, ready::ReadyService},
+    };
+
+    pub struct VerifySignatureService<S> {
+        pub(super) service: S,
+        pub(super) verifier: Verifier,
+    }
+
+    impl<'r, C, B, S, ResB> Service<WebContext<'r, C, B>> for VerifySignatureService<S>
+    where
+        S: for<'r2> Service<WebContext<'r2, C, B>, Response = WebResponse<ResB>, Error = Error>,
+    {
+        type Response = WebResponse<ResB>;
+        type Error = Error;
+
+        async fn call(&self, mut ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+            let verified = self.verifier.verify(ctx.req()).map_err(Error::from_service)?;
+            ctx.req_mut().extensions_mut().insert(verified);
+            self.service.call(ctx).await
+        }
+    }
+
+    impl<'r, C, B> Service<WebContext<'r, C, B>> for SignatureError {
+        type Response = WebResponse;
+        type Error = Infallible;
+
+        async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+            StatusCode::UNAUTHORIZED.call(ctx).await
+        }
+    }
+
+    impl<S> ReadyService for VerifySignatureService<S>
+    where
+        S: ReadyService,
+    {
+        type Ready = S::Ready;
+
+        #[inline]
+        async fn ready(&self) -> Self::Ready {
+            self.service.ready().await
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use xitca_unsafe_collection::futures::NowOrPanic;
+
+    use crate::{
+        App,
+        handler::{extension::ExtensionRef, handler_service},
+        http::{Request, StatusCode, Uri},
+        test::collect_string_body,
+    };
+
+    use super::*;
+
+    async fn key_id(ExtensionRef(v): ExtensionRef<'_, Verified>) -> String {
+        v.params().key_id().unwrap().to_owned()
+    }
+
+    #[test]
+    fn verify() {
+        let secret = b"secret";
+
+        let verifier = Verifier::new(|key_id: &str| (key_id == "key").then(|| VerifyingKey::hmac_sha256(b"secret")))
+            .required_components(["@method", "@path"]);
+
+        let service = App::new()
+            .at("/", handler_service(key_id))
+            .enclosed(VerifySignature::new(verifier))
+            .finish()
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let req = || {
+            let mut req = Request::default();
+            *req.uri_mut() = Uri::from_static("/");
+            req.headers_mut().insert("host", "localhost".parse().unwrap());
+            req
+        };
+
+        let res = service.call(req()).now_or_panic().unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let mut req = req();
+        http_signature::Signer::new("key", http_signature::SigningKey::hmac_sha256(secret))
+            .components(["@method", "@authority", "@path"])
+            .sign(&mut req)
+            .unwrap();
+
+        let res = service.call(req).now_or_panic().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = collect_string_body(res.into_body()).now_or_panic().unwrap();
+        assert_eq!(body, "key");
+    }
+}
@@ -18,9 +18,8 @@ pub struct TowerHttpCompat<L>(L);
 impl<L> TowerHttpCompat<L> {
     /// Construct a new xitca-web middleware from tower-http layer type.
     ///
-    /// # Limitation:
-    /// tower::Service::poll_ready implementation is ignored by TowerHttpCompat.
-    /// if a tower Service is sensitive to poll_ready implementation it should not be used.
+    /// `tower::Service::poll_ready` of the layered service is awaited before every call. Services enclosed
+    /// by the layer are always ready as xitca-service has no readiness notion per request.
     ///
     /// # Example:
     /// ```rust
@@ -0,0 +1,367 @@
+//! JSON Schema metadata of request and response types.
+//!
+//! [ApiSchema] trait describes the schema of a type and [Schema] can be rendered as JSON Schema document through
+//! it's [Display](core::fmt::Display) impl. the trait can be implemented by `codegen::ApiSchema` derive macro
+//! and the output can be used for generating OpenAPI document.
+//!
+//! # Example
+//! ```rust
+//! use xitca_web::schema::{ApiSchema, Field, Kind, Schema};
+//!
+//! struct User {
+//!     name: String,
+//!     age: Option<u8>,
+//! }
+//!
+//! impl ApiSchema for User {
+//!     fn schema() -> Schema {
+//!         Schema::new(Kind::Object(vec![
+//!             Field::new("name", String::schema()),
+//!             Field::new("age", <Option<u8>>::schema()),
+//!         ]))
+//!         .description("a registered user")
+//!     }
+//! }
+//!
+//! assert_eq!(
+//!     User::schema().to_string(),
+//!     r#"{"type":"object","description":"a registered user","properties":{"name":{"type":"string"},"age":{"type":["integer","null"]}},"required":["name"]}"#
+//! );
+//! ```
+
+use core::fmt;
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
+
+use crate::json::write_str;
+
+/// trait for type that can describe it's JSON Schema.
+pub trait ApiSchema {
+    fn schema() -> Schema;
+}
+
+/// JSON Schema of a type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    pub kind: Kind,
+    /// schema accepts null value in addition to it's kind.
+    pub nullable: bool,
+    pub description: Option<Cow<'static, str>>,
+    /// example value in raw JSON format.
+    pub example: Option<Cow<'static, str>>,
+}
+
+/// kind of [Schema].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind {
+    /// accept any value.
+    Any,
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    /// array with items of given schema.
+    Array(Box<Schema>),
+    /// object with given fields.
+    Object(Vec<Field>),
+    /// object with arbitrary keys and values of given schema.
+    Map(Box<Schema>),
+    /// string with given variants.
+    Enum(Vec<Cow<'static, str>>),
+    /// value matches one of given schemas.
+    OneOf(Vec<Schema>),
+}
+
+/// field of [Kind::Object].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: Cow<'static, str>,
+    pub schema: Schema,
+    pub required: bool,
+}
+
+impl Schema {
+    pub const fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            nullable: false,
+            description: None,
+            example: None,
+        }
+    }
+
+    /// mark schema as nullable.
+    pub fn nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+
+    /// set description of schema.
+    pub fn description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// set example of schema. example must be in valid JSON format.
+    pub fn example(mut self, example: impl Into<Cow<'static, str>>) -> Self {
+        self.example = Some(example.into());
+        self
+    }
+}
+
+impl Field {
+    /// construct a field with given name and schema. field is required when it's schema is not nullable.
+    pub fn new(name: impl Into<Cow<'static, str>>, schema: Schema) -> Self {
+        Self {
+            name: name.into(),
+            required: !schema.nullable,
+            schema,
+        }
+    }
+}
+
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = match self.kind {
+            Kind::Any | Kind::OneOf(_) => None,
+            Kind::Null => Some("null"),
+            Kind::Boolean => Some("boolean"),
+            Kind::Integer => Some("integer"),
+            Kind::Number => Some("number"),
+            Kind::String | Kind::Enum(_) => Some("string"),
+            Kind::Array(_) => Some("array"),
+            Kind::Object(_) | Kind::Map(_) => Some("object"),
+        };
+
+        f.write_str("{")?;
+
+        let mut comma = Comma(false);
+
+        match ty {
+            Some(ty) if self.nullable && ty != "null" => write!(f, "{}\"type\":[\"{ty}\",\"null\"]", comma.next())?,
+            Some(ty) => write!(f, "{}\"type\":\"{ty}\"", comma.next())?,
+            None => {}
+        }
+
+        if let Some(ref desc) = self.description {
+            write!(f, "{}\"description\":", comma.next())?;
+            write_str(f, desc)?;
+        }
+
+        if let Some(ref example) = self.example {
+            write!(f, "{}\"example\":{example}", comma.next())?;
+        }
+
+        match self.kind {
+            Kind::Array(ref items) => write!(f, "{}\"items\":{items}", comma.next())?,
+            Kind::Map(ref values) => write!(f, "{}\"additionalProperties\":{values}", comma.next())?,
+            Kind::Object(ref fields) => {
+                write!(f, "{}\"properties\":{{", comma.next())?;
+                for (idx, field) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_str(f, &field.name)?;
+                    write!(f, ":{}", field.schema)?;
+                }
+                f.write_str("}")?;
+
+                let mut required = fields.iter().filter(|field| field.required).peekable();
+                if required.peek().is_some() {
+                    write!(f, ",\"required\":[")?;
+                    for (idx, field) in required.enumerate() {
+                        if idx > 0 {
+                            f.write_str(",")?;
+                        }
+                        write_str(f, &field.name)?;
+                    }
+                    f.write_str("]")?;
+                }
+            }
+            Kind::Enum(ref variants) => {
+                write!(f, "{}\"enum\":[", comma.next())?;
+                for (idx, variant) in variants.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_str(f, variant)?;
+                }
+                if self.nullable {
+                    f.write_str(",null")?;
+                }
+                f.write_str("]")?;
+            }
+            Kind::OneOf(ref schemas) => {
+                write!(f, "{}\"oneOf\":[", comma.next())?;
+                for (idx, schema) in schemas.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{schema}")?;
+                }
+                if self.nullable {
+                    f.write_str(",{\"type\":\"null\"}")?;
+                }
+                f.write_str("]")?;
+            }
+            _ => {}
+        }
+
+        f.write_str("}")
+    }
+}
+
+// helper for writing comma between object entries.
+struct Comma(bool);
+
+impl Comma {
+    fn next(&mut self) -> &'static str {
+        if core::mem::replace(&mut self.0, true) { "," } else { "" }
+    }
+}
+
+macro_rules! kind_impl {
+    ($kind: ident, $($ty: ty),*) => {
+        $(
+            impl ApiSchema for $ty {
+                #[inline]
+                fn schema() -> Schema {
+                    Schema::new(Kind::$kind)
+                }
+            }
+        )*
+    };
+}
+
+kind_impl!(Null, ());
+kind_impl!(Boolean, bool);
+kind_impl!(Integer, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+kind_impl!(Number, f32, f64);
+kind_impl!(String, char, str, String);
+
+impl<T> ApiSchema for &T
+where
+    T: ApiSchema + ?Sized,
+{
+    #[inline]
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T> ApiSchema for Cow<'_, T>
+where
+    T: ApiSchema + ToOwned + ?Sized,
+{
+    #[inline]
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T> ApiSchema for Box<T>
+where
+    T: ApiSchema + ?Sized,
+{
+    #[inline]
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T> ApiSchema for Option<T>
+where
+    T: ApiSchema,
+{
+    #[inline]
+    fn schema() -> Schema {
+        T::schema().nullable()
+    }
+}
+
+impl<T> ApiSchema for Vec<T>
+where
+    T: ApiSchema,
+{
+    #[inline]
+    fn schema() -> Schema {
+        Schema::new(Kind::Array(Box::new(T::schema())))
+    }
+}
+
+impl<T> ApiSchema for [T]
+where
+    T: ApiSchema,
+{
+    #[inline]
+    fn schema() -> Schema {
+        Schema::new(Kind::Array(Box::new(T::schema())))
+    }
+}
+
+impl<T, const N: usize> ApiSchema for [T; N]
+where
+    T: ApiSchema,
+{
+    #[inline]
+    fn schema() -> Schema {
+        Schema::new(Kind::Array(Box::new(T::schema())))
+    }
+}
+
+impl<K, V, S> ApiSchema for HashMap<K, V, S>
+where
+    V: ApiSchema,
+{
+    #[inline]
+    fn schema() -> Schema {
+        Schema::new(Kind::Map(Box::new(V::schema())))
+    }
+}
+
+impl<K, V> ApiSchema for BTreeMap<K, V>
+where
+    V: ApiSchema,
+{
+    #[inline]
+    fn schema() -> Schema {
+        Schema::new(Kind::Map(Box::new(V::schema())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render() {
+        assert_eq!(u8::schema().to_string(), r#"{"type":"integer"}"#);
+        assert_eq!(
+            <Vec<Option<String>>>::schema().to_string(),
+            r#"{"type":"array","items":{"type":["string","null"]}}"#
+        );
+        assert_eq!(
+            <BTreeMap<String, bool>>::schema().to_string(),
+            r#"{"type":"object","additionalProperties":{"type":"boolean"}}"#
+        );
+
+        let schema = Schema::new(Kind::Enum(vec!["a".into(), "b".into()]))
+            .nullable()
+            .description("\"quoted\"")
+            .example(r#""a""#);
+        assert_eq!(
+            schema.to_string(),
+            r#"{"type":["string","null"],"description":"\"quoted\"","example":"a","enum":["a","b",null]}"#
+        );
+
+        let schema = Schema::new(Kind::OneOf(vec![u8::schema(), String::schema()]));
+        assert_eq!(
+            schema.to_string(),
+            r#"{"oneOf":[{"type":"integer"},{"type":"string"}]}"#
+        );
+    }
+}
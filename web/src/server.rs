@@ -84,6 +84,36 @@ where
         self
     }
 
+    /// Shutdown server gracefully on every stop signal with given drain timeout.
+    ///
+    /// See [xitca_server::Builder::graceful_signal] for detail.
+    pub fn graceful_signal(mut self, drain_timeout: Duration) -> Self {
+        self.builder = self.builder.graceful_signal(drain_timeout);
+        self
+    }
+
+    /// Callback called once when a stop signal is received and before server starts to shutdown.
+    ///
+    /// See [xitca_server::Builder::on_signal] for detail.
+    pub fn on_signal<F>(mut self, on_signal: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.builder = self.builder.on_signal(on_signal);
+        self
+    }
+
+    /// Callback called on every SIGHUP signal for reloading application state without restarting server.
+    ///
+    /// See [xitca_server::Builder::on_reload] for detail.
+    pub fn on_reload<F>(mut self, on_reload: F) -> Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.builder = self.builder.on_reload(on_reload);
+        self
+    }
+
     pub fn backlog(mut self, num: u32) -> Self {
         self.builder = self.builder.backlog(num);
         self
@@ -17,6 +17,7 @@ pub struct ServeDir<F: AsyncFs = dumb::Dumb> {
 #[cfg(feature = "file")]
 impl ServeDir {
     /// construct a new static file service that serve given relative file path's file based on file name.
+    /// when mounted at a route prefix the prefix is stripped and remaining path is matched against files.
     ///
     /// # Example
     /// ```rust
@@ -57,6 +58,53 @@ where
             inner: _ServeDir::with_fs(path, fs),
         }
     }
+
+    /// file name used for resolving request targeting a directory.
+    ///
+    /// # Default
+    /// disabled. request targeting a directory is responded with 400 status.
+    pub fn index_file(mut self, name: impl Into<String>) -> Self {
+        self.inner.index_file(name);
+        self
+    }
+
+    /// enable html listing of directory entries when request targeting a directory without index file.
+    ///
+    /// # Default
+    /// disabled.
+    pub fn directory_listing(mut self, enable: bool) -> Self {
+        self.inner.directory_listing(enable);
+        self
+    }
+
+    /// prefer precompressed `<file>.br` and `<file>.gz` variants of requested file when they exist and client
+    /// accepts the encoding. br variant is preferred over gz when both are accepted.
+    ///
+    /// # Default
+    /// disabled.
+    pub fn precompressed(mut self, br: bool, gzip: bool) -> Self {
+        self.inner.precompressed_br(br).precompressed_gzip(gzip);
+        self
+    }
+
+    /// file path relative to served directory used as response when requested file can not be found.
+    /// useful for single page application with client side routing.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use xitca_web::{handler::handler_service, service::file::ServeDir, App, WebContext};
+    /// App::new()
+    ///     // request to /app/foo/bar is served with ./dist/index.html when ./dist/foo/bar does not exist.
+    ///     .at("/app", ServeDir::new("dist").index_file("index.html").fallback("index.html"))
+    ///     # .at("/bar", handler_service(|_: &WebContext<'_>| async { "used for inferring types!" }));
+    /// ```
+    ///
+    /// # Default
+    /// disabled. request to non existing file is responded with 404 status.
+    pub fn fallback(mut self, path: impl Into<PathBuf>) -> Self {
+        self.inner.fallback(path);
+        self
+    }
 }
 
 impl<F> PathGen for ServeDir<F>
@@ -120,7 +168,10 @@ mod service {
         type Error = RouterError<Error>;
 
         async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
-            match self.0.serve(ctx.req()).await {
+            let req = ctx.req();
+            // catch all param generated by PathGen. it's the request path with route prefix stripped.
+            let path = req.body().params().get("p").unwrap_or_else(|| req.uri().path());
+            match self.0.serve_path(req, path).await {
                 Ok(res) => Ok(res.map(ResponseBody::box_stream)),
                 Err(ServeError::NotModified) => {
                     let mut res = ctx.into_response(ResponseBody::none());
@@ -192,3 +243,57 @@ mod dumb {
         }
     }
 }
+
+#[cfg(all(test, feature = "file"))]
+mod test {
+    use std::fs;
+
+    use crate::{
+        App,
+        http::StatusCode,
+        test::{TestApp, collect_string_body},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn serve_dir() {
+        let root = std::env::temp_dir().join(format!("xitca-web-serve-dir-{}", std::process::id()));
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("index.html"), "spa").unwrap();
+        fs::write(root.join("app.js"), "js").unwrap();
+
+        let app = TestApp::new(
+            App::new()
+                .at(
+                    "/static",
+                    ServeDir::new(root.clone())
+                        .index_file("index.html")
+                        .fallback("index.html"),
+                )
+                .at("/list", ServeDir::new(root.clone()).directory_listing(true))
+                .finish(),
+        )
+        .await;
+
+        // route prefix is stripped before matching file.
+        let res = app.get("/static/app.js").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(collect_string_body(res.into_body()).await.unwrap(), "js");
+
+        // unknown path falls back to index file.
+        let res = app.get("/static/foo/bar").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(collect_string_body(res.into_body()).await.unwrap(), "spa");
+
+        let res = app.get("/list/docs").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = collect_string_body(res.into_body()).await.unwrap();
+        assert!(body.contains("Index of /list/docs"));
+
+        let res = app.get("/static/%2e%2e/secret").await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
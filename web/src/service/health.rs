@@ -0,0 +1,437 @@
+//! health check services for liveness and readiness probes.
+
+use core::{
+    convert::Infallible,
+    fmt::{self, Write},
+    future::{Future, poll_fn},
+    pin::Pin,
+    task::Poll,
+    time::Duration,
+};
+
+use std::{
+    borrow::Cow,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use futures_core::future::LocalBoxFuture;
+use xitca_http::util::service::router::{PathGen, RouteGen, RouterMapErr};
+
+use crate::{
+    body::ResponseBody,
+    bytes::Bytes,
+    context::WebContext,
+    error::Error,
+    http::{
+        StatusCode, WebResponse,
+        header::{CACHE_CONTROL, CONTENT_TYPE, HeaderValue},
+    },
+    json::write_str,
+    service::Service,
+};
+
+type CheckFn = Box<dyn Fn() -> LocalBoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+struct Check {
+    name: Cow<'static, str>,
+    func: CheckFn,
+}
+
+/// builder type for health check services.
+///
+/// [Health::livez] produces liveness service that always responds with `200 OK` as long as the server is able to
+/// handle request. [Health::readyz] produces readiness service that runs every registered check concurrently and
+/// responds with `200 OK` when all of them pass and `503 Service Unavailable` otherwise. Both services respond
+/// with json body describing the status.
+///
+/// # Examples
+/// ```rust
+/// # use std::time::Duration;
+/// # use xitca_web::{handler::handler_service, service::health::Health, App, WebContext};
+/// let health = Health::new()
+///     .check("db", || async { Ok::<_, &str>(()) })
+///     .check("cache", || async { Err("connection refused") })
+///     .timeout(Duration::from_secs(1))
+///     .cache(Duration::from_secs(5));
+///
+/// // readiness handle can be used to mark application as not ready. (e.g: on graceful shutdown)
+/// let readiness = health.readiness();
+///
+/// App::new()
+///     .at("/livez", health.livez())
+///     .at("/readyz", health.readyz())
+///     # .at("/bar", handler_service(|_: &WebContext<'_>| async { "used for inferring types!" }));
+///
+/// readiness.shutdown();
+/// ```
+pub struct Health {
+    checks: Vec<Check>,
+    timeout: Duration,
+    cache: Duration,
+    readiness: Readiness,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Health {
+    /// construct a new health check builder without any check.
+    pub fn new() -> Self {
+        Self {
+            checks: Vec::new(),
+            timeout: Duration::from_secs(5),
+            cache: Duration::ZERO,
+            readiness: Readiness(Arc::new(AtomicBool::new(true))),
+        }
+    }
+
+    /// register a named async check. check passes when the returned future resolves to Ok and fails with error's
+    /// [fmt::Display] output as reason otherwise.
+    pub fn check<F, Fut, E>(mut self, name: impl Into<Cow<'static, str>>, func: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + 'static,
+        E: fmt::Display,
+    {
+        self.checks.push(Check {
+            name: name.into(),
+            func: Box::new(move || {
+                let fut = func();
+                Box::pin(async move { fut.await.map_err(|e| e.to_string()) })
+            }),
+        });
+        self
+    }
+
+    /// max duration of a single check. check is failed when it can not finish in time.
+    ///
+    /// # Default
+    /// 5 seconds.
+    pub fn timeout(mut self, dur: Duration) -> Self {
+        self.timeout = dur;
+        self
+    }
+
+    /// duration of caching readiness report. checks are not run again until cached report expires.
+    ///
+    /// # Default
+    /// zero duration where checks are run for every request.
+    pub fn cache(mut self, dur: Duration) -> Self {
+        self.cache = dur;
+        self
+    }
+
+    /// a handle for manually changing readiness state of application.
+    pub fn readiness(&self) -> Readiness {
+        self.readiness.clone()
+    }
+
+    /// construct liveness service.
+    pub fn livez(&self) -> Livez {
+        Livez { _p: () }
+    }
+
+    /// construct readiness service.
+    pub fn readyz(self) -> Readyz {
+        Readyz {
+            inner: Arc::new(Inner {
+                checks: self.checks,
+                timeout: self.timeout,
+                cache: self.cache,
+                cached: Mutex::new(None),
+                readiness: self.readiness,
+            }),
+        }
+    }
+}
+
+/// handle for changing readiness state of application. see [Health::readiness] for detail.
+#[derive(Clone)]
+pub struct Readiness(Arc<AtomicBool>);
+
+impl Readiness {
+    /// check if application is in ready state.
+    pub fn is_ready(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// set readiness state of application. when set to false readiness service responds with
+    /// `503 Service Unavailable` without running any check.
+    pub fn set_ready(&self, ready: bool) {
+        self.0.store(ready, Ordering::Release);
+    }
+
+    /// mark application as not ready because of shutting down.
+    /// should be called when graceful shutdown starts so load balancer can stop routing new request to it.
+    pub fn shutdown(&self) {
+        self.set_ready(false);
+    }
+}
+
+/// liveness service. see [Health::livez] for detail.
+#[derive(Clone)]
+pub struct Livez {
+    _p: (),
+}
+
+impl PathGen for Livez {}
+
+impl RouteGen for Livez {
+    type Route<R> = RouterMapErr<R>;
+
+    fn route_gen<R>(route: R) -> Self::Route<R> {
+        RouterMapErr(route)
+    }
+}
+
+impl Service for Livez {
+    type Response = Self;
+    type Error = Infallible;
+
+    async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+        Ok(self.clone())
+    }
+}
+
+impl<'r, C, B> Service<WebContext<'r, C, B>> for Livez {
+    type Response = WebResponse;
+    type Error = Error;
+
+    async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        Ok(json_response(
+            ctx,
+            StatusCode::OK,
+            Bytes::from_static(b"{\"status\":\"pass\"}"),
+        ))
+    }
+}
+
+struct Inner {
+    checks: Vec<Check>,
+    timeout: Duration,
+    cache: Duration,
+    cached: Mutex<Option<(Instant, StatusCode, Bytes)>>,
+    readiness: Readiness,
+}
+
+/// readiness service. see [Health::readyz] for detail.
+#[derive(Clone)]
+pub struct Readyz {
+    inner: Arc<Inner>,
+}
+
+impl PathGen for Readyz {}
+
+impl RouteGen for Readyz {
+    type Route<R> = RouterMapErr<R>;
+
+    fn route_gen<R>(route: R) -> Self::Route<R> {
+        RouterMapErr(route)
+    }
+}
+
+impl Service for Readyz {
+    type Response = Self;
+    type Error = Infallible;
+
+    async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+        Ok(self.clone())
+    }
+}
+
+impl<'r, C, B> Service<WebContext<'r, C, B>> for Readyz {
+    type Response = WebResponse;
+    type Error = Error;
+
+    async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        let (status, body) = self.inner.report().await;
+        Ok(json_response(ctx, status, body))
+    }
+}
+
+impl Inner {
+    async fn report(&self) -> (StatusCode, Bytes) {
+        if !self.readiness.is_ready() {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Bytes::from_static(b"{\"status\":\"fail\",\"reason\":\"shutting down\"}"),
+            );
+        }
+
+        match *self.cached.lock().unwrap() {
+            Some((at, status, ref body)) if at.elapsed() < self.cache => return (status, body.clone()),
+            _ => {}
+        }
+
+        let (status, body) = self.run().await;
+
+        if !self.cache.is_zero() {
+            *self.cached.lock().unwrap() = Some((Instant::now(), status, body.clone()));
+        }
+
+        (status, body)
+    }
+
+    // run all checks concurrently and render json report.
+    async fn run(&self) -> (StatusCode, Bytes) {
+        let mut futs = self
+            .checks
+            .iter()
+            .map(|check| {
+                let fut = (check.func)();
+                let timeout = self.timeout;
+                Box::pin(async move {
+                    let start = Instant::now();
+                    let res = match tokio::time::timeout(timeout, fut).await {
+                        Ok(res) => res,
+                        Err(_) => Err(String::from("timeout")),
+                    };
+                    (res, start.elapsed())
+                }) as LocalBoxFuture<'_, (Result<(), String>, Duration)>
+            })
+            .collect::<Vec<_>>();
+
+        let mut outputs = (0..futs.len()).map(|_| None).collect::<Vec<_>>();
+
+        poll_fn(|cx| {
+            let mut pending = false;
+            for (fut, output) in futs.iter_mut().zip(outputs.iter_mut()) {
+                if output.is_none() {
+                    match Pin::new(fut).poll(cx) {
+                        Poll::Ready(res) => *output = Some(res),
+                        Poll::Pending => pending = true,
+                    }
+                }
+            }
+            if pending { Poll::Pending } else { Poll::Ready(()) }
+        })
+        .await;
+
+        let pass = outputs.iter().all(|o| matches!(o, Some((Ok(_), _))));
+
+        let mut json = String::new();
+        json.push_str(if pass {
+            "{\"status\":\"pass\",\"checks\":{"
+        } else {
+            "{\"status\":\"fail\",\"checks\":{"
+        });
+
+        for (i, (check, output)) in self.checks.iter().zip(outputs).enumerate() {
+            let (res, dur) = output.unwrap();
+            if i > 0 {
+                json.push(',');
+            }
+            let _ = write_str(&mut json, &check.name);
+            match res {
+                Ok(_) => json.push_str(":{\"status\":\"pass\""),
+                Err(e) => {
+                    json.push_str(":{\"status\":\"fail\",\"error\":");
+                    let _ = write_str(&mut json, &e);
+                }
+            }
+            let _ = write!(json, ",\"duration_ms\":{}}}", dur.as_millis());
+        }
+
+        json.push_str("}}");
+
+        let status = if pass {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+
+        (status, Bytes::from(json))
+    }
+}
+
+fn json_response<C, B>(ctx: WebContext<'_, C, B>, status: StatusCode, body: Bytes) -> WebResponse {
+    let mut res = ctx.into_response(ResponseBody::from(body));
+    *res.status_mut() = status;
+    res.headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    res.headers_mut()
+        .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use core::sync::atomic::AtomicUsize;
+
+    use crate::{App, test::TestApp, test::collect_string_body};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn health() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let count2 = count.clone();
+
+        let health = Health::new()
+            .check("db", move || {
+                count2.fetch_add(1, Ordering::Relaxed);
+                async { Ok::<_, &str>(()) }
+            })
+            .cache(Duration::from_secs(60));
+        let readiness = health.readiness();
+
+        let app = TestApp::new(
+            App::new()
+                .at("/livez", health.livez())
+                .at("/readyz", health.readyz())
+                .finish(),
+        )
+        .await;
+
+        let res = app.get("/livez").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            collect_string_body(res.into_body()).await.unwrap(),
+            "{\"status\":\"pass\"}"
+        );
+
+        for _ in 0..2 {
+            let res = app.get("/readyz").await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = collect_string_body(res.into_body()).await.unwrap();
+            assert!(body.starts_with("{\"status\":\"pass\",\"checks\":{\"db\":{\"status\":\"pass\""));
+        }
+
+        // second request is served from cache.
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        readiness.shutdown();
+
+        let res = app.get("/readyz").await.unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn failed_check() {
+        let health = Health::new()
+            .check("ok", || async { Ok::<_, &str>(()) })
+            .check("err", || async { Err("bad \"conn\"") })
+            .check("slow", || async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok::<_, &str>(())
+            })
+            .timeout(Duration::from_millis(10));
+
+        let app = TestApp::new(App::new().at("/readyz", health.readyz()).finish()).await;
+
+        let res = app.get("/readyz").await.unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = collect_string_body(res.into_body()).await.unwrap();
+        assert!(body.starts_with("{\"status\":\"fail\""));
+        assert!(body.contains("\"ok\":{\"status\":\"pass\""));
+        assert!(body.contains("\"err\":{\"status\":\"fail\",\"error\":\"bad \\\"conn\\\"\""));
+        assert!(body.contains("\"slow\":{\"status\":\"fail\",\"error\":\"timeout\""));
+    }
+}
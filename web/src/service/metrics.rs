@@ -0,0 +1,163 @@
+//! service exposing transport layer runtime metrics.
+
+use core::{convert::Infallible, fmt::Write};
+
+use xitca_http::{
+    metrics::{MetricsSnapshot, snapshot},
+    util::service::router::{PathGen, RouteGen, RouterMapErr},
+};
+
+use crate::{
+    body::ResponseBody,
+    context::WebContext,
+    error::Error,
+    http::{
+        WebResponse,
+        header::{CACHE_CONTROL, CONTENT_TYPE, HeaderValue},
+    },
+    service::Service,
+};
+
+/// service responding with [snapshot] of transport layer runtime metrics in prometheus text exposition format.
+///
+/// byte counters are only updated after [enable_byte_counters](xitca_http::metrics::enable_byte_counters) is
+/// called.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_web::{handler::handler_service, service::metrics::Metrics, App, WebContext};
+/// App::new()
+///     .at("/metrics", Metrics::new())
+///     # .at("/bar", handler_service(|_: &WebContext<'_>| async { "used for inferring types!" }));
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Metrics {
+    _p: (),
+}
+
+impl Metrics {
+    /// construct a new metrics service.
+    pub const fn new() -> Self {
+        Self { _p: () }
+    }
+}
+
+impl PathGen for Metrics {}
+
+impl RouteGen for Metrics {
+    type Route<R> = RouterMapErr<R>;
+
+    fn route_gen<R>(route: R) -> Self::Route<R> {
+        RouterMapErr(route)
+    }
+}
+
+impl Service for Metrics {
+    type Response = Self;
+    type Error = Infallible;
+
+    async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+        Ok(*self)
+    }
+}
+
+impl<'r, C, B> Service<WebContext<'r, C, B>> for Metrics {
+    type Response = WebResponse;
+    type Error = Error;
+
+    async fn call(&self, ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+        let body = render(&snapshot());
+        let mut res = ctx.into_response(ResponseBody::from(body));
+        res.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4; charset=utf-8"),
+        );
+        res.headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        Ok(res)
+    }
+}
+
+fn render(s: &MetricsSnapshot) -> String {
+    let mut out = String::with_capacity(1024);
+
+    let mut metric = |name: &str, kind: &str, help: &str, samples: &[(Option<&str>, u64)]| {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} {kind}");
+        for (label, value) in samples {
+            let _ = match label {
+                Some(proto) => writeln!(out, "{name}{{protocol=\"{proto}\"}} {value}"),
+                None => writeln!(out, "{name} {value}"),
+            };
+        }
+    };
+
+    metric(
+        "xitca_accepted_connections_total",
+        "counter",
+        "Total count of accepted connections.",
+        &[(None, s.accepted_connections)],
+    );
+    metric(
+        "xitca_active_connections",
+        "gauge",
+        "Count of connections currently served.",
+        &[
+            (Some("http1"), s.active_h1_connections),
+            (Some("http2"), s.active_h2_connections),
+            (Some("http3"), s.active_h3_connections),
+        ],
+    );
+    metric(
+        "xitca_handshake_failures_total",
+        "counter",
+        "Total count of failed tls and http/2 handshakes.",
+        &[(None, s.handshake_failures)],
+    );
+    metric(
+        "xitca_h2_stream_resets_total",
+        "counter",
+        "Total count of http/2 streams ended with reset.",
+        &[(None, s.h2_stream_resets)],
+    );
+    metric(
+        "xitca_received_bytes_total",
+        "counter",
+        "Total application bytes read from connections.",
+        &[(None, s.bytes_in)],
+    );
+    metric(
+        "xitca_sent_bytes_total",
+        "counter",
+        "Total application bytes written to connections.",
+        &[(None, s.bytes_out)],
+    );
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        App,
+        test::{TestApp, collect_string_body},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn metrics() {
+        let app = TestApp::new(App::new().at("/metrics", Metrics::new()).finish()).await;
+
+        let res = app.get("/metrics").await.unwrap();
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "text/plain; version=0.0.4; charset=utf-8"
+        );
+
+        let body = collect_string_body(res.into_body()).await.unwrap();
+        assert!(body.contains("# TYPE xitca_accepted_connections_total counter\n"));
+        assert!(body.contains("xitca_active_connections{protocol=\"http2\"} "));
+        assert!(body.contains("xitca_sent_bytes_total "));
+    }
+}
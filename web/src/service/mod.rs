@@ -6,4 +6,12 @@ pub mod tower_http_compat;
 #[cfg(feature = "file-raw")]
 pub mod file;
 
+#[cfg(feature = "proxy")]
+pub mod proxy;
+
+#[cfg(feature = "health")]
+pub mod health;
+
+pub mod metrics;
+
 pub use xitca_service::*;
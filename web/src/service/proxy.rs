@@ -0,0 +1,492 @@
+//! reverse proxy forwarding request to upstream server.
+
+use core::convert::Infallible;
+
+use std::net::IpAddr;
+
+use xitca_client::Client;
+use xitca_http::util::service::router::{PathGen, RouteGen, RouterMapErr};
+
+use crate::{
+    http::{Uri, WebRequest},
+    service::Service,
+};
+
+/// trait for selecting upstream server of proxied request.
+pub trait Upstream {
+    /// return base uri of upstream server for given request. base uri must contain scheme and authority and it's
+    /// optional path is used as prefix of forwarded request's path.
+    ///
+    /// return None when there is no available upstream and proxy would respond with `503 Service Unavailable`.
+    fn select(&self, req: &WebRequest<()>) -> Option<Uri>;
+}
+
+impl Upstream for Uri {
+    #[inline]
+    fn select(&self, _: &WebRequest<()>) -> Option<Uri> {
+        Some(self.clone())
+    }
+}
+
+impl<F> Upstream for F
+where
+    F: Fn(&WebRequest<()>) -> Option<Uri>,
+{
+    #[inline]
+    fn select(&self, req: &WebRequest<()>) -> Option<Uri> {
+        self(req)
+    }
+}
+
+/// builder type for reverse proxy service.
+///
+/// request is forwarded with streaming body to upstream with [xitca_client::Client] and upstream's response is
+/// streamed back to client. hop-by-hop headers are removed from both request and response and `Forwarded`,
+/// `X-Forwarded-For`, `X-Forwarded-Host` and `X-Forwarded-Proto` headers are set for upstream.
+///
+/// path of request is forwarded as is. (e.g: proxy mounted at `/api` forwards `/api/foo` request as `/api/foo`)
+///
+/// # Examples
+/// ```rust
+/// # use xitca_web::{
+/// #     handler::handler_service,
+/// #     http::{Uri, WebRequest},
+/// #     service::proxy::Proxy,
+/// #     App, WebContext
+/// # };
+/// App::new()
+///     // forward every request under /api path to upstream server.
+///     .at("/api", Proxy::new(Uri::from_static("http://127.0.0.1:8081")))
+///     // select upstream dynamically by request.
+///     .at("/v2", Proxy::new(|req: &WebRequest<()>| {
+///         let uri = if req.headers().contains_key("x-canary") {
+///             "http://127.0.0.1:8082"
+///         } else {
+///             "http://127.0.0.1:8081"
+///         };
+///         Some(Uri::from_static(uri))
+///     }))
+///     # .at("/bar", handler_service(|_: &WebContext<'_>| async { "used for inferring types!" }));
+/// ```
+///
+/// # Default
+/// - each worker thread uses it's own [Client] constructed by [Client::new]. see [Proxy::client] for changing it.
+#[derive(Clone)]
+pub struct Proxy<U> {
+    upstream: U,
+    client: fn() -> Client,
+}
+
+impl<U> Proxy<U>
+where
+    U: Upstream,
+{
+    /// construct a new proxy service builder with given [Upstream] type.
+    pub fn new(upstream: U) -> Self {
+        Self {
+            upstream,
+            client: Client::new,
+        }
+    }
+
+    /// change constructor of [Client] used by proxy service.
+    pub fn client(mut self, client: fn() -> Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl<U> PathGen for Proxy<U> {
+    fn path_gen(&mut self, prefix: &str) -> String {
+        let mut prefix = String::from(prefix);
+        if prefix.ends_with('/') {
+            prefix.pop();
+        }
+
+        prefix.push_str("/*p");
+
+        prefix
+    }
+}
+
+impl<U> RouteGen for Proxy<U> {
+    type Route<R> = RouterMapErr<R>;
+
+    fn route_gen<R>(route: R) -> Self::Route<R> {
+        RouterMapErr(route)
+    }
+}
+
+impl<U> Service for Proxy<U>
+where
+    U: Clone,
+{
+    type Response = service::ProxyService<U>;
+    type Error = Infallible;
+
+    async fn call(&self, _: ()) -> Result<Self::Response, Self::Error> {
+        Ok(service::ProxyService {
+            upstream: self.upstream.clone(),
+            client: (self.client)(),
+        })
+    }
+}
+
+pub(crate) use service::{forward_headers, remove_hop_headers, upstream_uri};
+
+mod service {
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use futures_core::stream::Stream;
+    use xitca_client::error::{Error as ClientError, TimeoutError};
+    use xitca_http::body::exact_body_hint;
+    use xitca_unsafe_collection::fake::FakeSend;
+
+    use crate::{
+        body::{ResponseBody, none_body_hint},
+        bytes::{BufMut, Bytes, BytesMut},
+        context::WebContext,
+        error::{BodyError, Error, ErrorStatus},
+        http::{
+            StatusCode, Version, WebResponse,
+            header::{
+                CONNECTION, CONTENT_LENGTH, FORWARDED, HOST, HeaderMap, HeaderName, HeaderValue, PROXY_AUTHENTICATE,
+                PROXY_AUTHORIZATION, TE, TRAILER, TRANSFER_ENCODING, UPGRADE,
+            },
+        },
+        service::ready::ReadyService,
+    };
+
+    use super::*;
+
+    pub struct ProxyService<U> {
+        pub(super) upstream: U,
+        pub(super) client: Client,
+    }
+
+    impl<'r, C, B, T, E, U> Service<WebContext<'r, C, B>> for ProxyService<U>
+    where
+        U: Upstream,
+        B: Stream<Item = Result<T, E>> + Default + 'static,
+        T: Into<Bytes>,
+        E: Into<BodyError>,
+    {
+        type Response = WebResponse;
+        type Error = Error;
+
+        async fn call(&self, mut ctx: WebContext<'r, C, B>) -> Result<Self::Response, Self::Error> {
+            let base = self.upstream.select(ctx.req()).ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+            let uri = upstream_uri(&base, ctx.req().uri()).ok_or_else(ErrorStatus::internal)?;
+
+            let addr = ctx.req().body().socket_addr().ip();
+            let version = ctx.req().version();
+            let proto = match ctx.req().uri().scheme_str() {
+                Some("https") => "https",
+                _ => "http",
+            };
+
+            let (mut parts, ext) = ctx.take_request().into_parts();
+            let (_, body) = ext.replace_body(());
+
+            let size = body_hint(&parts.headers, version);
+
+            parts.uri = uri;
+            parts.version = Version::HTTP_11;
+            remove_hop_headers(&mut parts.headers);
+            forward_headers(&mut parts.headers, addr, proto);
+
+            let body = SendBody {
+                body: FakeSend::new(Box::pin(body)),
+                size,
+            };
+            let req = crate::http::Request::from_parts(parts, body);
+
+            let res = match self.client.request(req).send().await {
+                Ok(res) => res.into_inner(),
+                Err(e) => return Err(gateway_error(e).into()),
+            };
+
+            let (mut parts, body) = res.into_parts();
+            remove_hop_headers(&mut parts.headers);
+
+            // content length is only meaningful to http/1 and other version streams response body without it.
+            if !matches!(version, Version::HTTP_10 | Version::HTTP_11) {
+                parts.headers.remove(CONTENT_LENGTH);
+            }
+
+            Ok(WebResponse::from_parts(parts, ResponseBody::box_stream(body)))
+        }
+    }
+
+    impl<U> ReadyService for ProxyService<U> {
+        type Ready = ();
+
+        #[inline]
+        async fn ready(&self) -> Self::Ready {}
+    }
+
+    // request body is not Send and it's only accessed on the thread where proxy service runs.
+    struct SendBody<B> {
+        body: FakeSend<Pin<Box<B>>>,
+        size: (usize, Option<usize>),
+    }
+
+    impl<B, T, E> Stream for SendBody<B>
+    where
+        B: Stream<Item = Result<T, E>>,
+        T: Into<Bytes>,
+        E: Into<BodyError>,
+    {
+        type Item = Result<Bytes, BodyError>;
+
+        #[inline]
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.get_mut()
+                .body
+                .as_mut()
+                .poll_next(cx)
+                .map(|res| res.map(|res| res.map(Into::into).map_err(Into::into)))
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.size
+        }
+    }
+
+    // size of request body can not be inferred from request body type and it's decided by request headers.
+    fn body_hint(headers: &HeaderMap, version: Version) -> (usize, Option<usize>) {
+        let len = headers
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+
+        match len {
+            Some(len) => exact_body_hint(len),
+            None if matches!(version, Version::HTTP_10 | Version::HTTP_11)
+                && !headers.contains_key(TRANSFER_ENCODING) =>
+            {
+                none_body_hint()
+            }
+            None => (0, None),
+        }
+    }
+
+    fn gateway_error(e: ClientError) -> StatusCode {
+        match e {
+            ClientError::Std(ref e) if e.is::<TimeoutError>() => StatusCode::GATEWAY_TIMEOUT,
+            _ => StatusCode::BAD_GATEWAY,
+        }
+    }
+
+    pub(crate) fn upstream_uri(base: &Uri, uri: &Uri) -> Option<Uri> {
+        let prefix = base.path().trim_end_matches('/');
+        let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+
+        let mut parts = base.clone().into_parts();
+        parts.path_and_query = Some(format!("{prefix}{path}").parse().ok()?);
+        Uri::from_parts(parts).ok()
+    }
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const KEEP_ALIVE: HeaderName = HeaderName::from_static("keep-alive");
+    #[allow(clippy::declare_interior_mutable_const)]
+    const PROXY_CONNECTION: HeaderName = HeaderName::from_static("proxy-connection");
+    #[allow(clippy::declare_interior_mutable_const)]
+    const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+    #[allow(clippy::declare_interior_mutable_const)]
+    const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+    #[allow(clippy::declare_interior_mutable_const)]
+    const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+
+    pub(crate) fn remove_hop_headers(headers: &mut HeaderMap) {
+        // headers listed in connection header are hop-by-hop headers too.
+        let listed = headers
+            .get_all(CONNECTION)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok())
+            .collect::<Vec<_>>();
+
+        for name in listed {
+            headers.remove(name);
+        }
+
+        #[allow(clippy::borrow_interior_mutable_const)]
+        for name in [
+            CONNECTION,
+            KEEP_ALIVE,
+            PROXY_CONNECTION,
+            PROXY_AUTHENTICATE,
+            PROXY_AUTHORIZATION,
+            TE,
+            TRAILER,
+            TRANSFER_ENCODING,
+            UPGRADE,
+        ] {
+            headers.remove(name);
+        }
+    }
+
+    pub(crate) fn forward_headers(headers: &mut HeaderMap, addr: IpAddr, proto: &'static str) {
+        let mut forwarded = BytesMut::new();
+        match addr {
+            IpAddr::V4(addr) => forwarded.put_slice(format!("for={addr}").as_bytes()),
+            IpAddr::V6(addr) => forwarded.put_slice(format!("for=\"[{addr}]\"").as_bytes()),
+        }
+
+        // original host header is removed and upstream's host is set by client.
+        if let Some(host) = headers.remove(HOST) {
+            if let Ok(h) = host.to_str() {
+                forwarded.put_slice(format!(";host=\"{h}\"").as_bytes());
+            }
+            headers.insert(X_FORWARDED_HOST, host);
+        }
+
+        forwarded.put_slice(format!(";proto={proto}").as_bytes());
+
+        let forwarded = HeaderValue::from_maybe_shared(forwarded.freeze()).unwrap();
+        headers.append(FORWARDED, forwarded);
+
+        // previous hops can be spread across multiple headers.
+        let mut xff = headers
+            .get_all(X_FORWARDED_FOR)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !xff.is_empty() {
+            xff.push_str(", ");
+        }
+        xff.push_str(&addr.to_string());
+        headers.insert(X_FORWARDED_FOR, HeaderValue::try_from(xff).unwrap());
+        headers.insert(X_FORWARDED_PROTO, HeaderValue::from_static(proto));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use crate::{
+        App,
+        http::{
+            StatusCode,
+            header::{CONNECTION, HOST, HeaderMap, HeaderValue},
+        },
+        test::collect_string_body,
+    };
+
+    use super::{service::*, *};
+
+    #[test]
+    fn uri() {
+        let base = Uri::from_static("http://127.0.0.1:8080");
+        let uri = Uri::from_static("/foo?bar=1");
+        assert_eq!(upstream_uri(&base, &uri).unwrap(), "http://127.0.0.1:8080/foo?bar=1");
+
+        let base = Uri::from_static("http://127.0.0.1:8080/prefix/");
+        assert_eq!(
+            upstream_uri(&base, &uri).unwrap(),
+            "http://127.0.0.1:8080/prefix/foo?bar=1"
+        );
+    }
+
+    #[test]
+    fn headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONNECTION, HeaderValue::from_static("keep-alive, x-hop"));
+        headers.insert("keep-alive", HeaderValue::from_static("timeout=5"));
+        headers.insert("x-hop", HeaderValue::from_static("1"));
+        headers.insert("x-end", HeaderValue::from_static("1"));
+        headers.insert(HOST, HeaderValue::from_static("example.com"));
+        headers.insert("x-forwarded-for", HeaderValue::from_static("10.0.0.1"));
+        headers.append("x-forwarded-for", HeaderValue::from_static("10.0.0.2, 10.0.0.3"));
+
+        remove_hop_headers(&mut headers);
+        forward_headers(&mut headers, "::1".parse().unwrap(), "https");
+
+        assert!(!headers.contains_key(CONNECTION));
+        assert!(!headers.contains_key("keep-alive"));
+        assert!(!headers.contains_key("x-hop"));
+        assert!(!headers.contains_key(HOST));
+        assert_eq!(headers.get("x-end").unwrap(), "1");
+        assert_eq!(headers.get("x-forwarded-host").unwrap(), "example.com");
+        assert_eq!(headers.get_all("x-forwarded-for").iter().count(), 1);
+        assert_eq!(
+            headers.get("x-forwarded-for").unwrap(),
+            "10.0.0.1, 10.0.0.2, 10.0.0.3, ::1"
+        );
+        assert_eq!(headers.get("x-forwarded-proto").unwrap(), "https");
+        assert_eq!(
+            headers.get("forwarded").unwrap(),
+            "for=\"[::1]\";host=\"example.com\";proto=https"
+        );
+    }
+
+    #[tokio::test]
+    async fn proxy() {
+        // a minimal upstream echoing request head as response body.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let upstream = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0; 1024];
+            while !buf.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut chunk).unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            let res = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nx-upstream: 1\r\nkeep-alive: timeout=5\r\n\r\n",
+                buf.len()
+            );
+            stream.write_all(res.as_bytes()).unwrap();
+            stream.write_all(&buf).unwrap();
+        });
+
+        let uri = Uri::try_from(format!("http://{addr}")).unwrap();
+
+        let service = App::new()
+            .at("/api", Proxy::new(uri))
+            .at("/none", Proxy::new(|_: &WebRequest<()>| None))
+            .finish()
+            .call(())
+            .await
+            .unwrap();
+
+        let mut req = WebRequest::default();
+        *req.uri_mut() = Uri::from_static("/api/foo?bar=1");
+        req.headers_mut().insert(HOST, HeaderValue::from_static("example.com"));
+        req.headers_mut()
+            .insert(CONNECTION, HeaderValue::from_static("keep-alive"));
+
+        let res = service.call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get("x-upstream").unwrap(), "1");
+        assert!(!res.headers().contains_key("keep-alive"));
+
+        let body = collect_string_body(res.into_body()).await.unwrap();
+        assert!(body.starts_with("GET /api/foo?bar=1 HTTP/1.1\r\n"));
+        assert!(body.contains(&format!("host: {addr}\r\n")));
+        assert!(body.contains("x-forwarded-host: example.com\r\n"));
+        assert!(body.contains("x-forwarded-proto: http\r\n"));
+        assert!(!body.contains("connection: keep-alive"));
+
+        upstream.join().unwrap();
+
+        let mut req = WebRequest::default();
+        *req.uri_mut() = Uri::from_static("/none/foo");
+        let res = service.call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -1,6 +1,7 @@
 use core::{
     cell::RefCell,
     convert::Infallible,
+    future::poll_fn,
     pin::Pin,
     task::{Context, Poll, ready},
 };
@@ -26,6 +27,9 @@ use crate::{
 
 /// A middleware type that bridge `xitca-service` and `tower-service`.
 /// Any `tower-http` type that impl [tower_service::Service] trait can be passed to it and used as xitca-web's service type.
+///
+/// `tower_service::Service::poll_ready` is awaited before every call so back pressure services like
+/// concurrency limit and load shed work as expected.
 pub struct TowerHttpCompat<S>(S);
 
 impl<S> TowerHttpCompat<S> {
@@ -81,8 +85,15 @@ where
     async fn call(&self, mut ctx: WebContext<'r, C, ReqB>) -> Result<Self::Response, Self::Error> {
         let (parts, ext) = ctx.take_request().into_parts();
         let ctx = ctx.state().clone();
-        let req = Request::from_parts(parts, CompatReqBody::new(ext, ctx));
-        let fut = tower_service::Service::call(&mut *self.0.borrow_mut(), req);
+        let mut req = Some(Request::from_parts(parts, CompatReqBody::new(ext, ctx)));
+        // readiness and call must happen in the same borrow so concurrent requests can't steal the
+        // reserved capacity of tower service in between.
+        let fut = poll_fn(|cx| -> Poll<Result<_, S::Error>> {
+            let mut service = self.0.borrow_mut();
+            ready!(tower_service::Service::poll_ready(&mut *service, cx))?;
+            Poll::Ready(Ok(tower_service::Service::call(&mut *service, req.take().unwrap())))
+        })
+        .await?;
         fut.await.map(|res| res.map(CompatResBody::new))
     }
 }
@@ -237,10 +248,48 @@ fn size_hint(size: BodySize) -> SizeHint {
 
 #[cfg(test)]
 mod test {
+    use core::future::{Ready, ready};
+
     use xitca_http::body::{Once, exact_body_hint};
 
     use super::*;
 
+    // tower service becoming ready on every other poll_ready call.
+    #[derive(Default)]
+    struct Toggle {
+        ready: bool,
+    }
+
+    impl<B> tower_service::Service<Request<B>> for Toggle {
+        type Response = Response<CompatResBody<ResponseBody>>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            if self.ready {
+                return Poll::Ready(Ok(()));
+            }
+            self.ready = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+
+        fn call(&mut self, _: Request<B>) -> Self::Future {
+            assert!(core::mem::take(&mut self.ready), "tower service called before ready");
+            ready(Ok(Response::new(CompatResBody::new(ResponseBody::empty()))))
+        }
+    }
+
+    #[tokio::test]
+    async fn poll_ready() {
+        let service = TowerCompatService::new(Toggle::default());
+
+        for _ in 0..2 {
+            let mut ctx = WebContext::new_test(());
+            Service::call(&service, ctx.as_web_ctx()).await.unwrap();
+        }
+    }
+
     #[test]
     fn body_compat() {
         let buf = Bytes::from_static(b"996");
@@ -1,10 +1,13 @@
 //! utilities for testing web application
 
-use core::{future::poll_fn, pin::pin};
+use core::{fmt, future::poll_fn, pin::pin};
 
 use futures_core::stream::Stream;
 
-use crate::service::pipeline::PipelineE;
+use crate::{
+    http::WebRequest,
+    service::{Service, pipeline::PipelineE},
+};
 
 /// Collect request or response body to Vec.
 pub async fn collect_body<B, T, E>(body: B) -> Result<Vec<u8>, E>
@@ -34,3 +37,52 @@ where
     let body = collect_body(body).await.map_err(CollectStringError::Second)?;
     String::from_utf8(body).map_err(CollectStringError::First)
 }
+
+/// Harness of application service for testing.
+///
+/// # Examples
+/// ```rust
+/// # use xitca_web::{handler::handler_service, http::StatusCode, route::get, test::TestApp, App};
+/// # async fn test() {
+/// let app = TestApp::new(App::new().at("/", get(handler_service(|| async { "hello,world!" }))).finish()).await;
+///
+/// let res = app.get("/").await.unwrap();
+/// assert_eq!(res.status(), StatusCode::OK);
+/// # }
+/// ```
+pub struct TestApp<S> {
+    service: S,
+}
+
+impl<S> TestApp<S> {
+    /// Construct harness by building application service from given service factory.
+    ///
+    /// # Panics
+    /// When application service failed to build.
+    pub async fn new<F>(factory: F) -> Self
+    where
+        F: Service<Response = S>,
+        F::Error: fmt::Debug,
+    {
+        let service = factory.call(()).await.expect("failed to build application service");
+        Self { service }
+    }
+
+    /// Send request to application service and return it's response.
+    pub async fn call<B>(&self, req: WebRequest<B>) -> Result<S::Response, S::Error>
+    where
+        S: Service<WebRequest<B>>,
+    {
+        self.service.call(req).await
+    }
+
+    /// Send GET request with given path and empty body to application service and return it's response.
+    pub async fn get(&self, path: &str) -> Result<S::Response, S::Error>
+    where
+        S: Service<WebRequest>,
+    {
+        let mut req = WebRequest::default();
+        *req.uri_mut() = path.parse().expect("path must be valid uri");
+        self.service.call(req).await
+    }
+}